@@ -0,0 +1,198 @@
+//! # `acir` Subcommand
+//!
+//! Reports on the circuits of binary packages, as compiled by `nargo compile` into the
+//! target directory.
+
+use crate::error::{AnalysisError, write_file};
+use nargo::workspace::Workspace;
+use noir_analyzer::acir::checker::read_program;
+use noir_analyzer::acir::drift::{self, Drift};
+use noir_analyzer::acir::stats::{CircuitStats, circuit_stats};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Subcommands of `acir`.
+#[derive(Debug, clap::Subcommand)]
+pub enum AcirCommand {
+    /// Print the size of each compiled circuit: its opcodes by kind, witnesses and inputs
+    Stats {
+        /// Also report witness lifetimes: the peak number of witnesses live at once, how many
+        /// witnesses reusing dead ones would save, and the longest-lived witnesses
+        #[arg(long)]
+        detailed: bool,
+        /// Print the statistics as JSON, keyed by package name
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the hash and public-input layout of each circuit of the compiled binary package,
+    /// to store next to a deployed verifier
+    Fingerprint {
+        /// Write the fingerprint to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Report the functions whose circuit or public inputs differ from an earlier build,
+    /// failing if any does
+    Drift {
+        /// Program artifact or stored fingerprint of the earlier build
+        old: PathBuf,
+        /// Program artifact or stored fingerprint to compare, by default the compiled binary
+        /// package of the workspace
+        new: Option<PathBuf>,
+        /// Print the differences as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Runs an `acir` subcommand. Returns whether it succeeded.
+pub fn run(workspace: &Workspace, command: AcirCommand) -> Result<bool, AnalysisError> {
+    match command {
+        AcirCommand::Stats { detailed, json } => stats(workspace, detailed, json).map(|()| true),
+        AcirCommand::Fingerprint { output } => {
+            fingerprint(workspace, output.as_deref()).map(|()| true)
+        }
+        AcirCommand::Drift { old, new, json } => drift(workspace, &old, new, json),
+    }
+}
+
+fn stats(workspace: &Workspace, detailed: bool, json: bool) -> Result<(), AnalysisError> {
+    let mut reports = BTreeMap::new();
+    for package in workspace
+        .members
+        .iter()
+        .filter(|package| package.is_binary())
+    {
+        let artifact_path = workspace.package_build_path(package);
+        if !artifact_path.is_file() {
+            return Err(AnalysisError::Usage(format!(
+                "No compiled circuit for package {} at {artifact_path:?}, run `nargo compile` first",
+                package.name
+            )));
+        }
+        let program = read_program(&artifact_path)?;
+        let stats: Vec<CircuitStats> = program
+            .functions
+            .iter()
+            .map(|circuit| circuit_stats(circuit, detailed))
+            .collect();
+
+        if json {
+            reports.insert(package.name.to_string(), stats);
+            continue;
+        }
+
+        println!("Package: {} ({})", package.name, artifact_path.display());
+        for circuit in &stats {
+            print_stats(circuit);
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    Ok(())
+}
+
+fn fingerprint(workspace: &Workspace, output: Option<&Path>) -> Result<(), AnalysisError> {
+    let program = read_program(&binary_artifact(workspace)?)?;
+    let fingerprint = serde_json::to_string_pretty(&drift::fingerprint(&program))?;
+    match output {
+        Some(output) => write_file(output, fingerprint + "\n"),
+        None => {
+            println!("{fingerprint}");
+            Ok(())
+        }
+    }
+}
+
+fn drift(
+    workspace: &Workspace,
+    old: &Path,
+    new: Option<PathBuf>,
+    json: bool,
+) -> Result<bool, AnalysisError> {
+    let new = match new {
+        Some(new) => new,
+        None => binary_artifact(workspace)?,
+    };
+    let drifts = drift::compare(
+        &drift::read_fingerprint(old)?,
+        &drift::read_fingerprint(&new)?,
+    );
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&drifts)?);
+    } else if drifts.is_empty() {
+        println!(
+            "No circuit changed between {} and {}",
+            old.display(),
+            new.display()
+        );
+    } else {
+        println!(
+            "Circuits changed between {} and {}, deployed verifiers must be regenerated:",
+            old.display(),
+            new.display()
+        );
+        for drift in &drifts {
+            println!("  {drift}");
+        }
+    }
+    Ok(drifts.is_empty())
+}
+
+/// Returns the artifact of the only binary package of the workspace, which must be compiled.
+fn binary_artifact(workspace: &Workspace) -> Result<PathBuf, AnalysisError> {
+    let mut packages = workspace
+        .members
+        .iter()
+        .filter(|package| package.is_binary());
+    let (Some(package), None) = (packages.next(), packages.next()) else {
+        return Err(AnalysisError::Usage(
+            "Expected exactly one binary package, select one with --package".to_string(),
+        ));
+    };
+    let artifact_path = workspace.package_build_path(package);
+    if !artifact_path.is_file() {
+        return Err(AnalysisError::Usage(format!(
+            "No compiled circuit for package {} at {artifact_path:?}, run `nargo compile` first",
+            package.name
+        )));
+    }
+    Ok(artifact_path)
+}
+
+fn print_stats(stats: &CircuitStats) {
+    println!("  Function: {}", stats.function);
+    println!("    opcodes: {}", stats.opcodes);
+    for (kind, count) in &stats.opcodes_by_kind {
+        println!("      {kind:<13} {count:>8}");
+    }
+    println!("    witnesses: {}", stats.witnesses);
+    println!(
+        "    inputs: {} private, {} public, {} return values",
+        stats.private_inputs, stats.public_inputs, stats.return_values
+    );
+
+    let Some(lifetimes) = &stats.lifetimes else {
+        return;
+    };
+    println!(
+        "    peak live witnesses: {} ({} could be saved by reusing dead witnesses)",
+        lifetimes.peak_live, lifetimes.reusable
+    );
+    if lifetimes.long_lived.is_empty() {
+        return;
+    }
+    println!("    long-lived witnesses:");
+    for lifetime in &lifetimes.long_lived {
+        println!(
+            "      w{:<10} opcodes {} to {} ({} opcodes)",
+            lifetime.witness,
+            lifetime.defined_at,
+            lifetime.last_used_at,
+            lifetime.length()
+        );
+    }
+}
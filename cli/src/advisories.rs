@@ -0,0 +1,90 @@
+//! # `advisories` Subcommand
+//!
+//! Resolves the dependencies of each package and reports the ones affected by an advisory
+//! of the advisory database, with the versions fixing them.
+
+use crate::error::AnalysisError;
+use nargo::workspace::Workspace;
+use noir_analyzer::advisories::{ADVISORY_DB_FILE_NAME, AdvisoryDatabase, resolve_dependencies};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+
+/// Runs the `advisories` subcommand. Returns whether no dependency is affected.
+pub fn run(
+    workspace: &Workspace,
+    database: Option<PathBuf>,
+    json: bool,
+) -> Result<bool, AnalysisError> {
+    let database_path = database.unwrap_or_else(|| workspace.root_dir.join(ADVISORY_DB_FILE_NAME));
+    let database = AdvisoryDatabase::load(&database_path)?;
+
+    let mut clean = true;
+    let mut packages = serde_json::Map::new();
+    for package in &workspace.members {
+        let dependencies = resolve_dependencies(&package.root_dir.join("Nargo.toml"))?;
+        let affected = database.check(&dependencies);
+        clean &= affected.is_empty();
+
+        if json {
+            let affected: Vec<Value> = affected
+                .iter()
+                .map(|affected| {
+                    json!({
+                        "dependency": affected.dependency.name,
+                        "source": affected.dependency.source.to_string(),
+                        "version": affected.dependency.version,
+                        "required_by": affected.dependency.required_by,
+                        "advisory": affected.advisory.id,
+                        "title": affected.advisory.title,
+                        "severity": affected.advisory.severity.to_string(),
+                        "patched": affected.advisory.patched,
+                        "url": affected.advisory.url,
+                        "unknown_version": affected.unknown_version,
+                    })
+                })
+                .collect();
+            packages.insert(package.name.to_string(), Value::Array(affected));
+            continue;
+        }
+
+        println!(
+            "Package: {} ({} dependencies)",
+            package.name,
+            dependencies.len()
+        );
+        if affected.is_empty() {
+            println!("  No known advisories");
+            continue;
+        }
+        for affected in &affected {
+            let dependency = &affected.dependency;
+            let advisory = &affected.advisory;
+            println!(
+                "  {} ({}): {} {} ({})",
+                advisory.id,
+                advisory.severity,
+                dependency.name,
+                dependency.version.as_deref().unwrap_or("unknown version"),
+                dependency.source
+            );
+            println!("    {}", advisory.title);
+            if affected.unknown_version {
+                println!("    version is not a release, so it may not be affected");
+            }
+            println!("    required by: {}", dependency.required_by.join(" -> "));
+            if advisory.patched.is_empty() {
+                println!("    no patched version");
+            } else {
+                println!("    upgrade to: {}", advisory.patched.join(", "));
+            }
+            if let Some(url) = &advisory.url {
+                println!("    see {url}");
+            }
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&packages)?);
+    }
+    Ok(clean)
+}
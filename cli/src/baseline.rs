@@ -0,0 +1,62 @@
+//! # `baseline` Subcommand
+//!
+//! Maintains the baseline of accepted findings. Warnings printed by `nargo compile` can be
+//! imported, so that teams migrating to the analyzer start from the warnings they already
+//! accepted.
+
+use crate::error::{AnalysisError, read_file};
+use nargo::workspace::Workspace;
+use noir_analyzer::baseline::nargo::parse_warnings;
+use noir_analyzer::baseline::{BASELINE_FILE_NAME, Baseline};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Subcommands of `baseline`.
+#[derive(Debug, clap::Subcommand)]
+pub enum BaselineCommand {
+    /// Add the warnings printed by `nargo compile` to the baseline
+    ImportNargo {
+        /// File containing the nargo output, `-` to read it from stdin
+        input: PathBuf,
+        /// Baseline file to update. Defaults to `noir-analyzer-baseline.json` in the
+        /// workspace root
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Runs a `baseline` subcommand.
+pub fn run(workspace: &Workspace, command: BaselineCommand) -> Result<(), AnalysisError> {
+    match command {
+        BaselineCommand::ImportNargo { input, output } => {
+            let nargo_output = if input.as_os_str() == "-" {
+                let mut content = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut content)
+                    .map_err(|e| AnalysisError::io(Path::new("<stdin>"), e))?;
+                content
+            } else {
+                read_file(&input)?
+            };
+
+            let imported = parse_warnings(&nargo_output);
+            let imported_count = imported.len();
+
+            // Keep the entries of an existing baseline
+            let output = output.unwrap_or_else(|| workspace.root_dir.join(BASELINE_FILE_NAME));
+            let mut entries = if output.exists() {
+                Baseline::load(&output)?.entries
+            } else {
+                vec![]
+            };
+            entries.extend(imported);
+
+            Baseline::new(entries).save(&output)?;
+            println!(
+                "Imported {imported_count} warnings into {}",
+                output.display()
+            );
+            Ok(())
+        }
+    }
+}
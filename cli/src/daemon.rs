@@ -0,0 +1,400 @@
+//! # `daemon` Subcommand
+//!
+//! Keeps the analyzer running in the background and serves JSON-RPC 2.0 requests over a
+//! local Unix socket. Every analyzed file is cached together with its findings and is only
+//! parsed and analyzed again once it changes on disk, so build tools and editors get answers
+//! in milliseconds without going through a language server.
+//!
+//! Requests and responses are exchanged one JSON object per line. The supported methods are:
+//!
+//! - `analyze` with `{ "path": ... }` returns the findings of a file, in the same shape as
+//!   `check --format json`.
+//! - `fix` with `{ "path": ..., "allowRisky": false, "write": false }` returns the fixed
+//!   source along with the applied and skipped fixes, verified like those of `check --fix`.
+//!   The file is only rewritten when `write` is set, and restored when the fixes break
+//!   parsing or introduce findings.
+//! - `explain` with `{ "rule": ... }` describes a lint rule.
+//! - `shutdown` stops the daemon.
+//!
+//! Files are analyzed with the rules `check` runs on them, including those specific to
+//! their package. Relative paths are resolved against the workspace root, and paths outside
+//! of it are rejected.
+
+use crate::LinterOptions;
+use crate::error::AnalysisError;
+use crate::fix::{self, FixOptions};
+use crate::progress::Progress;
+use nargo::workspace::Workspace;
+use noir_analyzer::ast::parser::Parser;
+use noir_analyzer::baseline::{BASELINE_FILE_NAME, Baseline};
+use noir_analyzer::config::Config;
+use noir_analyzer::diagnostics::finding::{Finding, sort_findings};
+use noir_analyzer::diagnostics::reporter::Reporter;
+use noir_analyzer::lints::registry::LintRegistry;
+use serde_json::{Value, json};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::SystemTime;
+
+/// Name of the socket created in the workspace root when no socket path is given.
+pub const SOCKET_FILE_NAME: &str = ".noir-analyzer.sock";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// A JSON-RPC error object.
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// A source file analyzed by the daemon, reused until the file is modified.
+struct CachedFile {
+    modified: SystemTime,
+    findings: Vec<Finding>,
+}
+
+/// State shared by every connection of the daemon.
+struct Daemon {
+    workspace: Workspace,
+    /// Canonical path of the workspace root, which requested paths must be within
+    canonical_root: PathBuf,
+    config: Config,
+    baseline: Option<Baseline>,
+    files: HashMap<PathBuf, CachedFile>,
+    /// Registers the analyzed files, giving each its own file ID
+    parser: RefCell<Parser>,
+}
+
+/// Runs the daemon until a `shutdown` request is received.
+pub fn run(workspace: &Workspace, socket: Option<PathBuf>) -> Result<(), AnalysisError> {
+    let config = Config::load_from_dir(&workspace.root_dir)?;
+    // Invalid lint options fail at startup rather than on every request
+    crate::configured_lints(&config, true)?;
+    let baseline_path = workspace.root_dir.join(BASELINE_FILE_NAME);
+    let baseline = if baseline_path.exists() {
+        Some(Baseline::load(&baseline_path)?)
+    } else {
+        None
+    };
+
+    let socket_path = socket.unwrap_or_else(|| workspace.root_dir.join(SOCKET_FILE_NAME));
+    if socket_path.exists() {
+        if UnixStream::connect(&socket_path).is_ok() {
+            return Err(AnalysisError::Usage(format!(
+                "a daemon is already listening on {}",
+                socket_path.display()
+            )));
+        }
+        // Left behind by a daemon that did not shut down cleanly
+        fs::remove_file(&socket_path).map_err(|e| AnalysisError::io(&socket_path, e))?;
+    }
+    let listener =
+        UnixListener::bind(&socket_path).map_err(|e| AnalysisError::io(&socket_path, e))?;
+    eprintln!("Listening on {}", socket_path.display());
+
+    let canonical_root = workspace
+        .root_dir
+        .canonicalize()
+        .map_err(|e| AnalysisError::io(&workspace.root_dir, e))?;
+    let daemon = Arc::new(Mutex::new(Daemon {
+        workspace: workspace.clone(),
+        canonical_root,
+        config,
+        baseline,
+        files: HashMap::new(),
+        parser: RefCell::new(Parser::new(&workspace.root_dir)),
+    }));
+    let shutdown = Arc::new(AtomicBool::new(false));
+
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Error accepting connection: {e}");
+                continue;
+            }
+        };
+
+        let daemon = Arc::clone(&daemon);
+        let shutdown = Arc::clone(&shutdown);
+        let socket_path = socket_path.clone();
+        thread::spawn(move || {
+            if let Err(e) = serve(stream, &daemon, &shutdown) {
+                eprintln!("Error serving connection: {e}");
+            }
+            if shutdown.load(Ordering::SeqCst) {
+                // Wake up the listener, so it notices the shutdown
+                let _ = UnixStream::connect(&socket_path);
+            }
+        });
+    }
+
+    fs::remove_file(&socket_path).map_err(|e| AnalysisError::io(&socket_path, e))?;
+    Ok(())
+}
+
+/// Answers the requests of a connection, until the client disconnects or asks for a
+/// shutdown.
+fn serve(stream: UnixStream, daemon: &Mutex<Daemon>, shutdown: &AtomicBool) -> std::io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (response, stop) = match serde_json::from_str::<Value>(&line) {
+            Ok(request) => {
+                let mut daemon = daemon.lock().unwrap_or_else(|e| e.into_inner());
+                daemon.handle(&request)
+            }
+            Err(e) => (
+                error_response(Value::Null, RpcError::new(PARSE_ERROR, e.to_string())),
+                false,
+            ),
+        };
+        writeln!(writer, "{response}")?;
+
+        if stop {
+            shutdown.store(true, Ordering::SeqCst);
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+impl Daemon {
+    /// Handles a JSON-RPC request, returning the response and whether the daemon should stop.
+    fn handle(&mut self, request: &Value) -> (Value, bool) {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let Some(method) = request.get("method").and_then(Value::as_str) else {
+            return (
+                error_response(id, RpcError::new(INVALID_REQUEST, "missing method")),
+                false,
+            );
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "analyze" => self.analyze(&params),
+            "fix" => self.fix(&params),
+            "explain" => explain(&params),
+            "shutdown" => return (success_response(id, Value::Null), true),
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method '{method}'"),
+            )),
+        };
+
+        match result {
+            Ok(result) => (success_response(id, result), false),
+            Err(error) => (error_response(id, error), false),
+        }
+    }
+
+    /// Returns the findings of a file.
+    fn analyze(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let path = self.path_param(params)?;
+        let root = self.workspace.root_dir.clone();
+        let file = self.file(&path)?;
+
+        Ok(Reporter::json_value(&file.findings, &root))
+    }
+
+    /// Applies the suggestions of the findings of a file.
+    fn fix(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let path = self.path_param(params)?;
+        let allow_risky = params
+            .get("allowRisky")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let write = params
+            .get("write")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        let file = self.file(&path)?;
+        let suggestions = file
+            .findings
+            .iter()
+            .filter_map(|finding| finding.lint.suggestion.clone())
+            .collect();
+        let options = FixOptions {
+            allow_risky,
+            rollback: true,
+            dry_run: !write,
+        };
+        let file_fix = fix::fix_file(&path, suggestions, &self.config, &options)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+
+        let applied: Vec<&str> = file_fix
+            .applied
+            .iter()
+            .map(|suggestion| suggestion.message.as_str())
+            .collect();
+        let skipped: Vec<Value> = file_fix
+            .skipped
+            .iter()
+            .map(|skipped| {
+                json!({
+                    "message": skipped.suggestion.message,
+                    "reason": skipped.reason.to_string(),
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "source": file_fix.fixed,
+            "applied": applied,
+            "skipped": skipped,
+            "error": file_fix.problem,
+            "rolledBack": file_fix.rolled_back,
+        }))
+    }
+
+    /// Resolves the `path` parameter against the workspace root, rejecting paths that lead
+    /// outside of it, such as `../secret.nr` or absolute paths of other directories.
+    fn path_param(&self, params: &Value) -> Result<PathBuf, RpcError> {
+        let path = params
+            .get("path")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "missing 'path' parameter"))?;
+
+        let canonical_path = self
+            .workspace
+            .root_dir
+            .join(path)
+            .canonicalize()
+            .map_err(|e| RpcError::new(INVALID_PARAMS, format!("{path}: {e}")))?;
+        let relative_path = canonical_path
+            .strip_prefix(&self.canonical_root)
+            .map_err(|_| {
+                RpcError::new(
+                    INVALID_PARAMS,
+                    format!("{path} is outside of the workspace root"),
+                )
+            })?;
+        // Keep the paths of the workspace members, which packages are matched by
+        Ok(self.workspace.root_dir.join(relative_path))
+    }
+
+    /// Returns the cached analysis of a file, analyzing it again when it was modified.
+    fn file(&mut self, path: &Path) -> Result<&CachedFile, RpcError> {
+        let io_error =
+            |e: std::io::Error| RpcError::new(INTERNAL_ERROR, format!("{}: {e}", path.display()));
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(io_error)?;
+
+        let is_fresh = self
+            .files
+            .get(path)
+            .is_some_and(|file| file.modified == modified);
+        if !is_fresh {
+            let source = fs::read_to_string(path).map_err(io_error)?;
+            let findings = self.analyze_source(path, &source)?;
+            self.files
+                .insert(path.to_path_buf(), CachedFile { modified, findings });
+        }
+
+        Ok(&self.files[path])
+    }
+
+    /// Runs the lint rules `check` runs on a file, for the package it belongs to, leaving out
+    /// the findings of the baseline.
+    fn analyze_source(&mut self, path: &Path, source: &str) -> Result<Vec<Finding>, RpcError> {
+        let progress = Progress::new(0, false);
+        let options = LinterOptions {
+            config: &self.config,
+            changes: None,
+            scope: None,
+            jobs: 1,
+            low_memory: false,
+            expand: false,
+            typed: false,
+            root: &self.workspace.root_dir,
+            progress: &progress,
+            parser: &self.parser,
+        };
+        let package = crate::member_of(&self.workspace, path);
+        let (_, lints, _) = crate::run_linters(&path.to_path_buf(), &options, package)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+
+        let root = &self.workspace.root_dir;
+        let mut findings: Vec<Finding> = lints
+            .into_iter()
+            .map(|lint| Finding::new(lint, path, source))
+            .filter(|finding| {
+                self.baseline
+                    .as_ref()
+                    .is_none_or(|baseline| !baseline.contains(finding, root))
+            })
+            .collect();
+        sort_findings(&mut findings);
+        Ok(findings)
+    }
+}
+
+/// Describes the lint rule named by the `rule` parameter.
+fn explain(params: &Value) -> Result<Value, RpcError> {
+    let rule = params
+        .get("rule")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "missing 'rule' parameter"))?;
+
+    let explanation = LintRegistry::builtin()
+        .get(rule)
+        .map(|metadata| metadata.description)
+        .or_else(|| {
+            crate::registered_workspace_lints()
+                .iter()
+                .find(|lint_rule| lint_rule.name() == rule)
+                .map(|lint_rule| lint_rule.explanation())
+        });
+    let explanation = explanation.or_else(|| {
+        crate::registered_typed_lints()
+            .iter()
+            .find(|lint_rule| lint_rule.name() == rule)
+            .map(|lint_rule| lint_rule.explanation())
+    });
+    let explanation = explanation
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("unknown rule '{rule}'")))?;
+
+    Ok(json!({ "rule": rule, "explanation": explanation }))
+}
+
+fn success_response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
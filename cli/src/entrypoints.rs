@@ -0,0 +1,75 @@
+//! # `entrypoints` Subcommand
+//!
+//! Prints the functions of each package that can be reached from outside of it, with their
+//! signature, the visibility of their inputs and output, their attributes and an estimate
+//! of their constraint cost: a map of the surface to audit first.
+
+use crate::error::{AnalysisError, parse_file, read_file};
+use nargo::package::PackageType;
+use nargo::workspace::Workspace;
+use noir_analyzer::analysis::entry_points::{EntryPoint, entry_points};
+use noir_analyzer::ast::parser::Parser;
+
+/// Runs the `entrypoints` subcommand.
+pub fn run(workspace: &Workspace) -> Result<(), AnalysisError> {
+    let mut parser = Parser::new(&workspace.root_dir);
+    for package in &workspace.members {
+        let source = read_file(&package.entry_path)?;
+        let parsed_module = parse_file(&mut parser, &package.entry_path, &source)?;
+        let entry_points =
+            entry_points(&parsed_module, package.package_type == PackageType::Library);
+
+        println!(
+            "Package: {} ({})",
+            package.name,
+            package.entry_path.display()
+        );
+        if entry_points.is_empty() {
+            println!("  No entry points");
+            continue;
+        }
+
+        let path_width = entry_points
+            .iter()
+            .map(|entry_point| entry_point.path.len())
+            .max()
+            .unwrap_or(0);
+        for entry_point in &entry_points {
+            println!(
+                "  {:<8} {:<path_width$}  {:<10} {:>13}  {}{}  line {}",
+                entry_point.kind.as_str(),
+                entry_point.path,
+                entry_point.visibility,
+                cost(entry_point),
+                entry_point.signature(),
+                attributes(entry_point),
+                line(&source, entry_point),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the estimated cost column of an entry point.
+fn cost(entry_point: &EntryPoint) -> String {
+    if entry_point.is_unconstrained {
+        "unconstrained".to_string()
+    } else {
+        format!("~{}", entry_point.estimated_cost)
+    }
+}
+
+/// Returns the attributes of an entry point, preceded by a space when there are any.
+fn attributes(entry_point: &EntryPoint) -> String {
+    entry_point
+        .attributes
+        .iter()
+        .map(|attribute| format!(" {attribute}"))
+        .collect()
+}
+
+/// Returns the 1-based line where an entry point is declared.
+fn line(source: &str, entry_point: &EntryPoint) -> usize {
+    let start = (entry_point.span.start() as usize).min(source.len());
+    source[..start].matches('\n').count() + 1
+}
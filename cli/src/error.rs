@@ -0,0 +1,209 @@
+//! # CLI Errors
+//!
+//! Every failure of a subcommand is an `AnalysisError`. It is rendered for users with a hint
+//! on how to fix it, on stderr, or as a JSON document on stdout when the output format is
+//! `json`, so that tools reading the report can tell a failed run from a clean one.
+//!
+//! Panics of the analyzer are caught and reported as internal errors too.
+
+use crate::OutputFormat;
+use noir_analyzer::acir::checker::AcirError;
+use noir_analyzer::advisories::AdvisoryError;
+use noir_analyzer::ast::analyzer::AnalyzerError;
+use noir_analyzer::ast::parser::Parser;
+use noir_analyzer::baseline::BaselineError;
+use noir_analyzer::changes::ChangesError;
+use noir_analyzer::config::ConfigError;
+use noir_analyzer::diagnostics::internal_error::panic_message;
+use noir_analyzer::health::HistoryError;
+use noir_analyzer::noirc_frontend::ParsedModule;
+use noir_analyzer::sources::SourceError;
+use noir_analyzer::webhook::WebhookError;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Why a subcommand failed.
+#[derive(Debug, Error)]
+pub enum AnalysisError {
+    #[error("Could not find Nargo.toml in {0:?} or its parents")]
+    ManifestNotFound(PathBuf),
+    #[error("Invalid manifest {path:?}: {message}")]
+    Manifest { path: PathBuf, message: String },
+    /// The manifest lays out neither a single package nor a workspace of packages.
+    #[error("Invalid workspace manifest {path:?}: {message}")]
+    WorkspaceManifest { path: PathBuf, message: String },
+    #[error("Failed to access {path:?}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Failed to parse {path:?}: {errors} syntax error(s)")]
+    Parse { path: PathBuf, errors: usize },
+    #[error("Failed to analyze {path:?}: {source}")]
+    Analyzer {
+        path: PathBuf,
+        source: AnalyzerError,
+    },
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Baseline(#[from] BaselineError),
+    #[error(transparent)]
+    Advisory(#[from] AdvisoryError),
+    #[error(transparent)]
+    Changes(#[from] ChangesError),
+    #[error(transparent)]
+    History(#[from] HistoryError),
+    #[error(transparent)]
+    Sources(#[from] SourceError),
+    #[error(transparent)]
+    Webhook(#[from] WebhookError),
+    #[error(transparent)]
+    Acir(#[from] AcirError),
+    /// The command cannot run as requested, e.g. an unknown `--package`.
+    #[error("{0}")]
+    Usage(String),
+    /// A bug of the analyzer, such as a panic.
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl AnalysisError {
+    /// Wraps an error accessing `path`.
+    pub fn io(path: &Path, source: io::Error) -> Self {
+        Self::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// Converts the payload of a caught panic.
+    pub fn panic(payload: Box<dyn Any + Send>) -> Self {
+        Self::Internal(panic_message(payload.as_ref()))
+    }
+
+    /// Short identifier of the kind of error, for machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ManifestNotFound(_) => "manifest-not-found",
+            Self::Manifest { .. } => "manifest",
+            Self::WorkspaceManifest { .. } => "workspace-manifest",
+            Self::Io { .. } => "io",
+            Self::Parse { .. } => "parse",
+            Self::Analyzer { .. } => "analyzer",
+            Self::Config(_) => "config",
+            Self::Baseline(_) => "baseline",
+            Self::Advisory(_) => "advisory",
+            Self::Changes(_) => "changes",
+            Self::History(_) => "history",
+            Self::Sources(_) => "sources",
+            Self::Webhook(_) => "webhook",
+            Self::Acir(_) => "acir",
+            Self::Usage(_) => "usage",
+            Self::Internal(_) => "internal",
+        }
+    }
+
+    /// Suggests how to fix the error, when there is a usual fix.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::ManifestNotFound(_) => Some(
+                "run the analyzer inside a Noir project, or pass --program-dir or --manifest-path",
+            ),
+            Self::Manifest { .. } => {
+                Some("the [package] section needs a `name` and a `type` of bin, lib or contract")
+            }
+            Self::WorkspaceManifest { .. } => Some(
+                "declare either a [package], or a [workspace] whose `members` are directories with a Nargo.toml declaring a [package]",
+            ),
+            Self::Parse { .. } => Some("run `nargo check` to see the syntax errors"),
+            Self::Analyzer { .. } => Some(
+                "run `nargo check` on the file, and report the error at https://github.com/walnuthq/noir-static-analyzer/issues if it passes",
+            ),
+            Self::Config(_) => Some("fix noir-analyzer.toml, or recreate it with `init --force`"),
+            Self::Baseline(_) => Some("fix the baseline file, or delete it to start afresh"),
+            Self::Changes(_) => {
+                Some("--changed-since needs git, and a reference that exists in the repository")
+            }
+            Self::History(_) => Some("fix or delete target/noir-analyzer/history.json"),
+            Self::Sources(_) => Some("check the glob patterns of the command line and `exclude`"),
+            Self::Webhook(_) => {
+                Some("check the [webhook] url of noir-analyzer.toml, and that curl is installed")
+            }
+            Self::Acir(_) => Some(
+                "compile the package again with `nargo compile`, using the Noir release the analyzer is built against",
+            ),
+            Self::Internal(_) => Some(
+                "this is a bug, please report it at https://github.com/walnuthq/noir-static-analyzer/issues",
+            ),
+            Self::Io { .. } | Self::Advisory(_) | Self::Usage(_) => None,
+        }
+    }
+
+    /// Renders the error for users, followed by its hint.
+    pub fn render(&self) -> String {
+        let mut rendered = format!("\x1b[1;31merror\x1b[0m: {self}\n");
+        if let Some(hint) = self.hint() {
+            rendered.push_str(&format!("  \x1b[1;36mhint\x1b[0m: {hint}\n"));
+        }
+        rendered
+    }
+
+    /// Describes the error as JSON, with its kind, message and hint.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "kind": self.kind(),
+            "message": self.to_string(),
+            "hint": self.hint(),
+        })
+    }
+
+    /// Reports the error in the given output format: as a JSON document on stdout for
+    /// `json`, or rendered on stderr otherwise.
+    pub fn report(&self, format: OutputFormat) {
+        match format {
+            OutputFormat::Json => println!("{}", json!({ "error": self.to_json() })),
+            OutputFormat::Pretty
+            | OutputFormat::Short
+            | OutputFormat::Sarif
+            | OutputFormat::Markdown => {
+                eprint!("{}", self.render())
+            }
+        }
+    }
+}
+
+impl From<serde_json::Error> for AnalysisError {
+    fn from(error: serde_json::Error) -> Self {
+        // Serializing the reports only fails on bugs
+        Self::Internal(error.to_string())
+    }
+}
+
+/// Reads a file, keeping its path in the error.
+pub fn read_file(path: &Path) -> Result<String, AnalysisError> {
+    fs::read_to_string(path).map_err(|e| AnalysisError::io(path, e))
+}
+
+/// Parses a Noir source file, registering it with `parser` and keeping its path in the error.
+pub fn parse_file(
+    parser: &mut Parser,
+    path: &Path,
+    source: &str,
+) -> Result<ParsedModule, AnalysisError> {
+    parser
+        .parse_source(path, source)
+        .map_err(|e| AnalysisError::Parse {
+            path: path.to_path_buf(),
+            errors: match e {
+                AnalyzerError::ParsingError(errors) => errors.len(),
+                _ => 1,
+            },
+        })
+}
+
+/// Writes a file, keeping its path in the error.
+pub fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), AnalysisError> {
+    fs::write(path, contents).map_err(|e| AnalysisError::io(path, e))
+}
@@ -0,0 +1,241 @@
+//! # Fix Application
+//!
+//! Applies the suggestions of the reported findings to the files they were reported in,
+//! as requested by `check --fix`. A summary of the applied and skipped fixes is printed to
+//! stderr, so machine-readable reports on stdout stay intact.
+//!
+//! Every rewritten file is analyzed again. Findings introduced by the fixes that come with a
+//! fix of their own, such as a function only the removed code called, are fixed in turn,
+//! for a few rounds. A fix that breaks parsing or leaves new findings is reported as an
+//! error and, with `--rollback`, the file is restored. With `--dry-run`, files are left
+//! untouched and the changes are printed as a unified diff instead. The `fix` method of the
+//! daemon goes through the same steps.
+
+use crate::error::{AnalysisError, read_file, write_file};
+use noir_analyzer::ast::analyzer::Analyzer;
+use noir_analyzer::ast::parser::Parser;
+use noir_analyzer::config::Config;
+use noir_analyzer::diagnostics::finding::Finding;
+use noir_analyzer::diagnostics::lint::Lint;
+use noir_analyzer::diagnostics::suggestion::Suggestion;
+use noir_analyzer::fix::{Fixer, SkippedFix};
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Number of times a file is analyzed again for the fixes of the findings introduced by
+/// earlier fixes.
+const MAX_ROUNDS: usize = 8;
+
+/// How `check --fix` applies suggestions.
+pub struct FixOptions {
+    /// Also apply suggestions that are not machine-applicable.
+    pub allow_risky: bool,
+    /// Restore files whose fixes fail verification.
+    pub rollback: bool,
+    /// Print the changes as a unified diff instead of rewriting files.
+    pub dry_run: bool,
+}
+
+/// The fixes applied to a file.
+pub struct FileFix {
+    /// Source of the file before the fixes.
+    pub source: String,
+    /// Source of the file after the fixes.
+    pub fixed: String,
+    /// Suggestions applied, over every round.
+    pub applied: Vec<Suggestion>,
+    /// Suggestions left out, with the reason why.
+    pub skipped: Vec<SkippedFix>,
+    /// Why the fixed source failed verification, when it did.
+    pub problem: Option<String>,
+    /// Whether the file was restored after failing verification.
+    pub rolled_back: bool,
+}
+
+/// Applies the suggestions of the findings, rewriting the affected files.
+///
+/// Returns whether every rewritten file passed verification.
+pub fn run(
+    findings: &[Finding],
+    config: &Config,
+    options: &FixOptions,
+) -> Result<bool, AnalysisError> {
+    let mut suggestions_by_file: BTreeMap<&Path, Vec<Suggestion>> = BTreeMap::new();
+    for finding in findings {
+        if let Some(suggestion) = &finding.lint.suggestion {
+            suggestions_by_file
+                .entry(&finding.file_path)
+                .or_default()
+                .push(suggestion.clone());
+        }
+    }
+
+    let mut verified = true;
+    for (file_path, suggestions) in suggestions_by_file {
+        let file_fix = fix_file(file_path, suggestions, config, options)?;
+        for skipped in &file_fix.skipped {
+            eprintln!(
+                "Skipped fix in {} ({}): {}",
+                file_path.display(),
+                skipped.suggestion.message,
+                skipped.reason
+            );
+        }
+        if file_fix.applied.is_empty() {
+            continue;
+        }
+
+        if options.dry_run {
+            let path = file_path.display().to_string();
+            print!(
+                "{}",
+                TextDiff::from_lines(&file_fix.source, &file_fix.fixed)
+                    .unified_diff()
+                    .header(&path, &path)
+            );
+        }
+        let verb = if options.dry_run {
+            "Would fix"
+        } else {
+            "Fixed"
+        };
+        match &file_fix.problem {
+            None => eprintln!(
+                "{verb} {} issue(s) in {}",
+                file_fix.applied.len(),
+                file_path.display()
+            ),
+            Some(problem) => {
+                verified = false;
+                eprintln!("error: fixes applied to {} {problem}", file_path.display());
+                if file_fix.rolled_back {
+                    eprintln!("Rolled back {}", file_path.display());
+                }
+            }
+        }
+    }
+
+    Ok(verified)
+}
+
+/// Applies suggestions to a file, along with the fixes of the findings they introduce, and
+/// verifies the fixed source. Unless `dry_run` is set, the file is rewritten, then restored
+/// with `rollback` when the fixes fail verification.
+pub fn fix_file(
+    file_path: &Path,
+    suggestions: Vec<Suggestion>,
+    config: &Config,
+    options: &FixOptions,
+) -> Result<FileFix, AnalysisError> {
+    let source = read_file(file_path)?;
+    let fixer = Fixer::new(options.allow_risky);
+    let (fixed, applied, skipped) = apply_rounds(&fixer, &source, suggestions, config);
+    let mut file_fix = FileFix {
+        source,
+        fixed,
+        applied,
+        skipped,
+        problem: None,
+        rolled_back: false,
+    };
+    if file_fix.applied.is_empty() {
+        return Ok(file_fix);
+    }
+
+    if !options.dry_run {
+        write_file(file_path, &file_fix.fixed)?;
+    }
+    file_fix.problem = verify(&file_fix.source, &file_fix.fixed, config).err();
+    if file_fix.problem.is_some() && options.rollback && !options.dry_run {
+        write_file(file_path, &file_fix.source)?;
+        file_fix.rolled_back = true;
+    }
+    Ok(file_fix)
+}
+
+/// Applies the suggestions to a source, then the suggestions of the findings the fixes
+/// introduce, until no fix applies. Returns the fixed source with the applied and skipped
+/// suggestions.
+fn apply_rounds(
+    fixer: &Fixer,
+    source: &str,
+    suggestions: Vec<Suggestion>,
+    config: &Config,
+) -> (String, Vec<Suggestion>, Vec<SkippedFix>) {
+    let before = analyze(source, config).map(|lints| config.apply(lints));
+    let mut fixed = source.to_string();
+    let mut applied = vec![];
+    let mut skipped = vec![];
+    let mut suggestions = suggestions;
+    for _ in 0..MAX_ROUNDS {
+        let outcome = fixer.apply(&fixed, suggestions);
+        skipped.extend(outcome.skipped);
+        if outcome.applied.is_empty() {
+            break;
+        }
+        applied.extend(outcome.applied);
+        fixed = outcome.source;
+
+        let (Ok(before), Ok(after)) = (&before, analyze(&fixed, config)) else {
+            break;
+        };
+        let after = config.apply(after);
+        suggestions = introduced(before, &after)
+            .into_iter()
+            .filter_map(|lint| lint.suggestion.clone())
+            .collect();
+    }
+    (fixed, applied, skipped)
+}
+
+/// Analyzes the fixed source again, checking that it still parses and that no finding was
+/// introduced compared to the original source.
+fn verify(original: &str, fixed: &str, config: &Config) -> Result<(), String> {
+    let before = config.apply(analyze(original, config)?);
+    let after =
+        config.apply(analyze(fixed, config).map_err(|_| "introduced parse errors".to_string())?);
+
+    let introduced: Vec<String> = introduced(&before, &after)
+        .into_iter()
+        .map(|lint| format!("{}: {}", lint.name, lint.description))
+        .collect();
+    if introduced.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "introduced new findings:\n  {}",
+            introduced.join("\n  ")
+        ))
+    }
+}
+
+/// Returns the lints of `after` that `before` does not have.
+fn introduced<'a>(before: &[Lint], after: &'a [Lint]) -> Vec<&'a Lint> {
+    // Findings are compared by rule and message, since fixes move code around
+    let mut remaining: Vec<(&str, &str)> = before
+        .iter()
+        .map(|lint| (lint.name, lint.description.as_str()))
+        .collect();
+    let mut introduced = vec![];
+    for lint in after {
+        let key = (lint.name, lint.description.as_str());
+        match remaining.iter().position(|existing| *existing == key) {
+            Some(index) => {
+                remaining.swap_remove(index);
+            }
+            None => introduced.push(lint),
+        }
+    }
+    introduced
+}
+
+/// Runs every registered lint rule on a source.
+fn analyze(source: &str, config: &Config) -> Result<Vec<Lint>, String> {
+    let parsed_module =
+        Parser::parse_program_with_dummy_file(source).map_err(|e| format!("{e:?}"))?;
+    Analyzer::new(&crate::configured_lints(config, false).map_err(|e| e.to_string())?)
+        .with_source(source)
+        .analyze(&parsed_module)
+        .map_err(|e| format!("{e:?}"))
+}
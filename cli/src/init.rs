@@ -0,0 +1,251 @@
+//! # `init` Subcommand
+//!
+//! Inspects the workspace and writes a commented `noir-analyzer.toml` with a recommended
+//! lint profile. Optionally prints a pre-commit hook snippet and writes a wrapper script
+//! that CI systems can call without knowing about the analyzer's flags.
+
+use crate::error::{AnalysisError, write_file};
+use nargo::package::PackageType;
+use nargo::workspace::Workspace;
+use noir_analyzer::config::CONFIG_FILE_NAME;
+use noir_analyzer::lints::registry::LintRegistry;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Name of the generated wrapper script.
+const WRAPPER_SCRIPT_NAME: &str = "noir-analyzer.sh";
+
+const PRE_COMMIT_SNIPPET: &str = r#"# Add to .pre-commit-config.yaml
+repos:
+  - repo: local
+    hooks:
+      - id: noir-analyzer
+        name: noir-analyzer
+        entry: noir-analyzer check
+        language: system
+        files: \.nr$
+        pass_filenames: false
+"#;
+
+const WRAPPER_SCRIPT: &str = r#"#!/bin/sh
+# Runs noir-analyzer on this workspace and exits with a failure status when a
+# lint configured as "deny" is reported. Generated by `noir-analyzer init`.
+set -eu
+
+cd "$(dirname "$0")"
+
+if ! command -v noir-analyzer >/dev/null 2>&1; then
+    echo "noir-analyzer is not installed, install it with:" >&2
+    echo "  cargo install --git https://github.com/walnuthq/noir-static-analyzer" >&2
+    exit 127
+fi
+
+exec noir-analyzer check --manifest-path Nargo.toml "$@"
+"#;
+
+const LEVELS_COMMENT: &str = r#"#
+# Each entry under [lints] sets the level a lint is reported at:
+#   "allow" disables the lint, "warn" reports a warning and "deny" reports an
+#   error, making `noir-analyzer check` exit with a failure status.
+# Opt-in lints are listed commented out: uncomment them to enable them.
+
+"#;
+
+/// What `init` learned about a workspace member.
+struct PackageSummary {
+    name: String,
+    package_type: PackageType,
+    has_tests: bool,
+    has_contracts: bool,
+}
+
+/// Writes the configuration file and the optional helpers for the workspace.
+pub fn run(
+    workspace: &Workspace,
+    force: bool,
+    pre_commit: bool,
+    wrapper_script: bool,
+) -> Result<(), AnalysisError> {
+    let config_path = workspace.root_dir.join(CONFIG_FILE_NAME);
+    if config_path.exists() && !force {
+        return Err(AnalysisError::Usage(format!(
+            "{} already exists, pass --force to overwrite it",
+            config_path.display()
+        )));
+    }
+
+    let summaries: Vec<PackageSummary> = workspace
+        .members
+        .iter()
+        .map(|package| {
+            let sources = read_sources(&package.root_dir.join("src"));
+            PackageSummary {
+                name: package.name.to_string(),
+                package_type: package.package_type,
+                has_tests: sources.iter().any(|source| source.contains("#[test")),
+                has_contracts: package.package_type == PackageType::Contract
+                    || sources.iter().any(|source| {
+                        source
+                            .lines()
+                            .any(|line| line.trim_start().starts_with("contract "))
+                    }),
+            }
+        })
+        .collect();
+
+    write_file(&config_path, render_config(&summaries, &lint_entries()))?;
+    println!("Wrote {}", config_path.display());
+
+    if wrapper_script {
+        let script_path = workspace.root_dir.join(WRAPPER_SCRIPT_NAME);
+        write_file(&script_path, WRAPPER_SCRIPT)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755))
+                .map_err(|e| AnalysisError::io(&script_path, e))?;
+        }
+        println!("Wrote {}", script_path.display());
+    }
+
+    if pre_commit {
+        println!();
+        print!("{PRE_COMMIT_SNIPPET}");
+    }
+
+    Ok(())
+}
+
+/// Returns the name of every lint listed in the configuration, with whether it runs without
+/// being enabled.
+fn lint_entries() -> Vec<(&'static str, bool)> {
+    LintRegistry::builtin()
+        .metadata()
+        .map(|metadata| (metadata.name, metadata.enabled_by_default))
+        .chain(
+            crate::registered_workspace_lints()
+                .iter()
+                .map(|lint| (lint.name(), true)),
+        )
+        .collect()
+}
+
+/// Reads every `.nr` file below the given directory, skipping unreadable entries.
+fn read_sources(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut sources = vec![];
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            sources.extend(read_sources(&path));
+        } else if path.extension().is_some_and(|extension| extension == "nr") {
+            sources.extend(fs::read_to_string(&path));
+        }
+    }
+    sources
+}
+
+/// Returns the recommended level for a lint, with an optional explanation.
+fn recommended_level(
+    lint_name: &str,
+    summaries: &[PackageSummary],
+) -> (&'static str, Option<&'static str>) {
+    match lint_name {
+        "oversized-unsafe-block" if summaries.iter().any(|package| package.has_contracts) => (
+            "deny",
+            Some("Contracts are deployed code: keep unconstrained scopes minimal."),
+        ),
+        "duplicate-public-function" if summaries.len() < 2 => (
+            "allow",
+            Some("Single-package workspace: there is nothing to compare against."),
+        ),
+        "unused-function"
+            if summaries
+                .iter()
+                .any(|package| package.package_type == PackageType::Library) =>
+        {
+            (
+                "warn",
+                Some("Public functions of libraries are never reported as unused."),
+            )
+        }
+        _ => ("warn", None),
+    }
+}
+
+/// Renders the commented configuration file. Opt-in lints are written commented out, so that
+/// they stay off until enabled.
+fn render_config(summaries: &[PackageSummary], lints: &[(&'static str, bool)]) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "# Configuration for noir-analyzer, generated by `noir-analyzer init`."
+    )
+    .unwrap();
+    writeln!(output, "#").unwrap();
+    writeln!(output, "# Workspace members:").unwrap();
+    for package in summaries {
+        let mut traits = String::new();
+        if package.has_tests {
+            traits.push_str(", has tests");
+        }
+        if package.has_contracts {
+            traits.push_str(", has contracts");
+        }
+        writeln!(
+            output,
+            "#   - {} ({}){}",
+            package.name, package.package_type, traits
+        )
+        .unwrap();
+    }
+    output.push_str(LEVELS_COMMENT);
+    writeln!(output, "[lints]").unwrap();
+
+    for (lint_name, enabled_by_default) in lints {
+        let (level, reason) = recommended_level(lint_name, summaries);
+        if let Some(reason) = reason {
+            writeln!(output, "# {reason}").unwrap();
+        }
+        let comment = if *enabled_by_default { "" } else { "# " };
+        writeln!(output, "{comment}{lint_name} = \"{level}\"").unwrap();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use noir_analyzer::config::Config;
+
+    #[test]
+    fn test_opt_in_lints_stay_off() {
+        let summaries = [PackageSummary {
+            name: "circuit".to_string(),
+            package_type: PackageType::Binary,
+            has_tests: false,
+            has_contracts: false,
+        }];
+        let rendered = render_config(&summaries, &lint_entries());
+        assert!(rendered.contains("\nunused-function = \"warn\"\n"));
+        assert!(rendered.contains("\n# import-order = \"warn\"\n"));
+
+        let config = Config::parse(&rendered).expect("Should have parsed");
+        let mut registry = LintRegistry::builtin();
+        config.enable_configured(&mut registry);
+        for metadata in registry.metadata() {
+            assert_eq!(
+                registry.is_enabled(metadata.name),
+                metadata.enabled_by_default,
+                "{}",
+                metadata.name
+            );
+        }
+    }
+}
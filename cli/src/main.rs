@@ -1,13 +1,76 @@
+mod acir;
+mod advisories;
+mod baseline;
+#[cfg(unix)]
+mod daemon;
+mod entrypoints;
+mod error;
+mod fix;
+mod init;
+mod progress;
+mod provenance;
+mod recursive;
+mod schema;
+mod score;
+
+use error::{AnalysisError, parse_file, read_file};
 use nargo::package::{Package, PackageType};
 use nargo::workspace::Workspace;
+use noir_analyzer::acir::checker::{self, CircuitLint};
+use noir_analyzer::acir::correlation;
+use noir_analyzer::advisories::resolve_dependencies;
+use noir_analyzer::analysis::dependencies::DependencyGraph;
 use noir_analyzer::ast::analyzer::Analyzer;
+use noir_analyzer::ast::coverage::Coverage;
+use noir_analyzer::ast::flags;
 use noir_analyzer::ast::parser::Parser;
-use noir_analyzer::diagnostics::reporter::Reporter;
-use noir_analyzer::lints::lint_rule::LintRule;
+use noir_analyzer::baseline::{BASELINE_FILE_NAME, Baseline};
+use noir_analyzer::blame::Blamer;
+use noir_analyzer::changes::ChangedLines;
+use noir_analyzer::compiler_version::NoirVersion;
+use noir_analyzer::config::{CONFIG_FILE_NAME, Config, ConfigError, ConfigOverride};
+use noir_analyzer::diagnostics::finding::{Finding, sort_findings};
+use noir_analyzer::diagnostics::internal_error::{
+    INTERNAL_ERROR_LINT, analysis_panicked, rule_panicked,
+};
+use noir_analyzer::diagnostics::lint::{Confidence, Lint, Severity};
+use noir_analyzer::diagnostics::reporter::{LocationStyle, Reporter};
+use noir_analyzer::expansion;
+use noir_analyzer::expectations::{Expectations, UNFULFILLED_EXPECTATION};
+use noir_analyzer::hir::TypedContext;
+use noir_analyzer::lints::call_chain_depth::CallChainDepth;
+use noir_analyzer::lints::config_file::{self, lint_config};
+use noir_analyzer::lints::deep_constraint_expression::DeepConstraintExpression;
+use noir_analyzer::lints::dependency_hygiene::{DeclaredDependency, DependencyHygiene};
+use noir_analyzer::lints::god_module::GodModule;
+use noir_analyzer::lints::hint_drift::HintDrift;
+use noir_analyzer::lints::large_struct::LargeStruct;
+use noir_analyzer::lints::lint_rule::TypedLintRule;
+use noir_analyzer::lints::lint_rule::{LintRule, WorkspaceLintRule, WorkspacePackage};
+use noir_analyzer::lints::manifest::{self, lint_manifest};
+use noir_analyzer::lints::needless_visibility::NeedlessVisibility;
+use noir_analyzer::lints::oversized_entry_point::OversizedEntryPoint;
+use noir_analyzer::lints::prover_input_mismatch::ProverInputMismatch;
+use noir_analyzer::lints::registry::LintRegistry;
+use noir_analyzer::lints::truncating_cast::TruncatingCast;
+use noir_analyzer::lints::unsupported_syntax::UnsupportedSyntax;
+use noir_analyzer::noirc_frontend::ParsedModule;
+use noir_analyzer::scope::Scope;
+use noir_analyzer::sources::SourceWalker;
+use progress::Progress;
 use serde::Deserialize;
+use serde_json::{Value, json};
+use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Source files larger than this are analyzed in low-memory mode, even without `--low-memory`.
+const LOW_MEMORY_THRESHOLD: usize = 1024 * 1024;
 
 /// CLI arguments for the Noir Analyzer.
 #[derive(Debug, clap::Parser)]
@@ -17,15 +80,219 @@ use std::path::PathBuf;
     about = "Static analysis for Noir programs"
 )]
 struct Cli {
-    /// Path to the Nargo.toml file
-    #[arg(long, value_name = "PATH", default_value = "Nargo.toml")]
-    manifest_path: PathBuf,
+    /// Path to the Nargo.toml file. Defaults to the closest `Nargo.toml` in the program
+    /// directory or its parents, like nargo
+    #[arg(long, value_name = "PATH", global = true)]
+    manifest_path: Option<PathBuf>,
+
+    /// Directory of the Noir program, as accepted by nargo. Defaults to the current directory
+    #[arg(long, value_name = "DIR", global = true)]
+    program_dir: Option<PathBuf>,
+
+    /// Only analyze the workspace member with this name
+    #[arg(long, value_name = "NAME", global = true)]
+    package: Option<String>,
+
+    /// Analyze every member of the workspace. This is the default, the flag is accepted for
+    /// compatibility with nargo
+    #[arg(long, global = true, conflicts_with = "package")]
+    workspace: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Subcommands of the Noir Analyzer. Running without a subcommand is the same as `check`.
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Analyze the workspace and report lints
+    Check(CheckArgs),
+    /// Write a `noir-analyzer.toml` with a rule profile recommended for the workspace
+    Init {
+        /// Overwrite an existing configuration file
+        #[arg(long)]
+        force: bool,
+        /// Print a pre-commit hook snippet running the analyzer
+        #[arg(long)]
+        pre_commit: bool,
+        /// Write a `noir-analyzer.sh` wrapper script usable from any CI system
+        #[arg(long)]
+        wrapper_script: bool,
+    },
+    /// List the functions reachable from outside of each package, with their inputs, outputs,
+    /// attributes and estimated constraint cost
+    Entrypoints,
+    /// Print the parameters and return value of `main`, or of every contract function, with
+    /// their types and visibilities as JSON, to generate bindings from
+    Schema,
+    /// Report on the circuits of binary packages compiled by `nargo compile`
+    Acir {
+        #[command(subcommand)]
+        command: acir::AcirCommand,
+    },
+    /// Report the dependencies affected by a known vulnerability, as listed in an advisory
+    /// database. Fails when any dependency is affected
+    Advisories {
+        /// Advisory database to check against. Defaults to `noir-advisories.toml` in the
+        /// workspace root
+        #[arg(long, value_name = "PATH")]
+        database: Option<PathBuf>,
+        /// Print the affected dependencies as JSON, keyed by package name
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report the source, pinned tag or commit and license of every dependency. Fails when
+    /// a git dependency is pinned to a branch
+    Provenance {
+        /// Print the report as JSON, keyed by package name
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compute a health score from the findings and size of the workspace, and report its
+    /// change since the previous run, recorded in `target/noir-analyzer/history.json`
+    Score {
+        /// Print the score, its change and what it was computed from as JSON
+        #[arg(long)]
+        json: bool,
+        /// Do not record this run in the history
+        #[arg(long)]
+        no_save: bool,
+    },
+    /// Manage the baseline of accepted findings
+    Baseline {
+        #[command(subcommand)]
+        command: baseline::BaselineCommand,
+    },
+    /// Serve analyze, fix and explain requests over a local socket using JSON-RPC, keeping
+    /// analyzed files in memory between requests
+    #[cfg(unix)]
+    Daemon {
+        /// Path of the Unix socket to listen on. Defaults to `.noir-analyzer.sock` in the
+        /// workspace root
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
+}
+
+/// Arguments of the `check` subcommand.
+#[derive(Debug, Default, clap::Args)]
+struct CheckArgs {
+    /// Analyze every source file matching these globs instead of the package entry
+    /// points, e.g. `src/**/*.nr`
+    patterns: Vec<String>,
+    /// Only report findings on lines changed since this git reference, e.g. `main`.
+    /// The whole project is still analyzed
+    #[arg(long, value_name = "GIT_REF")]
+    changed_since: Option<String>,
+    /// Only report findings in this function, named by its path from the crate root or the
+    /// end of it, e.g. `main` or `utils::Point::new`. Files without it are not analyzed
+    #[arg(long, value_name = "PATH", value_parser = Scope::function, conflicts_with = "span")]
+    function: Option<Scope>,
+    /// Only report findings on these lines of a file, e.g. `src/main.nr:10-80`. Other files
+    /// are not analyzed
+    #[arg(long, value_name = "FILE:LINES", value_parser = Scope::lines)]
+    span: Option<Scope>,
+    /// Analyze every project with a `Nargo.toml` below this directory, e.g. the circuits of
+    /// a monorepo, and report their findings grouped by project
+    #[arg(long, value_name = "DIR")]
+    recursive: Option<PathBuf>,
+    /// Format of the reported findings
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+    /// How file locations are printed by the pretty and short formats: relative to the
+    /// workspace root, absolute, or as a clickable `file:line:col` token. Defaults to the
+    /// `locations` setting of the configuration, then to relative
+    #[arg(long, value_name = "STYLE")]
+    locations: Option<LocationStyle>,
+    /// Annotate findings with the author, commit and age of the reported line, as given by
+    /// `git blame` (json and sarif formats only)
+    #[arg(long)]
+    with_blame: bool,
+    /// Suppress the findings accepted in this baseline file. Defaults to
+    /// `noir-analyzer-baseline.json` in the workspace root, when it exists
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<PathBuf>,
+    /// Only report findings with at least this confidence: low, medium or high
+    #[arg(long, value_name = "LEVEL")]
+    min_confidence: Option<Confidence>,
+    /// Number of threads running lint rules on each file. Defaults to the number of CPUs
+    #[arg(long, short = 'j', value_name = "N")]
+    jobs: Option<usize>,
+    /// Analyze one function at a time and skip the rules needing the whole program, to bound
+    /// memory use. Enabled automatically for files larger than 1 MiB
+    #[arg(long)]
+    low_memory: bool,
+    /// Also analyze the code generated by macros, as printed by `nargo expand`, reporting
+    /// its findings on the macro invocations. Requires `nargo` on the PATH
+    #[arg(long)]
+    expand: bool,
+    /// Also check the circuits of binary packages compiled by `nargo compile`, read from the
+    /// target directory, such as the range constraints on black-box function inputs
+    #[arg(long)]
+    acir: bool,
+    /// Also type check each package with the Noir compiler and run the lint rules needing
+    /// types, such as truncating casts. Packages depending on other packages than the standard
+    /// library, or failing to type check, are only analyzed on their AST
+    #[arg(long)]
+    typed: bool,
+    /// Do not draw the progress bar shown on stderr while analyzing, e.g. in CI logs
+    #[arg(long)]
+    no_progress: bool,
+    /// After the run, print the kinds of AST nodes the analyzer skipped, whose contents lint
+    /// rules did not see
+    #[arg(long)]
+    report_coverage: bool,
+    /// Apply the machine-applicable suggestions of the reported findings
+    #[arg(long)]
+    fix: bool,
+    /// With `--fix`, also apply suggestions that may be incorrect or contain placeholders
+    #[arg(long, requires = "fix")]
+    allow_risky: bool,
+    /// With `--fix`, restore files whose fixes break parsing or introduce new findings
+    #[arg(long, requires = "fix")]
+    rollback: bool,
+    /// With `--fix`, print the changes as a unified diff instead of rewriting files
+    #[arg(long, requires = "fix")]
+    dry_run: bool,
+    /// Override a configuration key, e.g. `thresholds.call-chain-depth=12`. Takes precedence
+    /// over `NOIR_ANALYZER_*` environment variables, which take precedence over
+    /// `noir-analyzer.toml`. Can be repeated
+    #[arg(long = "config", value_name = "KEY=VALUE", value_parser = ConfigOverride::parse)]
+    config_overrides: Vec<ConfigOverride>,
+    /// Print the effective configuration, with the environment and `--config` overrides
+    /// applied, instead of analyzing the workspace
+    #[arg(long)]
+    print_config: bool,
+}
+
+/// Output formats of the `check` subcommand.
+#[derive(Debug, Default, Clone, Copy, PartialEq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Colored, human-readable report
+    #[default]
+    Pretty,
+    /// JSON document listing every finding
+    Json,
+    /// SARIF 2.1.0 log for code scanning services
+    Sarif,
+    /// One `path:line:col: severity: message` line per finding, like compilers and grep
+    Short,
+    /// Markdown summary of the new and fixed findings, for pull request comments
+    Markdown,
 }
 
 #[derive(Debug, Deserialize)]
 struct NargoToml {
-    package: PackageConfig,
-    _dependencies: Option<BTreeMap<String, DependencyConfig>>,
+    package: Option<PackageConfig>,
+    workspace: Option<WorkspaceConfig>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, DependencyConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkspaceConfig {
+    /// Directories of the member packages, relative to the workspace root
+    members: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,94 +308,1108 @@ struct PackageConfig {
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum DependencyConfig {
-    Path { _path: String },
-    Git { _git: String, _tag: String },
+    Path {
+        path: String,
+    },
+    Git {
+        git: String,
+        tag: Option<String>,
+        rev: Option<String>,
+    },
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = <Cli as clap::Parser>::parse();
-    println!("Using manifest path: {:?}", args.manifest_path);
-
-    match parse_workspace(&args.manifest_path) {
-        Ok(workspace) => {
-            println!("Workspace root: {:?}", workspace.root_dir);
-            for package in &workspace.members {
-                println!("Package: {}", package.name);
-                println!("Entry point: {:?}", package.entry_path);
-
-                // Run linters on the entrypoint
-                if let Err(e) = run_linters(&package.entry_path) {
-                    eprintln!("Error running linters: {:?}", e);
+    let format = match &args.command {
+        Some(Command::Check(check_args)) => check_args.format,
+        // Keep stdout for the JSON document
+        Some(
+            Command::Schema
+            | Command::Advisories { json: true, .. }
+            | Command::Provenance { json: true }
+            | Command::Score { json: true, .. },
+        ) => OutputFormat::Json,
+        Some(Command::Acir {
+            command:
+                acir::AcirCommand::Stats { json: true, .. }
+                | acir::AcirCommand::Drift { json: true, .. },
+        }) => OutputFormat::Json,
+        _ => OutputFormat::Pretty,
+    };
+    // Panics of the analyzer are reported like any other error
+    let result = panic::catch_unwind(|| run(args, format))
+        .unwrap_or_else(|payload| Err(AnalysisError::panic(payload)));
+    match result {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            e.report(format);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the command selected on the command line.
+fn run(args: Cli, format: OutputFormat) -> Result<ExitCode, AnalysisError> {
+    let command = args
+        .command
+        .unwrap_or_else(|| Command::Check(CheckArgs::default()));
+    if let Command::Check(check_args) = &command
+        && let Some(dir) = &check_args.recursive
+    {
+        return recursive::run(dir, check_args);
+    }
+    let manifest_path = match args.manifest_path {
+        Some(manifest_path) => manifest_path,
+        None => {
+            let program_dir = args.program_dir.as_deref().unwrap_or(Path::new(""));
+            find_manifest(program_dir)
+                .ok_or_else(|| AnalysisError::ManifestNotFound(program_dir.to_path_buf()))?
+        }
+    };
+    status(
+        format,
+        format_args!("Using manifest path: {:?}", manifest_path),
+    );
+
+    let mut workspace = parse_workspace(&manifest_path)?;
+    if let Some(package) = &args.package {
+        workspace
+            .members
+            .retain(|member| member.name.to_string() == *package);
+        if workspace.members.is_empty() {
+            return Err(AnalysisError::Usage(format!(
+                "Package '{package}' is not a member of the workspace"
+            )));
+        }
+    }
+
+    let succeeded = match command {
+        Command::Check(check_args) => return check(&workspace, &check_args),
+        Command::Init {
+            force,
+            pre_commit,
+            wrapper_script,
+        } => init::run(&workspace, force, pre_commit, wrapper_script).map(|()| true)?,
+        Command::Entrypoints => entrypoints::run(&workspace).map(|()| true)?,
+        Command::Schema => schema::run(&workspace).map(|()| true)?,
+        Command::Acir { command } => acir::run(&workspace, command)?,
+        Command::Advisories { database, json } => advisories::run(&workspace, database, json)?,
+        Command::Provenance { json } => provenance::run(&workspace, json)?,
+        Command::Score { json, no_save } => score::run(&workspace, json, no_save).map(|()| true)?,
+        Command::Baseline { command } => baseline::run(&workspace, command).map(|()| true)?,
+        #[cfg(unix)]
+        Command::Daemon { socket } => daemon::run(&workspace, socket).map(|()| true)?,
+    };
+    Ok(if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Runs all lints on the workspace. Fails when any lint is reported as an error.
+///
+/// When glob patterns are given, every matching source file is analyzed on its own instead
+/// of the package entry points. When a git reference is given, only findings on lines
+/// changed since that reference are reported. Findings accepted in the baseline are never
+/// reported.
+fn check(workspace: &Workspace, args: &CheckArgs) -> Result<ExitCode, AnalysisError> {
+    if args.print_config {
+        print!("{}", load_config(&workspace.root_dir, args)?.to_toml());
+        return Ok(ExitCode::SUCCESS);
+    }
+    let mut checked = check_workspace(workspace, args, jobs(args), false)?;
+    // The JSON report of a run that could not analyze every file is the error
+    if args.format == OutputFormat::Json && !checked.errors.is_empty() {
+        return Err(checked.errors.swap_remove(0));
+    }
+
+    let mut findings = checked.findings;
+    enrich_with_blame(&mut findings, args);
+
+    match args.format {
+        OutputFormat::Pretty | OutputFormat::Short => {}
+        OutputFormat::Json => {
+            let mut report = Reporter::json_value(&findings, &workspace.root_dir);
+            label_packages(&mut report, &findings, workspace);
+            println!("{}", serde_json::to_string_pretty(&report).unwrap());
+        }
+        OutputFormat::Sarif => {
+            println!("{}", Reporter::sarif_report(&findings, &workspace.root_dir))
+        }
+        OutputFormat::Markdown => print!(
+            "{}",
+            Reporter::markdown_report(
+                &findings,
+                checked.fixed,
+                &workspace.root_dir,
+                checked.locations
+            )
+        ),
+    }
+    if args.report_coverage {
+        eprint!("{}", Reporter::coverage_report(&checked.coverage));
+    }
+    notify(&checked.config, &findings, &workspace.root_dir);
+
+    if args.fix && !apply_fixes(&findings, &checked.config, args)? {
+        return Ok(ExitCode::FAILURE);
+    }
+
+    Ok(if checked.errors.is_empty() {
+        exit_code(&findings)
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Loads the configuration of a workspace, with the `NOIR_ANALYZER_*` environment variables
+/// and then the `--config` overrides applied over it.
+fn load_config(root: &Path, args: &CheckArgs) -> Result<Config, AnalysisError> {
+    let variables = env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)));
+    let mut overrides = ConfigOverride::from_env(variables)?;
+    overrides.extend(args.config_overrides.iter().cloned());
+    Ok(Config::load_with_overrides(root, &overrides)?)
+}
+
+/// Returns the number of threads to use, defaulting to the number of CPUs.
+fn jobs(args: &CheckArgs) -> usize {
+    args.jobs.unwrap_or_else(|| {
+        std::thread::available_parallelism().map_or(1, |parallelism| parallelism.get())
+    })
+}
+
+/// Findings of a workspace analyzed by `check_workspace`.
+struct CheckedWorkspace {
+    config: Config,
+    locations: LocationStyle,
+    findings: Vec<Finding>,
+    /// Number of baseline entries no finding matches anymore, when the whole workspace was
+    /// analyzed against a baseline
+    fixed: Option<usize>,
+    coverage: Coverage,
+    /// Failures of the files that could not be analyzed, already printed to stderr
+    errors: Vec<AnalysisError>,
+}
+
+/// Analyzes the workspace with up to `jobs` threads per file, collecting its findings.
+/// Progress and pretty findings are printed as the analysis goes, unless `deferred`, in
+/// which case the caller reports the findings.
+fn check_workspace(
+    workspace: &Workspace,
+    args: &CheckArgs,
+    jobs: usize,
+    deferred: bool,
+) -> Result<CheckedWorkspace, AnalysisError> {
+    let config = load_config(&workspace.root_dir, args)?;
+
+    let walker = SourceWalker::new(&workspace.root_dir)
+        .include(&args.patterns)
+        .and_then(|walker| walker.exclude(&config.exclude))?;
+
+    let changes = args
+        .changed_since
+        .as_deref()
+        .map(|git_ref| ChangedLines::since(&workspace.root_dir, git_ref))
+        .transpose()?;
+
+    let baseline_path = args
+        .baseline
+        .clone()
+        .unwrap_or_else(|| workspace.root_dir.join(BASELINE_FILE_NAME));
+    let baseline = if args.baseline.is_some() || baseline_path.exists() {
+        Some(Baseline::load(&baseline_path)?)
+    } else {
+        None
+    };
+
+    let files = if args.patterns.is_empty() {
+        vec![]
+    } else {
+        walker.walk()
+    };
+    let total = if args.patterns.is_empty() {
+        workspace.members.len()
+    } else {
+        files.len()
+    };
+    let progress = Progress::new(total, !args.no_progress && !deferred);
+
+    let locations = args.locations.unwrap_or(config.locations);
+    let mut report = Report {
+        format: args.format,
+        deferred,
+        locations,
+        root: &workspace.root_dir,
+        baseline,
+        min_confidence: args.min_confidence,
+        progress: &progress,
+        findings: vec![],
+        accepted: vec![],
+        circuit_lints: vec![],
+    };
+    report.status(format_args!("Workspace root: {:?}", workspace.root_dir));
+
+    let scope = args.function.as_ref().or(args.span.as_ref());
+    let mut coverage = Coverage::default();
+    let mut errors = vec![];
+    let parser = RefCell::new(Parser::new(&workspace.root_dir));
+    let options = LinterOptions {
+        config: &config,
+        changes: changes.as_ref(),
+        scope,
+        jobs,
+        low_memory: args.low_memory,
+        expand: args.expand,
+        typed: args.typed,
+        root: &workspace.root_dir,
+        progress: &progress,
+        parser: &parser,
+    };
+    // Manifests and the configuration are never in the scope of a function or lines
+    for package in workspace.members.iter().filter(|_| scope.is_none()) {
+        let manifest_path = package.root_dir.join("Nargo.toml");
+        let lints = run_manifest_linters(&manifest_path, package, &config, changes.as_ref());
+        report.record(lints, &manifest_path);
+    }
+    let config_path = workspace.root_dir.join(CONFIG_FILE_NAME);
+    if let Some(source) = fs::read_to_string(&config_path)
+        .ok()
+        .filter(|_| scope.is_none())
+    {
+        let lints = lint_config(&source, &known_lint_names(), &THRESHOLD_LINTS);
+        report.record(config.apply(lints), &config_path);
+    }
+    if args.acir {
+        for package in workspace
+            .members
+            .iter()
+            .filter(|package| package.is_binary())
+        {
+            let artifact_path = workspace.package_build_path(package);
+            if !artifact_path.is_file() {
+                report.status(format_args!(
+                    "No compiled circuit for package {} at {artifact_path:?}, run `nargo compile` first",
+                    package.name
+                ));
+                continue;
+            }
+            match run_acir_linters(&artifact_path, &config) {
+                Ok(circuit_lints) => {
+                    // Lints without a source location are reported on the artifact
+                    let (located, unlocated): (Vec<CircuitLint>, Vec<CircuitLint>) = circuit_lints
+                        .into_iter()
+                        .partition(|circuit_lint| !circuit_lint.locations.is_empty());
+                    let lints = unlocated
+                        .into_iter()
+                        .map(|circuit_lint| circuit_lint.lint)
+                        .filter(|_| scope.is_none())
+                        .collect();
+                    report.record(lints, &artifact_path);
+                    report.circuit_lints.extend(located);
+                }
+                Err(e) => {
+                    progress.suspend(|| eprint!("{}", e.render()));
+                    errors.push(e);
                 }
             }
         }
-        Err(e) => eprintln!("Error parsing Nargo.toml: {:?}", e),
+    }
+    if !args.patterns.is_empty() {
+        for file in files {
+            report.status(format_args!("Source file: {file:?}"));
+            match run_linters(&file, &options, member_of(workspace, &file)) {
+                Ok((_, lints, file_coverage)) => {
+                    report.record(lints, &file);
+                    coverage.merge(&file_coverage);
+                }
+                Err(e) => {
+                    progress.suspend(|| eprint!("{}", e.render()));
+                    errors.push(e);
+                }
+            }
+            progress.advance();
+        }
+    } else {
+        let mut parsed_packages = vec![];
+        for package in &workspace.members {
+            report.status(format_args!("Package: {}", package.name));
+            report.status(format_args!("Entry point: {:?}", package.entry_path));
+
+            if walker.is_excluded(&package.entry_path) {
+                report.status(format_args!("Skipping excluded entry point"));
+                progress.advance();
+                continue;
+            }
+
+            // Run linters on the entrypoint
+            match run_linters(&package.entry_path, &options, Some(package)) {
+                Ok((parsed_module, lints, package_coverage)) => {
+                    report.record(lints, &package.entry_path);
+                    coverage.merge(&package_coverage);
+                    parsed_packages.push((package, parsed_module));
+                }
+                Err(e) => {
+                    progress.suspend(|| eprint!("{}", e.render()));
+                    errors.push(e);
+                }
+            }
+            progress.advance();
+        }
+
+        // Comparing packages needs every parsed program at once
+        if !args.low_memory {
+            for (index, lint) in run_workspace_linters(&parsed_packages, &config, changes.as_ref())
+            {
+                let (package, parsed_module) = &parsed_packages[index];
+                let lints = match scope {
+                    Some(scope) => {
+                        let source = fs::read_to_string(&package.entry_path).unwrap_or_default();
+                        scope.retain(vec![lint], &package.entry_path, &source, parsed_module)
+                    }
+                    None => vec![lint],
+                };
+                report.record(lints, &package.entry_path);
+            }
+        }
+    }
+
+    // Circuit lints no source finding absorbed are reported at their own location
+    let mut by_file: BTreeMap<PathBuf, Vec<Lint>> = BTreeMap::new();
+    for circuit_lint in std::mem::take(&mut report.circuit_lints) {
+        if let (Some(file_path), lint) = correlation::locate(circuit_lint) {
+            by_file.entry(file_path).or_default().push(lint);
+        }
+    }
+    for (file_path, lints) in by_file {
+        let lints = match scope {
+            Some(scope) => retain_in_scope(&mut parser.borrow_mut(), scope, lints, &file_path),
+            None => lints,
+        };
+        report.record(lints, &file_path);
+    }
+
+    progress.finish();
+    // Entries of files that were not analyzed cannot be told fixed
+    let fixed = report
+        .baseline
+        .as_ref()
+        .filter(|_| args.patterns.is_empty() && args.changed_since.is_none() && scope.is_none())
+        .map(|baseline| baseline.unmatched(&report.accepted, report.root).len());
+    let mut findings = report.findings;
+    sort_findings(&mut findings);
+    Ok(CheckedWorkspace {
+        config,
+        locations,
+        findings,
+        fixed,
+        coverage,
+        errors,
+    })
+}
+
+/// Annotates findings with blame data when requested, for the formats that report it.
+fn enrich_with_blame(findings: &mut [Finding], args: &CheckArgs) {
+    if args.with_blame {
+        if matches!(
+            args.format,
+            OutputFormat::Pretty | OutputFormat::Short | OutputFormat::Markdown
+        ) {
+            eprintln!("--with-blame only applies to the json and sarif formats");
+        } else {
+            Blamer::new().enrich(findings);
+        }
+    }
+}
+
+/// Posts the findings to the webhook of the configuration, if any. A webhook that cannot be
+/// reached is reported, but does not fail the run.
+fn notify(config: &Config, findings: &[Finding], root: &Path) {
+    if let Some(webhook) = &config.webhook
+        && let Err(e) = webhook.send(findings, root)
+    {
+        eprint!("{}", AnalysisError::from(e).render());
+    }
+}
+
+/// Applies the suggestions of the findings. Returns whether every fix could be applied.
+fn apply_fixes(
+    findings: &[Finding],
+    config: &Config,
+    args: &CheckArgs,
+) -> Result<bool, AnalysisError> {
+    let options = fix::FixOptions {
+        allow_risky: args.allow_risky,
+        rollback: args.rollback,
+        dry_run: args.dry_run,
+    };
+    fix::run(findings, config, &options)
+}
+
+/// Prints progress information. Machine-readable formats keep stdout for the report, so it
+/// goes to stderr instead.
+fn status(format: OutputFormat, message: fmt::Arguments) {
+    match format {
+        OutputFormat::Pretty => println!("{message}"),
+        OutputFormat::Json | OutputFormat::Sarif | OutputFormat::Short | OutputFormat::Markdown => {
+            eprintln!("{message}")
+        }
+    }
+}
+
+/// Prints the findings of a file in the pretty format.
+fn print_pretty(findings: &[Finding], file_path: &Path, root: &Path, locations: LocationStyle) {
+    println!(
+        "{}",
+        Reporter::pretty_findings_report(findings, file_path, root, locations)
+    );
+}
+
+/// Findings collected by a `check` run.
+struct Report<'a> {
+    format: OutputFormat,
+    /// Whether printing progress and pretty findings is left to the caller
+    deferred: bool,
+    locations: LocationStyle,
+    root: &'a Path,
+    baseline: Option<Baseline>,
+    min_confidence: Option<Confidence>,
+    progress: &'a Progress,
+    findings: Vec<Finding>,
+    /// Findings accepted in the baseline, which are not reported
+    accepted: Vec<Finding>,
+    /// Circuit lints with a source location, merged into the first source finding
+    /// overlapping it as circuit-level evidence
+    circuit_lints: Vec<CircuitLint>,
+}
+
+impl Report<'_> {
+    /// Prints progress information, unless output is deferred.
+    fn status(&self, message: fmt::Arguments) {
+        if !self.deferred {
+            self.progress.suspend(|| status(self.format, message));
+        }
+    }
+
+    /// Records the lints reported in a file, setting aside the ones accepted in the baseline,
+    /// dropping the ones below the minimum confidence, and merging the pending circuit lints
+    /// they overlap. The pretty and short formats print them right away, unless output is
+    /// deferred.
+    fn record(&mut self, lints: Vec<Lint>, file_path: &Path) {
+        let source = fs::read_to_string(file_path).unwrap_or_default();
+        let (accepted, findings): (Vec<Finding>, Vec<Finding>) = lints
+            .into_iter()
+            .map(|lint| Finding::new(lint, file_path, &source))
+            .partition(|finding| {
+                self.baseline
+                    .as_ref()
+                    .is_some_and(|baseline| baseline.contains(finding, self.root))
+            });
+        self.accepted.extend(accepted);
+        let mut findings: Vec<Finding> = findings
+            .into_iter()
+            .filter(|finding| {
+                self.min_confidence
+                    .is_none_or(|min_confidence| finding.lint.confidence >= min_confidence)
+            })
+            .collect();
+        for finding in &mut findings {
+            correlation::attach_evidence(finding, &mut self.circuit_lints);
+        }
+        sort_findings(&mut findings);
+
+        if !self.deferred {
+            self.progress.suspend(|| match self.format {
+                OutputFormat::Pretty => {
+                    print_pretty(&findings, file_path, self.root, self.locations)
+                }
+                OutputFormat::Short => print!(
+                    "{}",
+                    Reporter::short_report(&findings, self.root, self.locations)
+                ),
+                OutputFormat::Json | OutputFormat::Sarif | OutputFormat::Markdown => {}
+            });
+        }
+        self.progress.found(&findings);
+
+        self.findings.extend(findings);
+    }
+}
+
+/// Returns a failure exit code when any reported lint is an error.
+fn exit_code(findings: &[Finding]) -> ExitCode {
+    if findings
+        .iter()
+        .any(|finding| finding.lint.severity == Severity::Error)
+    {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
     }
 }
 
-/// Parses `Nargo.toml` and constructs a `Workspace`
-fn parse_workspace(manifest_path: &PathBuf) -> Result<Workspace, Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(manifest_path)?;
-    let parsed: NargoToml = toml::from_str(&content)?;
+/// Returns the registered lint rules the configuration enables, configured with their
+/// options. The rules of the library-hygiene group are only included for libraries
+fn configured_lints(
+    config: &Config,
+    is_library: bool,
+) -> Result<Vec<Box<dyn LintRule>>, ConfigError> {
+    let mut registry = LintRegistry::builtin();
+    config.enable_configured(&mut registry);
+    let mut lints = registry.rules();
+    if is_library {
+        lints.extend(registry.library_rules());
+    }
+    config.configure(&mut lints)?;
+    Ok(lints)
+}
+
+/// Names of the lints configurable under `[thresholds]`
+const THRESHOLD_LINTS: [&str; 6] = [
+    CallChainDepth::NAME,
+    DeepConstraintExpression::NAME,
+    GodModule::NAME,
+    HintDrift::NAME,
+    LargeStruct::NAME,
+    OversizedEntryPoint::NAME,
+];
+
+/// Returns the name of every lint the analyzer can report, for validating the configuration
+fn known_lint_names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = LintRegistry::builtin()
+        .metadata()
+        .map(|metadata| metadata.name)
+        .chain(
+            registered_workspace_lints()
+                .iter()
+                .map(|lint_rule| lint_rule.name()),
+        )
+        .collect();
+    names.extend([
+        DependencyHygiene::NAME,
+        NeedlessVisibility::NAME,
+        ProverInputMismatch::NAME,
+        UnsupportedSyntax::NAME,
+        INTERNAL_ERROR_LINT,
+        UNFULFILLED_EXPECTATION,
+        config_file::NAME,
+    ]);
+    names.extend(manifest::LINT_NAMES);
+    names.extend([
+        noir_analyzer::acir::black_box_inputs::NAME,
+        noir_analyzer::acir::repeated_subexpressions::NAME,
+    ]);
+    names.extend(
+        registered_typed_lints()
+            .iter()
+            .map(|lint_rule| lint_rule.name()),
+    );
+    names
+}
+
+/// Returns every lint rule comparing the packages of a workspace
+fn registered_workspace_lints() -> Vec<Box<dyn WorkspaceLintRule>> {
+    vec![Box::new(
+        noir_analyzer::lints::duplicate_public_function::DuplicatePublicFunction,
+    )]
+}
+
+/// Returns every lint rule run on the elaborated HIR of packages, with `--typed`
+fn registered_typed_lints() -> Vec<Box<dyn TypedLintRule>> {
+    vec![Box::new(TruncatingCast)]
+}
+
+/// Returns the closest `Nargo.toml` in the given directory or its parents, the way nargo
+/// resolves the manifest of a program
+fn find_manifest(program_dir: &Path) -> Option<PathBuf> {
+    let manifest_path = program_dir.join("Nargo.toml");
+    if manifest_path.is_file() {
+        // Keep the path as given, so reported paths stay relative
+        return Some(manifest_path);
+    }
+
+    let program_dir = fs::canonicalize(if program_dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        program_dir
+    })
+    .ok()?;
+    program_dir
+        .ancestors()
+        .map(|dir| dir.join("Nargo.toml"))
+        .find(|manifest_path| manifest_path.is_file())
+}
+
+/// Parses `Nargo.toml` and constructs a `Workspace`: the package it declares, or the members
+/// listed by its `[workspace]` section, in order
+fn parse_workspace(manifest_path: &PathBuf) -> Result<Workspace, AnalysisError> {
+    let invalid = |message: String| AnalysisError::Manifest {
+        path: manifest_path.clone(),
+        message,
+    };
+    let invalid_workspace = |message: &str| AnalysisError::WorkspaceManifest {
+        path: manifest_path.clone(),
+        message: message.to_string(),
+    };
+    let content = read_file(manifest_path)?;
+    let parsed: NargoToml = toml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    let root_dir = manifest_path.parent().unwrap().to_path_buf();
+
+    // Like nargo, a package manifest is assumed to be a workspace of its own
+    let (members, is_assumed) = match (parsed.package, parsed.workspace) {
+        (Some(package), None) => (vec![parse_package(manifest_path, package)?], true),
+        (None, Some(workspace)) => {
+            let mut members = vec![];
+            for member in workspace.members {
+                let member_manifest_path = root_dir.join(member).join("Nargo.toml");
+                let content = read_file(&member_manifest_path)?;
+                let member_invalid = |message: String| AnalysisError::Manifest {
+                    path: member_manifest_path.clone(),
+                    message,
+                };
+                let parsed: NargoToml =
+                    toml::from_str(&content).map_err(|e| member_invalid(e.to_string()))?;
+                let package = parsed
+                    .package
+                    .ok_or_else(|| AnalysisError::WorkspaceManifest {
+                        path: member_manifest_path.clone(),
+                        message: "workspace members must declare a [package]".to_string(),
+                    })?;
+                members.push(parse_package(&member_manifest_path, package)?);
+            }
+            (members, false)
+        }
+        (Some(_), Some(_)) => {
+            return Err(invalid_workspace(
+                "a manifest declares either a [package] or a [workspace], not both",
+            ));
+        }
+        (None, None) => {
+            return Err(invalid_workspace(
+                "missing [package] or [workspace] section",
+            ));
+        }
+    };
+
+    let workspace = Workspace {
+        root_dir,
+        target_dir: None,
+        members,
+        selected_package_index: None,
+        is_assumed,
+    };
+
+    Ok(workspace)
+}
 
-    let package_type = match parsed.package.package_type.as_str() {
+/// Constructs the `Package` declared by the `[package]` section of a manifest
+fn parse_package(manifest_path: &Path, parsed: PackageConfig) -> Result<Package, AnalysisError> {
+    let invalid = |message: String| AnalysisError::Manifest {
+        path: manifest_path.to_path_buf(),
+        message,
+    };
+    let package_type = match parsed.package_type.as_str() {
         "bin" => PackageType::Binary,
         "lib" => PackageType::Library,
         "contract" => PackageType::Contract,
-        _ => return Err("Invalid package type in Nargo.toml".into()),
+        package_type => {
+            return Err(invalid(format!("invalid package type '{package_type}'")));
+        }
     };
 
-    let package = Package {
+    Ok(Package {
         name: parsed
-            .package
             .name
             .parse()
-            .map_err(|_| "Invalid package name")?,
-        version: parsed.package.version,
-        compiler_required_version: parsed.package.compiler_version,
+            .map_err(|_| invalid(format!("invalid package name '{}'", parsed.name)))?,
+        version: parsed.version,
+        compiler_required_version: parsed.compiler_version,
         root_dir: manifest_path.parent().unwrap().to_path_buf(),
         entry_path: manifest_path
             .parent()
             .unwrap()
-            .join(parsed.package.entry.unwrap_or_else(|| "src/main.nr".into())),
+            .join(parsed.entry.unwrap_or_else(|| "src/main.nr".into())),
         package_type,
         dependencies: BTreeMap::new(),
         expression_width: None,
+    })
+}
+
+/// Returns the workspace member containing a file
+fn member_of<'a>(workspace: &'a Workspace, file_path: &Path) -> Option<&'a Package> {
+    workspace
+        .members
+        .iter()
+        .find(|package| file_path.starts_with(&package.root_dir))
+}
+
+/// Names the workspace member of each finding of a JSON report, so that findings can be
+/// grouped per package. Findings outside of the members, such as those of the
+/// configuration file, have a `null` package
+fn label_packages(report: &mut Value, findings: &[Finding], workspace: &Workspace) {
+    let values = report["findings"].as_array_mut().into_iter().flatten();
+    for (value, finding) in values.zip(findings) {
+        value["package"] =
+            json!(member_of(workspace, &finding.file_path).map(|package| package.name.to_string()));
+    }
+}
+
+/// Settings shared by every run of the lint rules
+struct LinterOptions<'a> {
+    config: &'a Config,
+    changes: Option<&'a ChangedLines>,
+    /// Restricts the findings to a function or lines, with `--function` or `--span`
+    scope: Option<&'a Scope>,
+    jobs: usize,
+    low_memory: bool,
+    /// Also analyzes the code generated by macros
+    expand: bool,
+    /// Also runs the typed lint rules on the elaborated HIR
+    typed: bool,
+    /// Root of the analyzed workspace
+    root: &'a Path,
+    /// Shows the rule being run
+    progress: &'a Progress,
+    /// Registers the analyzed files, giving each its own file ID
+    parser: &'a RefCell<Parser>,
+}
+
+/// Returns the compiler version declared by a package, when it is valid
+fn compiler_version(package: &Package) -> Option<NoirVersion> {
+    let requirement = package.compiler_required_version.as_ref()?;
+    match requirement.parse() {
+        Ok(compiler_version) => Some(compiler_version),
+        Err(e) => {
+            eprintln!("Ignoring compiler_version of {}: {e}", package.name);
+            None
+        }
+    }
+}
+
+/// Returns the lint checking the dependencies declared in the manifest of a package. An
+/// unreadable manifest is reported and not checked.
+fn dependency_hygiene(package: &Package, root: &Path) -> Option<DependencyHygiene> {
+    let manifest_path = package.root_dir.join("Nargo.toml");
+    let manifest: NargoToml = match fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| toml::from_str(&content).map_err(|e| e.to_string()))
+    {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("Ignoring dependencies of {manifest_path:?}: {e}");
+            return None;
+        }
     };
 
-    let workspace = Workspace {
-        root_dir: manifest_path.parent().unwrap().to_path_buf(),
-        target_dir: None,
-        members: vec![package],
-        selected_package_index: None,
-        is_assumed: false,
+    let dependencies = manifest
+        .dependencies
+        .into_iter()
+        .map(|(name, dependency)| {
+            let dependency = match dependency {
+                DependencyConfig::Path { path } => DeclaredDependency::Path(path.into()),
+                DependencyConfig::Git { git, tag, rev } => DeclaredDependency::Git {
+                    url: git,
+                    reference: tag.or(rev),
+                },
+            };
+            (name, dependency)
+        })
+        .collect();
+    // Relative paths leaving an empty root, such as `..`, must not look inside of it
+    let absolute = |dir: &Path| fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    Some(DependencyHygiene::new(
+        absolute(&package.root_dir),
+        absolute(root),
+        dependencies,
+    ))
+}
+
+/// Returns the graph of the dependencies of a package, whose functions lints resolve calls
+/// to. Dependencies that cannot be resolved are reported and left out.
+fn dependency_graph(package: &Package) -> DependencyGraph {
+    let manifest_path = package.root_dir.join("Nargo.toml");
+    match resolve_dependencies(&manifest_path) {
+        Ok(dependencies) => DependencyGraph::build(&package.name.to_string(), &dependencies),
+        Err(e) => {
+            eprintln!("Ignoring dependencies of {manifest_path:?}: {e}");
+            DependencyGraph::default()
+        }
+    }
+}
+
+/// Returns the lint checking the `Prover.toml` and `Verifier.toml` of a binary package.
+/// Missing files are not checked, and unreadable ones are reported and skipped.
+fn prover_inputs(package: &Package) -> ProverInputMismatch {
+    let read = |file_name: &str| -> Option<toml::Table> {
+        let path = package.root_dir.join(file_name);
+        if !path.exists() {
+            return None;
+        }
+        match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| toml::from_str(&content).map_err(|e| e.to_string()))
+        {
+            Ok(table) => Some(table),
+            Err(e) => {
+                eprintln!("Ignoring {path:?}: {e}");
+                None
+            }
+        }
     };
 
-    Ok(workspace)
+    ProverInputMismatch::new(read("Prover.toml"), read("Verifier.toml"))
 }
 
-/// Runs lint rules on the given entry point
-/// Runs lint rules on the given entry point
-fn run_linters(entry_path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+/// Runs lint rules on the given entry point, adapted to the package it belongs to. Returns
+/// the nodes the analyzer skipped along with the lints
+fn run_linters(
+    entry_path: &PathBuf,
+    options: &LinterOptions,
+    package: Option<&Package>,
+) -> Result<(ParsedModule, Vec<Lint>, Coverage), AnalysisError> {
     // Read the source file
-    let source = fs::read_to_string(entry_path)?;
+    let source = read_file(entry_path)?;
+    if !options.low_memory && source.len() > LOW_MEMORY_THRESHOLD {
+        eprintln!("{entry_path:?} is larger than 1 MiB, analyzing it in low-memory mode");
+    }
+    let low_memory = options.low_memory || source.len() > LOW_MEMORY_THRESHOLD;
 
-    let parsed_module = Parser::parse_program_with_dummy_file(&source)
-        .map_err(|_| "Failed to parse entry point")?;
+    let mut parsed_module = parse_file(&mut options.parser.borrow_mut(), entry_path, &source)?;
+    flags::select_branches(&mut parsed_module, &options.config.flags);
+    if options
+        .scope
+        .is_some_and(|scope| !scope.covers(entry_path, &parsed_module))
+    {
+        return Ok((parsed_module, vec![], Coverage::default()));
+    }
 
-    // Collect all registered lints
-    let lints: Vec<Box<dyn LintRule>> = vec![Box::new(
-        noir_analyzer::lints::unused_function::UnusedFunction,
-    )];
+    // Collect the registered lints the configuration enables, adapted to the package type
+    // and targeted compiler, then the rules specific to the package
+    let is_library = package.is_some_and(|package| package.package_type == PackageType::Library);
+    let mut lints = configured_lints(options.config, is_library)?;
+    let mut package_lints: Vec<Box<dyn LintRule>> = vec![];
+    if let Some(compiler_version) = package.and_then(compiler_version) {
+        lints.retain(|lint_rule| compiler_version.is_lint_relevant(lint_rule.name()));
+        package_lints.push(Box::new(UnsupportedSyntax::new(compiler_version)));
+    }
+    if let Some(package) = package.filter(|package| package.package_type == PackageType::Binary) {
+        package_lints.push(Box::new(NeedlessVisibility));
+        package_lints.push(Box::new(prover_inputs(package)));
+    }
+    // Checked once per package, along with its entry point
+    if let Some(dependency_hygiene) = package
+        .filter(|package| package.entry_path == *entry_path)
+        .and_then(|package| dependency_hygiene(package, options.root))
+    {
+        package_lints.push(Box::new(dependency_hygiene));
+    }
+    options.config.configure(&mut package_lints)?;
+    package_lints.retain(|lint_rule| options.config.enables(lint_rule.as_ref()));
+    lints.extend(package_lints);
 
-    let mut analyzer = Analyzer::new(&lints);
-    match analyzer.analyze(&parsed_module) {
-        Ok(lints) => {
-            // Pass entry_path to pretty_report instead of FileManager
-            println!("{}", Reporter::pretty_report(&lints, entry_path));
+    let dependencies = package.map(dependency_graph).unwrap_or_default();
+    let observer = |rule| options.progress.rule(rule);
+    let mut analyzer = Analyzer::new(&lints)
+        .with_threads(options.jobs)
+        .with_source(&source)
+        .with_rule_observer(&observer)
+        .with_dependencies(&dependencies);
+    // Rules are guarded by the analyzer; a bug of the traversal should not abort the
+    // analysis of the other files either
+    let analyzed = panic::catch_unwind(AssertUnwindSafe(|| {
+        if low_memory {
+            analyzer.analyze_low_memory(&parsed_module)
+        } else {
+            analyzer.analyze(&parsed_module)
+        }
+    }))
+    .unwrap_or_else(|payload| Ok(vec![analysis_panicked(payload.as_ref())]));
+    let mut reported = analyzed.map_err(|source| AnalysisError::Analyzer {
+        path: entry_path.clone(),
+        source,
+    })?;
+    if options.expand
+        && let Some(package) = package.filter(|package| package.entry_path == *entry_path)
+    {
+        let generated =
+            run_expanded_linters(package, &source, &parsed_module, &reported, &lints, options);
+        reported.extend(generated);
+    }
+    if options.typed
+        && let Some(package) = package.filter(|package| package.entry_path == *entry_path)
+    {
+        reported.extend(run_typed_linters(&mut analyzer, package));
+    }
+    let lints = reported;
+    let lints = Expectations::parse(&source).apply(lints, &source);
+    let lints = options.config.apply(lints);
+    let lints = match options.changes {
+        Some(changes) => changes.retain_changed(lints, entry_path, &source),
+        None => lints,
+    };
+    let reported = match options.scope {
+        Some(scope) => scope.retain(lints, entry_path, &source, &parsed_module),
+        None => lints,
+    };
+
+    let coverage = analyzer.coverage().clone();
+    Ok((parsed_module, reported, coverage))
+}
+
+/// Runs the lint rules on the code generated by macros in a package, as printed by
+/// `nargo expand`, returning the findings it adds to those of the source. Packages that
+/// cannot be expanded are reported and analyzed without their generated code.
+fn run_expanded_linters(
+    package: &Package,
+    source: &str,
+    parsed_module: &ParsedModule,
+    lints: &[Lint],
+    lint_rules: &[Box<dyn LintRule>],
+    options: &LinterOptions,
+) -> Vec<Lint> {
+    if options.config.silence_generated {
+        return vec![];
+    }
+    let expanded = match expansion::expand(&package.root_dir, &package.name.to_string()) {
+        Ok(expanded) => expanded,
+        Err(e) => {
+            eprintln!("Not analyzing the generated code of {}: {e}", package.name);
+            return vec![];
+        }
+    };
+    let parsed = parse_file(
+        &mut options.parser.borrow_mut(),
+        &package.entry_path,
+        &expanded,
+    );
+    let mut expanded_module = match parsed {
+        Ok(expanded_module) => expanded_module,
+        Err(e) => {
+            eprintln!("Not analyzing the generated code of {}: {e}", package.name);
+            return vec![];
+        }
+    };
+    flags::select_branches(&mut expanded_module, &options.config.flags);
+
+    let analyzed = panic::catch_unwind(AssertUnwindSafe(|| {
+        Analyzer::new(lint_rules)
+            .with_threads(options.jobs)
+            .with_source(&expanded)
+            .analyze(&expanded_module)
+    }));
+    match analyzed {
+        Ok(Ok(expanded_lints)) => expansion::generated_lints(
+            source,
+            parsed_module,
+            lints,
+            &expanded_module,
+            expanded_lints,
+        ),
+        Ok(Err(_)) => vec![],
+        Err(payload) => vec![analysis_panicked(payload.as_ref())],
+    }
+}
+
+/// Runs the typed lint rules on the elaborated HIR of a package, whose AST `analyzer` has
+/// just analyzed. Packages that cannot be elaborated are reported and analyzed on their AST
+/// only
+fn run_typed_linters(analyzer: &mut Analyzer, package: &Package) -> Vec<Lint> {
+    let elaborated = panic::catch_unwind(AssertUnwindSafe(|| {
+        TypedContext::from_entry_file(&package.entry_path)
+    }));
+    match elaborated {
+        Ok(Ok(types)) => analyzer.analyze_types(&types, &registered_typed_lints()),
+        Ok(Err(e)) => {
+            eprintln!("Not analyzing the types of {}: {e}", package.name);
+            vec![]
+        }
+        Err(payload) => vec![analysis_panicked(payload.as_ref())],
+    }
+}
+
+/// Runs the circuit lints on the program compiled into the artifact of a binary package,
+/// located through its debug symbols. `--changed-since` does not filter them
+fn run_acir_linters(
+    artifact_path: &Path,
+    config: &Config,
+) -> Result<Vec<CircuitLint>, AnalysisError> {
+    let artifact = checker::read_artifact(artifact_path)?;
+    // Applied one lint at a time, since the policy may drop lints
+    Ok(checker::check_artifact(&artifact)
+        .into_iter()
+        .filter_map(|circuit_lint| {
+            let lint = config.apply(vec![circuit_lint.lint]).pop()?;
+            Some(CircuitLint {
+                lint,
+                ..circuit_lint
+            })
+        })
+        .collect())
+}
+
+/// Runs the manifest lints on the `Nargo.toml` of a package
+fn run_manifest_linters(
+    manifest_path: &Path,
+    package: &Package,
+    config: &Config,
+    changes: Option<&ChangedLines>,
+) -> Vec<Lint> {
+    let Ok(source) = fs::read_to_string(manifest_path) else {
+        return vec![];
+    };
+    let lints = config.apply(lint_manifest(&source, &package.root_dir));
+    match changes {
+        Some(changes) => changes.retain_changed(lints, manifest_path, &source),
+        None => lints,
+    }
+}
+
+/// Keeps the lints of a file that are in scope, dropping them all when it does not parse
+fn retain_in_scope(
+    parser: &mut Parser,
+    scope: &Scope,
+    lints: Vec<Lint>,
+    file_path: &Path,
+) -> Vec<Lint> {
+    let Ok(source) = read_file(file_path) else {
+        return vec![];
+    };
+    match parse_file(parser, file_path, &source) {
+        Ok(parsed_module) => scope.retain(lints, file_path, &source, &parsed_module),
+        Err(_) => vec![],
+    }
+}
+
+/// Runs lint rules comparing the packages of the workspace with each other, returning each
+/// lint together with the index of the package it was reported in
+fn run_workspace_linters(
+    packages: &[(&Package, ParsedModule)],
+    config: &Config,
+    changes: Option<&ChangedLines>,
+) -> Vec<(usize, Lint)> {
+    let workspace_packages: Vec<WorkspacePackage> = packages
+        .iter()
+        .map(|(package, parsed_module)| WorkspacePackage {
+            name: package.name.to_string(),
+            parsed_module,
+        })
+        .collect();
+
+    let mut reported = vec![];
+    for lint_rule in &registered_workspace_lints() {
+        let lints = panic::catch_unwind(AssertUnwindSafe(|| lint_rule.lint(&workspace_packages)))
+            .unwrap_or_else(|payload| vec![(0, rule_panicked(lint_rule.name(), payload.as_ref()))]);
+        for (index, lint) in lints {
+            let entry_path = &packages[index].0.entry_path;
+            let mut lints = config.apply(vec![lint]);
+            if let Some(changes) = changes {
+                let source = fs::read_to_string(entry_path).unwrap_or_default();
+                lints = changes.retain_changed(lints, entry_path, &source);
+            }
+            reported.extend(lints.into_iter().map(|lint| (index, lint)));
         }
-        Err(_) => println!("Ignore errors in PoC"),
     }
 
-    Ok(())
+    reported
 }
@@ -0,0 +1,40 @@
+//! # `nargo analyzer` Shim
+//!
+//! Entry point of the analyzer as a nargo external subcommand. `nargo analyzer <ARGS>` runs
+//! `nargo-analyzer analyzer <ARGS>`, the same convention as cargo. The subcommand name is
+//! dropped and the remaining arguments, which may include nargo flags such as
+//! `--program-dir` and `--package`, are passed to the `noir-analyzer` binary installed next
+//! to this one.
+
+use std::env;
+use std::ffi::OsString;
+use std::process::{Command, ExitCode};
+
+/// Name nargo passes as the first argument of the external subcommand.
+const SUBCOMMAND_NAME: &str = "analyzer";
+
+fn main() -> ExitCode {
+    let mut args: Vec<OsString> = env::args_os().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == SUBCOMMAND_NAME) {
+        args.remove(0);
+    }
+
+    let analyzer = env::current_exe()
+        .ok()
+        .and_then(|path| {
+            let analyzer = path.with_file_name(format!("noir-analyzer{}", env::consts::EXE_SUFFIX));
+            analyzer.is_file().then_some(analyzer)
+        })
+        .unwrap_or_else(|| "noir-analyzer".into());
+
+    match Command::new(&analyzer).args(args).status() {
+        Ok(status) => match status.code() {
+            Some(code) => ExitCode::from(code as u8),
+            None => ExitCode::FAILURE,
+        },
+        Err(e) => {
+            eprintln!("Error running {}: {e}", analyzer.display());
+            ExitCode::FAILURE
+        }
+    }
+}
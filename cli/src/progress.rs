@@ -0,0 +1,122 @@
+//! # Progress Bar
+//!
+//! While `check` runs on a large workspace, a single status line on stderr shows how many
+//! files were analyzed, how many errors and warnings were found so far and which rule is
+//! running. Findings are printed above it as soon as their file is analyzed.
+//!
+//! The bar is only drawn when stderr is a terminal, and can be turned off with
+//! `--no-progress`, e.g. in CI logs.
+
+use noir_analyzer::diagnostics::finding::Finding;
+use noir_analyzer::diagnostics::lint::Severity;
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+
+/// Width of the bar, in characters.
+const BAR_WIDTH: usize = 24;
+
+/// A progress bar over the files of a run.
+pub struct Progress {
+    enabled: bool,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    total: usize,
+    done: usize,
+    errors: usize,
+    warnings: usize,
+    rule: &'static str,
+}
+
+impl Progress {
+    /// Creates a bar over `total` files, drawn when `enabled` and stderr is a terminal.
+    pub fn new(total: usize, enabled: bool) -> Self {
+        Self {
+            enabled: enabled && std::io::stderr().is_terminal(),
+            state: Mutex::new(State {
+                total,
+                ..State::default()
+            }),
+        }
+    }
+
+    /// Shows the rule that started running.
+    pub fn rule(&self, rule: &'static str) {
+        self.update(|state| state.rule = rule);
+    }
+
+    /// Counts the findings confirmed so far, by severity.
+    pub fn found(&self, findings: &[Finding]) {
+        self.update(|state| {
+            for finding in findings {
+                match finding.lint.severity {
+                    Severity::Error => state.errors += 1,
+                    Severity::Warning => state.warnings += 1,
+                }
+            }
+        });
+    }
+
+    /// Marks a file as analyzed.
+    pub fn advance(&self) {
+        self.update(|state| {
+            state.done += 1;
+            state.rule = "";
+        });
+    }
+
+    /// Runs `print` with the bar cleared, so that its output does not mix with the bar.
+    pub fn suspend(&self, print: impl FnOnce()) {
+        if !self.enabled {
+            print();
+            return;
+        }
+        let state = self.state.lock().unwrap();
+        clear();
+        print();
+        draw(&state);
+    }
+
+    /// Removes the bar once the run is over.
+    pub fn finish(&self) {
+        if self.enabled {
+            clear();
+        }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut State)) {
+        let mut state = self.state.lock().unwrap();
+        change(&mut state);
+        if self.enabled {
+            draw(&state);
+        }
+    }
+}
+
+fn clear() {
+    eprint!("\r\x1b[K");
+}
+
+fn draw(state: &State) {
+    let filled = (BAR_WIDTH * state.done)
+        .checked_div(state.total)
+        .unwrap_or(BAR_WIDTH)
+        .min(BAR_WIDTH);
+    let errors = if state.errors > 0 {
+        format!("\x1b[1;31m{} errors\x1b[0m", state.errors)
+    } else {
+        "0 errors".to_string()
+    };
+    eprint!(
+        "\r\x1b[K[{}{}] {}/{} files, {errors}, {} warnings {}",
+        "=".repeat(filled),
+        " ".repeat(BAR_WIDTH - filled),
+        state.done,
+        state.total,
+        state.warnings,
+        state.rule
+    );
+    let _ = std::io::stderr().flush();
+}
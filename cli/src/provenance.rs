@@ -0,0 +1,63 @@
+//! # `provenance` Subcommand
+//!
+//! Prints where each dependency of each package comes from, what it is pinned to and its
+//! license, flagging git dependencies pinned to a branch.
+
+use crate::error::AnalysisError;
+use nargo::workspace::Workspace;
+use noir_analyzer::advisories::resolve_dependencies;
+use noir_analyzer::provenance::{Pin, Provenance};
+use std::collections::BTreeMap;
+
+/// Runs the `provenance` subcommand. Returns whether no git dependency is pinned to a
+/// branch.
+pub fn run(workspace: &Workspace, json: bool) -> Result<bool, AnalysisError> {
+    let mut pinned = true;
+    let mut reports = BTreeMap::new();
+    for package in &workspace.members {
+        let dependencies = resolve_dependencies(&package.root_dir.join("Nargo.toml"))?;
+        let provenances: Vec<Provenance> = dependencies.iter().map(Provenance::of).collect();
+        pinned &= provenances
+            .iter()
+            .all(|provenance| provenance.pin != Pin::Branch);
+
+        if json {
+            reports.insert(package.name.to_string(), provenances);
+            continue;
+        }
+
+        println!("Package: {}", package.name);
+        if provenances.is_empty() {
+            println!("  No dependencies");
+            continue;
+        }
+        for provenance in &provenances {
+            println!("  {} ({})", provenance.name, provenance.source);
+            match (&provenance.reference, provenance.pin) {
+                (Some(reference), Pin::Branch) => println!(
+                    "    \x1b[1;33mwarning\x1b[0m: pinned to branch '{reference}', pin a tag or commit instead"
+                ),
+                (Some(reference), _) => println!("    pinned to: {reference}"),
+                (None, _) => {}
+            }
+            if let Some(commit) = &provenance.commit {
+                println!("    commit: {commit}");
+            }
+            match &provenance.license {
+                Some(license) if provenance.license_guessed => {
+                    println!("    license: {license} (guessed from license files)")
+                }
+                Some(license) => println!("    license: {license}"),
+                None => println!("    license: unknown"),
+            }
+            if provenance.required_by.len() > 1 {
+                println!("    required by: {}", provenance.required_by.join(" -> "));
+            }
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    Ok(pinned)
+}
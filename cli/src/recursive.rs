@@ -0,0 +1,186 @@
+//! `check --recursive`: analyzes every project below a directory, such as the circuits of a
+//! monorepo, and reports their findings grouped by project.
+//!
+//! Projects are analyzed in parallel, one per thread. Each one is analyzed as if `check`
+//! ran in its directory: it loads its own configuration and baseline.
+
+use crate::error::AnalysisError;
+use crate::{
+    CheckArgs, CheckedWorkspace, OutputFormat, apply_fixes, check_workspace, enrich_with_blame,
+    exit_code, jobs, label_packages, notify, parse_workspace, print_pretty, status,
+};
+use nargo::workspace::Workspace;
+use noir_analyzer::ast::coverage::Coverage;
+use noir_analyzer::diagnostics::reporter::Reporter;
+use noir_analyzer::sources::find_manifests;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Analyzes every project with a `Nargo.toml` below `dir`. Fails when a project cannot be
+/// analyzed or any lint is reported as an error.
+pub fn run(dir: &Path, args: &CheckArgs) -> Result<ExitCode, AnalysisError> {
+    let mut succeeded = true;
+    let mut workspaces = vec![];
+    for manifest_path in find_manifests(dir) {
+        match parse_workspace(&manifest_path) {
+            Ok(workspace) if workspace.is_assumed => workspaces.push(workspace),
+            // Workspace manifests list members, which have manifests of their own
+            Ok(_) => status(
+                args.format,
+                format_args!(
+                    "Skipping workspace {manifest_path:?}, whose members are found on their own"
+                ),
+            ),
+            Err(e) => status(args.format, format_args!("Skipping {manifest_path:?}: {e}")),
+        }
+    }
+    if workspaces.is_empty() {
+        return Err(AnalysisError::Usage(format!(
+            "Could not find any package Nargo.toml below {dir:?}"
+        )));
+    }
+    status(
+        args.format,
+        format_args!("Found {} projects below {dir:?}", workspaces.len()),
+    );
+
+    let results = check_workspaces(&workspaces, args);
+
+    let mut findings = vec![];
+    let mut projects = vec![];
+    let mut coverage = Coverage::default();
+    let mut fixed: Option<usize> = None;
+    for (workspace, result) in workspaces.iter().zip(results) {
+        let project = project_name(&workspace.root_dir, dir);
+        let mut checked = match result {
+            Ok(checked) => checked,
+            Err(e) => {
+                match args.format {
+                    OutputFormat::Json => projects.push(json!({
+                        "project": project.display().to_string().replace('\\', "/"),
+                        "error": e.to_json(),
+                    })),
+                    _ => eprint!("{}: {}", project.display(), e.render()),
+                }
+                succeeded = false;
+                continue;
+            }
+        };
+        succeeded &= checked.errors.is_empty();
+        enrich_with_blame(&mut checked.findings, args);
+        notify(&checked.config, &checked.findings, &workspace.root_dir);
+        coverage.merge(&checked.coverage);
+        if let Some(count) = checked.fixed {
+            *fixed.get_or_insert(0) += count;
+        }
+
+        match args.format {
+            OutputFormat::Pretty => {
+                println!("\x1b[1mProject: {}\x1b[0m", project.display());
+                for file_findings in checked.findings.chunk_by(|a, b| a.file_path == b.file_path) {
+                    print_pretty(
+                        file_findings,
+                        &file_findings[0].file_path,
+                        &workspace.root_dir,
+                        checked.locations,
+                    );
+                }
+            }
+            OutputFormat::Short => print!(
+                "{}",
+                Reporter::short_report(&checked.findings, dir, checked.locations)
+            ),
+            OutputFormat::Json => {
+                let mut value = Reporter::json_value(&checked.findings, &workspace.root_dir);
+                label_packages(&mut value, &checked.findings, workspace);
+                value["project"] = json!(project.display().to_string().replace('\\', "/"));
+                projects.push(value);
+            }
+            OutputFormat::Sarif | OutputFormat::Markdown => {}
+        }
+
+        if args.fix {
+            succeeded &=
+                apply_fixes(&checked.findings, &checked.config, args).unwrap_or_else(|e| {
+                    eprint!("{}", e.render());
+                    false
+                });
+        }
+        findings.extend(checked.findings);
+    }
+
+    match args.format {
+        OutputFormat::Pretty | OutputFormat::Short => {}
+        OutputFormat::Json => {
+            let report = json!({ "projects": Value::Array(projects) });
+            println!("{}", serde_json::to_string_pretty(&report).unwrap());
+        }
+        OutputFormat::Sarif => println!("{}", Reporter::sarif_report(&findings, dir)),
+        OutputFormat::Markdown => print!(
+            "{}",
+            Reporter::markdown_report(&findings, fixed, dir, args.locations.unwrap_or_default())
+        ),
+    }
+    if args.report_coverage {
+        eprint!("{}", Reporter::coverage_report(&coverage));
+    }
+
+    Ok(if succeeded {
+        exit_code(&findings)
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Analyzes the workspaces on up to `--jobs` threads, one workspace per thread at a time.
+/// Results are returned in the order of the workspaces, so the report does not depend on
+/// scheduling.
+fn check_workspaces(
+    workspaces: &[Workspace],
+    args: &CheckArgs,
+) -> Vec<Result<CheckedWorkspace, AnalysisError>> {
+    let threads = jobs(args).min(workspaces.len()).max(1);
+    let next_workspace = AtomicUsize::new(0);
+    let mut results: Vec<(usize, Result<CheckedWorkspace, AnalysisError>)> =
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = vec![];
+                        loop {
+                            let index = next_workspace.fetch_add(1, Ordering::Relaxed);
+                            let Some(workspace) = workspaces.get(index) else {
+                                break;
+                            };
+                            results.push((index, check_workspace(workspace, args, 1, true)));
+                        }
+                        results
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Returns the path of a project relative to the analyzed directory.
+fn project_name(root_dir: &Path, dir: &Path) -> PathBuf {
+    match root_dir.strip_prefix(dir) {
+        Ok(path) if path.as_os_str().is_empty() => PathBuf::from("."),
+        Ok(path) => path.to_path_buf(),
+        Err(_) => root_dir.to_path_buf(),
+    }
+}
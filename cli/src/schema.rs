@@ -0,0 +1,24 @@
+//! # `schema` Subcommand
+//!
+//! Prints the interface of the circuits of the workspace as JSON, keyed by package name, so
+//! that bindings can be generated from it.
+
+use crate::error::{AnalysisError, parse_file, read_file};
+use nargo::workspace::Workspace;
+use noir_analyzer::ast::parser::Parser;
+use noir_analyzer::schema::Schema;
+use std::collections::BTreeMap;
+
+/// Runs the `schema` subcommand.
+pub fn run(workspace: &Workspace) -> Result<(), AnalysisError> {
+    let mut schemas = BTreeMap::new();
+    let mut parser = Parser::new(&workspace.root_dir);
+    for package in &workspace.members {
+        let source = read_file(&package.entry_path)?;
+        let parsed_module = parse_file(&mut parser, &package.entry_path, &source)?;
+        schemas.insert(package.name.to_string(), Schema::extract(&parsed_module));
+    }
+
+    println!("{}", serde_json::to_string_pretty(&schemas)?);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! # `score` Subcommand
+//!
+//! Analyzes the workspace like `check`, and sums up its findings and size into a health
+//! score between 0 and 100. Each run is recorded in `target/noir-analyzer/history.json`,
+//! so that the score is reported with its change since the previous run: a single trend
+//! number for dashboards.
+
+use crate::error::{AnalysisError, parse_file, read_file};
+use crate::{CheckArgs, check_workspace, jobs};
+use nargo::workspace::Workspace;
+use noir_analyzer::ast::parser::Parser;
+use noir_analyzer::health::{Category, HISTORY_PATH, History, Metrics, Score};
+use noir_analyzer::sources::SourceWalker;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Runs the `score` subcommand.
+pub fn run(workspace: &Workspace, json: bool, no_save: bool) -> Result<(), AnalysisError> {
+    let args = CheckArgs {
+        no_progress: true,
+        ..CheckArgs::default()
+    };
+    let checked = check_workspace(workspace, &args, jobs(&args), true)?;
+
+    let mut metrics = Metrics::default();
+    let walker = SourceWalker::new(&workspace.root_dir).exclude(&checked.config.exclude)?;
+    let mut parser = Parser::new(&workspace.root_dir);
+    for file in walker.walk() {
+        let source = read_file(&file)?;
+        metrics.add_file(
+            &source,
+            parse_file(&mut parser, &file, &source).ok().as_ref(),
+        );
+    }
+    let score = Score::compute(&checked.findings, metrics);
+
+    let history_path = workspace.root_dir.join(HISTORY_PATH);
+    let mut history = History::load(&history_path)?;
+    let previous = history.previous().map(|previous| previous.value);
+    let delta = history.record(score.clone(), timestamp());
+    if !no_save {
+        history.save(&history_path)?;
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "score": score.value,
+                "previous": previous,
+                "delta": delta,
+                "findings": score.findings,
+                "metrics": score.metrics,
+            }))?
+        );
+        return Ok(());
+    }
+
+    match delta {
+        Some(delta) => println!(
+            "Health score: {:.1} ({delta:+.1} since the previous run)",
+            score.value
+        ),
+        None => println!("Health score: {:.1}", score.value),
+    }
+    for category in Category::ALL {
+        println!(
+            "  {:<16} {:>4} finding(s), weight {}",
+            category.as_str(),
+            score.findings.get(&category).copied().unwrap_or(0),
+            category.weight()
+        );
+    }
+    println!(
+        "  {} files, {} lines, {} functions",
+        metrics.files, metrics.lines, metrics.functions
+    );
+    Ok(())
+}
+
+/// Returns the current time, in seconds since the Unix epoch.
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
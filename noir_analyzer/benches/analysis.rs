@@ -0,0 +1,88 @@
+//! Benchmarks of the analysis pipeline and of its parts, on generated programs.
+//!
+//! Run with `cargo bench -p noir_analyzer`. To validate a change, save a baseline before
+//! it with `-- --save-baseline main` and compare with `-- --baseline main`.
+
+mod fixtures;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use noir_analyzer::ast::analyzer::Analyzer;
+use noir_analyzer::ast::parser::Parser;
+use noir_analyzer::config::Config;
+use std::hint::black_box;
+
+fn parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parsing");
+    for fixture in fixtures::all() {
+        group.throughput(Throughput::Bytes(fixture.source.len() as u64));
+        group.bench_function(fixture.name, |b| {
+            b.iter(|| Parser::parse_program_with_dummy_file(black_box(&fixture.source)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// Visits the programs without any rule, i.e. the cost shared by every rule.
+fn traversal(c: &mut Criterion) {
+    let mut group = c.benchmark_group("traversal");
+    for fixture in fixtures::all() {
+        let parsed_module = Parser::parse_program_with_dummy_file(&fixture.source).unwrap();
+        group.bench_function(fixture.name, |b| {
+            b.iter(|| {
+                Analyzer::new(&[])
+                    .analyze(black_box(&parsed_module))
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Runs each rule alone on the program made of every fixture.
+fn rules(c: &mut Criterion) {
+    let fixture = fixtures::all().pop().unwrap();
+    let parsed_module = Parser::parse_program_with_dummy_file(&fixture.source).unwrap();
+    let mut group = c.benchmark_group("rules");
+    for rule in fixtures::rules() {
+        let name = rule.name();
+        let lint_rules = vec![rule];
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &lint_rules,
+            |b, lint_rules| {
+                b.iter(|| {
+                    Analyzer::new(lint_rules)
+                        .with_source(&fixture.source)
+                        .analyze(black_box(&parsed_module))
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Parses, analyzes with every rule and applies the configuration, as `check` does.
+fn pipeline(c: &mut Criterion) {
+    let lint_rules = fixtures::rules();
+    let config = Config::default();
+    let mut group = c.benchmark_group("pipeline");
+    for fixture in fixtures::all() {
+        group.throughput(Throughput::Bytes(fixture.source.len() as u64));
+        group.bench_function(fixture.name, |b| {
+            b.iter(|| {
+                let source = black_box(&fixture.source);
+                let parsed_module = Parser::parse_program_with_dummy_file(source).unwrap();
+                let lints = Analyzer::new(&lint_rules)
+                    .with_source(source)
+                    .analyze(&parsed_module)
+                    .unwrap();
+                config.apply(lints)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parsing, traversal, rules, pipeline);
+criterion_main!(benches);
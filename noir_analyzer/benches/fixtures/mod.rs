@@ -0,0 +1,153 @@
+//! Large programs generated for the benchmarks, shaped after the code that slows the
+//! analyzer down in practice: contracts with many similar functions, deep call graphs and
+//! webs of modules importing each other. Generation is deterministic, so that results are
+//! comparable between runs.
+
+use noir_analyzer::lints::lint_rule::LintRule;
+use noir_analyzer::lints::registry::LintRegistry;
+use std::fmt::Write;
+
+/// A generated program.
+pub struct Fixture {
+    pub name: &'static str,
+    pub source: String,
+}
+
+/// Returns every fixture, and a program made of all of them.
+pub fn all() -> Vec<Fixture> {
+    let fixtures = vec![
+        generated_contract(200),
+        deep_call_graph(300),
+        module_web(100),
+    ];
+    let combined = Fixture {
+        name: "combined",
+        source: fixtures
+            .iter()
+            .map(|fixture| fixture.source.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    fixtures.into_iter().chain([combined]).collect()
+}
+
+/// Returns every built-in lint rule run on packages, opt-in ones included.
+pub fn rules() -> Vec<Box<dyn LintRule>> {
+    let mut registry = LintRegistry::builtin();
+    registry.enable_all();
+    registry.rules()
+}
+
+/// A contract of `functions` similar functions, each with an unsafe hint, a loop
+/// accumulating into a `Field` and a call to the previous one.
+fn generated_contract(functions: usize) -> Fixture {
+    let mut source = String::from(
+        "contract Generated {\n    struct Note { owner: Field, value: Field, nonce: Field }\n\n    unconstrained fn hint(x: Field) -> Field { x * 2 }\n",
+    );
+    for index in 0..functions {
+        let previous = match index {
+            0 => "0".to_string(),
+            _ => format!("step_{}(note, x)", index - 1),
+        };
+        write!(
+            source,
+            r#"
+    fn step_{index}(note: Note, x: Field) -> Field {{
+        // Safety: checked below
+        let h = unsafe {{ hint(x) }};
+        assert(h == x * 2);
+        let mut acc = 0;
+        for j in 0..16 {{
+            acc += note.value * x + j as Field;
+        }}
+        if acc == note.owner {{
+            acc = acc + 1;
+        }}
+        assert((acc + note.nonce) * (x + 1) != note.owner * {index});
+        acc + {previous}
+    }}
+"#
+        )
+        .unwrap();
+    }
+    source.push_str("}\n");
+
+    Fixture {
+        name: "generated_contract",
+        source,
+    }
+}
+
+/// A chain of `depth` functions from `main`, each also calling the function two levels
+/// below it, so that the call graph has exponentially many paths.
+fn deep_call_graph(depth: usize) -> Fixture {
+    let mut source = String::from("fn main(x: Field) -> pub Field {\n    node_0(x)\n}\n");
+    for index in 0..depth {
+        let body = match depth - index {
+            1 => "x".to_string(),
+            2 => format!("node_{}(x + 1)", index + 1),
+            _ => format!("node_{}(x + 1) + node_{}(x * 2)", index + 1, index + 2),
+        };
+        writeln!(
+            source,
+            "\nfn node_{index}(x: Field) -> Field {{\n    {body}\n}}"
+        )
+        .unwrap();
+    }
+
+    Fixture {
+        name: "deep_call_graph",
+        source,
+    }
+}
+
+/// `modules` modules, each importing the previous one and the first one, with a cycle
+/// through the last one.
+fn module_web(modules: usize) -> Fixture {
+    let mut source = String::new();
+    for index in 0..modules {
+        let mut imports = String::new();
+        let body = match index {
+            0 => {
+                writeln!(
+                    imports,
+                    "    use crate::m_{}::value_{};",
+                    modules - 1,
+                    modules - 1
+                )
+                .unwrap();
+                "x".to_string()
+            }
+            _ => {
+                writeln!(
+                    imports,
+                    "    use crate::m_{}::value_{};",
+                    index - 1,
+                    index - 1
+                )
+                .unwrap();
+                writeln!(imports, "    use crate::m_0::value_0;").unwrap();
+                format!("value_{}(x) + value_0(x)", index - 1)
+            }
+        };
+        write!(
+            source,
+            r#"
+mod m_{index} {{
+{imports}
+    pub struct Wide{index} {{ a: [Field; 16], b: [u64; 8] }}
+
+    pub fn value_{index}(x: Field) -> Field {{
+        {body}
+    }}
+}}
+"#
+        )
+        .unwrap();
+    }
+
+    Fixture {
+        name: "module_web",
+        source,
+    }
+}
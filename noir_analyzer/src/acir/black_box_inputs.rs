@@ -0,0 +1,248 @@
+//! # Black-Box Input Ranges
+//!
+//! Black-box functions such as Keccak, SHA-256 or ECDSA verification expect inputs of a
+//! documented width, e.g. bytes or 64-bit lanes, but do not constrain them themselves. The
+//! compiler emits a `RANGE` constraint for each input; an input without one, or with a
+//! wider one, lets the prover pass values the function does not expect, a soundness gap
+//! invisible in the source.
+//!
+//! A witness is range-constrained to the smallest width of a `RANGE` constraint on it
+//! anywhere in the circuit, or of the black-box call that outputs it, e.g. the bytes of a
+//! Blake2s digest. Constant inputs must fit in the expected width. Witnesses constrained
+//! through arithmetic only, such as a bit decomposition, are not recognized.
+
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use acir::circuit::opcodes::{BlackBoxFuncCall, FunctionInput};
+use acir::circuit::{Circuit, Opcode};
+use acir::native_types::Witness;
+use acir::{AcirField, FieldElement};
+use std::collections::BTreeMap;
+
+pub const NAME: &str = "black-box-input-range";
+
+/// Checks the inputs of the black-box calls of a circuit against their expected width,
+/// returning each lint together with the index of the opcode it was reported on.
+pub fn check_circuit(circuit: &Circuit<FieldElement>) -> Vec<(usize, Lint)> {
+    let mut widths: BTreeMap<Witness, u32> = BTreeMap::new();
+    for opcode in &circuit.opcodes {
+        if let Opcode::BlackBoxFuncCall(call) = opcode {
+            for (witness, bits) in known_widths(call) {
+                widths
+                    .entry(witness)
+                    .and_modify(|width| *width = (*width).min(bits))
+                    .or_insert(bits);
+            }
+        }
+    }
+
+    let mut lints = vec![];
+    for (index, opcode) in circuit.opcodes.iter().enumerate() {
+        let Opcode::BlackBoxFuncCall(call) = opcode else {
+            continue;
+        };
+        let Some((expected, inputs)) = expected_inputs(call) else {
+            continue;
+        };
+
+        let mut missing = 0;
+        let mut widest = None;
+        for input in &inputs {
+            let bits = match input {
+                FunctionInput::Constant(value) => Some(value.num_bits()),
+                FunctionInput::Witness(witness) => widths.get(witness).copied(),
+            };
+            match bits {
+                None => missing += 1,
+                Some(bits) if bits > expected => widest = widest.max(Some(bits)),
+                Some(_) => {}
+            }
+        }
+
+        let message = |id| {
+            Message::new(id)
+                .with("function", call.name())
+                .with("opcode", index)
+                .with("circuit", &circuit.function_name)
+                .with("bits", expected)
+        };
+        if missing > 0 {
+            lints.push((
+                index,
+                lint(
+                    message("black-box-input-range.missing")
+                        .with("count", missing)
+                        .with("inputs", inputs.len()),
+                ),
+            ));
+        }
+        if let Some(bits) = widest {
+            lints.push((
+                index,
+                lint(message("black-box-input-range.too-wide").with("actual", bits)),
+            ));
+        }
+    }
+    lints
+}
+
+/// Returns the inputs of a black-box call that must fit in a documented width, with that
+/// width.
+fn expected_inputs(
+    call: &BlackBoxFuncCall<FieldElement>,
+) -> Option<(u32, Vec<&FunctionInput<FieldElement>>)> {
+    match call {
+        BlackBoxFuncCall::AES128Encrypt {
+            inputs, iv, key, ..
+        } => Some((
+            8,
+            inputs.iter().chain(iv.iter()).chain(key.iter()).collect(),
+        )),
+        BlackBoxFuncCall::AND {
+            lhs, rhs, num_bits, ..
+        }
+        | BlackBoxFuncCall::XOR {
+            lhs, rhs, num_bits, ..
+        } => Some((*num_bits, vec![lhs, rhs])),
+        BlackBoxFuncCall::Blake2s { inputs, .. } | BlackBoxFuncCall::Blake3 { inputs, .. } => {
+            Some((8, inputs.iter().collect()))
+        }
+        BlackBoxFuncCall::EcdsaSecp256k1 {
+            public_key_x,
+            public_key_y,
+            signature,
+            hashed_message,
+            ..
+        }
+        | BlackBoxFuncCall::EcdsaSecp256r1 {
+            public_key_x,
+            public_key_y,
+            signature,
+            hashed_message,
+            ..
+        } => Some((
+            8,
+            public_key_x
+                .iter()
+                .chain(public_key_y.iter())
+                .chain(signature.iter())
+                .chain(hashed_message.iter())
+                .collect(),
+        )),
+        BlackBoxFuncCall::Keccakf1600 { inputs, .. } => Some((64, inputs.iter().collect())),
+        BlackBoxFuncCall::Sha256Compression {
+            inputs,
+            hash_values,
+            ..
+        } => Some((32, inputs.iter().chain(hash_values.iter()).collect())),
+        _ => None,
+    }
+}
+
+/// Returns the witnesses a black-box call constrains to a width: the input of a `RANGE`
+/// constraint, and the outputs whose width the backend guarantees.
+fn known_widths(call: &BlackBoxFuncCall<FieldElement>) -> Vec<(Witness, u32)> {
+    let all = |outputs: &[Witness], bits| outputs.iter().map(|output| (*output, bits)).collect();
+    match call {
+        BlackBoxFuncCall::RANGE {
+            input: FunctionInput::Witness(witness),
+            num_bits,
+        } => vec![(*witness, *num_bits)],
+        BlackBoxFuncCall::AND {
+            output, num_bits, ..
+        }
+        | BlackBoxFuncCall::XOR {
+            output, num_bits, ..
+        } => vec![(*output, *num_bits)],
+        BlackBoxFuncCall::AES128Encrypt { outputs, .. } => all(outputs, 8),
+        BlackBoxFuncCall::Blake2s { outputs, .. } | BlackBoxFuncCall::Blake3 { outputs, .. } => {
+            all(outputs.as_slice(), 8)
+        }
+        BlackBoxFuncCall::Keccakf1600 { outputs, .. } => all(outputs.as_slice(), 64),
+        BlackBoxFuncCall::Sha256Compression { outputs, .. } => all(outputs.as_slice(), 32),
+        BlackBoxFuncCall::EcdsaSecp256k1 { output, .. }
+        | BlackBoxFuncCall::EcdsaSecp256r1 { output, .. } => vec![(*output, 1)],
+        _ => vec![],
+    }
+}
+
+fn lint(message: Message) -> Lint {
+    Lint {
+        name: NAME,
+        severity: Severity::Error,
+        // Witnesses equal to a range-constrained one are not recognized
+        confidence: Confidence::Medium,
+        description: message.to_string(),
+        message: Some(message),
+        span: None,
+        file_id: None,
+        suggestion: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn witness(index: u32) -> FunctionInput<FieldElement> {
+        FunctionInput::Witness(Witness(index))
+    }
+
+    #[test]
+    fn test_black_box_inputs_need_a_range_constraint_of_their_width() {
+        let opcodes = vec![
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: witness(0),
+                num_bits: 8,
+            }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: witness(1),
+                num_bits: 16,
+            }),
+            // Witness 2 is not range-constrained, witness 1 is too wide
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Blake2s {
+                inputs: vec![witness(0), witness(1), witness(2)],
+                outputs: Box::new(std::array::from_fn(|index| Witness(10 + index as u32))),
+            }),
+            // Bytes of the digest fit in 64-bit lanes
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Keccakf1600 {
+                inputs: Box::new(std::array::from_fn(|index| witness(10 + index as u32 % 32))),
+                outputs: Box::new(std::array::from_fn(|index| Witness(100 + index as u32))),
+            }),
+            // A 64-bit lane and a constant that does not fit in a byte
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
+                lhs: witness(100),
+                rhs: FunctionInput::Constant(FieldElement::from(300u128)),
+                num_bits: 8,
+                output: Witness(200),
+            }),
+        ];
+        let circuit = Circuit {
+            function_name: "main".to_string(),
+            opcodes,
+            ..Circuit::default()
+        };
+
+        let lints = check_circuit(&circuit);
+
+        let opcodes: Vec<usize> = lints.iter().map(|(opcode, _)| *opcode).collect();
+        assert_eq!(opcodes, [2, 2, 4]);
+        let lints: Vec<Lint> = lints.into_iter().map(|(_, lint)| lint).collect();
+        let messages: Vec<_> = lints
+            .iter()
+            .map(|lint| lint.message.as_ref().unwrap().id)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "black-box-input-range.missing",
+                "black-box-input-range.too-wide",
+                "black-box-input-range.too-wide",
+            ]
+        );
+        assert!(lints[0].description.starts_with("1 of the 3 inputs"));
+        assert!(lints[1].description.contains("only constrained to 16 bits"));
+        assert!(lints[2].description.contains("opcode 4"));
+        assert!(lints[2].description.contains("64 bits"));
+    }
+}
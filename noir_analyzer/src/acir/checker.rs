@@ -1 +1,76 @@
+//! # Artifact Checker
+//!
+//! Reads the program compiled by `nargo compile` from its JSON artifact, and runs the
+//! circuit lints on each of its functions. The debug symbols of the artifact map the opcode
+//! each lint is reported on back to the source locations it was compiled from.
 
+use crate::acir::{black_box_inputs, repeated_subexpressions};
+use crate::diagnostics::lint::Lint;
+use acir::FieldElement;
+use acir::circuit::{AcirOpcodeLocation, Circuit, Program};
+use noirc_artifacts::program::ProgramArtifact;
+use noirc_frontend::hir::resolution::errors::Span;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AcirError {
+    #[error("Failed to read {0:?}: {1}")]
+    FileReadError(PathBuf, String),
+    #[error("Failed to parse {0:?}: {1}")]
+    ParseError(PathBuf, String),
+}
+
+/// A circuit lint with the source locations of the opcode it was reported on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitLint {
+    pub lint: Lint,
+    /// Call stack of the opcode, the innermost call last. Empty when the artifact has no
+    /// debug symbols for it.
+    pub locations: Vec<(PathBuf, Span)>,
+}
+
+/// Reads the artifact of a compiled program, e.g. `target/<package>.json`.
+pub fn read_artifact(path: &Path) -> Result<ProgramArtifact, AcirError> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| AcirError::FileReadError(path.to_path_buf(), e.to_string()))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| AcirError::ParseError(path.to_path_buf(), e.to_string()))
+}
+
+/// Reads the program compiled into an artifact.
+pub fn read_program(path: &Path) -> Result<Program<FieldElement>, AcirError> {
+    read_artifact(path).map(|artifact| artifact.bytecode)
+}
+
+/// Runs the circuit lints on every function of an artifact, resolving the source locations
+/// of each lint through its debug symbols.
+pub fn check_artifact(artifact: &ProgramArtifact) -> Vec<CircuitLint> {
+    let mut circuit_lints = vec![];
+    for (function, circuit) in artifact.bytecode.functions.iter().enumerate() {
+        let debug_info = artifact.debug_symbols.debug_infos.get(function);
+        for (opcode, lint) in check_circuit(circuit) {
+            let locations = debug_info
+                .and_then(|debug_info| {
+                    debug_info.acir_opcode_location(&AcirOpcodeLocation::new(opcode))
+                })
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|location| {
+                    let file = artifact.file_map.get(&location.file)?;
+                    Some((file.path.clone(), location.span))
+                })
+                .collect();
+            circuit_lints.push(CircuitLint { lint, locations });
+        }
+    }
+    circuit_lints
+}
+
+/// Runs the circuit lints on a function, returning each lint with the index of its opcode.
+fn check_circuit(circuit: &Circuit<FieldElement>) -> Vec<(usize, Lint)> {
+    let mut lints = black_box_inputs::check_circuit(circuit);
+    lints.extend(repeated_subexpressions::check_circuit(circuit));
+    lints
+}
@@ -0,0 +1,116 @@
+//! # Source and Circuit Correlation
+//!
+//! A source lint and a circuit lint often describe the same problem from two sides, e.g. an
+//! unconstrained value in the source and the black-box call missing its range constraint in
+//! the circuit. When the debug symbols place a circuit lint inside the span of a source
+//! finding, the circuit lint is merged into that finding as circuit-level evidence instead
+//! of being reported a second time.
+
+use crate::acir::checker::CircuitLint;
+use crate::diagnostics::finding::Finding;
+use crate::diagnostics::lint::{Lint, Severity};
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+
+/// Merges the circuit lints located in the span of a finding into it, removing them from
+/// `circuit_lints`. A finding merging an error becomes an error itself.
+pub fn attach_evidence(finding: &mut Finding, circuit_lints: &mut Vec<CircuitLint>) {
+    let Some(span) = finding.lint.span else {
+        return;
+    };
+    let file_path = canonical(&finding.file_path);
+    let (matched, rest): (Vec<CircuitLint>, Vec<CircuitLint>) = mem::take(circuit_lints)
+        .into_iter()
+        .partition(|circuit_lint| {
+            circuit_lint.locations.iter().any(|(path, location)| {
+                location.start() < span.end()
+                    && span.start() < location.end()
+                    && canonical(path) == file_path
+            })
+        });
+    *circuit_lints = rest;
+
+    for circuit_lint in matched {
+        if circuit_lint.lint.severity == Severity::Error {
+            finding.lint.severity = Severity::Error;
+        }
+        finding.circuit_evidence.push(circuit_lint.lint);
+    }
+}
+
+/// Returns the lint of a circuit lint located at its innermost source location, with the
+/// file of that location. Without one, the lint is returned as is.
+pub fn locate(circuit_lint: CircuitLint) -> (Option<PathBuf>, Lint) {
+    let mut lint = circuit_lint.lint;
+    match circuit_lint.locations.into_iter().last() {
+        Some((path, span)) => {
+            lint.span = Some(span);
+            (Some(path), lint)
+        }
+        None => (None, lint),
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::lint::Confidence;
+    use noirc_frontend::hir::resolution::errors::Span;
+
+    fn lint(name: &'static str, severity: Severity, span: Option<Span>) -> Lint {
+        Lint {
+            name,
+            severity,
+            confidence: Confidence::High,
+            description: name.to_string(),
+            message: None,
+            span,
+            file_id: None,
+            suggestion: None,
+        }
+    }
+
+    fn circuit_lint(path: &str, start: u32, end: u32) -> CircuitLint {
+        CircuitLint {
+            lint: lint("black-box-input-range", Severity::Error, None),
+            locations: vec![
+                (PathBuf::from("src/main.nr"), Span::from(0..4)),
+                (PathBuf::from(path), Span::from(start..end)),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_overlapping_circuit_lints_become_evidence() {
+        let source = "fn main() {}\nfn hash(x: Field) {}\n";
+        let mut finding = Finding::new(
+            lint(
+                "unused-variable",
+                Severity::Warning,
+                Some(Span::from(21..22)),
+            ),
+            Path::new("src/lib.nr"),
+            source,
+        );
+        let mut circuit_lints = vec![
+            circuit_lint("src/lib.nr", 20, 30),
+            circuit_lint("src/other.nr", 20, 30),
+            circuit_lint("src/lib.nr", 30, 40),
+        ];
+
+        attach_evidence(&mut finding, &mut circuit_lints);
+
+        assert_eq!(finding.circuit_evidence.len(), 1);
+        assert_eq!(finding.lint.severity, Severity::Error);
+        assert_eq!(circuit_lints.len(), 2);
+
+        let (path, lint) = locate(circuit_lints.remove(0));
+        assert_eq!(path, Some(PathBuf::from("src/other.nr")));
+        assert_eq!(lint.span, Some(Span::from(20..30)));
+    }
+}
@@ -0,0 +1,258 @@
+//! # Circuit Drift
+//!
+//! A deployed verifier is generated from the circuit it verifies: any change to the
+//! constraints, or to the order of the public inputs, invalidates it, even when the source
+//! change behind it looked harmless. A fingerprint records the hash of each circuit of a
+//! program and its public-input layout, so that a compiled program can be compared with an
+//! earlier artifact, or with a fingerprint stored next to the deployed verifier.
+//!
+//! Circuits are hashed with 64-bit FNV-1a over their JSON serialization, which is stable
+//! across runs but not across ACIR format changes: upgrading the compiler may report every
+//! circuit as changed.
+
+use crate::acir::checker::{AcirError, read_artifact};
+use acir::FieldElement;
+use acir::circuit::{Circuit, Program};
+use acir::native_types::Witness;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Hash and public-input layout of a circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitFingerprint {
+    pub function: String,
+    /// Hash of the opcodes and witnesses of the circuit, as 16 hexadecimal digits.
+    pub hash: String,
+    /// Witnesses of the public inputs, in the order the verifier expects them.
+    pub public_inputs: Vec<u32>,
+    /// Witnesses of the return values, which the verifier also receives as public inputs.
+    pub return_values: Vec<u32>,
+}
+
+/// Fingerprints of the circuits of a program, in function order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramFingerprint {
+    pub functions: Vec<CircuitFingerprint>,
+}
+
+/// Difference between the circuits of two fingerprints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Drift {
+    Added {
+        function: String,
+    },
+    Removed {
+        function: String,
+    },
+    /// The constraints changed, while the public inputs kept their layout.
+    CircuitChanged {
+        function: String,
+    },
+    PublicInputsChanged {
+        function: String,
+        old: Vec<u32>,
+        new: Vec<u32>,
+    },
+    ReturnValuesChanged {
+        function: String,
+        old: Vec<u32>,
+        new: Vec<u32>,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::Added { function } => write!(f, "Function '{function}' was added"),
+            Drift::Removed { function } => write!(f, "Function '{function}' was removed"),
+            Drift::CircuitChanged { function } => {
+                write!(f, "Circuit of function '{function}' changed")
+            }
+            Drift::PublicInputsChanged { function, old, new } => write!(
+                f,
+                "Public inputs of function '{function}' changed from {} to {}",
+                render_witnesses(old),
+                render_witnesses(new)
+            ),
+            Drift::ReturnValuesChanged { function, old, new } => write!(
+                f,
+                "Return values of function '{function}' changed from {} to {}",
+                render_witnesses(old),
+                render_witnesses(new)
+            ),
+        }
+    }
+}
+
+/// Computes the fingerprint of every circuit of a program.
+pub fn fingerprint(program: &Program<FieldElement>) -> ProgramFingerprint {
+    ProgramFingerprint {
+        functions: program.functions.iter().map(circuit_fingerprint).collect(),
+    }
+}
+
+fn circuit_fingerprint(circuit: &Circuit<FieldElement>) -> CircuitFingerprint {
+    let serialized = serde_json::to_vec(circuit).unwrap_or_default();
+    CircuitFingerprint {
+        function: circuit.function_name.clone(),
+        hash: format!("{:016x}", fnv1a(&serialized)),
+        public_inputs: witness_indices(&circuit.public_parameters.0),
+        return_values: witness_indices(&circuit.return_values.0),
+    }
+}
+
+/// Reads a fingerprint, either stored as JSON or computed from a program artifact.
+pub fn read_fingerprint(path: &Path) -> Result<ProgramFingerprint, AcirError> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| AcirError::FileReadError(path.to_path_buf(), e.to_string()))?;
+    match serde_json::from_str(&contents) {
+        Ok(fingerprint) => Ok(fingerprint),
+        Err(_) => read_artifact(path).map(|artifact| fingerprint(&artifact.bytecode)),
+    }
+}
+
+/// Compares two fingerprints, matching circuits by function name. A function whose circuit
+/// changed reports its layout changes, or the circuit change when its layout is unchanged.
+pub fn compare(old: &ProgramFingerprint, new: &ProgramFingerprint) -> Vec<Drift> {
+    let find = |fingerprint: &ProgramFingerprint, function: &str| {
+        fingerprint
+            .functions
+            .iter()
+            .find(|circuit| circuit.function == function)
+            .cloned()
+    };
+
+    let mut drifts = vec![];
+    for old_circuit in &old.functions {
+        let function = old_circuit.function.clone();
+        let Some(new_circuit) = find(new, &function) else {
+            drifts.push(Drift::Removed { function });
+            continue;
+        };
+        if old_circuit.hash == new_circuit.hash {
+            continue;
+        }
+        let mut layout_changed = false;
+        if old_circuit.public_inputs != new_circuit.public_inputs {
+            layout_changed = true;
+            drifts.push(Drift::PublicInputsChanged {
+                function: function.clone(),
+                old: old_circuit.public_inputs.clone(),
+                new: new_circuit.public_inputs.clone(),
+            });
+        }
+        if old_circuit.return_values != new_circuit.return_values {
+            layout_changed = true;
+            drifts.push(Drift::ReturnValuesChanged {
+                function: function.clone(),
+                old: old_circuit.return_values.clone(),
+                new: new_circuit.return_values.clone(),
+            });
+        }
+        if !layout_changed {
+            drifts.push(Drift::CircuitChanged { function });
+        }
+    }
+    for new_circuit in &new.functions {
+        if find(old, &new_circuit.function).is_none() {
+            drifts.push(Drift::Added {
+                function: new_circuit.function.clone(),
+            });
+        }
+    }
+    drifts
+}
+
+fn witness_indices(witnesses: &BTreeSet<Witness>) -> Vec<u32> {
+    witnesses
+        .iter()
+        .map(|witness| witness.witness_index())
+        .collect()
+}
+
+/// Renders witness indices like ACIR, e.g. `[w0, w3]`.
+fn render_witnesses(witnesses: &[u32]) -> String {
+    let witnesses: Vec<String> = witnesses
+        .iter()
+        .map(|witness| format!("w{witness}"))
+        .collect();
+    format!("[{}]", witnesses.join(", "))
+}
+
+/// 64-bit FNV-1a hash, stable across platforms and Rust releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use acir::AcirField;
+    use acir::circuit::{Opcode, PublicInputs};
+    use acir::native_types::Expression;
+
+    fn circuit(function: &str, constant: u128, public_inputs: &[u32]) -> Circuit<FieldElement> {
+        Circuit {
+            function_name: function.to_string(),
+            current_witness_index: 2,
+            opcodes: vec![Opcode::AssertZero(Expression {
+                mul_terms: vec![],
+                linear_combinations: vec![(FieldElement::one(), Witness(0))],
+                q_c: FieldElement::from(constant),
+            })],
+            public_parameters: PublicInputs(public_inputs.iter().copied().map(Witness).collect()),
+            ..Circuit::default()
+        }
+    }
+
+    fn program(functions: Vec<Circuit<FieldElement>>) -> ProgramFingerprint {
+        fingerprint(&Program {
+            functions,
+            ..Program::default()
+        })
+    }
+
+    #[test]
+    fn test_drift_reports_the_changed_functions() {
+        let old = program(vec![
+            circuit("main", 1, &[0]),
+            circuit("helper", 1, &[]),
+            circuit("removed", 1, &[]),
+        ]);
+        let new = program(vec![
+            circuit("main", 1, &[0, 1]),
+            circuit("helper", 2, &[]),
+            circuit("added", 1, &[]),
+        ]);
+
+        assert!(compare(&old, &old).is_empty());
+        let drifts: Vec<String> = compare(&old, &new).iter().map(Drift::to_string).collect();
+        assert_eq!(
+            drifts,
+            [
+                "Public inputs of function 'main' changed from [w0] to [w0, w1]",
+                "Circuit of function 'helper' changed",
+                "Function 'removed' was removed",
+                "Function 'added' was added",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fingerprints_round_trip_through_json() {
+        let fingerprint = program(vec![circuit("main", 1, &[0])]);
+        let json = serde_json::to_string(&fingerprint).unwrap();
+
+        assert_eq!(fingerprint.functions[0].hash.len(), 16);
+        assert_eq!(
+            serde_json::from_str::<ProgramFingerprint>(&json).unwrap(),
+            fingerprint
+        );
+    }
+}
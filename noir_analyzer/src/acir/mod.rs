@@ -1 +1,16 @@
+//! # ACIR Analysis
+//!
+//! Checks of the compiled circuits of a program, read from the artifacts `nargo compile`
+//! writes to the `target` directory. Unlike the AST, the circuit shows the constraints the
+//! compiler actually emitted, such as the range constraints on black-box function inputs.
+//!
+//! Circuit lints are mapped back to the source through the debug symbols of the artifact.
+//! A source finding overlapping the location of a circuit lint absorbs it as circuit-level
+//! evidence; the others are reported at their own location, or on the artifact without one.
+
+pub mod black_box_inputs;
 pub mod checker;
+pub mod correlation;
+pub mod drift;
+pub mod repeated_subexpressions;
+pub mod stats;
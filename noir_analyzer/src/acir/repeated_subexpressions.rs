@@ -0,0 +1,252 @@
+//! # Repeated Linear Combinations
+//!
+//! The compiler emits each assertion as an `AssertZero` opcode of its own, so a linear
+//! combination used by many assertions, e.g. a weighted sum of the same inputs, is repeated
+//! in every one of them, and each term costs width in the gates the backend lays them out
+//! on. Binding the combination to an intermediate witness once replaces its terms with a
+//! single one in every use.
+//!
+//! Combinations are compared up to a common factor: `2*w1 + 4*w2` and `w1 + 2*w2` are the
+//! same combination. Pairs of terms shared by several opcodes are grown into the largest
+//! combination these opcodes share, and reported when the terms it saves outweigh the new
+//! opcode defining it: `uses * (terms - 1) - (terms + 1)`.
+
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use acir::circuit::{Circuit, Opcode};
+use acir::native_types::{Expression, Witness};
+use acir::{AcirField, FieldElement};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const NAME: &str = "repeated-linear-combination";
+
+/// Number of opcodes a combination must appear in to be reported.
+const MIN_USES: usize = 3;
+
+/// Opcodes with more linear terms are not searched, as the number of pairs grows
+/// quadratically.
+const MAX_TERMS: usize = 16;
+
+/// Number of combinations reported per circuit, those saving the most terms first.
+const MAX_REPORTED: usize = 10;
+
+/// Terms of a combination, keyed by witness, scaled so that the coefficient of its first
+/// witness is one.
+type Combination = BTreeMap<Witness, FieldElement>;
+
+/// Suggests intermediate witnesses for the linear combinations repeated across the
+/// `AssertZero` opcodes of a circuit, returning each lint together with the index of the
+/// first opcode using the combination.
+pub fn check_circuit(circuit: &Circuit<FieldElement>) -> Vec<(usize, Lint)> {
+    let expressions: Vec<(usize, &Expression<FieldElement>)> = circuit
+        .opcodes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, opcode)| match opcode {
+            Opcode::AssertZero(expression) => Some((index, expression)),
+            _ => None,
+        })
+        .filter(|(_, expression)| (2..=MAX_TERMS).contains(&expression.linear_combinations.len()))
+        .collect();
+
+    // Opcodes using each pair of terms, keyed by their witnesses and the ratio of their
+    // coefficients
+    let mut pairs: BTreeMap<(Witness, Witness, Vec<u8>), Vec<usize>> = BTreeMap::new();
+    for (position, (_, expression)) in expressions.iter().enumerate() {
+        let terms = terms(expression);
+        let terms: Vec<(&Witness, &FieldElement)> = terms.iter().collect();
+        for (first, (first_witness, first_coefficient)) in terms.iter().enumerate() {
+            for (second_witness, second_coefficient) in &terms[first + 1..] {
+                let ratio = **second_coefficient / **first_coefficient;
+                pairs
+                    .entry((**first_witness, **second_witness, ratio.to_be_bytes()))
+                    .or_default()
+                    .push(position);
+            }
+        }
+    }
+
+    let mut reported = BTreeSet::new();
+    let mut suggestions = vec![];
+    for ((anchor, _, _), positions) in pairs {
+        if positions.len() < MIN_USES {
+            continue;
+        }
+        let combination = common_combination(
+            positions.iter().map(|position| expressions[*position].1),
+            anchor,
+        );
+        let key: Vec<(Witness, Vec<u8>)> = combination
+            .iter()
+            .map(|(witness, coefficient)| (*witness, coefficient.to_be_bytes()))
+            .collect();
+        let uses = positions.len();
+        let saved = (uses * (combination.len() - 1)).saturating_sub(combination.len() + 1);
+        if saved == 0 || !reported.insert(key) {
+            continue;
+        }
+        let opcodes: Vec<usize> = positions
+            .iter()
+            .map(|position| expressions[*position].0)
+            .collect();
+        suggestions.push((saved, combination, opcodes));
+    }
+
+    suggestions.sort_by(|(a, _, a_opcodes), (b, _, b_opcodes)| {
+        b.cmp(a).then_with(|| a_opcodes.cmp(b_opcodes))
+    });
+    suggestions.truncate(MAX_REPORTED);
+    suggestions
+        .into_iter()
+        .map(|(saved, combination, opcodes)| {
+            let lint = lint(
+                Message::new(NAME)
+                    .with("combination", render(&combination))
+                    .with("count", opcodes.len())
+                    .with("circuit", &circuit.function_name)
+                    .with("opcodes", render_opcodes(&opcodes))
+                    .with("saved", saved),
+            );
+            (opcodes[0], lint)
+        })
+        .collect()
+}
+
+/// Returns the linear terms of an expression by witness, summing repeated witnesses.
+fn terms(expression: &Expression<FieldElement>) -> BTreeMap<Witness, FieldElement> {
+    let mut terms = BTreeMap::new();
+    for (coefficient, witness) in &expression.linear_combinations {
+        *terms.entry(*witness).or_insert_with(FieldElement::zero) += *coefficient;
+    }
+    terms.retain(|_, coefficient| !coefficient.is_zero());
+    terms
+}
+
+/// Returns the largest combination containing `anchor` that every expression contains,
+/// up to a common factor, scaled so that the coefficient of its first witness is one.
+fn common_combination<'e>(
+    mut expressions: impl Iterator<Item = &'e Expression<FieldElement>>,
+    anchor: Witness,
+) -> Combination {
+    let scaled = |expression: &Expression<FieldElement>| -> Combination {
+        let terms = terms(expression);
+        let factor = terms[&anchor];
+        terms
+            .into_iter()
+            .map(|(witness, coefficient)| (witness, coefficient / factor))
+            .collect()
+    };
+    let Some(first) = expressions.next() else {
+        return Combination::new();
+    };
+    let mut common = scaled(first);
+    for expression in expressions {
+        let other = scaled(expression);
+        common.retain(|witness, coefficient| other.get(witness) == Some(coefficient));
+    }
+    // Pairs anchored on different witnesses of the same combination yield the same terms
+    let factor = common
+        .values()
+        .next()
+        .copied()
+        .unwrap_or_else(FieldElement::one);
+    common
+        .into_iter()
+        .map(|(witness, coefficient)| (witness, coefficient / factor))
+        .collect()
+}
+
+/// Renders a combination like ACIR, e.g. `w1 + 2*w5 - w7`.
+fn render(combination: &Combination) -> String {
+    let mut rendered = String::new();
+    for (witness, coefficient) in combination {
+        let coefficient = coefficient.to_string();
+        let (sign, magnitude) = match coefficient.strip_prefix('-') {
+            Some(magnitude) => ("-", magnitude),
+            None => ("+", coefficient.as_str()),
+        };
+        if rendered.is_empty() {
+            rendered.push_str(if sign == "-" { "-" } else { "" });
+        } else {
+            rendered.push_str(&format!(" {sign} "));
+        }
+        if magnitude != "1" {
+            rendered.push_str(&format!("{magnitude}*"));
+        }
+        rendered.push_str(&format!("w{}", witness.witness_index()));
+    }
+    rendered
+}
+
+/// Renders the first opcode indices of a list, e.g. `3, 8, 12 and 4 more`.
+fn render_opcodes(opcodes: &[usize]) -> String {
+    const SHOWN: usize = 3;
+    let shown: Vec<String> = opcodes.iter().take(SHOWN).map(usize::to_string).collect();
+    match opcodes.len().saturating_sub(SHOWN) {
+        0 => shown.join(", "),
+        more => format!("{} and {more} more", shown.join(", ")),
+    }
+}
+
+fn lint(message: Message) -> Lint {
+    Lint {
+        name: NAME,
+        severity: Severity::Warning,
+        confidence: Confidence::High,
+        description: message.to_string(),
+        message: Some(message),
+        span: None,
+        file_id: None,
+        suggestion: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_zero(terms: &[(u128, u32)], constant: u128) -> Opcode<FieldElement> {
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: terms
+                .iter()
+                .map(|(coefficient, witness)| (FieldElement::from(*coefficient), Witness(*witness)))
+                .collect(),
+            q_c: FieldElement::from(constant),
+        })
+    }
+
+    #[test]
+    fn test_repeated_linear_combinations_are_reported_with_savings() {
+        let circuit = Circuit {
+            function_name: "main".to_string(),
+            opcodes: vec![
+                // `w1 + 2*w2 + 3*w3` four times, once scaled by 2
+                assert_zero(&[(1, 1), (2, 2), (3, 3), (1, 10)], 0),
+                assert_zero(&[(1, 1), (2, 2), (3, 3), (1, 11)], 5),
+                assert_zero(&[(2, 1), (4, 2), (6, 3), (1, 12)], 0),
+                assert_zero(&[(1, 1), (2, 2), (3, 3)], 7),
+                // Shares `w1 + 2*w2` only, which saves less over its 5 uses
+                assert_zero(&[(1, 1), (2, 2), (5, 3)], 0),
+                // Shares nothing
+                assert_zero(&[(1, 4), (1, 5)], 0),
+            ],
+            ..Circuit::default()
+        };
+
+        let lints = check_circuit(&circuit);
+
+        assert_eq!(lints.len(), 2);
+        assert_eq!(lints[0].0, 0);
+        assert_eq!(
+            lints[0].1.description,
+            "Linear combination w1 + 2*w2 + 3*w3 appears in 4 assertions of 'main' (opcodes 0, 1, 2 and 1 more); binding it to an intermediate witness would save about 4 terms"
+        );
+        assert!(
+            lints[1]
+                .1
+                .description
+                .starts_with("Linear combination w1 + 2*w2 appears in 5 assertions")
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! # Circuit Statistics
+//!
+//! Summarizes the size of a compiled circuit: its opcodes by kind, its witnesses and its
+//! inputs. The detailed statistics add the lifetime of each witness, from the first opcode
+//! referencing it to the last, for backends keeping witnesses in memory: ACIR assigns every
+//! value a witness of its own, so the peak number of witnesses live at once tells how far
+//! reusing dead ones could shrink the witness map, and the longest-lived witnesses show
+//! which values pin memory across the whole circuit.
+//!
+//! Inputs are live from the first opcode, and return values until the last one.
+
+use acir::FieldElement;
+use acir::circuit::brillig::{BrilligInputs, BrilligOutputs};
+use acir::circuit::opcodes::FunctionInput;
+use acir::circuit::{Circuit, Opcode};
+use acir::native_types::{Expression, Witness};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Number of long-lived witnesses reported, the longest-lived first.
+const MAX_LONG_LIVED: usize = 20;
+
+/// Size of a compiled circuit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CircuitStats {
+    pub function: String,
+    pub opcodes: usize,
+    /// Number of opcodes of each kind, e.g. `assert-zero` or `brillig-call`.
+    pub opcodes_by_kind: BTreeMap<&'static str, usize>,
+    /// Number of witnesses the circuit allocates.
+    pub witnesses: u32,
+    pub private_inputs: usize,
+    pub public_inputs: usize,
+    pub return_values: usize,
+    /// Witness lifetimes, in the detailed statistics only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifetimes: Option<WitnessLifetimes>,
+}
+
+/// Opcodes between which a witness is live.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WitnessLifetime {
+    pub witness: u32,
+    /// Index of the first opcode referencing the witness, zero for inputs.
+    pub defined_at: usize,
+    /// Index of the last opcode referencing the witness, the last opcode for return values.
+    pub last_used_at: usize,
+}
+
+impl WitnessLifetime {
+    /// Number of opcodes the witness outlives its definition by.
+    pub fn length(&self) -> usize {
+        self.last_used_at - self.defined_at
+    }
+}
+
+/// Lifetimes of the witnesses of a circuit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WitnessLifetimes {
+    /// Lifetime of every witness the circuit references, by witness index.
+    #[serde(skip)]
+    pub lifetimes: Vec<WitnessLifetime>,
+    /// Largest number of witnesses live at once, the size of the witness map if dead
+    /// witnesses were reused.
+    pub peak_live: usize,
+    /// Number of witnesses reuse would save.
+    pub reusable: usize,
+    /// Witnesses live over more than half of the opcodes, the longest-lived first.
+    pub long_lived: Vec<WitnessLifetime>,
+}
+
+/// Computes the statistics of a circuit, with witness lifetimes when `detailed`.
+pub fn circuit_stats(circuit: &Circuit<FieldElement>, detailed: bool) -> CircuitStats {
+    let mut opcodes_by_kind = BTreeMap::new();
+    for opcode in &circuit.opcodes {
+        *opcodes_by_kind.entry(opcode_kind(opcode)).or_insert(0) += 1;
+    }
+
+    CircuitStats {
+        function: circuit.function_name.clone(),
+        opcodes: circuit.opcodes.len(),
+        opcodes_by_kind,
+        witnesses: circuit.current_witness_index + 1,
+        private_inputs: circuit.private_parameters.len(),
+        public_inputs: circuit.public_parameters.0.len(),
+        return_values: circuit.return_values.0.len(),
+        lifetimes: detailed.then(|| witness_lifetimes(circuit)),
+    }
+}
+
+/// Computes the lifetime of every witness of a circuit.
+pub fn witness_lifetimes(circuit: &Circuit<FieldElement>) -> WitnessLifetimes {
+    let last_opcode = circuit.opcodes.len().saturating_sub(1);
+    let mut ranges: BTreeMap<Witness, (usize, usize)> = BTreeMap::new();
+    let mut live = |witness: Witness, start: usize, end: usize| {
+        let range = ranges.entry(witness).or_insert((start, end));
+        range.0 = range.0.min(start);
+        range.1 = range.1.max(end);
+    };
+    for witness in circuit
+        .private_parameters
+        .iter()
+        .chain(&circuit.public_parameters.0)
+    {
+        live(*witness, 0, 0);
+    }
+    for (index, opcode) in circuit.opcodes.iter().enumerate() {
+        for witness in opcode_witnesses(opcode) {
+            live(witness, index, index);
+        }
+    }
+    for witness in &circuit.return_values.0 {
+        live(*witness, last_opcode, last_opcode);
+    }
+
+    let lifetimes: Vec<WitnessLifetime> = ranges
+        .into_iter()
+        .map(|(witness, (defined_at, last_used_at))| WitnessLifetime {
+            witness: witness.witness_index(),
+            defined_at,
+            last_used_at,
+        })
+        .collect();
+
+    // Witnesses becoming live at each opcode, and dead after it
+    let mut changes = vec![0isize; circuit.opcodes.len() + 1];
+    for lifetime in &lifetimes {
+        changes[lifetime.defined_at] += 1;
+        changes[lifetime.last_used_at + 1] -= 1;
+    }
+    let peak_live = changes
+        .iter()
+        .scan(0isize, |live, change| {
+            *live += change;
+            Some(*live)
+        })
+        .max()
+        .unwrap_or(0) as usize;
+
+    let mut long_lived: Vec<WitnessLifetime> = lifetimes
+        .iter()
+        .filter(|lifetime| lifetime.length() * 2 > circuit.opcodes.len())
+        .copied()
+        .collect();
+    long_lived.sort_by(|a, b| b.length().cmp(&a.length()).then(a.witness.cmp(&b.witness)));
+    long_lived.truncate(MAX_LONG_LIVED);
+
+    WitnessLifetimes {
+        reusable: lifetimes.len() - peak_live,
+        peak_live,
+        long_lived,
+        lifetimes,
+    }
+}
+
+/// Returns the kind of an opcode, as reported in the statistics.
+fn opcode_kind(opcode: &Opcode<FieldElement>) -> &'static str {
+    match opcode {
+        Opcode::AssertZero(_) => "assert-zero",
+        Opcode::BlackBoxFuncCall(_) => "black-box",
+        Opcode::MemoryOp { .. } => "memory-op",
+        Opcode::MemoryInit { .. } => "memory-init",
+        Opcode::BrilligCall { .. } => "brillig-call",
+        Opcode::Call { .. } => "call",
+    }
+}
+
+/// Returns the witnesses an opcode references, read or written.
+fn opcode_witnesses(opcode: &Opcode<FieldElement>) -> Vec<Witness> {
+    let mut witnesses = vec![];
+    match opcode {
+        Opcode::AssertZero(expression) => expression_witnesses(expression, &mut witnesses),
+        Opcode::BlackBoxFuncCall(call) => {
+            for input in call.get_inputs_vec() {
+                if let FunctionInput::Witness(witness) = input {
+                    witnesses.push(witness);
+                }
+            }
+            witnesses.extend(call.get_outputs_vec());
+        }
+        Opcode::MemoryOp { op, .. } => {
+            for expression in [&op.operation, &op.index, &op.value] {
+                expression_witnesses(expression, &mut witnesses);
+            }
+        }
+        Opcode::MemoryInit { init, .. } => witnesses.extend(init),
+        Opcode::BrilligCall {
+            inputs,
+            outputs,
+            predicate,
+            ..
+        } => {
+            for input in inputs {
+                match input {
+                    BrilligInputs::Single(expression) => {
+                        expression_witnesses(expression, &mut witnesses)
+                    }
+                    BrilligInputs::Array(expressions) => {
+                        for expression in expressions {
+                            expression_witnesses(expression, &mut witnesses);
+                        }
+                    }
+                    BrilligInputs::MemoryArray(_) => {}
+                }
+            }
+            for output in outputs {
+                match output {
+                    BrilligOutputs::Simple(witness) => witnesses.push(*witness),
+                    BrilligOutputs::Array(array) => witnesses.extend(array),
+                }
+            }
+            if let Some(predicate) = predicate {
+                expression_witnesses(predicate, &mut witnesses);
+            }
+        }
+        Opcode::Call {
+            inputs,
+            outputs,
+            predicate,
+            ..
+        } => {
+            witnesses.extend(inputs);
+            witnesses.extend(outputs);
+            if let Some(predicate) = predicate {
+                expression_witnesses(predicate, &mut witnesses);
+            }
+        }
+    }
+    witnesses
+}
+
+fn expression_witnesses(expression: &Expression<FieldElement>, witnesses: &mut Vec<Witness>) {
+    for (_, lhs, rhs) in &expression.mul_terms {
+        witnesses.extend([*lhs, *rhs]);
+    }
+    for (_, witness) in &expression.linear_combinations {
+        witnesses.push(*witness);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use acir::AcirField;
+    use acir::circuit::PublicInputs;
+
+    /// Asserts `w{a} + w{b} - w{out} = 0`.
+    fn add(a: u32, b: u32, out: u32) -> Opcode<FieldElement> {
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(a)),
+                (FieldElement::one(), Witness(b)),
+                (-FieldElement::one(), Witness(out)),
+            ],
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    #[test]
+    fn test_witness_lifetimes_span_from_definition_to_last_use() {
+        // w0 is an input used by every opcode, w5 the return value
+        let circuit = Circuit {
+            function_name: "main".to_string(),
+            current_witness_index: 5,
+            opcodes: vec![
+                add(0, 0, 1),
+                add(0, 1, 2),
+                add(0, 2, 3),
+                add(0, 3, 4),
+                add(4, 4, 5),
+            ],
+            private_parameters: [Witness(0)].into(),
+            return_values: PublicInputs([Witness(5)].into()),
+            ..Circuit::default()
+        };
+
+        let stats = circuit_stats(&circuit, true);
+        assert_eq!(stats.opcodes_by_kind["assert-zero"], 5);
+        assert_eq!(stats.witnesses, 6);
+        assert_eq!(stats.private_inputs, 1);
+
+        let lifetimes = stats.lifetimes.unwrap();
+        assert_eq!(lifetimes.lifetimes.len(), 6);
+        // Each intermediate value dies as soon as the next one is computed
+        assert_eq!(lifetimes.peak_live, 3);
+        assert_eq!(lifetimes.reusable, 3);
+        assert_eq!(
+            lifetimes.long_lived,
+            [WitnessLifetime {
+                witness: 0,
+                defined_at: 0,
+                last_used_at: 3,
+            }]
+        );
+        assert!(circuit_stats(&circuit, false).lifetimes.is_none());
+    }
+}
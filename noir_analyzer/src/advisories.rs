@@ -0,0 +1,527 @@
+//! # Dependency Advisories
+//!
+//! Noir libraries are pulled in by `Nargo.toml` as git tags or local paths, and vulnerable
+//! releases of a library, such as an under-constrained gadget, stay in use until each
+//! dependent bumps its tag. This module resolves the dependency tree of a package and
+//! compares it against an advisory database listing known-vulnerable library versions:
+//!
+//! ```toml
+//! [[advisory]]
+//! id = "NOIR-2025-0001"
+//! package = "https://github.com/noir-lang/ec"
+//! title = "Point addition does not constrain the result to the curve"
+//! severity = "high"
+//! affected = ">=0.1.0, <0.1.3"
+//! patched = ["0.1.3"]
+//! url = "https://github.com/noir-lang/ec/security/advisories/GHSA-xxxx"
+//! ```
+//!
+//! Advisories name the affected library by its git repository or by its package name.
+//! Git dependencies are resolved through the checkouts nargo keeps in `~/nargo`, so the
+//! dependencies of a library that was never fetched are not listed.
+
+use crate::compiler_version::NoirVersion;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Name of the advisory database looked up in the workspace root.
+pub const ADVISORY_DB_FILE_NAME: &str = "noir-advisories.toml";
+
+#[derive(Debug, Error)]
+pub enum AdvisoryError {
+    #[error("Failed to read {0:?}: {1}")]
+    FileReadError(PathBuf, String),
+    #[error("Failed to parse {0:?}: {1}")]
+    ParseError(PathBuf, String),
+    #[error("Advisory {0} has an invalid version requirement: {1}")]
+    InvalidRequirement(String, String),
+}
+
+/// Where a dependency comes from, as declared in `Nargo.toml`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencySource {
+    /// A tag of a git repository.
+    Git { url: String, tag: String },
+    /// A directory on disk.
+    Path(PathBuf),
+}
+
+impl fmt::Display for DependencySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencySource::Git { url, tag } => write!(f, "{url}, tag {tag}"),
+            DependencySource::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// A direct or transitive dependency of a package.
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    /// Name under which the dependency is declared.
+    pub name: String,
+    pub source: DependencySource,
+    /// Version of the dependency: its git tag, or the version declared by its manifest.
+    pub version: Option<String>,
+    /// Names of the packages requiring the dependency, from the analyzed package down.
+    pub required_by: Vec<String>,
+    /// Directory of the dependency, when it is on disk.
+    pub dir: Option<PathBuf>,
+    /// License declared by the manifest of the dependency.
+    pub license: Option<String>,
+}
+
+impl Dependency {
+    /// Returns the parsed version, when it is a release number such as `v0.1.2`.
+    pub fn release(&self) -> Option<NoirVersion> {
+        let version = self.version.as_deref()?;
+        version.strip_prefix('v').unwrap_or(version).parse().ok()
+    }
+}
+
+/// Severity of an advisory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisorySeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for AdvisorySeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AdvisorySeverity::Low => "low",
+            AdvisorySeverity::Medium => "medium",
+            AdvisorySeverity::High => "high",
+            AdvisorySeverity::Critical => "critical",
+        })
+    }
+}
+
+/// A known vulnerability of some versions of a library.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Advisory {
+    pub id: String,
+    /// Git repository or package name of the affected library.
+    pub package: String,
+    pub title: String,
+    pub severity: AdvisorySeverity,
+    /// Requirement matching the affected versions, e.g. `>=0.1.0, <0.1.3`.
+    pub affected: String,
+    /// Versions fixing the vulnerability.
+    #[serde(default)]
+    pub patched: Vec<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl Advisory {
+    /// Returns whether the advisory is about the library of the dependency.
+    fn concerns(&self, dependency: &Dependency) -> bool {
+        match &dependency.source {
+            DependencySource::Git { url, .. } if is_url(&self.package) => {
+                normalize_url(url) == normalize_url(&self.package)
+            }
+            _ => dependency.name == self.package,
+        }
+    }
+
+    /// Returns whether the advisory affects a version. Every version is affected when the
+    /// requirement cannot be parsed.
+    fn affects(&self, version: NoirVersion) -> bool {
+        parse_requirement(&self.affected).is_none_or(|comparators| {
+            comparators
+                .iter()
+                .all(|(operator, bound)| operator.matches(version, *bound))
+        })
+    }
+}
+
+/// Advisories loaded from a database file.
+#[derive(Debug, Default, Deserialize)]
+pub struct AdvisoryDatabase {
+    #[serde(default, rename = "advisory")]
+    pub advisories: Vec<Advisory>,
+}
+
+/// A dependency affected by an advisory.
+#[derive(Debug, Clone)]
+pub struct AffectedDependency {
+    pub dependency: Dependency,
+    pub advisory: Advisory,
+    /// Whether the dependency version is not a release number, e.g. a branch name, so it
+    /// could not be compared with the affected versions.
+    pub unknown_version: bool,
+}
+
+impl AdvisoryDatabase {
+    /// Parses a database from TOML source.
+    pub fn parse(content: &str, path: &Path) -> Result<Self, AdvisoryError> {
+        let database: Self = toml::from_str(content)
+            .map_err(|e| AdvisoryError::ParseError(path.to_path_buf(), e.to_string()))?;
+        for advisory in &database.advisories {
+            if parse_requirement(&advisory.affected).is_none() {
+                return Err(AdvisoryError::InvalidRequirement(
+                    advisory.id.clone(),
+                    advisory.affected.clone(),
+                ));
+            }
+        }
+        Ok(database)
+    }
+
+    /// Loads the database file at the given path.
+    pub fn load(path: &Path) -> Result<Self, AdvisoryError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| AdvisoryError::FileReadError(path.to_path_buf(), e.to_string()))?;
+        Self::parse(&content, path)
+    }
+
+    /// Returns the dependencies affected by an advisory, most severe first. Dependencies
+    /// whose version is unknown are reported for every advisory about their library.
+    pub fn check(&self, dependencies: &[Dependency]) -> Vec<AffectedDependency> {
+        let mut affected = vec![];
+        for dependency in dependencies {
+            for advisory in &self.advisories {
+                if !advisory.concerns(dependency) {
+                    continue;
+                }
+                let release = dependency.release();
+                if release.is_some_and(|version| !advisory.affects(version)) {
+                    continue;
+                }
+                affected.push(AffectedDependency {
+                    dependency: dependency.clone(),
+                    advisory: advisory.clone(),
+                    unknown_version: release.is_none(),
+                });
+            }
+        }
+        affected.sort_by(|a, b| b.advisory.severity.cmp(&a.advisory.severity));
+        affected
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    package: Option<ManifestPackage>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, DependencySpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPackage {
+    name: Option<String>,
+    version: Option<String>,
+    license: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DependencySpec {
+    Git {
+        git: String,
+        tag: String,
+        directory: Option<String>,
+    },
+    Path {
+        path: String,
+    },
+}
+
+/// Resolves the direct and transitive dependencies of the package with the given manifest.
+/// Dependencies reached along several paths are listed once, with the first path found.
+pub fn resolve_dependencies(manifest_path: &Path) -> Result<Vec<Dependency>, AdvisoryError> {
+    let manifest = read_manifest(manifest_path)?;
+    let package_name = manifest
+        .package
+        .and_then(|package| package.name)
+        .unwrap_or_default();
+
+    let mut dependencies = vec![];
+    let mut visited = HashSet::new();
+    collect_dependencies(
+        manifest_path,
+        &manifest.dependencies,
+        &[package_name],
+        &mut visited,
+        &mut dependencies,
+    )?;
+    Ok(dependencies)
+}
+
+fn collect_dependencies(
+    manifest_path: &Path,
+    specs: &BTreeMap<String, DependencySpec>,
+    required_by: &[String],
+    visited: &mut HashSet<PathBuf>,
+    dependencies: &mut Vec<Dependency>,
+) -> Result<(), AdvisoryError> {
+    let manifest_dir = manifest_path.parent().unwrap_or(Path::new(""));
+    for (name, spec) in specs {
+        let (source, dir) = match spec {
+            DependencySpec::Git {
+                git,
+                tag,
+                directory,
+            } => {
+                let dir = git_checkout_dir(git, tag)
+                    .map(|dir| dir.join(directory.as_deref().unwrap_or_default()));
+                let source = DependencySource::Git {
+                    url: git.clone(),
+                    tag: tag.clone(),
+                };
+                (source, dir)
+            }
+            DependencySpec::Path { path } => {
+                let dir = manifest_dir.join(path);
+                (DependencySource::Path(dir.clone()), Some(dir))
+            }
+        };
+
+        let dir = dir.filter(|dir| dir.is_dir());
+        let dependency_manifest_path = dir
+            .as_ref()
+            .map(|dir| dir.join("Nargo.toml"))
+            .filter(|path| path.is_file());
+        let manifest = dependency_manifest_path
+            .as_deref()
+            .map(read_manifest)
+            .transpose()?;
+
+        let package = manifest
+            .as_ref()
+            .and_then(|manifest| manifest.package.as_ref());
+        let version = match &source {
+            DependencySource::Git { tag, .. } => Some(tag.clone()),
+            DependencySource::Path(_) => package.and_then(|package| package.version.clone()),
+        };
+        dependencies.push(Dependency {
+            name: name.clone(),
+            source,
+            version,
+            required_by: required_by.to_vec(),
+            dir,
+            license: package.and_then(|package| package.license.clone()),
+        });
+
+        if let (Some(path), Some(manifest)) = (dependency_manifest_path, manifest) {
+            let key = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if visited.insert(key) {
+                let mut required_by = required_by.to_vec();
+                required_by.push(name.clone());
+                collect_dependencies(
+                    &path,
+                    &manifest.dependencies,
+                    &required_by,
+                    visited,
+                    dependencies,
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_manifest(path: &Path) -> Result<Manifest, AdvisoryError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| AdvisoryError::FileReadError(path.to_path_buf(), e.to_string()))?;
+    toml::from_str(&content)
+        .map_err(|e| AdvisoryError::ParseError(path.to_path_buf(), e.to_string()))
+}
+
+/// Returns the directory where nargo checks out a tag of a git repository, e.g.
+/// `~/nargo/github.com/noir-lang/ec/v0.1.2`.
+fn git_checkout_dir(url: &str, tag: &str) -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    let (_, location) = url.split_once("://")?;
+    Some(
+        PathBuf::from(home)
+            .join("nargo")
+            .join(location.trim_end_matches('/'))
+            .join(tag),
+    )
+}
+
+fn is_url(package: &str) -> bool {
+    package.contains("://")
+}
+
+/// Normalizes a repository URL, so that `https://github.com/org/lib.git/` matches
+/// `https://github.com/Org/lib`.
+fn normalize_url(url: &str) -> String {
+    url.trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_lowercase()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl Operator {
+    fn matches(&self, version: NoirVersion, bound: NoirVersion) -> bool {
+        match self {
+            Operator::Less => version < bound,
+            Operator::LessOrEqual => version <= bound,
+            Operator::Greater => version > bound,
+            Operator::GreaterOrEqual => version >= bound,
+            Operator::Equal => version == bound,
+        }
+    }
+}
+
+/// Parses a comma-separated list of comparisons, such as `>=0.1.0, <0.1.3`.
+fn parse_requirement(requirement: &str) -> Option<Vec<(Operator, NoirVersion)>> {
+    requirement
+        .split(',')
+        .map(|comparator| {
+            let comparator = comparator.trim();
+            let (operator, version) = [
+                ("<=", Operator::LessOrEqual),
+                (">=", Operator::GreaterOrEqual),
+                ("<", Operator::Less),
+                (">", Operator::Greater),
+                ("=", Operator::Equal),
+            ]
+            .into_iter()
+            .find_map(|(prefix, operator)| {
+                Some((operator, comparator.strip_prefix(prefix)?.trim()))
+            })
+            .unwrap_or((Operator::Equal, comparator));
+            Some((operator, version.parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATABASE: &str = r#"
+        [[advisory]]
+        id = "NOIR-2025-0001"
+        package = "https://github.com/noir-lang/ec"
+        title = "Point addition is under-constrained"
+        severity = "high"
+        affected = ">=0.1.0, <0.1.3"
+        patched = ["0.1.3"]
+
+        [[advisory]]
+        id = "NOIR-2025-0002"
+        package = "bignum"
+        title = "Reduction accepts non-canonical limbs"
+        severity = "critical"
+        affected = "<0.4.0"
+    "#;
+
+    fn dependency(name: &str, source: DependencySource, version: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            source,
+            version: Some(version.to_string()),
+            required_by: vec!["main".to_string()],
+            dir: None,
+            license: None,
+        }
+    }
+
+    fn git(url: &str, tag: &str) -> DependencySource {
+        DependencySource::Git {
+            url: url.to_string(),
+            tag: tag.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_affected_versions_are_reported() {
+        let database = AdvisoryDatabase::parse(DATABASE, Path::new("db.toml")).unwrap();
+        let dependencies = vec![
+            dependency(
+                "ec",
+                git("https://github.com/noir-lang/ec.git", "v0.1.2"),
+                "v0.1.2",
+            ),
+            dependency(
+                "ec_new",
+                git("https://github.com/noir-lang/ec", "v0.1.3"),
+                "v0.1.3",
+            ),
+            dependency(
+                "bignum",
+                DependencySource::Path("../bignum".into()),
+                "0.3.1",
+            ),
+        ];
+
+        let affected = database.check(&dependencies);
+
+        let ids: Vec<(&str, &str)> = affected
+            .iter()
+            .map(|affected| {
+                (
+                    affected.dependency.name.as_str(),
+                    affected.advisory.id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            ids,
+            vec![("bignum", "NOIR-2025-0002"), ("ec", "NOIR-2025-0001")]
+        );
+    }
+
+    #[test]
+    fn test_invalid_requirements_are_rejected() {
+        let database = r#"
+            [[advisory]]
+            id = "NOIR-2025-0003"
+            package = "lib"
+            title = ""
+            severity = "low"
+            affected = ">=latest"
+        "#;
+
+        assert!(AdvisoryDatabase::parse(database, Path::new("db.toml")).is_err());
+    }
+
+    #[test]
+    fn test_path_dependencies_are_resolved_transitively() {
+        let root = std::env::temp_dir().join("noir-analyzer-advisories-path");
+        let _ = fs::remove_dir_all(&root);
+        for (path, content) in [
+            (
+                "app/Nargo.toml",
+                "[package]\nname = \"app\"\n[dependencies]\nlib = { path = \"../lib\" }\n",
+            ),
+            (
+                "lib/Nargo.toml",
+                "[package]\nname = \"lib\"\nversion = \"0.2.0\"\n[dependencies]\nbase = { path = \"../base\" }\n",
+            ),
+            ("base/Nargo.toml", "[package]\nname = \"base\"\n"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let dependencies = resolve_dependencies(&root.join("app/Nargo.toml")).unwrap();
+
+        assert_eq!(dependencies.len(), 2);
+        assert_eq!(dependencies[0].name, "lib");
+        assert_eq!(dependencies[0].version.as_deref(), Some("0.2.0"));
+        assert_eq!(dependencies[1].name, "base");
+        assert_eq!(dependencies[1].required_by, vec!["app", "lib"]);
+    }
+}
@@ -0,0 +1,158 @@
+//! # Aztec Attribute Registry
+//!
+//! Aztec contracts select how each function executes with attributes: `#[private]`,
+//! `#[public]` and `#[utility]` pick the execution context, while `#[view]`, `#[internal]`,
+//! `#[initializer]` and `#[noinitcheck]` restrict how the function may be called. This
+//! registry records the known attributes, their role and the combinations that contradict
+//! each other, so that lints agree on what an annotation means.
+
+use noirc_frontend::ast::FunctionDefinition;
+
+/// Where an Aztec function is executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionContext {
+    /// In a private circuit, proven by the user.
+    Private,
+    /// In the public VM, by the sequencer.
+    Public,
+    /// Off-chain, without constraints, e.g. to read notes.
+    Utility,
+}
+
+/// What an Aztec attribute says about a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeRole {
+    /// Selects the execution context of the function.
+    Execution(ExecutionContext),
+    /// Restricts how the function may be called.
+    Modifier,
+    /// Marks a helper that is not a contract entry point.
+    Helper,
+}
+
+/// A function attribute known to the analyzer.
+#[derive(Debug)]
+pub struct AztecAttribute {
+    /// Name of the attribute, without `#[` and `]`.
+    pub name: &'static str,
+    pub role: AttributeRole,
+}
+
+/// Function attributes of Aztec contracts.
+pub const AZTEC_ATTRIBUTES: &[AztecAttribute] = &[
+    AztecAttribute {
+        name: "private",
+        role: AttributeRole::Execution(ExecutionContext::Private),
+    },
+    AztecAttribute {
+        name: "public",
+        role: AttributeRole::Execution(ExecutionContext::Public),
+    },
+    AztecAttribute {
+        name: "utility",
+        role: AttributeRole::Execution(ExecutionContext::Utility),
+    },
+    AztecAttribute {
+        name: "view",
+        role: AttributeRole::Modifier,
+    },
+    AztecAttribute {
+        name: "internal",
+        role: AttributeRole::Modifier,
+    },
+    AztecAttribute {
+        name: "initializer",
+        role: AttributeRole::Modifier,
+    },
+    AztecAttribute {
+        name: "noinitcheck",
+        role: AttributeRole::Modifier,
+    },
+    AztecAttribute {
+        name: "contract_library_method",
+        role: AttributeRole::Helper,
+    },
+];
+
+/// Pairs of attributes that cannot annotate the same function, besides several execution
+/// contexts.
+pub const CONTRADICTORY_ATTRIBUTES: &[(&str, &str)] = &[
+    ("view", "initializer"),
+    ("initializer", "noinitcheck"),
+    ("utility", "view"),
+    ("utility", "internal"),
+    ("utility", "initializer"),
+    ("utility", "noinitcheck"),
+    ("contract_library_method", "private"),
+    ("contract_library_method", "public"),
+    ("contract_library_method", "utility"),
+];
+
+/// Returns the registered attribute written as `attribute`, e.g. `#[private]`.
+pub fn lookup(attribute: &str) -> Option<&'static AztecAttribute> {
+    let name = attribute
+        .trim()
+        .strip_prefix("#[")
+        .and_then(|attribute| attribute.strip_suffix(']'))
+        .unwrap_or(attribute)
+        .trim();
+    AZTEC_ATTRIBUTES
+        .iter()
+        .find(|aztec_attribute| aztec_attribute.name == name)
+}
+
+/// Returns the registered attributes of a function, in written order.
+pub fn function_attributes(function: &FunctionDefinition) -> Vec<&'static AztecAttribute> {
+    function
+        .attributes
+        .secondary
+        .iter()
+        .filter_map(|attribute| lookup(&attribute.to_string()))
+        .collect()
+}
+
+/// Returns the execution context selected by the attributes of a function, if exactly one
+/// is.
+pub fn execution_context(function: &FunctionDefinition) -> Option<ExecutionContext> {
+    let mut contexts = function_attributes(function)
+        .into_iter()
+        .filter_map(|attribute| match attribute.role {
+            AttributeRole::Execution(context) => Some(context),
+            _ => None,
+        });
+    let context = contexts.next()?;
+    contexts.next().is_none().then_some(context)
+}
+
+/// Returns whether two attributes cannot annotate the same function.
+pub fn contradict(first: &AztecAttribute, second: &AztecAttribute) -> bool {
+    let both_execution = matches!(
+        (first.role, second.role),
+        (AttributeRole::Execution(a), AttributeRole::Execution(b)) if a != b
+    );
+    both_execution
+        || CONTRADICTORY_ATTRIBUTES.iter().any(|&(a, b)| {
+            (first.name, second.name) == (a, b) || (first.name, second.name) == (b, a)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attributes_are_looked_up_and_compared() {
+        let private = lookup("#[private]").unwrap();
+        let public = lookup("public").unwrap();
+        let view = lookup("#[view]").unwrap();
+
+        assert_eq!(
+            private.role,
+            AttributeRole::Execution(ExecutionContext::Private)
+        );
+        assert!(lookup("#[test]").is_none());
+        assert!(contradict(private, public));
+        assert!(!contradict(public, view));
+        assert!(contradict(lookup("#[utility]").unwrap(), view));
+    }
+}
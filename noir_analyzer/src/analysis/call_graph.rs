@@ -0,0 +1,387 @@
+//! # Call Graph
+//!
+//! Builds a directed graph between the functions of a parsed program, where an edge `a → b`
+//! means that the body of `a` calls `b`. The graph is built once per analysis and shared by
+//! the lints through the `AstContext`.
+//!
+//! Free functions are identified by name, like the function index of the `AstContext`, and
+//! calls to them are resolved by the last segment of their path. Methods of `impl` blocks are
+//! identified by the name of their type and their own, such as `Point::new`, and calls to them
+//! are resolved when written through the type (`Point::new(..)`, `Self::new(..)`) or on `self`
+//! within the `impl` block. Calls in the body of a lambda belong to the function defining it,
+//! and calls of a variable bound to a lambda are not calls to a function of the same name.
+//! Other method calls, whose receiver type is unknown without type checking, and calls to
+//! functions defined outside of the program are not part of the graph.
+
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    CallExpression, ExpressionKind, LetStatement, MethodCallExpression, NoirFunction, Pattern,
+    TraitImplItemKind, UnresolvedType, UnresolvedTypeData, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Directed graph of calls between functions.
+#[derive(Debug, Default)]
+pub struct CallGraph {
+    /// Callees of each function, in the order of their first call.
+    calls: BTreeMap<String, Vec<String>>,
+}
+
+impl CallGraph {
+    /// Builds the call graph of the given parsed module.
+    pub fn build(parsed_module: &ParsedModule) -> Self {
+        let mut functions = vec![];
+        collect_functions(parsed_module, &mut functions);
+
+        let names: BTreeSet<&str> = functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect();
+
+        let mut graph = Self::default();
+        for function in &functions {
+            let mut collector = CallCollector {
+                self_type: function.self_type.as_deref(),
+                lambdas: HashSet::new(),
+                callees: vec![],
+            };
+            for statement in &function.function.def.body.statements {
+                statement.accept(&mut collector);
+            }
+
+            let callees = graph.calls.entry(function.name.clone()).or_default();
+            for callee in collector.callees {
+                let callee = callee
+                    .method
+                    .filter(|method| names.contains(method.as_str()))
+                    .or(callee.name.filter(|name| names.contains(name.as_str())));
+                if let Some(callee) = callee
+                    && !callees.contains(&callee)
+                {
+                    callees.push(callee);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the functions of the graph, in alphabetical order.
+    pub fn functions(&self) -> impl Iterator<Item = &str> {
+        self.calls.keys().map(String::as_str)
+    }
+
+    /// Returns the functions called by `function`, in the order of their first call.
+    pub fn callees_of(&self, function: &str) -> &[String] {
+        self.calls.get(function).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the functions calling `function`, in alphabetical order.
+    pub fn callers_of(&self, function: &str) -> Vec<&str> {
+        self.calls
+            .iter()
+            .filter(|(_, callees)| callees.iter().any(|callee| callee == function))
+            .map(|(caller, _)| caller.as_str())
+            .collect()
+    }
+
+    /// Returns the functions `function` calls directly or through other functions,
+    /// including itself, in alphabetical order.
+    pub fn reachable_from(&self, function: &str) -> BTreeSet<&str> {
+        let mut reachable = BTreeSet::new();
+        let mut pending = vec![function];
+        while let Some(function) = pending.pop() {
+            if reachable.insert(function) {
+                pending.extend(self.callees_of(function).iter().map(String::as_str));
+            }
+        }
+        reachable
+    }
+
+    /// Returns whether `function` calls itself, directly or through other functions.
+    pub fn is_recursive(&self, function: &str) -> bool {
+        self.callees_of(function)
+            .iter()
+            .any(|callee| self.reachable_from(callee).contains(function))
+    }
+
+    /// Returns the longest chain of calls starting at `function`, including it. Recursive
+    /// calls are not followed, so the chain never visits a function twice.
+    pub fn longest_chain(&self, function: &str) -> Vec<String> {
+        let mut memo = BTreeMap::new();
+        let mut visiting = BTreeSet::new();
+        self.longest_chain_from(function, &mut visiting, &mut memo)
+    }
+
+    fn longest_chain_from(
+        &self,
+        function: &str,
+        visiting: &mut BTreeSet<String>,
+        memo: &mut BTreeMap<String, Vec<String>>,
+    ) -> Vec<String> {
+        if let Some(chain) = memo.get(function) {
+            return chain.clone();
+        }
+
+        visiting.insert(function.to_string());
+        let mut longest: Vec<String> = vec![];
+        for callee in self.callees_of(function) {
+            if visiting.contains(callee) {
+                continue;
+            }
+            let chain = self.longest_chain_from(callee, visiting, memo);
+            if chain.len() > longest.len() {
+                longest = chain;
+            }
+        }
+        visiting.remove(function);
+
+        let mut chain = vec![function.to_string()];
+        chain.extend(longest);
+        memo.insert(function.to_string(), chain.clone());
+        chain
+    }
+}
+
+/// A function or method of the program, as a node of the graph.
+pub(crate) struct Node<'ast> {
+    /// Name of the function, prefixed by the name of its type for methods.
+    pub(crate) name: String,
+    /// Type of the enclosing `impl` block, for methods.
+    pub(crate) self_type: Option<String>,
+    pub(crate) function: &'ast NoirFunction,
+    /// Names of the inline submodules the function is defined in, outermost first.
+    pub(crate) module: Vec<String>,
+    /// Span of the module item defining the function: the function itself, or the `impl`
+    /// block of a method.
+    pub(crate) span: Span,
+}
+
+/// Collects every function and method of the module and its submodules, in source order.
+pub(crate) fn collect_functions<'ast>(
+    parsed_module: &'ast ParsedModule,
+    functions: &mut Vec<Node<'ast>>,
+) {
+    collect_module_functions(parsed_module, &mut vec![], functions);
+}
+
+fn collect_module_functions<'ast>(
+    parsed_module: &'ast ParsedModule,
+    module: &mut Vec<String>,
+    functions: &mut Vec<Node<'ast>>,
+) {
+    for item in &parsed_module.items {
+        let (span, _) = Noir::item_location(item);
+        match &item.kind {
+            ItemKind::Function(function) => functions.push(Node {
+                name: function.name().to_string(),
+                self_type: None,
+                function,
+                module: module.clone(),
+                span,
+            }),
+            ItemKind::Submodules(submodule) => {
+                module.push(submodule.name.to_string());
+                collect_module_functions(&submodule.contents, module, functions);
+                module.pop();
+            }
+            ItemKind::Impl(type_impl) => {
+                let Some(self_type) = type_name(&type_impl.object_type) else {
+                    continue;
+                };
+                for (method, _) in &type_impl.methods {
+                    functions.push(Node {
+                        name: format!("{self_type}::{}", method.item.name()),
+                        self_type: Some(self_type.clone()),
+                        function: &method.item,
+                        module: module.clone(),
+                        span,
+                    });
+                }
+            }
+            ItemKind::TraitImpl(trait_impl) => {
+                let Some(self_type) = type_name(&trait_impl.object_type) else {
+                    continue;
+                };
+                for item in &trait_impl.items {
+                    if let TraitImplItemKind::Function(function) = &item.item.kind {
+                        functions.push(Node {
+                            name: format!("{self_type}::{}", function.name()),
+                            self_type: Some(self_type.clone()),
+                            function,
+                            module: module.clone(),
+                            span,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the name of a named type, without its path and generics.
+pub(crate) fn type_name(r#type: &UnresolvedType) -> Option<String> {
+    match &r#type.typ {
+        UnresolvedTypeData::Named(path, ..) => path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// A call, with the names of the nodes it may resolve to.
+struct Callee {
+    /// Method the call resolves to, if it is written through a type or on `self`.
+    method: Option<String>,
+    /// Last segment of the called path, for calls to free functions.
+    name: Option<String>,
+}
+
+/// Collects the calls made while visiting a function body.
+struct CallCollector<'a> {
+    /// Type of the enclosing `impl` block, for `Self::` paths and method calls on `self`.
+    self_type: Option<&'a str>,
+    /// Variables currently bound to a lambda.
+    lambdas: HashSet<String>,
+    callees: Vec<Callee>,
+}
+
+impl Visitor for CallCollector<'_> {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        if let Pattern::Identifier(ident) = &let_statement.pattern {
+            if matches!(let_statement.expression.kind, ExpressionKind::Lambda(_)) {
+                self.lambdas.insert(ident.to_string());
+            } else {
+                self.lambdas.remove(&ident.to_string());
+            }
+        }
+        true
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression, _: Span) -> bool {
+        let ExpressionKind::Variable(path) = &call.func.kind else {
+            return true;
+        };
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        match segments.as_slice() {
+            [name] if self.lambdas.contains(name) => {}
+            [.., owner, name] => {
+                let owner = match owner.as_str() {
+                    "Self" => self.self_type.unwrap_or("Self"),
+                    owner => owner,
+                };
+                self.callees.push(Callee {
+                    method: Some(format!("{owner}::{name}")),
+                    name: Some(name.clone()),
+                });
+            }
+            [name] => self.callees.push(Callee {
+                method: None,
+                name: Some(name.clone()),
+            }),
+            [] => {}
+        }
+        true
+    }
+
+    fn visit_method_call_expression(
+        &mut self,
+        method_call: &MethodCallExpression,
+        _: Span,
+    ) -> bool {
+        if let (Some(self_type), ExpressionKind::Variable(path)) =
+            (self.self_type, &method_call.object.kind)
+            && path.segments.len() == 1
+            && path.segments[0].ident.to_string() == "self"
+        {
+            self.callees.push(Callee {
+                method: Some(format!("{self_type}::{}", method_call.method_name)),
+                name: None,
+            });
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_longest_chain_skips_recursive_calls() {
+        let source_code = r#"
+            mod utils {
+                pub fn leaf() -> Field { 1 }
+                pub fn middle() -> Field { leaf() + crate::utils::leaf() }
+            }
+            fn recursive(n: u32) -> Field {
+                if n == 0 { utils::middle() } else { recursive(n - 1) }
+            }
+            fn short() -> Field { utils::leaf() }
+            fn main() {
+                let _ = short();
+                let _ = recursive(3);
+                let _ = std::hash::pedersen_hash([1]);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let graph = CallGraph::build(&root);
+
+        assert_eq!(graph.callees_of("main"), ["short", "recursive"]);
+        assert_eq!(graph.callees_of("middle"), ["leaf"]);
+        assert_eq!(graph.callers_of("leaf"), ["middle", "short"]);
+        assert_eq!(
+            graph.longest_chain("main"),
+            vec!["main", "recursive", "middle", "leaf"]
+        );
+        assert!(graph.is_recursive("recursive"));
+        assert!(!graph.is_recursive("main"));
+    }
+
+    #[test]
+    fn test_methods_and_lambdas_are_resolved() {
+        let source_code = r#"
+            struct Point { x: Field }
+            impl Point {
+                fn new(x: Field) -> Self { Self { x: double(x) } }
+                fn scaled(self) -> Self { Self::new(self.norm()) }
+                fn norm(self) -> Field { self.x }
+            }
+            fn double(x: Field) -> Field { x * 2 }
+            fn norm(x: Field) -> Field { x }
+            fn unused() {}
+            fn main(x: Field) {
+                let double = |y| norm(y);
+                let point = Point::new(double(x)).scaled();
+                assert(point.norm() == x);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let graph = CallGraph::build(&root);
+
+        // The lambda calls `norm`, and method calls on other values than `self` are unknown
+        assert_eq!(graph.callees_of("main"), ["norm", "Point::new"]);
+        assert_eq!(
+            graph.callees_of("Point::scaled"),
+            ["Point::new", "Point::norm"]
+        );
+        assert_eq!(graph.callers_of("double"), ["Point::new"]);
+        assert_eq!(
+            graph.reachable_from("main").into_iter().collect::<Vec<_>>(),
+            ["Point::new", "double", "main", "norm"]
+        );
+        assert!(!graph.reachable_from("main").contains("unused"));
+    }
+}
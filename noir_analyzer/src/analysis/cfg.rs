@@ -0,0 +1,443 @@
+//! # Control-Flow Graphs
+//!
+//! Builds a graph of basic blocks from the body of each function, where a block is a sequence
+//! of statements executed one after the other and ends with a terminator leading to the next
+//! blocks. `if` expressions, `match` expressions, `for`, `loop` and `while` loops, `break` and
+//! `continue` split the body into blocks, whether they stand as statements, as the
+//! initializer of a `let` statement or as the trailing expression of a block. Control flow
+//! nested deeper in an expression, such as in the argument of a call, is part of the
+//! statement containing it, and so are the bodies of lambdas.
+//!
+//! Conditions that are boolean literals, possibly negated, are constant, and only the branch
+//! they take is followed when computing the reachable blocks. Blocks leaving the function
+//! record the expression whose value is returned, if any, so that paths ending without one
+//! can be told apart.
+
+use crate::analysis::call_graph::{Node, collect_functions};
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    Expression, ExpressionKind, ForRange, FunctionDefinition, IfExpression, Literal, Statement,
+    StatementKind, UnaryOp,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Index of a basic block in its graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BlockId(usize);
+
+/// How control leaves a basic block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Terminator {
+    /// Continues with another block.
+    Goto(BlockId),
+    /// Continues with one of two blocks depending on a condition: the condition of an `if`
+    /// expression or a `while` loop, or the range of a `for` loop, whose body runs while
+    /// there are elements left.
+    Branch {
+        condition: Span,
+        /// Value of the condition, when it is constant.
+        value: Option<bool>,
+        then_block: BlockId,
+        else_block: BlockId,
+    },
+    /// Continues with the block of one of the arms of a `match` expression.
+    Switch { scrutinee: Span, arms: Vec<BlockId> },
+    /// Leaves the function, returning the value of the given expression, if any.
+    Return { value: Option<Span> },
+}
+
+/// A sequence of statements executed one after the other.
+#[derive(Debug, Clone)]
+pub struct BasicBlock {
+    /// Spans of the statements of the block, in execution order.
+    pub statements: Vec<Span>,
+    pub terminator: Terminator,
+}
+
+/// The basic blocks of a function body, starting with its entry block.
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph {
+    blocks: Vec<BasicBlock>,
+}
+
+impl ControlFlowGraph {
+    /// Builds the control-flow graph of the body of a function.
+    pub fn build(function: &FunctionDefinition) -> Self {
+        let mut builder = Builder {
+            blocks: vec![],
+            current: BlockId(0),
+            loops: vec![],
+        };
+        builder.current = builder.new_block();
+        builder.lower_statements(&function.body.statements, true);
+        Self {
+            blocks: builder.blocks,
+        }
+    }
+
+    /// Returns the block the function starts with.
+    pub fn entry(&self) -> BlockId {
+        BlockId(0)
+    }
+
+    /// Returns a block of the graph.
+    pub fn block(&self, id: BlockId) -> &BasicBlock {
+        &self.blocks[id.0]
+    }
+
+    /// Returns every block of the graph, in the order they were created.
+    pub fn blocks(&self) -> impl Iterator<Item = (BlockId, &BasicBlock)> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (BlockId(index), block))
+    }
+
+    /// Returns the blocks control can continue with after a block, leaving out the branch
+    /// a constant condition never takes.
+    pub fn successors(&self, id: BlockId) -> Vec<BlockId> {
+        match &self.block(id).terminator {
+            Terminator::Goto(target) => vec![*target],
+            Terminator::Branch {
+                value,
+                then_block,
+                else_block,
+                ..
+            } => match value {
+                Some(true) => vec![*then_block],
+                Some(false) => vec![*else_block],
+                None => vec![*then_block, *else_block],
+            },
+            Terminator::Switch { arms, .. } => arms.clone(),
+            Terminator::Return { .. } => vec![],
+        }
+    }
+
+    /// Returns the blocks reachable from the entry block.
+    pub fn reachable_blocks(&self) -> BTreeSet<BlockId> {
+        let mut reachable = BTreeSet::new();
+        let mut pending = vec![self.entry()];
+        while let Some(block) = pending.pop() {
+            if reachable.insert(block) {
+                pending.extend(self.successors(block));
+            }
+        }
+        reachable
+    }
+
+    /// Returns the spans of the statements that no path from the entry block reaches, in
+    /// the order of their blocks.
+    pub fn unreachable_statements(&self) -> Vec<Span> {
+        let reachable = self.reachable_blocks();
+        self.blocks()
+            .filter(|(id, _)| !reachable.contains(id))
+            .flat_map(|(_, block)| block.statements.iter().copied())
+            .collect()
+    }
+
+    /// Returns the reachable conditions that are constant, with their value.
+    pub fn constant_conditions(&self) -> Vec<(Span, bool)> {
+        let reachable = self.reachable_blocks();
+        reachable
+            .iter()
+            .filter_map(|id| match self.block(*id).terminator {
+                Terminator::Branch {
+                    condition,
+                    value: Some(value),
+                    ..
+                } => Some((condition, value)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns whether a reachable path leaves the function without a trailing expression
+    /// to return, such as after a final `let` statement or an `if` without `else`.
+    pub fn returns_without_value(&self) -> bool {
+        self.reachable_blocks().iter().any(|id| {
+            matches!(
+                self.block(*id).terminator,
+                Terminator::Return { value: None }
+            )
+        })
+    }
+}
+
+/// Builds the control-flow graphs of the functions and methods of a module and its
+/// submodules, keyed like the nodes of the call graph. When functions share a name, the
+/// last one in source order is kept, like in the function index of the `AstContext`.
+pub fn control_flow_graphs(parsed_module: &ParsedModule) -> BTreeMap<String, ControlFlowGraph> {
+    let mut functions = vec![];
+    collect_functions(parsed_module, &mut functions);
+    functions
+        .into_iter()
+        .map(|Node { name, function, .. }| (name, ControlFlowGraph::build(&function.def)))
+        .collect()
+}
+
+/// Lowers statements into basic blocks.
+struct Builder {
+    blocks: Vec<BasicBlock>,
+    /// Block the next statement is added to.
+    current: BlockId,
+    /// Blocks `continue` and `break` lead to in the enclosing loops, innermost last.
+    loops: Vec<(BlockId, BlockId)>,
+}
+
+impl Builder {
+    fn new_block(&mut self) -> BlockId {
+        self.blocks.push(BasicBlock {
+            statements: vec![],
+            terminator: Terminator::Return { value: None },
+        });
+        BlockId(self.blocks.len() - 1)
+    }
+
+    fn push(&mut self, span: Span) {
+        self.blocks[self.current.0].statements.push(span);
+    }
+
+    fn terminate(&mut self, terminator: Terminator) {
+        self.blocks[self.current.0].terminator = terminator;
+    }
+
+    /// Lowers the statements of a block. In tail position, the value of the block is the
+    /// value of the function, and every path through the statements ends by returning it.
+    fn lower_statements(&mut self, statements: &[Statement], tail: bool) {
+        for (index, statement) in statements.iter().enumerate() {
+            let (span, _) = Noir::statement_location(statement);
+            match &statement.kind {
+                StatementKind::Expression(expression) if tail && index + 1 == statements.len() => {
+                    self.lower_tail(expression);
+                    return;
+                }
+                StatementKind::Expression(expression) | StatementKind::Semi(expression) => {
+                    self.lower_expression(expression);
+                }
+                StatementKind::Let(let_statement) => {
+                    self.lower_control_flow(&let_statement.expression, false);
+                    self.push(span);
+                }
+                StatementKind::Assign(assign) => {
+                    self.lower_control_flow(&assign.expression, false);
+                    self.push(span);
+                }
+                StatementKind::For(for_loop) => {
+                    let range = match &for_loop.range {
+                        ForRange::Range(bounds) => {
+                            let start = Noir::expression_location(&bounds.start).0;
+                            let end = Noir::expression_location(&bounds.end).0;
+                            Span::from(start.start()..end.end())
+                        }
+                        ForRange::Array(array) => Noir::expression_location(array).0,
+                    };
+                    self.lower_loop(Some((range, None)), &for_loop.block);
+                }
+                StatementKind::Loop(body, _) => self.lower_loop(None, body),
+                StatementKind::While(while_statement) => {
+                    let condition = &while_statement.condition;
+                    let (span, _) = Noir::expression_location(condition);
+                    self.lower_loop(Some((span, constant(condition))), &while_statement.body);
+                }
+                StatementKind::Break | StatementKind::Continue => {
+                    self.push(span);
+                    if let Some((header, exit)) = self.loops.last().copied() {
+                        let target = match statement.kind {
+                            StatementKind::Break => exit,
+                            _ => header,
+                        };
+                        self.terminate(Terminator::Goto(target));
+                        // Statements after the jump start a block no path leads to
+                        self.current = self.new_block();
+                    }
+                }
+                _ => self.push(span),
+            }
+        }
+        if tail {
+            self.terminate(Terminator::Return { value: None });
+        }
+    }
+
+    /// Lowers an expression evaluated for its effects or as the value of a branch.
+    fn lower_expression(&mut self, expression: &Expression) {
+        if !self.lower_control_flow(expression, false) {
+            self.push(Noir::expression_location(expression).0);
+        }
+    }
+
+    /// Lowers an expression whose value the function returns.
+    fn lower_tail(&mut self, expression: &Expression) {
+        if !self.lower_control_flow(expression, true) {
+            let (span, _) = Noir::expression_location(expression);
+            self.push(span);
+            self.terminate(Terminator::Return { value: Some(span) });
+        }
+    }
+
+    /// Lowers an expression if it is a block, an `if` or a `match` expression, and returns
+    /// whether it was one.
+    fn lower_control_flow(&mut self, expression: &Expression, tail: bool) -> bool {
+        match &expression.kind {
+            ExpressionKind::Block(block) => self.lower_statements(&block.statements, tail),
+            ExpressionKind::Unsafe(..) => {
+                let Some(block) = Noir::unsafe_block(expression) else {
+                    return false;
+                };
+                self.lower_statements(&block.statements, tail);
+            }
+            ExpressionKind::If(if_expression) => self.lower_if(if_expression, tail),
+            ExpressionKind::Match(match_expression) => {
+                let (scrutinee, _) = Noir::expression_location(&match_expression.expression);
+                let arms: Vec<BlockId> = match_expression
+                    .rules
+                    .iter()
+                    .map(|_| self.new_block())
+                    .collect();
+                self.terminate(Terminator::Switch {
+                    scrutinee,
+                    arms: arms.clone(),
+                });
+                let join = (!tail).then(|| self.new_block());
+                for (arm, (_, branch)) in arms.into_iter().zip(&match_expression.rules) {
+                    self.current = arm;
+                    self.lower_branch(branch, tail, join);
+                }
+                if let Some(join) = join {
+                    self.current = join;
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn lower_if(&mut self, if_expression: &IfExpression, tail: bool) {
+        let (condition, _) = Noir::expression_location(&if_expression.condition);
+        let then_block = self.new_block();
+        let else_block = self.new_block();
+        self.terminate(Terminator::Branch {
+            condition,
+            value: constant(&if_expression.condition),
+            then_block,
+            else_block,
+        });
+        let join = (!tail).then(|| self.new_block());
+
+        self.current = then_block;
+        self.lower_branch(&if_expression.consequence, tail, join);
+        self.current = else_block;
+        match (&if_expression.alternative, join) {
+            (Some(alternative), _) => self.lower_branch(alternative, tail, join),
+            (None, Some(join)) => self.terminate(Terminator::Goto(join)),
+            // Without `else`, the `if` expression has no value
+            (None, None) => self.terminate(Terminator::Return { value: None }),
+        }
+        if let Some(join) = join {
+            self.current = join;
+        }
+    }
+
+    /// Lowers a branch of an `if` or `match` expression, continuing with `join` unless the
+    /// branch is in tail position.
+    fn lower_branch(&mut self, branch: &Expression, tail: bool, join: Option<BlockId>) {
+        if tail {
+            self.lower_tail(branch);
+        } else {
+            self.lower_expression(branch);
+        }
+        if let Some(join) = join {
+            self.terminate(Terminator::Goto(join));
+        }
+    }
+
+    /// Lowers a loop, given the condition of its header for `for` and `while` loops with its
+    /// constant value, if any, and its body.
+    fn lower_loop(&mut self, condition: Option<(Span, Option<bool>)>, body: &Expression) {
+        let header = self.new_block();
+        let body_block = match condition {
+            Some(_) => self.new_block(),
+            None => header,
+        };
+        let exit = self.new_block();
+        self.terminate(Terminator::Goto(header));
+        if let Some((condition, value)) = condition {
+            self.current = header;
+            self.terminate(Terminator::Branch {
+                condition,
+                value,
+                then_block: body_block,
+                else_block: exit,
+            });
+        }
+
+        self.loops.push((header, exit));
+        self.current = body_block;
+        self.lower_expression(body);
+        self.terminate(Terminator::Goto(header));
+        self.loops.pop();
+        self.current = exit;
+    }
+}
+
+/// Returns the value of a condition that is a boolean literal, possibly negated.
+fn constant(condition: &Expression) -> Option<bool> {
+    match &condition.kind {
+        ExpressionKind::Literal(Literal::Bool(value)) => Some(*value),
+        ExpressionKind::Parenthesized(inner) => constant(inner),
+        ExpressionKind::Prefix(prefix) if matches!(prefix.operator, UnaryOp::Not) => {
+            constant(&prefix.rhs).map(|value| !value)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+
+    /// Returns the source text of the given spans, without the `;` ending statements.
+    fn texts<'a>(source: &'a str, spans: &[Span]) -> Vec<&'a str> {
+        spans
+            .iter()
+            .map(|span| source[span.start() as usize..span.end() as usize].trim_end_matches(';'))
+            .collect()
+    }
+
+    #[test]
+    fn test_unreachable_statements_and_constant_conditions() {
+        let source = "unconstrained fn main(x: u32) -> pub u32 {\n    let mut sum = 0;\n    for i in 0..x {\n        if i == 3 {\n            break;\n            sum = 1;\n        }\n        sum += i;\n    }\n    if false {\n        sum = 2;\n    }\n    loop {\n        sum += 1;\n    }\n    assert(sum > 0);\n    sum\n}\n\nunconstrained fn partial(x: u32) -> u32 {\n    while !false {\n        if x == 0 { break; }\n    }\n    if x > 1 {\n        x\n    } else if x == 1 {\n        1\n    }\n}\n";
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let graphs = control_flow_graphs(&root);
+
+        let main = &graphs["main"];
+        assert_eq!(
+            texts(source, &main.unreachable_statements()),
+            ["sum = 1", "sum = 2", "assert(sum > 0)", "sum"]
+        );
+        let conditions: Vec<(&str, bool)> = main
+            .constant_conditions()
+            .into_iter()
+            .map(|(span, value)| (texts(source, &[span])[0], value))
+            .collect();
+        assert_eq!(conditions, [("false", false)]);
+        // The trailing `sum` is never reached, so no path returns without a value
+        assert!(!main.returns_without_value());
+
+        let partial = &graphs["partial"];
+        assert!(partial.unreachable_statements().is_empty());
+        assert!(partial.returns_without_value());
+        let returned: Vec<Span> = partial
+            .blocks()
+            .filter_map(|(_, block)| match block.terminator {
+                Terminator::Return { value } => value,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(texts(source, &returned), ["x", "1"]);
+    }
+}
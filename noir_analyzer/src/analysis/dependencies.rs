@@ -0,0 +1,193 @@
+//! # Package Dependency Graph
+//!
+//! Links a package to the dependencies declared in its `Nargo.toml`, as resolved by
+//! `crate::advisories::resolve_dependencies`: path dependencies, and git dependencies that
+//! nargo already checked out in `~/nargo`. The public functions of every dependency are
+//! indexed by signature, so that lints can tell what a call into a dependency does instead
+//! of treating it as unknown.
+//!
+//! Dependencies are read from their `src/lib.nr`, inline submodules included. Dependencies
+//! that are not on disk or do not parse are part of the graph without functions, so calls
+//! into them stay unknown.
+
+use crate::advisories::Dependency;
+use crate::analysis::call_graph::collect_functions;
+use crate::ast::parser::Parser;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::ItemVisibility;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Entry point of a library package, relative to its directory.
+const LIBRARY_ENTRY: &str = "src/lib.nr";
+
+/// A public function of a dependency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionSignature {
+    /// Name under which the dependency declaring the function is declared.
+    pub package: String,
+    /// Path of the function within its package, e.g. `poseidon::hash`.
+    pub path: String,
+    pub is_unconstrained: bool,
+    /// Number of parameters.
+    pub parameters: usize,
+}
+
+/// The dependencies of a package, with the public functions they define.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// Direct dependencies of each package, by package name.
+    edges: BTreeMap<String, BTreeSet<String>>,
+    /// Public functions of each dependency, by package name and path.
+    functions: BTreeMap<String, BTreeMap<String, FunctionSignature>>,
+}
+
+impl DependencyGraph {
+    /// Builds the graph of the named package from its resolved dependencies.
+    pub fn build(package: &str, dependencies: &[Dependency]) -> Self {
+        let mut graph = Self::default();
+        let mut parser = Parser::default();
+        for dependency in dependencies {
+            let requirer = dependency
+                .required_by
+                .last()
+                .map_or(package, String::as_str);
+            graph
+                .edges
+                .entry(requirer.to_string())
+                .or_default()
+                .insert(dependency.name.clone());
+
+            // Dependencies reached along several paths are resolved once
+            if graph.functions.contains_key(&dependency.name) {
+                continue;
+            }
+            let parsed_module = dependency
+                .dir
+                .as_ref()
+                .and_then(|dir| parser.parse_file(&dir.join(LIBRARY_ENTRY)).ok());
+            match parsed_module {
+                Some(parsed_module) => graph.add_package(&dependency.name, &parsed_module),
+                None => {
+                    graph
+                        .functions
+                        .insert(dependency.name.clone(), BTreeMap::new());
+                }
+            }
+        }
+        graph
+    }
+
+    /// Indexes the public functions of a dependency.
+    pub fn add_package(&mut self, package: &str, parsed_module: &ParsedModule) {
+        let mut nodes = vec![];
+        collect_functions(parsed_module, &mut nodes);
+        let functions = self.functions.entry(package.to_string()).or_default();
+        // Methods are called through their type, which calls are not resolved to
+        let public_functions = nodes.into_iter().filter(|node| {
+            node.self_type.is_none() && node.function.def.visibility == ItemVisibility::Public
+        });
+        for node in public_functions {
+            let mut path = node.module;
+            path.push(node.name);
+            let path = path.join("::");
+            functions.insert(
+                path.clone(),
+                FunctionSignature {
+                    package: package.to_string(),
+                    path,
+                    is_unconstrained: node.function.def.is_unconstrained,
+                    parameters: node.function.def.parameters.len(),
+                },
+            );
+        }
+    }
+
+    /// Returns the names of the direct dependencies of a package.
+    pub fn dependencies_of(&self, package: &str) -> impl Iterator<Item = &str> {
+        self.edges
+            .get(package)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Resolves the path of a called function to a function of a dependency: either a path
+    /// starting with the name of the dependency, after an optional `dep::`, or the name of a
+    /// function imported from a dependency, when no other dependency defines one with the
+    /// same name.
+    pub fn function(&self, segments: &[String]) -> Option<&FunctionSignature> {
+        let segments = match segments {
+            [first, rest @ ..] if first == "dep" => rest,
+            _ => segments,
+        };
+        match segments {
+            [] => None,
+            [name] => {
+                let mut candidates = self
+                    .functions
+                    .values()
+                    .flat_map(|functions| functions.values())
+                    .filter(|function| function.path.rsplit("::").next() == Some(name.as_str()));
+                let function = candidates.next()?;
+                candidates.next().is_none().then_some(function)
+            }
+            [package, path @ ..] => self.functions.get(package)?.get(&path.join("::")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advisories::resolve_dependencies;
+    use std::fs;
+
+    fn segments(path: &str) -> Vec<String> {
+        path.split("::").map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_path_dependencies_are_indexed_by_signature() {
+        let root = std::env::temp_dir().join("noir-analyzer-dependency-graph");
+        let _ = fs::remove_dir_all(&root);
+        for (path, content) in [
+            (
+                "app/Nargo.toml",
+                "[package]\nname = \"app\"\n[dependencies]\nhashes = { path = \"../hashes\" }\n",
+            ),
+            (
+                "hashes/Nargo.toml",
+                "[package]\nname = \"hashes\"\n[dependencies]\nbase = { path = \"../base\" }\n",
+            ),
+            (
+                "hashes/src/lib.nr",
+                "pub fn hash(x: Field) -> Field { x }\nfn helper() {}\npub mod hints {\n    pub unconstrained fn hash(x: Field, y: Field) -> Field { x + y }\n}\n",
+            ),
+            ("base/Nargo.toml", "[package]\nname = \"base\"\n"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let dependencies = resolve_dependencies(&root.join("app/Nargo.toml")).unwrap();
+        let graph = DependencyGraph::build("app", &dependencies);
+
+        assert_eq!(graph.dependencies_of("app").collect::<Vec<_>>(), ["hashes"]);
+        assert_eq!(
+            graph.dependencies_of("hashes").collect::<Vec<_>>(),
+            ["base"]
+        );
+
+        let hash = graph.function(&segments("dep::hashes::hash")).unwrap();
+        assert!(!hash.is_unconstrained);
+        assert_eq!(hash.parameters, 1);
+        let hint = graph.function(&segments("hashes::hints::hash")).unwrap();
+        assert!(hint.is_unconstrained);
+
+        // Private functions are not callable, and imported names must be unambiguous
+        assert!(graph.function(&segments("hashes::helper")).is_none());
+        assert!(graph.function(&segments("hash")).is_none());
+        assert!(graph.function(&segments("base::hash")).is_none());
+    }
+}
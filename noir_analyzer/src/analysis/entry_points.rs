@@ -0,0 +1,324 @@
+//! # Entry Points
+//!
+//! Lists the functions of a program that can be reached from outside of it: `main`, the
+//! functions of contracts, tests and, for libraries, public items. Together they form the
+//! surface an auditor has to review first.
+//!
+//! Each entry point comes with a rough estimate of its constraint cost: the number of
+//! assertions, arithmetic and comparison operations, array accesses and calls in its body,
+//! with loop bodies counted once per iteration when the loop bounds are integer literals.
+//! Callees are not expanded, so the estimate is only meant to compare entry points.
+
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    CallExpression, ConstrainExpression, Expression, ExpressionKind, ForLoopStatement, ForRange,
+    FunctionDefinition, IndexExpression, InfixExpression, ItemVisibility, Literal,
+    MethodCallExpression, Visibility, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use noirc_frontend::token::FunctionAttribute;
+use serde::Serialize;
+
+/// Why a function can be reached from outside of the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EntryPointKind {
+    /// The `main` function of the crate root.
+    Main,
+    /// A function of a `contract` module.
+    Contract,
+    /// A public function of a library.
+    Public,
+    /// A function run by `nargo test`.
+    Test,
+}
+
+impl EntryPointKind {
+    /// Returns the lowercase name of the kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryPointKind::Main => "main",
+            EntryPointKind::Contract => "contract",
+            EntryPointKind::Public => "public",
+            EntryPointKind::Test => "test",
+        }
+    }
+}
+
+/// A parameter of an entry point.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntryPointParameter {
+    /// The pattern binding the parameter, usually its name.
+    pub name: String,
+    /// The written type of the parameter.
+    pub r#type: String,
+    /// Whether the verifier sees the value: `public`, `private` or `databus`.
+    pub visibility: &'static str,
+}
+
+/// A function reachable from outside of the program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryPoint {
+    pub kind: EntryPointKind,
+    /// Path of the function from the crate root, e.g. `utils::hash`.
+    pub path: String,
+    /// Item visibility of the function: `pub`, `pub(crate)` or `private`.
+    pub visibility: &'static str,
+    pub is_unconstrained: bool,
+    pub parameters: Vec<EntryPointParameter>,
+    /// The written return type, `None` for functions returning nothing.
+    pub return_type: Option<String>,
+    /// Visibility of the returned value, as for parameters.
+    pub return_visibility: &'static str,
+    /// The attributes of the function, as written.
+    pub attributes: Vec<String>,
+    /// Rough number of constraints of the function body, zero for unconstrained functions.
+    pub estimated_cost: u64,
+    pub span: Span,
+    pub file_id: FileId,
+}
+
+impl EntryPoint {
+    /// Returns the signature of the function, annotated with the visibility of its inputs
+    /// and output, e.g. `fn main(x: Field [private], y: Field [public]) -> Field [public]`.
+    pub fn signature(&self) -> String {
+        let parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|parameter| {
+                format!(
+                    "{}: {} [{}]",
+                    parameter.name, parameter.r#type, parameter.visibility
+                )
+            })
+            .collect();
+        let name = self.path.rsplit("::").next().unwrap_or(&self.path);
+        let mut signature = format!("fn {name}({})", parameters.join(", "));
+        if let Some(return_type) = &self.return_type {
+            signature.push_str(&format!(" -> {return_type} [{}]", self.return_visibility));
+        }
+        signature
+    }
+}
+
+/// Returns the entry points of a parsed program, ordered by kind and then by position.
+/// Public functions are only entry points of libraries.
+pub fn entry_points(parsed_module: &ParsedModule, is_library: bool) -> Vec<EntryPoint> {
+    let mut entry_points = vec![];
+    collect(parsed_module, &[], false, is_library, &mut entry_points);
+    entry_points.sort_by_key(|entry_point| (entry_point.kind, entry_point.span.start()));
+    entry_points
+}
+
+fn collect(
+    parsed_module: &ParsedModule,
+    module_path: &[String],
+    in_contract: bool,
+    is_library: bool,
+    entry_points: &mut Vec<EntryPoint>,
+) {
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Submodules(submodule) => {
+                let mut path = module_path.to_vec();
+                path.push(submodule.name.to_string());
+                collect(
+                    &submodule.contents,
+                    &path,
+                    in_contract || submodule.is_contract,
+                    is_library,
+                    entry_points,
+                );
+            }
+            ItemKind::Function(function) => {
+                let attribute = Noir::function_attribute(&function.def);
+                let kind = if matches!(attribute, Some(FunctionAttribute::Test(..))) {
+                    EntryPointKind::Test
+                } else if in_contract {
+                    EntryPointKind::Contract
+                } else if module_path.is_empty() && function.name() == "main" {
+                    EntryPointKind::Main
+                } else if is_library && function.def.visibility == ItemVisibility::Public {
+                    EntryPointKind::Public
+                } else {
+                    continue;
+                };
+
+                let mut path = module_path.to_vec();
+                path.push(function.name().to_string());
+                entry_points.push(entry_point(kind, path.join("::"), &function.def));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn entry_point(kind: EntryPointKind, path: String, function: &FunctionDefinition) -> EntryPoint {
+    let (span, file_id) = Noir::function_location(function);
+    let return_type = function.return_type.to_string();
+
+    EntryPoint {
+        kind,
+        path,
+        visibility: match function.visibility {
+            ItemVisibility::Public => "pub",
+            ItemVisibility::PublicCrate => "pub(crate)",
+            ItemVisibility::Private => "private",
+        },
+        is_unconstrained: function.is_unconstrained,
+        parameters: function
+            .parameters
+            .iter()
+            .map(|parameter| EntryPointParameter {
+                name: parameter.pattern.to_string(),
+                r#type: parameter.typ.to_string(),
+                visibility: visibility_name(&parameter.visibility),
+            })
+            .collect(),
+        return_type: (!return_type.is_empty()).then_some(return_type),
+        return_visibility: visibility_name(&function.return_visibility),
+        attributes: Noir::function_attribute(function)
+            .map(ToString::to_string)
+            .into_iter()
+            .chain(
+                function
+                    .attributes
+                    .secondary
+                    .iter()
+                    .map(ToString::to_string),
+            )
+            .collect(),
+        estimated_cost: if function.is_unconstrained {
+            0
+        } else {
+            estimated_cost(function)
+        },
+        span,
+        file_id,
+    }
+}
+
+fn visibility_name(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "public",
+        Visibility::Private => "private",
+        _ => "databus",
+    }
+}
+
+/// Estimates the number of constraints of a function body.
+pub(crate) fn estimated_cost(function: &FunctionDefinition) -> u64 {
+    let mut counter = CostCounter::default();
+    for statement in &function.body.statements {
+        statement.accept(&mut counter);
+    }
+    counter.cost
+}
+
+#[derive(Default)]
+struct CostCounter {
+    cost: u64,
+}
+
+impl Visitor for CostCounter {
+    fn visit_constrain_statement(&mut self, _: &ConstrainExpression) -> bool {
+        self.cost = self.cost.saturating_add(1);
+        true
+    }
+
+    fn visit_infix_expression(&mut self, _: &InfixExpression, _: Span) -> bool {
+        self.cost = self.cost.saturating_add(1);
+        true
+    }
+
+    fn visit_index_expression(&mut self, _: &IndexExpression, _: Span) -> bool {
+        self.cost = self.cost.saturating_add(1);
+        true
+    }
+
+    fn visit_call_expression(&mut self, _: &CallExpression, _: Span) -> bool {
+        self.cost = self.cost.saturating_add(1);
+        true
+    }
+
+    fn visit_method_call_expression(&mut self, _: &MethodCallExpression, _: Span) -> bool {
+        self.cost = self.cost.saturating_add(1);
+        true
+    }
+
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        let iterations = match &for_loop.range {
+            ForRange::Range(bounds) => integer_literal(&bounds.start)
+                .zip(integer_literal(&bounds.end))
+                .and_then(|(start, end)| end.checked_sub(start))
+                .map(|iterations| iterations + u64::from(bounds.inclusive)),
+            _ => None,
+        };
+
+        let mut body = CostCounter::default();
+        for_loop.block.accept(&mut body);
+        self.cost = self
+            .cost
+            .saturating_add(body.cost.saturating_mul(iterations.unwrap_or(1)));
+        false
+    }
+}
+
+fn integer_literal(expression: &Expression) -> Option<u64> {
+    match &expression.kind {
+        ExpressionKind::Literal(Literal::Integer(value)) if !value.is_negative() => {
+            value.absolute_value().try_into_u128()?.try_into().ok()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_entry_points_of_a_library() {
+        let source_code = r#"
+            mod utils {
+                pub fn double(x: Field) -> Field { x * 2 }
+                fn helper() {}
+            }
+
+            #[test]
+            fn test_double() {
+                assert(utils::double(1) == 2);
+            }
+
+            pub fn sum(values: [Field; 4], offset: pub Field) -> pub Field {
+                let mut total = offset;
+                for i in 0..4 {
+                    total = total + values[i];
+                }
+                total
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let entry_points = entry_points(&root, true);
+
+        let paths: Vec<&str> = entry_points
+            .iter()
+            .map(|entry_point| entry_point.path.as_str())
+            .collect();
+        assert_eq!(paths, ["utils::double", "sum", "test_double"]);
+
+        let sum = &entry_points[1];
+        assert_eq!(sum.kind, EntryPointKind::Public);
+        assert_eq!(
+            sum.signature(),
+            "fn sum(values: [Field; 4] [private], offset: Field [public]) -> Field [public]"
+        );
+        // Four additions and array accesses
+        assert_eq!(sum.estimated_cost, 8);
+        assert_eq!(entry_points[2].attributes, ["#[test]"]);
+    }
+}
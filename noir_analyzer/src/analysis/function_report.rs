@@ -0,0 +1,147 @@
+//! # Function Report
+//!
+//! Gathers what the analyzer knows about a single function, for editors showing it on hover
+//! or in an inspection panel: the lints reported in it, its estimated constraint cost, its
+//! callers and callees, and which of its variables hold unconstrained values.
+//!
+//! Reports are built from the context and lints of a finished analysis, so that they are
+//! served without analyzing the program again.
+
+use crate::analysis::entry_points::estimated_cost;
+use crate::analysis::taint::{Taint, TaintSummary};
+use crate::ast::ast_context::AstContext;
+use crate::diagnostics::lint::Lint;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+
+/// What the analyzer knows about a function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionReport {
+    pub name: String,
+    /// Span of the whole function, attributes and body included.
+    pub span: Span,
+    pub file_id: FileId,
+    /// The lints reported within the function.
+    pub lints: Vec<Lint>,
+    /// Rough number of constraints of the body, as for entry points; zero for unconstrained
+    /// functions.
+    pub estimated_cost: u64,
+    /// Functions calling this one, in alphabetical order.
+    pub callers: Vec<String>,
+    /// Functions called by this one, in the order of their first call.
+    pub callees: Vec<String>,
+    pub taint: TaintSummary,
+}
+
+/// Reports on the function of the given name, from the context and lints of an analysis.
+/// Returns `None` when the program defines no such function.
+pub fn analyze_function(
+    context: &AstContext,
+    name: &str,
+    lints: &[Lint],
+) -> Option<FunctionReport> {
+    let function = context.function_definitions.get(name)?;
+    let (span, file_id) = function_span(context.parsed_module, name)
+        .unwrap_or_else(|| Noir::function_location(function));
+
+    Some(FunctionReport {
+        name: name.to_string(),
+        span,
+        file_id,
+        lints: lints
+            .iter()
+            .filter(|lint| {
+                lint.file_id
+                    .is_none_or(|lint_file_id| lint_file_id == file_id)
+                    && lint.span.is_some_and(|lint_span| {
+                        span.start() <= lint_span.start() && lint_span.end() <= span.end()
+                    })
+            })
+            .cloned()
+            .collect(),
+        estimated_cost: if function.is_unconstrained {
+            0
+        } else {
+            estimated_cost(function)
+        },
+        callers: context
+            .call_graph
+            .callers_of(name)
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        callees: context.call_graph.callees_of(name).to_vec(),
+        taint: Taint::of_function(context, function).summary(),
+    })
+}
+
+/// Returns the location of the item defining the function, which spans its body unlike
+/// the location of the definition.
+fn function_span(parsed_module: &ParsedModule, name: &str) -> Option<(Span, FileId)> {
+    parsed_module
+        .items
+        .iter()
+        .find_map(|item| match &item.kind {
+            ItemKind::Function(function) if function.name() == name => {
+                Some(Noir::item_location(item))
+            }
+            ItemKind::Submodules(submodule) => function_span(&submodule.contents, name),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::passes::Analysis;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::lints::deep_constraint_expression::DeepConstraintExpression;
+    use crate::lints::lint_rule::LintRule;
+
+    #[test]
+    fn test_function_report_gathers_lints_calls_and_taint() {
+        let source_code = r#"
+            unconstrained fn hint() -> Field { 1 }
+
+            fn check(x: Field) -> Field {
+                // Safety: only a is checked
+                let a = unsafe { hint() };
+                // Safety: b is not
+                let b = unsafe { hint() };
+                assert((a + 1) * 2 == x);
+                a + b
+            }
+
+            fn main(x: Field) {
+                let _ = check(x);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint_rules: Vec<Box<dyn LintRule>> =
+            vec![Box::new(DeepConstraintExpression::with_max_depth(2))];
+        let mut analyzer = Analyzer::new(&lint_rules).with_analyses(&[Analysis::CallGraph]);
+        let lints = analyzer.analyze(&root).expect("Should have passed");
+        assert_eq!(lints.len(), 1);
+
+        let report = analyzer
+            .analyze_function("check")
+            .expect("Should be defined");
+        assert_eq!(report.lints, lints);
+        assert_eq!(report.callers, ["main"]);
+        assert_eq!(report.callees, ["hint"]);
+        assert_eq!(report.taint.unconstrained, ["a", "b"]);
+        assert_eq!(report.taint.unchecked, ["b"]);
+        assert!(report.estimated_cost > 0);
+
+        let report = analyzer
+            .analyze_function("main")
+            .expect("Should be defined");
+        assert!(report.lints.is_empty());
+        assert_eq!(report.callees, ["check"]);
+        assert!(analyzer.analyze_function("missing").is_none());
+    }
+}
@@ -0,0 +1,17 @@
+//! # Analysis Module
+//!
+//! Whole-program analyses computed from the parsed AST and shared between lints.
+
+pub mod aztec_attributes;
+pub mod call_graph;
+pub mod cfg;
+pub mod dependencies;
+pub mod entry_points;
+pub mod function_report;
+pub mod module_graph;
+pub mod passes;
+pub mod private_taint;
+pub mod resolution;
+pub mod similarity;
+pub mod taint;
+pub mod usage;
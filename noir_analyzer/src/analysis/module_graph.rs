@@ -0,0 +1,337 @@
+//! # Module Dependency Graph
+//!
+//! Builds a directed graph between the modules of a parsed program, where an edge `a → b`
+//! means that module `a` imports something from module `b`.
+//!
+//! Module paths are rendered as strings rooted at `crate` (e.g. `crate::utils::hash`).
+//! Imports from dependencies (`dep::` paths or unknown crates) are not part of the graph.
+
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{PathKind, UseTree, UseTreeKind};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Name of the root module of the crate.
+pub const ROOT_MODULE: &str = "crate";
+
+/// A module known to the graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleNode {
+    /// Number of items declared directly in the module (nested module contents excluded).
+    pub item_count: usize,
+    /// Span of the module declaration, `None` for the crate root.
+    pub span: Option<Span>,
+    /// File containing the module declaration, `None` for the crate root.
+    pub file_id: Option<FileId>,
+}
+
+/// Directed graph of import dependencies between modules.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    modules: BTreeMap<String, ModuleNode>,
+    dependencies: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ModuleGraph {
+    /// Builds the module graph of the given parsed module.
+    pub fn build(parsed_module: &ParsedModule) -> Self {
+        let mut graph = Self::default();
+        let mut imports = Vec::new();
+
+        graph.modules.insert(
+            ROOT_MODULE.to_string(),
+            ModuleNode {
+                item_count: 0,
+                span: None,
+                file_id: None,
+            },
+        );
+        graph.collect_modules(parsed_module, &[ROOT_MODULE.to_string()], &mut imports);
+
+        for (module, segments) in imports {
+            let target = graph
+                .resolve_module(&segments)
+                .filter(|target| *target != module);
+            if let Some(target) = target {
+                graph.dependencies.entry(module).or_default().insert(target);
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the paths of every module, in sorted order.
+    pub fn modules(&self) -> impl Iterator<Item = (&String, &ModuleNode)> {
+        self.modules.iter()
+    }
+
+    /// Returns the module stored under the given path.
+    pub fn module(&self, path: &str) -> Option<&ModuleNode> {
+        self.modules.get(path)
+    }
+
+    /// Returns the modules the given module imports from.
+    pub fn dependencies_of(&self, path: &str) -> impl Iterator<Item = &String> {
+        self.dependencies.get(path).into_iter().flatten()
+    }
+
+    /// Returns the number of distinct modules importing from the given module.
+    pub fn fan_in(&self, path: &str) -> usize {
+        self.dependencies
+            .values()
+            .filter(|targets| targets.contains(path))
+            .count()
+    }
+
+    /// Returns every import cycle as the list of modules forming it.
+    ///
+    /// Each cycle is a strongly connected component with more than one module, listed in
+    /// sorted order. Cycles themselves are sorted by their first module.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut tarjan = Tarjan::new(self);
+        for module in self.modules.keys() {
+            if !tarjan.indices.contains_key(module.as_str()) {
+                tarjan.connect(module);
+            }
+        }
+
+        let mut cycles: Vec<Vec<String>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Registers the modules declared in `parsed_module` and collects its imports as
+    /// `(importing module, absolute path segments)` pairs.
+    fn collect_modules(
+        &mut self,
+        parsed_module: &ParsedModule,
+        path: &[String],
+        imports: &mut Vec<(String, Vec<String>)>,
+    ) {
+        let module_path = path.join("::");
+
+        for item in &parsed_module.items {
+            let (span, file_id) = Noir::item_location(item);
+            match &item.kind {
+                ItemKind::Submodules(submodule) => {
+                    let mut submodule_path = path.to_vec();
+                    submodule_path.push(submodule.name.to_string());
+                    self.modules.insert(
+                        submodule_path.join("::"),
+                        ModuleNode {
+                            item_count: 0,
+                            span: Some(span),
+                            file_id: Some(file_id),
+                        },
+                    );
+                    self.collect_modules(&submodule.contents, &submodule_path, imports);
+                }
+                ItemKind::ModuleDecl(declaration) => {
+                    let mut submodule_path = path.to_vec();
+                    submodule_path.push(declaration.ident.to_string());
+                    self.modules.insert(
+                        submodule_path.join("::"),
+                        ModuleNode {
+                            item_count: 0,
+                            span: Some(span),
+                            file_id: Some(file_id),
+                        },
+                    );
+                }
+                ItemKind::Import(use_tree, _) => {
+                    for segments in flatten_use_tree(use_tree, path, Vec::new()) {
+                        imports.push((module_path.clone(), segments));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(node) = self.modules.get_mut(&module_path) {
+            node.item_count = parsed_module.items.len();
+        }
+    }
+
+    /// Resolves an absolute import path to the deepest known module it refers to.
+    fn resolve_module(&self, segments: &[String]) -> Option<String> {
+        (1..=segments.len())
+            .rev()
+            .map(|length| segments[..length].join("::"))
+            .find(|candidate| self.modules.contains_key(candidate))
+    }
+}
+
+/// Expands a use tree into absolute path segments, one entry per imported name.
+///
+/// Returns nothing for imports that do not point into the current crate.
+fn flatten_use_tree(
+    use_tree: &UseTree,
+    current_module: &[String],
+    mut prefix: Vec<String>,
+) -> Vec<Vec<String>> {
+    if prefix.is_empty() {
+        prefix = match use_tree.prefix.kind {
+            PathKind::Crate => vec![ROOT_MODULE.to_string()],
+            PathKind::Super => current_module[..current_module.len().saturating_sub(1)].to_vec(),
+            PathKind::Plain => current_module.to_vec(),
+            PathKind::Dep => return Vec::new(),
+        };
+    }
+    prefix.extend(
+        use_tree
+            .prefix
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string()),
+    );
+
+    match &use_tree.kind {
+        UseTreeKind::Path(name, _alias) => {
+            let mut segments = prefix;
+            segments.push(name.to_string());
+            vec![segments]
+        }
+        UseTreeKind::List(trees) => trees
+            .iter()
+            .flat_map(|tree| flatten_use_tree(tree, current_module, prefix.clone()))
+            .collect(),
+    }
+}
+
+/// Tarjan's strongly connected components algorithm over the module graph.
+struct Tarjan<'graph> {
+    graph: &'graph ModuleGraph,
+    index: usize,
+    indices: BTreeMap<&'graph str, usize>,
+    low_links: BTreeMap<&'graph str, usize>,
+    stack: Vec<&'graph str>,
+    on_stack: BTreeSet<&'graph str>,
+    components: Vec<Vec<String>>,
+}
+
+impl<'graph> Tarjan<'graph> {
+    fn new(graph: &'graph ModuleGraph) -> Self {
+        Self {
+            graph,
+            index: 0,
+            indices: BTreeMap::new(),
+            low_links: BTreeMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            components: Vec::new(),
+        }
+    }
+
+    fn connect(&mut self, module: &'graph str) {
+        self.indices.insert(module, self.index);
+        self.low_links.insert(module, self.index);
+        self.index += 1;
+        self.stack.push(module);
+        self.on_stack.insert(module);
+
+        let graph = self.graph;
+        for dependency in graph.dependencies_of(module) {
+            if !self.indices.contains_key(dependency.as_str()) {
+                self.connect(dependency);
+                let low_link = self.low_links[module].min(self.low_links[dependency.as_str()]);
+                self.low_links.insert(module, low_link);
+            } else if self.on_stack.contains(dependency.as_str()) {
+                let low_link = self.low_links[module].min(self.indices[dependency.as_str()]);
+                self.low_links.insert(module, low_link);
+            }
+        }
+
+        if self.low_links[module] == self.indices[module] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member.to_string());
+                if member == module {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_module_graph_collects_nested_modules() {
+        let source_code = r#"
+            mod a {
+                mod b {
+                    pub fn foo() {}
+                }
+            }
+            fn main() {}
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let graph = ModuleGraph::build(&root);
+
+        let modules: Vec<&String> = graph.modules().map(|(path, _)| path).collect();
+        assert_eq!(modules, vec!["crate", "crate::a", "crate::a::b"]);
+        assert_eq!(graph.module("crate").unwrap().item_count, 2);
+        assert_eq!(graph.module("crate::a::b").unwrap().item_count, 1);
+    }
+
+    #[test]
+    fn test_module_graph_records_import_edges() {
+        let source_code = r#"
+            mod a {
+                pub fn foo() {}
+            }
+            mod b {
+                use crate::a::foo;
+                use super::a;
+            }
+            use a::foo;
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let graph = ModuleGraph::build(&root);
+
+        let dependencies: Vec<&String> = graph.dependencies_of("crate::b").collect();
+        assert_eq!(dependencies, vec!["crate::a"]);
+        assert_eq!(graph.fan_in("crate::a"), 2);
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn test_module_graph_detects_cycles() {
+        let source_code = r#"
+            mod a {
+                use crate::b::bar;
+                pub fn foo() {}
+            }
+            mod b {
+                use crate::a::foo;
+                pub fn bar() {}
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let graph = ModuleGraph::build(&root);
+
+        assert_eq!(
+            graph.cycles(),
+            vec![vec!["crate::a".to_string(), "crate::b".to_string()]]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! # Analysis Passes
+//!
+//! Whole-program analyses stored in the `AstContext`, which lint rules declare they require.
+//! The analyzer computes only the analyses required by the rules it runs, together with the
+//! analyses these are computed from, once per run and in dependency order. A run of rules
+//! only looking at the AST thus does not pay for the heavier graphs.
+
+/// A whole-program analysis of the `AstContext`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Analysis {
+    /// Import dependencies between modules, in `module_graph`.
+    ModuleGraph,
+    /// Calls between functions and methods, in `call_graph`.
+    CallGraph,
+    /// Control-flow graphs of function bodies, in `control_flow`.
+    ControlFlow,
+    /// Flows of the private inputs of `main`, in `private_taint`.
+    PrivateTaint,
+}
+
+impl Analysis {
+    /// Every analysis, in dependency order.
+    pub const ALL: [Analysis; 4] = [
+        Analysis::ModuleGraph,
+        Analysis::CallGraph,
+        Analysis::ControlFlow,
+        Analysis::PrivateTaint,
+    ];
+
+    /// Returns the analyses this one is computed from.
+    pub fn dependencies(self) -> &'static [Analysis] {
+        match self {
+            Analysis::PrivateTaint => &[Analysis::CallGraph],
+            Analysis::ModuleGraph | Analysis::CallGraph | Analysis::ControlFlow => &[],
+        }
+    }
+}
+
+/// Returns the requested analyses and those they depend on, each once, with every analysis
+/// after its dependencies.
+pub fn schedule(requested: impl IntoIterator<Item = Analysis>) -> Vec<Analysis> {
+    let mut order = vec![];
+    for analysis in requested {
+        push_with_dependencies(analysis, &mut order);
+    }
+    order
+}
+
+fn push_with_dependencies(analysis: Analysis, order: &mut Vec<Analysis>) {
+    if order.contains(&analysis) {
+        return;
+    }
+    for dependency in analysis.dependencies() {
+        push_with_dependencies(*dependency, order);
+    }
+    order.push(analysis);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_adds_dependencies_first_and_deduplicates() {
+        assert_eq!(
+            schedule([
+                Analysis::PrivateTaint,
+                Analysis::ModuleGraph,
+                Analysis::CallGraph,
+                Analysis::PrivateTaint,
+            ]),
+            [
+                Analysis::CallGraph,
+                Analysis::PrivateTaint,
+                Analysis::ModuleGraph,
+            ]
+        );
+        assert!(schedule([]).is_empty());
+        assert_eq!(schedule(Analysis::ALL), Analysis::ALL);
+    }
+}
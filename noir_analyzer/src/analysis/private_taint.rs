@@ -0,0 +1,681 @@
+//! # Private Taint
+//!
+//! Tracks where the private inputs of a program flow. The sources are the parameters of
+//! `main` that are neither `pub` nor databus inputs; values computed from them through `let`
+//! bindings, assignments, operators, calls and returned values are tainted. A tainted value
+//! reaching a sink leaks:
+//!
+//! - arguments of `print` and `println` end up in the output of the prover;
+//! - arguments of unconstrained functions called from constrained code are computed on
+//!   outside of the circuit, where nothing constrains what is done with them;
+//! - the value returned by `main` is a public output, which the verifier sees.
+//!
+//! Each function is summarized by the parameters its returned value depends on and the sinks
+//! its parameters reach, directly or through the functions it calls. Summaries are computed
+//! again until none changes, so that calls are followed whatever the order of the functions;
+//! callees are summarized before their callers along the call graph, so that most summaries
+//! are final after the first pass. Calls to functions outside of the program, such as the
+//! standard library, propagate the taint of their arguments, except hashes and commitments,
+//! whose outputs do not reveal their inputs. Variables are tracked by name, and an expression
+//! depends on every variable it reads, conditions included; the bodies of lambdas are not
+//! followed.
+
+use crate::analysis::call_graph::{CallGraph, Node, collect_functions};
+use crate::ast::scopes::pattern_idents;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    AssignStatement, CallExpression, Expression, ExpressionKind, ForLoopStatement, ForRange,
+    LValue, Lambda, LetStatement, MethodCallExpression, NoirFunction, Path, StatementKind,
+    Visibility, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use noirc_frontend::token::FmtStrFragment;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Name fragments of the functions whose output does not reveal their inputs.
+const SANITIZERS: &[&str] = &[
+    "blake", "commit", "hash", "keccak", "mimc", "pedersen", "poseidon", "sha",
+];
+
+/// Indexes of the parameters of a function a value depends on.
+type Labels = BTreeSet<usize>;
+
+/// Where a private value must not flow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sink {
+    /// An argument of `print` or `println`.
+    Print,
+    /// An argument of an unconstrained function called from constrained code.
+    Unconstrained { function: String },
+    /// The value returned by `main`.
+    PublicOutput,
+}
+
+/// A flow of a parameter of a function into a sink.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkFlow {
+    /// Index of the parameter.
+    pub parameter: usize,
+    pub sink: Sink,
+    /// The function containing the sink, which may be called by the function the parameter
+    /// belongs to.
+    pub function: String,
+    pub span: Span,
+    pub file_id: FileId,
+}
+
+/// How the parameters of a function flow through it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FunctionTaint {
+    /// Indexes of the parameters the returned value depends on.
+    pub returned: Labels,
+    /// Span of the returned expression, if the body ends with one.
+    pub return_span: Option<Span>,
+    /// The sinks the parameters reach, in the function or in the functions it calls.
+    pub sinks: Vec<SinkFlow>,
+}
+
+/// A private input of `main` reaching a sink.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leak {
+    /// Name of the private input.
+    pub input: String,
+    pub sink: Sink,
+    /// The function containing the sink.
+    pub function: String,
+    pub span: Span,
+    pub file_id: FileId,
+}
+
+/// The flows of the private inputs of a program.
+#[derive(Debug, Default)]
+pub struct PrivateTaint {
+    /// Summary of each function and method, keyed like the nodes of the call graph.
+    functions: BTreeMap<String, FunctionTaint>,
+    leaks: Vec<Leak>,
+}
+
+impl PrivateTaint {
+    /// Summarizes the functions of a module and its submodules, and follows the private
+    /// inputs of its `main` function. When functions share a name, the last one in source
+    /// order is kept, like in the function index of the `AstContext`.
+    pub fn build(parsed_module: &ParsedModule, call_graph: &CallGraph) -> Self {
+        let mut nodes = vec![];
+        collect_functions(parsed_module, &mut nodes);
+        let nodes: BTreeMap<String, Node> = nodes
+            .into_iter()
+            .map(|node| (node.name.clone(), node))
+            .collect();
+        let unconstrained: BTreeSet<&str> = nodes
+            .values()
+            .filter(|node| node.function.def.is_unconstrained)
+            .map(|node| node.name.as_str())
+            .collect();
+
+        // Summaries only grow as the summaries of callees do, so this terminates
+        let mut functions: BTreeMap<String, FunctionTaint> = nodes
+            .keys()
+            .map(|name| (name.clone(), FunctionTaint::default()))
+            .collect();
+        let order = callees_first(&nodes, call_graph);
+        loop {
+            let mut changed = false;
+            for node in order.iter().map(|name| &nodes[*name]) {
+                let summary = summarize(node, &functions, &unconstrained);
+                if functions.get(&node.name) != Some(&summary) {
+                    functions.insert(node.name.clone(), summary);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut taint = Self {
+            functions,
+            leaks: vec![],
+        };
+        if let Some(main) = main_function(parsed_module) {
+            taint.leaks = taint.main_leaks(main);
+        }
+        taint
+    }
+
+    /// Returns the summary of a function or method, by call graph name.
+    pub fn function(&self, name: &str) -> Option<&FunctionTaint> {
+        self.functions.get(name)
+    }
+
+    /// Returns the private inputs of `main` reaching a sink, by input and then by position
+    /// of the sink.
+    pub fn leaks(&self) -> &[Leak] {
+        &self.leaks
+    }
+
+    fn main_leaks(&self, main: &NoirFunction) -> Vec<Leak> {
+        let Some(summary) = self.function("main") else {
+            return vec![];
+        };
+        let (_, file_id) = Noir::function_location(&main.def);
+
+        let mut leaks = vec![];
+        for (index, parameter) in main.def.parameters.iter().enumerate() {
+            if parameter.visibility != Visibility::Private {
+                continue;
+            }
+            let input = parameter.pattern.to_string();
+            let mut flows: Vec<&SinkFlow> = summary
+                .sinks
+                .iter()
+                .filter(|flow| flow.parameter == index)
+                .collect();
+            flows.sort_by_key(|flow| flow.span.start());
+            leaks.extend(flows.into_iter().map(|flow| Leak {
+                input: input.clone(),
+                sink: flow.sink.clone(),
+                function: flow.function.clone(),
+                span: flow.span,
+                file_id: flow.file_id,
+            }));
+            if let Some(span) = summary.return_span
+                && summary.returned.contains(&index)
+            {
+                leaks.push(Leak {
+                    input,
+                    sink: Sink::PublicOutput,
+                    function: "main".to_string(),
+                    span,
+                    file_id,
+                });
+            }
+        }
+        leaks
+    }
+}
+
+/// Returns the `main` function of the crate root, if any.
+fn main_function(parsed_module: &ParsedModule) -> Option<&NoirFunction> {
+    parsed_module
+        .items
+        .iter()
+        .find_map(|item| match &item.kind {
+            ItemKind::Function(function) if function.name() == "main" => Some(function),
+            _ => None,
+        })
+}
+
+/// Returns the names of the functions, each after the functions it calls as far as the call
+/// graph knows them, except within cycles of recursive calls.
+fn callees_first<'n>(nodes: &'n BTreeMap<String, Node>, call_graph: &CallGraph) -> Vec<&'n str> {
+    fn visit<'n>(
+        name: &str,
+        nodes: &'n BTreeMap<String, Node>,
+        call_graph: &CallGraph,
+        visited: &mut BTreeSet<&'n str>,
+        order: &mut Vec<&'n str>,
+    ) {
+        let Some((name, _)) = nodes.get_key_value(name) else {
+            return;
+        };
+        let name = name.as_str();
+        if !visited.insert(name) {
+            return;
+        }
+        for callee in call_graph.callees_of(name) {
+            visit(callee, nodes, call_graph, visited, order);
+        }
+        order.push(name);
+    }
+
+    let mut visited = BTreeSet::new();
+    let mut order = vec![];
+    for name in nodes.keys() {
+        visit(name, nodes, call_graph, &mut visited, &mut order);
+    }
+    order
+}
+
+/// Summarizes a function given the current summaries of the others.
+fn summarize(
+    node: &Node,
+    summaries: &BTreeMap<String, FunctionTaint>,
+    unconstrained: &BTreeSet<&str>,
+) -> FunctionTaint {
+    let definition = &node.function.def;
+    let mut collector = FlowCollector {
+        function: &node.name,
+        file_id: Noir::function_location(definition).1,
+        self_type: node.self_type.as_deref(),
+        is_unconstrained: definition.is_unconstrained,
+        summaries,
+        unconstrained,
+        variables: HashMap::new(),
+        sinks: vec![],
+    };
+    for (index, parameter) in definition.parameters.iter().enumerate() {
+        let mut idents = vec![];
+        pattern_idents(&parameter.pattern, false, &mut idents);
+        for (ident, _) in idents {
+            collector
+                .variables
+                .insert(ident.to_string(), Labels::from([index]));
+        }
+    }
+
+    // Variables assigned at the end of a loop body taint its beginning, so the body is
+    // analyzed until the taint of the variables is stable
+    loop {
+        let variables = collector.variables.clone();
+        for statement in &definition.body.statements {
+            statement.accept(&mut collector);
+        }
+        if collector.variables == variables {
+            break;
+        }
+    }
+
+    let returned = match definition
+        .body
+        .statements
+        .last()
+        .map(|statement| &statement.kind)
+    {
+        Some(StatementKind::Expression(expression)) => Some(expression),
+        _ => None,
+    };
+    FunctionTaint {
+        returned: returned.map_or_else(Labels::new, |expression| collector.labels(expression)),
+        return_span: returned.map(|expression| Noir::expression_location(expression).0),
+        sinks: collector.sinks,
+    }
+}
+
+/// A call, with the arguments given to each parameter of the callee.
+struct Call<'e> {
+    /// Name of the callee in the call graph, if it is a function of the program.
+    callee: Option<String>,
+    /// Last segment of the called path, or name of the method.
+    name: String,
+    arguments: Vec<&'e Expression>,
+}
+
+/// Follows the taint of the parameters of a function through its body.
+struct FlowCollector<'a> {
+    function: &'a str,
+    file_id: FileId,
+    /// Type of the enclosing `impl` block, for `Self::` paths and method calls on `self`.
+    self_type: Option<&'a str>,
+    is_unconstrained: bool,
+    summaries: &'a BTreeMap<String, FunctionTaint>,
+    unconstrained: &'a BTreeSet<&'a str>,
+    /// Parameters each variable depends on.
+    variables: HashMap<String, Labels>,
+    sinks: Vec<SinkFlow>,
+}
+
+impl FlowCollector<'_> {
+    /// Returns the parameters the value of an expression depends on.
+    fn labels(&self, expression: &Expression) -> Labels {
+        let mut labels = LabelCollector {
+            flow: self,
+            labels: Labels::new(),
+        };
+        expression.accept(&mut labels);
+        labels.labels
+    }
+
+    fn variable(&self, name: &str) -> Labels {
+        self.variables.get(name).cloned().unwrap_or_default()
+    }
+
+    fn call<'e>(&self, call: &'e CallExpression) -> Option<Call<'e>> {
+        let ExpressionKind::Variable(path) = &call.func.kind else {
+            return None;
+        };
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let name = segments.last()?.clone();
+        let method = match segments.as_slice() {
+            [.., owner, name] => {
+                let owner = match owner.as_str() {
+                    "Self" => self.self_type.unwrap_or("Self"),
+                    owner => owner,
+                };
+                Some(format!("{owner}::{name}"))
+            }
+            _ => None,
+        };
+        let callee = method
+            .filter(|method| self.is_function(method))
+            .or_else(|| Some(name.clone()).filter(|name| self.is_function(name)));
+        Some(Call {
+            callee,
+            name,
+            arguments: call.arguments.iter().collect(),
+        })
+    }
+
+    fn method_call<'e>(&self, method_call: &'e MethodCallExpression) -> Call<'e> {
+        let name = method_call.method_name.to_string();
+        let callee = match (self.self_type, &method_call.object.kind) {
+            (Some(self_type), ExpressionKind::Variable(path))
+                if path.segments.len() == 1 && path.segments[0].ident.to_string() == "self" =>
+            {
+                Some(format!("{self_type}::{name}")).filter(|method| self.is_function(method))
+            }
+            _ => None,
+        };
+        Call {
+            callee,
+            name,
+            arguments: std::iter::once(&method_call.object)
+                .chain(&method_call.arguments)
+                .collect(),
+        }
+    }
+
+    fn is_function(&self, name: &str) -> bool {
+        self.summaries.contains_key(name)
+    }
+
+    /// Returns the parameters the value returned by a call depends on.
+    fn call_labels(&self, call: &Call) -> Labels {
+        match call
+            .callee
+            .as_ref()
+            .and_then(|callee| self.summaries.get(callee))
+        {
+            Some(summary) => summary
+                .returned
+                .iter()
+                .filter_map(|index| call.arguments.get(*index))
+                .flat_map(|argument| self.labels(argument))
+                .collect(),
+            None if is_sanitizer(&call.name) => Labels::new(),
+            None => call
+                .arguments
+                .iter()
+                .flat_map(|argument| self.labels(argument))
+                .collect(),
+        }
+    }
+
+    /// Records the sinks a call reaches with tainted arguments.
+    fn record_call(&mut self, call: &Call, span: Span) {
+        let file_id = self.file_id;
+        let mut sinks = vec![];
+        if call.callee.is_none() && matches!(call.name.as_str(), "print" | "println") {
+            for argument in &call.arguments {
+                sinks.extend(self.labels(argument).into_iter().map(|parameter| SinkFlow {
+                    parameter,
+                    sink: Sink::Print,
+                    function: self.function.to_string(),
+                    span,
+                    file_id,
+                }));
+            }
+        }
+        if let Some(callee) = &call.callee {
+            if !self.is_unconstrained && self.unconstrained.contains(callee.as_str()) {
+                for argument in &call.arguments {
+                    sinks.extend(self.labels(argument).into_iter().map(|parameter| SinkFlow {
+                        parameter,
+                        sink: Sink::Unconstrained {
+                            function: callee.clone(),
+                        },
+                        function: self.function.to_string(),
+                        span,
+                        file_id,
+                    }));
+                }
+            }
+            for flow in self
+                .summaries
+                .get(callee)
+                .map_or(&[][..], |summary| summary.sinks.as_slice())
+            {
+                let Some(argument) = call.arguments.get(flow.parameter) else {
+                    continue;
+                };
+                sinks.extend(self.labels(argument).into_iter().map(|parameter| SinkFlow {
+                    parameter,
+                    ..flow.clone()
+                }));
+            }
+        }
+
+        for sink in sinks {
+            if !self.sinks.contains(&sink) {
+                self.sinks.push(sink);
+            }
+        }
+    }
+
+    /// Taints a variable with more parameters.
+    fn taint(&mut self, name: String, labels: Labels) {
+        self.variables.entry(name).or_default().extend(labels);
+    }
+}
+
+impl Visitor for FlowCollector<'_> {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        let_statement.expression.accept(self);
+        let labels = self.labels(&let_statement.expression);
+        let mut idents = vec![];
+        pattern_idents(&let_statement.pattern, false, &mut idents);
+        for (ident, _) in idents {
+            // A new binding replaces the taint of the variable it shadows
+            self.variables.insert(ident.to_string(), labels.clone());
+        }
+        false
+    }
+
+    fn visit_assign_statement(&mut self, assign: &AssignStatement) -> bool {
+        assign.expression.accept(self);
+        let labels = self.labels(&assign.expression);
+        // Assigning an element or a field taints the whole variable
+        let mut lvalue = &assign.lvalue;
+        loop {
+            match lvalue {
+                LValue::Ident(ident) => {
+                    self.taint(ident.to_string(), labels);
+                    break;
+                }
+                LValue::MemberAccess { object, .. } => lvalue = object,
+                LValue::Index { array, .. } => lvalue = array,
+                LValue::Dereference(inner, _) => lvalue = inner,
+                _ => break,
+            }
+        }
+        false
+    }
+
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        let labels = match &for_loop.range {
+            ForRange::Range(bounds) => {
+                let mut labels = self.labels(&bounds.start);
+                labels.extend(self.labels(&bounds.end));
+                labels
+            }
+            ForRange::Array(array) => self.labels(array),
+        };
+        self.taint(for_loop.identifier.to_string(), labels);
+        true
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression, span: Span) -> bool {
+        if let Some(call) = self.call(call) {
+            self.record_call(&call, span);
+        }
+        true
+    }
+
+    fn visit_method_call_expression(
+        &mut self,
+        method_call: &MethodCallExpression,
+        span: Span,
+    ) -> bool {
+        let call = self.method_call(method_call);
+        self.record_call(&call, span);
+        true
+    }
+
+    fn visit_lambda(&mut self, _: &Lambda, _: Span) -> bool {
+        false
+    }
+}
+
+/// Collects the parameters an expression depends on.
+struct LabelCollector<'a, 'b> {
+    flow: &'a FlowCollector<'b>,
+    labels: Labels,
+}
+
+impl Visitor for LabelCollector<'_, '_> {
+    fn visit_variable(&mut self, path: &Path, _: Span) -> bool {
+        if let [segment] = path.segments.as_slice() {
+            self.labels
+                .extend(self.flow.variable(&segment.ident.to_string()));
+        }
+        true
+    }
+
+    fn visit_literal_fmt_str(&mut self, fragments: &[FmtStrFragment], _length: u32, _: Span) {
+        for fragment in fragments {
+            if let FmtStrFragment::Interpolation(value, _) = fragment {
+                self.labels.extend(self.flow.variable(value));
+            }
+        }
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression, _: Span) -> bool {
+        match self.flow.call(call) {
+            Some(call) => self.labels.extend(self.flow.call_labels(&call)),
+            // Calls through expressions, such as lambdas stored in arrays
+            None => return true,
+        }
+        false
+    }
+
+    fn visit_method_call_expression(
+        &mut self,
+        method_call: &MethodCallExpression,
+        _: Span,
+    ) -> bool {
+        let call = self.flow.method_call(method_call);
+        self.labels.extend(self.flow.call_labels(&call));
+        false
+    }
+
+    fn visit_lambda(&mut self, _: &Lambda, _: Span) -> bool {
+        false
+    }
+}
+
+/// Returns whether a function hashes or commits to its inputs, judging by its name.
+fn is_sanitizer(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SANITIZERS.iter().any(|fragment| name.contains(fragment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+
+    fn leaks(source: &str) -> Vec<(String, Sink, String)> {
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        PrivateTaint::build(&root, &CallGraph::build(&root))
+            .leaks()
+            .iter()
+            .map(|leak| (leak.input.clone(), leak.sink.clone(), leak.function.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_private_inputs_are_followed_into_sinks() {
+        let source = r#"
+            unconstrained fn hint(x: Field) -> Field {
+                x * 2
+            }
+
+            fn double(x: Field) -> Field {
+                x + x
+            }
+
+            fn log(value: Field) {
+                println(f"value: {value}");
+            }
+
+            fn main(secret: Field, salt: Field, limit: pub Field) -> pub Field {
+                let doubled = double(secret);
+                log(doubled);
+                // Safety: checked below
+                let hinted = unsafe { hint(salt) };
+                assert(hinted == salt * 2);
+                assert(secret != limit);
+                let mut total = 0;
+                for i in 0..3 {
+                    total += salt * i;
+                }
+                println(limit);
+                std::hash::pedersen_hash([secret, total])
+            }
+        "#;
+
+        assert_eq!(
+            leaks(source),
+            [
+                ("secret".to_string(), Sink::Print, "log".to_string()),
+                (
+                    "salt".to_string(),
+                    Sink::Unconstrained {
+                        function: "hint".to_string()
+                    },
+                    "main".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_returned_private_inputs_are_public_outputs() {
+        let source = r#"
+            struct Pair {
+                a: Field,
+                b: Field,
+            }
+
+            impl Pair {
+                fn sum(self) -> Field {
+                    self.a + self.b
+                }
+            }
+
+            fn main(a: Field, b: pub Field) -> pub Field {
+                let mut pair = Pair { a: 0, b };
+                pair.a = a;
+                pair.sum()
+            }
+        "#;
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let taint = PrivateTaint::build(&root, &CallGraph::build(&root));
+
+        assert_eq!(
+            taint.function("Pair::sum").map(|summary| &summary.returned),
+            Some(&Labels::from([0]))
+        );
+        assert_eq!(
+            leaks(source),
+            [("a".to_string(), Sink::PublicOutput, "main".to_string())]
+        );
+    }
+}
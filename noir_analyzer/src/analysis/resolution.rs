@@ -0,0 +1,196 @@
+//! # Name Resolution
+//!
+//! Resolves the paths of called functions to fully-qualified symbols: the path of the module
+//! defining the function followed by its name, rooted at `crate` like the module graph (e.g.
+//! `crate::utils::hash`). Paths are resolved from the module they are written in, following
+//! `crate::` and `super::` prefixes, the imports of each module, aliased ones included
+//! (`use utils::hash as digest`), and re-exports (`pub use`).
+//!
+//! Only the free functions of the parsed program are symbols: paths into dependencies or the
+//! standard library, methods and associated functions resolve to nothing.
+
+use crate::analysis::module_graph::ROOT_MODULE;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{Path, PathKind, UseTree, UseTreeKind};
+use noirc_frontend::parser::ItemKind;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Maximum number of imports followed to resolve a path, which bounds import cycles.
+const MAX_IMPORT_HOPS: usize = 16;
+
+/// The functions of a program and the names imported into each of its modules.
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    /// Fully-qualified paths of the free functions.
+    functions: BTreeSet<String>,
+    /// Absolute paths of the imported names, by module path and name in scope.
+    imports: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl SymbolTable {
+    /// Builds the symbol table of the given parsed module.
+    pub fn build(parsed_module: &ParsedModule) -> Self {
+        let mut table = Self::default();
+        table.collect(parsed_module, &[ROOT_MODULE.to_string()]);
+        table
+    }
+
+    /// Returns the fully-qualified paths of the functions of the program, in sorted order.
+    pub fn functions(&self) -> impl Iterator<Item = &String> {
+        self.functions.iter()
+    }
+
+    /// Resolves a path written in the given module to the fully-qualified path of the
+    /// function it refers to, when it is a function of the program.
+    pub fn resolve_function(&self, module_path: &[String], path: &Path) -> Option<String> {
+        let mut segments = match path.kind {
+            PathKind::Crate => vec![ROOT_MODULE.to_string()],
+            PathKind::Super => module_path[..module_path.len().saturating_sub(1)].to_vec(),
+            PathKind::Plain => module_path.to_vec(),
+            PathKind::Dep => return None,
+        };
+        segments.extend(
+            path.segments
+                .iter()
+                .map(|segment| segment.ident.to_string()),
+        );
+
+        for _ in 0..MAX_IMPORT_HOPS {
+            let absolute = segments.join("::");
+            if self.functions.contains(&absolute) {
+                return Some(absolute);
+            }
+
+            // Replace the first imported name along the path by the path it refers to
+            let (length, target) = (1..segments.len()).find_map(|length| {
+                let target = self
+                    .imports
+                    .get(&segments[..length].join("::"))?
+                    .get(&segments[length])?;
+                Some((length, target))
+            })?;
+            let mut resolved: Vec<String> = target.split("::").map(str::to_string).collect();
+            resolved.extend_from_slice(&segments[length + 1..]);
+            segments = resolved;
+        }
+        None
+    }
+
+    /// Registers the functions and imports of a module and its submodules.
+    fn collect(&mut self, parsed_module: &ParsedModule, module_path: &[String]) {
+        let module = module_path.join("::");
+        for item in &parsed_module.items {
+            match &item.kind {
+                ItemKind::Function(function) => {
+                    self.functions
+                        .insert(format!("{module}::{}", function.name()));
+                }
+                ItemKind::Submodules(submodule) => {
+                    let mut submodule_path = module_path.to_vec();
+                    submodule_path.push(submodule.name.to_string());
+                    self.collect(&submodule.contents, &submodule_path);
+                }
+                ItemKind::Import(use_tree, _) => {
+                    let imports = self.imports.entry(module.clone()).or_default();
+                    for (path, name) in flatten_use_tree(use_tree, module_path, Vec::new()) {
+                        imports.insert(name, path);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Expands a use tree into `(absolute path, name in scope)` pairs, one per imported name.
+///
+/// Paths into dependencies are rooted at `dep`.
+pub(crate) fn flatten_use_tree(
+    use_tree: &UseTree,
+    current_module: &[String],
+    mut prefix: Vec<String>,
+) -> Vec<(String, String)> {
+    if prefix.is_empty() {
+        prefix = match use_tree.prefix.kind {
+            PathKind::Crate => vec![ROOT_MODULE.to_string()],
+            PathKind::Super => current_module[..current_module.len().saturating_sub(1)].to_vec(),
+            PathKind::Plain => current_module.to_vec(),
+            PathKind::Dep => vec!["dep".to_string()],
+        };
+    }
+    prefix.extend(
+        use_tree
+            .prefix
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string()),
+    );
+
+    match &use_tree.kind {
+        UseTreeKind::Path(name, alias) => {
+            let name = name.to_string();
+            let scope_name = alias
+                .as_ref()
+                .map_or_else(|| name.clone(), ToString::to_string);
+            prefix.push(name);
+            vec![(prefix.join("::"), scope_name)]
+        }
+        UseTreeKind::List(trees) => trees
+            .iter()
+            .flat_map(|tree| flatten_use_tree(tree, current_module, prefix.clone()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_calls_resolve_through_imports_and_module_paths() {
+        let source_code = r#"
+            mod utils {
+                pub use crate::math::double;
+                pub fn hash(x: Field) -> Field { x }
+                pub fn square(x: Field) -> Field { x * x }
+                pub mod inner {
+                    pub fn scale(x: Field) -> Field { super::square(x) }
+                }
+            }
+            mod math {
+                pub fn double(x: Field) -> Field { x * 2 }
+            }
+            mod other {
+                pub fn hash(x: Field) -> Field { x }
+            }
+            use utils::hash as digest;
+
+            fn main(x: Field) -> pub Field {
+                let hashed = std::hash::pedersen_hash([x]);
+                digest(x) + utils::double(x) + crate::utils::inner::scale(hashed) + x.square()
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let table = SymbolTable::build(&root);
+        assert_eq!(table.functions().count(), 6);
+
+        let mut analyzer = Analyzer::new(&[]);
+        analyzer.analyze(&root).expect("Should have passed");
+        let context = analyzer.context.expect("Analyzer should have the context");
+
+        // Dependencies, the standard library and methods are not symbols of the program
+        let called: Vec<&String> = context.resolved_calls.keys().collect();
+        assert_eq!(
+            called,
+            [
+                "crate::math::double",
+                "crate::utils::hash",
+                "crate::utils::inner::scale",
+                "crate::utils::square",
+            ]
+        );
+    }
+}
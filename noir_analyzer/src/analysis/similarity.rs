@@ -0,0 +1,146 @@
+//! # Code Similarity
+//!
+//! Compares pieces of code at the token level, to find code duplicated with or without
+//! edits. Code is compared as rendered from the AST, so that formatting and comments do
+//! not count, and either token by token, or by shape, where every identifier and literal is
+//! replaced by a placeholder so that renamed copies still match.
+//!
+//! Similarity is the ratio of tokens in the longest common subsequence of two sequences to
+//! their mean length: 1 for identical sequences, 0 for sequences sharing no token.
+
+/// Placeholder of identifiers in the shape of a token sequence.
+const IDENTIFIER: &str = "$ident";
+/// Placeholder of literals in the shape of a token sequence.
+const LITERAL: &str = "$literal";
+
+/// Keywords kept as is in the shape of a token sequence, as they carry its structure.
+const KEYWORDS: &[&str] = &[
+    "as",
+    "assert",
+    "assert_eq",
+    "else",
+    "false",
+    "for",
+    "if",
+    "in",
+    "let",
+    "match",
+    "mut",
+    "return",
+    "true",
+    "unsafe",
+];
+
+/// Operators made of two characters, lexed as a single token.
+const OPERATORS: &[&str] = &[
+    "!=", "&&", "->", "::", "<<", "<=", "==", "=>", ">=", ">>", "..", "||",
+];
+
+/// Splits code into identifiers, numbers, string literals and operators.
+pub fn tokens(code: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut chars = code.char_indices().peekable();
+    while let Some((start, character)) = chars.next() {
+        if character.is_whitespace() {
+            continue;
+        }
+        let mut end = start + character.len_utf8();
+        if character.is_alphanumeric() || character == '_' {
+            while let Some(&(index, next)) = chars.peek() {
+                if !next.is_alphanumeric() && next != '_' {
+                    break;
+                }
+                end = index + next.len_utf8();
+                chars.next();
+            }
+        } else if character == '"' {
+            while let Some((index, next)) = chars.next() {
+                end = index + next.len_utf8();
+                if next == '\\' {
+                    chars.next();
+                } else if next == '"' {
+                    break;
+                }
+            }
+        } else if let Some(&(index, next)) = chars.peek()
+            && OPERATORS.contains(&&code[start..index + next.len_utf8()])
+        {
+            end = index + next.len_utf8();
+            chars.next();
+        }
+        tokens.push(code[start..end].to_string());
+    }
+    tokens
+}
+
+/// Returns the shape of a token sequence: its tokens, with identifiers and literals
+/// replaced by placeholders.
+pub fn shape(tokens: &[String]) -> Vec<&str> {
+    tokens
+        .iter()
+        .map(|token| {
+            if KEYWORDS.contains(&token.as_str()) {
+                token.as_str()
+            } else if token
+                .starts_with(|character: char| character.is_ascii_digit() || character == '"')
+            {
+                LITERAL
+            } else if token
+                .starts_with(|character: char| character.is_alphanumeric() || character == '_')
+            {
+                IDENTIFIER
+            } else {
+                token.as_str()
+            }
+        })
+        .collect()
+}
+
+/// Returns the similarity of two token sequences, between 0 and 1.
+pub fn similarity<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    2.0 * longest_common_subsequence(a, b) as f64 / (a.len() + b.len()) as f64
+}
+
+/// Length of the longest common subsequence of two sequences.
+fn longest_common_subsequence<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut previous = vec![0; b.len() + 1];
+    for a_item in a {
+        let mut current = vec![0; b.len() + 1];
+        for (j, b_item) in b.iter().enumerate() {
+            current[j + 1] = if a_item == b_item {
+                previous[j] + 1
+            } else {
+                previous[j + 1].max(current[j])
+            };
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_split_identifiers_literals_and_operators() {
+        assert_eq!(
+            tokens("a::b(y) >= 10;"),
+            ["a", "::", "b", "(", "y", ")", ">=", "10", ";"]
+        );
+        assert_eq!(tokens(r#"s == "c \" d""#), ["s", "==", r#""c \" d""#]);
+    }
+
+    #[test]
+    fn test_renamed_copies_have_the_same_shape() {
+        let original = tokens("let sum = a + b * 2; sum");
+        let renamed = tokens("let total = x + y * 3; total");
+        assert_eq!(shape(&original), shape(&renamed));
+        assert_eq!(similarity(&shape(&original), &shape(&renamed)), 1.0);
+        assert_eq!(similarity(&original, &renamed), 0.5);
+        assert_eq!(similarity::<String>(&[], &[]), 1.0);
+    }
+}
@@ -0,0 +1,139 @@
+//! # Taint
+//!
+//! Tracks which local variables of a function hold unconstrained values, and which are
+//! referenced by its constraints. A value is unconstrained when it comes from an
+//! `unsafe { }` block or a call to an unconstrained function, or when it is bound to such a
+//! value. A tainted variable that no constraint references can be set freely by the prover.
+
+use crate::ast::ast_context::AstContext;
+use crate::frontend::Noir;
+use noirc_frontend::ast::{
+    ConstrainExpression, Expression, ExpressionKind, FunctionDefinition, LetStatement, Path,
+    Pattern, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::BTreeSet;
+
+/// The variables of a function bound to unconstrained values, and those its constraints
+/// reference.
+pub struct Taint<'a, 'ast> {
+    context: &'a AstContext<'ast>,
+    /// Variables bound to unconstrained values at the end of the function.
+    pub tainted: BTreeSet<String>,
+    /// Variables referenced by an assertion of the function.
+    pub constrained: BTreeSet<String>,
+}
+
+/// Owned summary of the taint of a function.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaintSummary {
+    /// Variables bound to unconstrained values, by name.
+    pub unconstrained: Vec<String>,
+    /// The unconstrained variables that no constraint references.
+    pub unchecked: Vec<String>,
+}
+
+impl<'a, 'ast> Taint<'a, 'ast> {
+    /// Computes the taint of the body of a function.
+    pub fn of_function(context: &'a AstContext<'ast>, function: &FunctionDefinition) -> Self {
+        let mut taint = Self {
+            context,
+            tainted: BTreeSet::new(),
+            constrained: BTreeSet::new(),
+        };
+        for statement in &function.body.statements {
+            statement.accept(&mut taint);
+        }
+        taint
+    }
+
+    /// Returns whether the expression directly evaluates to an unconstrained value.
+    pub fn is_unconstrained(&self, expression: &Expression) -> bool {
+        if Noir::unsafe_block(expression).is_some() {
+            return true;
+        }
+
+        match &expression.kind {
+            ExpressionKind::Variable(path) => {
+                variable_name(path).is_some_and(|variable| self.tainted.contains(&variable))
+            }
+            ExpressionKind::Parenthesized(expression) => self.is_unconstrained(expression),
+            ExpressionKind::Call(call) => match &call.func.kind {
+                ExpressionKind::Variable(path) => path
+                    .segments
+                    .last()
+                    .and_then(|segment| {
+                        self.context
+                            .function_definitions
+                            .get(&segment.ident.to_string())
+                    })
+                    .is_some_and(|function| function.is_unconstrained),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns whether the variable holds an unconstrained value that no constraint
+    /// references.
+    pub fn is_unchecked(&self, variable: &str) -> bool {
+        self.tainted.contains(variable) && !self.constrained.contains(variable)
+    }
+
+    /// Returns the tainted and unchecked variables, in alphabetical order.
+    pub fn summary(&self) -> TaintSummary {
+        TaintSummary {
+            unconstrained: self.tainted.iter().cloned().collect(),
+            unchecked: self
+                .tainted
+                .iter()
+                .filter(|variable| self.is_unchecked(variable))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl Visitor for Taint<'_, '_> {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        if let Pattern::Identifier(ident) = &let_statement.pattern {
+            if self.is_unconstrained(&let_statement.expression) {
+                self.tainted.insert(ident.to_string());
+            } else {
+                // A non-tainted binding shadows any previous one
+                self.tainted.remove(&ident.to_string());
+            }
+        }
+        true
+    }
+
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        let mut references = ReferenceCollector::default();
+        for argument in &constrain.arguments {
+            argument.accept(&mut references);
+        }
+        self.constrained.extend(references.variables);
+        true
+    }
+}
+
+/// Collects the local variables referenced by an expression.
+#[derive(Default)]
+pub(crate) struct ReferenceCollector {
+    pub(crate) variables: Vec<String>,
+}
+
+impl Visitor for ReferenceCollector {
+    fn visit_variable(&mut self, path: &Path, _: Span) -> bool {
+        self.variables.extend(variable_name(path));
+        true
+    }
+}
+
+/// Returns the name of a local variable, i.e. a single-segment path.
+pub fn variable_name(path: &Path) -> Option<String> {
+    match path.segments.as_slice() {
+        [segment] => Some(segment.ident.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,100 @@
+//! # Variable Usage
+//!
+//! Indexes how the local variables of a function are read: at which indices, for arrays
+//! read through index expressions, and whether they are also used as whole values, e.g.
+//! passed to a function, returned or compared as a whole. Lints use it to tell arrays only
+//! ever inspected element by element from those handled as values, such as hash outputs.
+
+use crate::analysis::taint::variable_name;
+use noirc_frontend::ast::{ExpressionKind, FunctionDefinition, IndexExpression, Path, Visitor};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How the local variables of a function are read.
+#[derive(Debug, Default)]
+pub struct UsageIndex {
+    /// Indices each variable is read at, as written, by variable name.
+    pub indices: BTreeMap<String, BTreeSet<String>>,
+    /// Variables read other than through an index expression.
+    pub whole: BTreeSet<String>,
+}
+
+impl UsageIndex {
+    /// Indexes the reads of the body of a function.
+    pub fn of_function(function: &FunctionDefinition) -> Self {
+        let mut usage = Self::default();
+        for statement in &function.body.statements {
+            statement.accept(&mut usage);
+        }
+        usage
+    }
+
+    /// Returns the indices a variable is read at.
+    pub fn indices(&self, variable: &str) -> Option<&BTreeSet<String>> {
+        self.indices.get(variable)
+    }
+
+    /// Returns whether a variable is read as a whole value.
+    pub fn is_used_whole(&self, variable: &str) -> bool {
+        self.whole.contains(variable)
+    }
+}
+
+impl Visitor for UsageIndex {
+    fn visit_index_expression(&mut self, index: &IndexExpression, _: Span) -> bool {
+        let ExpressionKind::Variable(path) = &index.collection.kind else {
+            return true;
+        };
+        let Some(variable) = variable_name(path) else {
+            return true;
+        };
+        self.indices
+            .entry(variable)
+            .or_default()
+            .insert(index.index.to_string());
+        index.index.accept(self);
+        false
+    }
+
+    fn visit_variable(&mut self, path: &Path, _: Span) -> bool {
+        self.whole.extend(variable_name(path));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+    use noirc_frontend::parser::ItemKind;
+
+    #[test]
+    fn test_usage_index_separates_indexed_and_whole_reads() {
+        let source_code = r#"
+            fn main(a: [Field; 2], b: [Field; 2], i: u32) {
+                assert(a[0] == b[i]);
+                let c = a;
+                assert(c[1] == 0);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let ItemKind::Function(function) = &root.items[0].kind else {
+            panic!("Expected a function");
+        };
+        let usage = UsageIndex::of_function(&function.def);
+
+        assert_eq!(
+            usage.indices("a").unwrap().iter().collect::<Vec<_>>(),
+            ["0"]
+        );
+        assert_eq!(
+            usage.indices("b").unwrap().iter().collect::<Vec<_>>(),
+            ["i"]
+        );
+        assert!(usage.is_used_whole("a"));
+        assert!(usage.is_used_whole("i"));
+        assert!(!usage.is_used_whole("b"));
+        assert!(!usage.is_used_whole("c"));
+    }
+}
@@ -8,6 +8,16 @@
 //! - Implements the `Visitor` trait to traverse AST nodes.
 //! - Collects lints related to expressions, functions, and variable declarations.
 //! - Stores detected lints for reporting after traversal.
+//! - Runs lint rules concurrently over the shared, read-only `AstContext` when configured
+//!   with several threads.
+//! - Offers a low-memory mode analyzing one function at a time, for huge generated files.
+//! - Records the nodes it skips in a `Coverage` report instead of failing on them.
+//! - Maps reported lints through a `SeverityPolicy`, for embedders remapping severities.
+//! - Notifies an observer of each rule it starts, e.g. to display progress.
+//! - Reports a rule that panics as an internal error lint, and runs the other rules.
+//! - Reports on a single function of the last analyzed program, e.g. for editor hovers.
+//! - Runs typed lint rules on the elaborated HIR of the last analyzed program.
+//! - Computes only the whole-program analyses required by its rules, once per run.
 //!
 //! ## Future Improvements
 //! - Expand linting rules for additional AST elements.
@@ -15,19 +25,31 @@
 //! - Support for configurable lint levels and suppression attributes.
 //!
 
+use crate::analysis::call_graph::collect_functions;
+use crate::analysis::dependencies::DependencyGraph;
+use crate::analysis::function_report::{self, FunctionReport};
+use crate::analysis::module_graph::ROOT_MODULE;
+use crate::analysis::passes::Analysis;
 use crate::ast::analyzer::AnalyzerError::GenericError;
-use crate::ast::ast_context::AstContext;
+use crate::ast::ast_context::{AstContext, FunctionCall, UnsafeBlock};
+use crate::ast::coverage::Coverage;
+use crate::ast::scopes::{BindingKind, ScopeKind, ScopeTree};
+use crate::diagnostics::internal_error::rule_panicked;
 use crate::diagnostics::lint::Lint;
+use crate::diagnostics::severity_policy::SeverityPolicy;
+use crate::frontend::Noir;
+use crate::hir::TypedContext;
 use crate::lints::lint_rule::LintRule;
+use crate::lints::lint_rule::TypedLintRule;
 use noirc_frontend::ast::{
     ArrayLiteral, AsTraitPath, AssignStatement, AttributeTarget, BlockExpression, CallExpression,
     CastExpression, ConstrainExpression, ConstructorExpression, Expression, ExpressionKind,
-    ForLoopStatement, ForRange, FunctionReturnType, GenericTypeArgs, Ident, IfExpression,
-    IndexExpression, InfixExpression, IntegerBitSize, ItemVisibility, LValue, Lambda, LetStatement,
-    Literal, MatchExpression, MemberAccessExpression, MethodCallExpression, ModuleDeclaration,
-    NoirEnumeration, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Path,
-    PathKind, Pattern, PrefixExpression, Statement, StatementKind, TraitBound, TraitImplItem,
-    TraitImplItemKind, TraitItem, TypeImpl, TypePath, UnresolvedGenerics,
+    ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, GenericTypeArgs, Ident,
+    IfExpression, IndexExpression, InfixExpression, IntegerBitSize, ItemVisibility, LValue, Lambda,
+    LetStatement, Literal, MatchExpression, MemberAccessExpression, MethodCallExpression,
+    ModuleDeclaration, NoirEnumeration, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl,
+    NoirTypeAlias, Path, PathKind, Pattern, PrefixExpression, Statement, StatementKind, TraitBound,
+    TraitImplItem, TraitImplItemKind, TraitItem, TypeImpl, TypePath, UnresolvedGenerics,
     UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeExpression, UnsafeExpression, UseTree,
     Visitor,
 };
@@ -42,7 +64,10 @@ use noirc_frontend::signed_field::SignedField;
 use noirc_frontend::token::{FmtStrFragment, MetaAttribute, SecondaryAttribute, Tokens};
 use noirc_frontend::{ParsedModule, QuotedType};
 use std::ops::Add;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -55,11 +80,27 @@ pub enum AnalyzerError {
     FileReadError(PathBuf, String),
 }
 
+/// Callback invoked with the name of each lint rule before it runs. Rules may run on
+/// several threads at once.
+pub type RuleObserver = dyn Fn(&'static str) + Sync;
+
 /// Implements an AST-based analyzer using the Noir visitor pattern.
 pub struct Analyzer<'ast> {
     pub(crate) context: Option<AstContext<'ast>>,
     pub(crate) lint_rules: Vec<Box<dyn LintRule>>,
+    /// Lints reported by the last analysis of the whole program.
+    lints: Vec<Lint>,
+    source: Option<&'ast str>,
+    dependencies: Option<&'ast DependencyGraph>,
+    /// Analyses computed in addition to those required by the lint rules.
+    analyses: Vec<Analysis>,
+    threads: usize,
     stack: Vec<StackItem>,
+    /// Path of the module being traversed, rooted at `crate`.
+    module_path: Vec<String>,
+    coverage: Coverage,
+    severity_policy: SeverityPolicy,
+    rule_observer: Option<&'ast RuleObserver>,
 }
 
 impl<'ast> Analyzer<'ast> {
@@ -70,35 +111,284 @@ impl<'ast> Analyzer<'ast> {
                 .iter()
                 .map(|lint_rule| lint_rule.boxed_clone())
                 .collect(),
+            lints: Vec::new(),
+            source: None,
+            dependencies: None,
+            analyses: Vec::new(),
+            threads: 1,
             stack: Vec::new(),
+            module_path: vec![ROOT_MODULE.to_string()],
+            coverage: Coverage::default(),
+            severity_policy: SeverityPolicy::default(),
+            rule_observer: None,
         }
     }
 
+    /// Runs the lint rules on up to `threads` threads. Lints are reported in the same order
+    /// as with a single thread.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Maps the reported lints through a severity policy before returning them.
+    pub fn with_severity_policy(mut self, severity_policy: SeverityPolicy) -> Self {
+        self.severity_policy = severity_policy;
+        self
+    }
+
+    /// Invokes `observer` with the name of each lint rule before it runs.
+    pub fn with_rule_observer(mut self, observer: &'ast RuleObserver) -> Self {
+        self.rule_observer = Some(observer);
+        self
+    }
+
+    /// Gives lint rules access to the source of the analyzed program.
+    pub fn with_source(mut self, source: &'ast str) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Gives lint rules access to the dependencies of the package of the analyzed program.
+    pub fn with_dependencies(mut self, dependencies: &'ast DependencyGraph) -> Self {
+        self.dependencies = Some(dependencies);
+        self
+    }
+
+    /// Computes the given analyses even when no lint rule requires them, e.g. the call graph
+    /// for the callers and callees of `analyze_function`.
+    pub fn with_analyses(mut self, analyses: &[Analysis]) -> Self {
+        self.analyses.extend_from_slice(analyses);
+        self
+    }
+
     pub fn analyze(
         &mut self,
         parsed_module: &'ast ParsedModule,
     ) -> Result<Vec<Lint>, AnalyzerError> {
-        self.context = Some(AstContext::new(parsed_module));
+        let analyses: Vec<Analysis> = self
+            .lint_rules
+            .iter()
+            .flat_map(|lint_rule| lint_rule.required_analyses())
+            .chain(&self.analyses)
+            .copied()
+            .collect();
+        let mut context = AstContext::with_analyses(parsed_module, &analyses);
+        context.source = self.source;
+        context.dependencies = self.dependencies;
+        self.context = Some(context);
+        self.module_path = vec![ROOT_MODULE.to_string()];
 
         if !self.visit_parsed_module(parsed_module) {
             return Err(GenericError("AST traversal failed".to_string()));
         }
 
-        let mut lints = vec![];
-
         match &self.context {
             None => panic!("Context must be initialized!"),
             Some(context) => {
-                for lint_rule in &self.lint_rules {
-                    lints.extend(lint_rule.lint(context));
+                let lints = self.severity_policy.apply(run_lint_rules(
+                    &self.lint_rules,
+                    context,
+                    self.threads,
+                    self.rule_observer,
+                ));
+                self.lints = lints.clone();
+                Ok(lints)
+            }
+        }
+    }
+
+    /// Reports on a function of the program last given to `analyze`: its lints, estimated
+    /// cost, callers and callees, and taint. Nothing is analyzed again, so editors can call
+    /// it on every hover; callers and callees are only known when the call graph was
+    /// computed, see `with_analyses`. Returns `None` when no program was analyzed as a
+    /// whole, or when it defines no such function.
+    pub fn analyze_function(&self, name: &str) -> Option<FunctionReport> {
+        let context = self.context.as_ref()?;
+        function_report::analyze_function(context, name, &self.lints)
+    }
+
+    /// Runs the typed lint rules on the elaborated HIR of the program last given to
+    /// `analyze`, as a second phase of its analysis. Their lints are added to those of the
+    /// program, e.g. for `analyze_function`, and mapped through the severity policy. Returns
+    /// no lints when no program was analyzed as a whole.
+    pub fn analyze_types(
+        &mut self,
+        types: &TypedContext,
+        lint_rules: &[Box<dyn TypedLintRule>],
+    ) -> Vec<Lint> {
+        let Some(context) = &self.context else {
+            return vec![];
+        };
+        let lints: Vec<Lint> = lint_rules
+            .iter()
+            .flat_map(|lint_rule| {
+                if let Some(observer) = self.rule_observer {
+                    observer(lint_rule.name());
                 }
+                panic::catch_unwind(AssertUnwindSafe(|| lint_rule.lint(context, types)))
+                    .unwrap_or_else(|payload| {
+                        vec![rule_panicked(lint_rule.name(), payload.as_ref())]
+                    })
+            })
+            .collect();
+        let lints = self.severity_policy.apply(lints);
+        self.lints.extend(lints.iter().cloned());
+        lints
+    }
+
+    /// Analyzes the module one function at a time, bounding the memory used by indexes.
+    ///
+    /// A fresh context is built for every function and dropped as soon as the rules ran on
+    /// it. Rules requiring the whole program are skipped.
+    pub fn analyze_low_memory(
+        &mut self,
+        parsed_module: &'ast ParsedModule,
+    ) -> Result<Vec<Lint>, AnalyzerError> {
+        let lint_rules: Vec<Box<dyn LintRule>> = self
+            .lint_rules
+            .iter()
+            .filter(|lint_rule| !lint_rule.requires_whole_program())
+            .map(|lint_rule| lint_rule.boxed_clone())
+            .collect();
+
+        self.lints.clear();
+        let mut functions = vec![];
+        collect_functions(parsed_module, &mut functions);
+
+        let mut lints = vec![];
+        for node in functions.iter().filter(|node| node.self_type.is_none()) {
+            let mut context = AstContext::for_function(parsed_module);
+            context.source = self.source;
+            context.dependencies = self.dependencies;
+            self.context = Some(context);
+            self.stack.clear();
+            self.stack.push(StackItem::Module);
+
+            if !self.visit_noir_function(node.function, node.span) {
+                return Err(GenericError("AST traversal failed".to_string()));
+            }
+
+            if let Some(context) = self.context.take() {
+                lints.extend(run_lint_rules(
+                    &lint_rules,
+                    &context,
+                    self.threads,
+                    self.rule_observer,
+                ));
             }
         }
+        self.stack.clear();
+
+        Ok(self.severity_policy.apply(lints))
+    }
 
-        Ok(lints)
+    /// Returns the nodes skipped by the analyses run so far.
+    pub fn coverage(&self) -> &Coverage {
+        &self.coverage
+    }
+
+    /// Records a node the analyzer does not descend into, and carries on with the traversal.
+    fn skip(&mut self, kind: &'static str) -> bool {
+        self.coverage.record(kind);
+        true
+    }
+
+    /// Visits the body of a method. Methods are not registered as function definitions,
+    /// since they are called through their type rather than by name.
+    fn visit_method(&mut self, method: &NoirFunction) -> bool {
+        let stack_size = self.stack.len();
+        self.stack.push(StackItem::Function);
+        self.enter_function(&method.def);
+        let result = method
+            .def
+            .body
+            .statements
+            .iter()
+            .all(|statement| self.visit_statement(statement));
+        self.scopes().exit();
+        self.stack.truncate(stack_size);
+        result
+    }
+
+    /// Returns the scope tree populated by the traversal.
+    fn scopes(&mut self) -> &mut ScopeTree {
+        match &mut self.context {
+            None => panic!("Context not initialized!"),
+            Some(context) => &mut context.scopes,
+        }
+    }
+
+    /// Opens the scope of a function, declaring its parameters.
+    fn enter_function(&mut self, function: &FunctionDefinition) {
+        let scopes = self.scopes();
+        scopes.enter(ScopeKind::Function, Noir::function_location(function).0);
+        for parameter in &function.parameters {
+            scopes.declare(&parameter.pattern, BindingKind::Parameter, 0);
+        }
     }
 }
 
+/// Runs every lint rule on the context, using up to `threads` threads.
+///
+/// Rules only read the context, so they can run concurrently. Each thread picks the next
+/// rule that has not run yet and buffers its lints; the buffers are then merged in rule
+/// order, so the result does not depend on scheduling. A rule that panics is reported as an
+/// internal error instead of its lints.
+fn run_lint_rules(
+    lint_rules: &[Box<dyn LintRule>],
+    context: &AstContext,
+    threads: usize,
+    observer: Option<&RuleObserver>,
+) -> Vec<Lint> {
+    let run = |lint_rule: &dyn LintRule| {
+        if let Some(observer) = observer {
+            observer(lint_rule.name());
+        }
+        panic::catch_unwind(AssertUnwindSafe(|| lint_rule.lint(context)))
+            .unwrap_or_else(|payload| vec![rule_panicked(lint_rule.name(), payload.as_ref())])
+    };
+
+    let threads = threads.min(lint_rules.len());
+    if threads <= 1 {
+        return lint_rules
+            .iter()
+            .flat_map(|lint_rule| run(lint_rule.as_ref()))
+            .collect();
+    }
+
+    let next_rule = AtomicUsize::new(0);
+    let mut buffers: Vec<(usize, Vec<Lint>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut buffers = vec![];
+                    loop {
+                        let index = next_rule.fetch_add(1, Ordering::Relaxed);
+                        let Some(lint_rule) = lint_rules.get(index) else {
+                            break;
+                        };
+                        buffers.push((index, run(lint_rule.as_ref())));
+                    }
+                    buffers
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+
+    buffers.sort_by_key(|(index, _)| *index);
+    buffers.into_iter().flat_map(|(_, lints)| lints).collect()
+}
+
 enum StackItem {
     Module,
     Identifiers(Vec<Ident>),
@@ -109,26 +399,64 @@ enum StackItem {
 impl Visitor for Analyzer<'_> {
     fn visit_parsed_module(&mut self, parsed_module: &ParsedModule) -> bool {
         self.stack.push(StackItem::Module);
+        self.scopes()
+            .enter(ScopeKind::Module, Span::from(0..u32::MAX));
         for item in &parsed_module.items {
             if !self.visit_item(item) {
                 return false;
             }
         }
 
+        self.scopes().exit();
         self.stack.clear();
 
         true
     }
 
     fn visit_item(&mut self, item: &Item) -> bool {
+        let (span, _) = Noir::item_location(item);
         match &item.kind {
-            ItemKind::Function(function) => self.visit_noir_function(function, item.location.span),
-            _ => todo!("Not implemented!"),
+            ItemKind::Function(function) => self.visit_noir_function(function, span),
+            ItemKind::Submodules(submodule) => self.visit_parsed_submodule(submodule, span),
+            ItemKind::Import(use_tree, visibility) => {
+                self.visit_import(use_tree, span, *visibility)
+            }
+            ItemKind::ModuleDecl(declaration) => {
+                self.visit_module_declaration(declaration, span);
+                true
+            }
+            ItemKind::Struct(noir_struct) => self.visit_noir_struct(noir_struct, span),
+            ItemKind::Impl(type_impl) => self.visit_type_impl(type_impl, span),
+            ItemKind::TraitImpl(trait_impl) => self.visit_noir_trait_impl(trait_impl, span),
+            ItemKind::Trait(noir_trait) => self.visit_noir_trait(noir_trait, span),
+            ItemKind::Enum(noir_enum) => self.visit_noir_enum(noir_enum, span),
+            // Global values are read by the lint rules from the parsed module, and only
+            // declared in the scope of their module here
+            ItemKind::Global(let_statement, ..) => {
+                self.scopes()
+                    .declare(&let_statement.pattern, BindingKind::Global, 0);
+                true
+            }
+            ItemKind::TypeAlias(type_alias) => self.visit_noir_type_alias(type_alias, span),
+            #[allow(unreachable_patterns)]
+            _ => self.skip("other item"),
         }
     }
 
-    fn visit_parsed_submodule(&mut self, _: &ParsedSubModule, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_parsed_submodule(&mut self, submodule: &ParsedSubModule, span: Span) -> bool {
+        let stack_size = self.stack.len();
+        self.stack.push(StackItem::Module);
+        self.module_path.push(submodule.name.to_string());
+        self.scopes().enter(ScopeKind::Module, span);
+        let result = submodule
+            .contents
+            .items
+            .iter()
+            .all(|item| self.visit_item(item));
+        self.scopes().exit();
+        self.module_path.pop();
+        self.stack.truncate(stack_size);
+        result
     }
 
     fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
@@ -141,43 +469,58 @@ impl Visitor for Analyzer<'_> {
                     .function_definitions
                     .insert(function.name().to_string(), function.def.clone());
 
+                self.enter_function(&function.def);
                 for item in &function.def.body.statements {
                     self.visit_statement(item);
                 }
+                self.scopes().exit();
             }
         }
         self.stack.truncate(stack_size);
         true
     }
 
-    fn visit_noir_trait_impl(&mut self, _: &NoirTraitImpl, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_noir_trait_impl(&mut self, trait_impl: &NoirTraitImpl, _: Span) -> bool {
+        trait_impl
+            .items
+            .iter()
+            .all(|item| self.visit_trait_impl_item(&item.item))
     }
 
-    fn visit_type_impl(&mut self, _: &TypeImpl, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_type_impl(&mut self, type_impl: &TypeImpl, _: Span) -> bool {
+        type_impl
+            .methods
+            .iter()
+            .all(|(method, _)| self.visit_method(&method.item))
     }
 
-    fn visit_trait_impl_item(&mut self, _: &TraitImplItem) -> bool {
-        todo!("Not implemented!")
+    fn visit_trait_impl_item(&mut self, item: &TraitImplItem) -> bool {
+        match &item.kind {
+            TraitImplItemKind::Function(function) => self.visit_method(function),
+            // Associated constants and types hold no function bodies
+            _ => true,
+        }
     }
 
-    fn visit_trait_impl_item_kind(&mut self, _: &TraitImplItemKind, _span: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_trait_impl_item_kind(&mut self, kind: &TraitImplItemKind, _span: Span) -> bool {
+        match kind {
+            TraitImplItemKind::Function(function) => self.visit_method(function),
+            _ => true,
+        }
     }
 
-    fn visit_trait_impl_item_function(&mut self, _: &NoirFunction, _span: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_trait_impl_item_function(&mut self, function: &NoirFunction, _span: Span) -> bool {
+        self.visit_method(function)
     }
 
     fn visit_trait_impl_item_constant(
         &mut self,
         _name: &Ident,
         _typ: &UnresolvedType,
-        _expression: &Expression,
+        expression: &Expression,
         _span: Span,
     ) -> bool {
-        todo!("Not implemented!")
+        self.visit_expression(expression)
     }
 
     fn visit_trait_impl_item_type(
@@ -186,15 +529,17 @@ impl Visitor for Analyzer<'_> {
         _alias: &UnresolvedType,
         _span: Span,
     ) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_noir_trait(&mut self, _: &NoirTrait, _: Span) -> bool {
-        todo!("Not implemented!")
+        // Default method bodies are only analyzed through the impls using them
+        true
     }
 
     fn visit_trait_item(&mut self, _: &TraitItem) -> bool {
-        todo!("Not implemented!")
+        // Like traits, see `visit_noir_trait`
+        true
     }
 
     fn visit_trait_item_function(
@@ -206,7 +551,7 @@ impl Visitor for Analyzer<'_> {
         _where_clause: &[UnresolvedTraitConstraint],
         _body: &Option<BlockExpression>,
     ) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_trait_item_constant(
@@ -215,31 +560,35 @@ impl Visitor for Analyzer<'_> {
         _typ: &UnresolvedType,
         _default_value: &Option<Expression>,
     ) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_trait_item_type(&mut self, _: &Ident) {}
 
     fn visit_use_tree(&mut self, _: &UseTree) -> bool {
-        todo!("Not implemented!")
+        // Like imports, see `visit_import`
+        true
     }
 
     fn visit_use_tree_path(&mut self, _: &UseTree, _ident: &Ident, _alias: &Option<Ident>) {}
 
     fn visit_use_tree_list(&mut self, _: &UseTree, _: &[UseTree]) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_noir_struct(&mut self, _: &NoirStruct, _: Span) -> bool {
-        todo!("Not implemented!")
+        // Struct definitions hold no expressions
+        true
     }
 
     fn visit_noir_enum(&mut self, _: &NoirEnumeration, _: Span) -> bool {
-        todo!("Not implemented!")
+        // Enum definitions hold no expressions
+        true
     }
 
     fn visit_noir_type_alias(&mut self, _: &NoirTypeAlias, _: Span) -> bool {
-        todo!("Not implemented!")
+        // Type aliases hold no expressions
+        true
     }
 
     fn visit_module_declaration(&mut self, _: &ModuleDeclaration, _: Span) {}
@@ -247,54 +596,70 @@ impl Visitor for Analyzer<'_> {
     fn visit_expression(&mut self, expression: &Expression) -> bool {
         let stack_size = self.stack.len();
         self.stack.push(StackItem::FunctionCall);
-        match &expression.kind {
-            ExpressionKind::Call(call) => {
-                if call.is_macro_call {
-                    todo!("Not implemented!")
+        let (span, file_id) = Noir::expression_location(expression);
+        let result = match &expression.kind {
+            ExpressionKind::Call(call) => self.visit_call_expression(call, span),
+            ExpressionKind::Block(block) => self.visit_block_expression(block, Some(span)),
+            ExpressionKind::Unsafe(..) => {
+                let block = Noir::unsafe_block(expression).expect("Unsafe expression");
+                match &mut self.context {
+                    None => panic!("Context not initialized!"),
+                    Some(context) => context.unsafe_blocks.push(UnsafeBlock {
+                        block: block.clone(),
+                        span,
+                        file_id,
+                    }),
                 }
-
-                match &call.func.kind {
-                    ExpressionKind::Variable(variable) => {
-                        self.visit_path(variable);
-                        if let Some(StackItem::Identifiers(identifiers)) = self.stack.last() {
-                            match &mut self.context {
-                                None => panic!("Context not initialized!"),
-                                Some(context) => {
-                                    let entry = context
-                                        .function_calls
-                                        .entry(
-                                            identifiers.iter().fold(String::new(), |acc, def| {
-                                                acc.add(&def.to_string())
-                                            }),
-                                        )
-                                        .or_insert(Vec::new());
-                                    entry.push(call.clone());
-                                }
-                            }
-                        } else {
-                            panic!("Should have identifiers in the call")
-                        }
-                    }
-                    _ => todo!("Not implemented!"),
-                }
-
-                self.stack.truncate(stack_size);
-                true
+                self.visit_block_expression(block, Some(span))
             }
-            _ => todo!("Not implemented!"),
-        }
+            ExpressionKind::Constrain(constrain) => self.visit_constrain_statement(constrain),
+            ExpressionKind::Infix(infix) => self.visit_infix_expression(infix, span),
+            ExpressionKind::Prefix(prefix) => self.visit_prefix_expression(prefix, span),
+            ExpressionKind::Cast(cast) => self.visit_cast_expression(cast, span),
+            ExpressionKind::Constructor(constructor) => {
+                self.visit_constructor_expression(constructor, span)
+            }
+            ExpressionKind::Match(match_expression) => {
+                self.visit_match_expression(match_expression, span)
+            }
+            ExpressionKind::MethodCall(method_call) => {
+                self.visit_method_call_expression(method_call, span)
+            }
+            ExpressionKind::Index(index) => self.visit_index_expression(index, span),
+            ExpressionKind::If(if_expression) => self.visit_if_expression(if_expression, span),
+            ExpressionKind::Comptime(block, _) => self.visit_comptime_expression(block, span),
+            ExpressionKind::Parenthesized(expression) => self.visit_parenthesized(expression, span),
+            ExpressionKind::Literal(literal) => self.visit_literal(literal, span),
+            ExpressionKind::Variable(path) => self.visit_variable(path, span),
+            ExpressionKind::MemberAccess(member_access) => {
+                self.visit_member_access_expression(member_access, span)
+            }
+            ExpressionKind::Tuple(elements) => self.visit_tuple(elements, span),
+            ExpressionKind::Lambda(lambda) => self.visit_lambda(lambda, span),
+            ExpressionKind::Quote(_) => self.skip("quote"),
+            ExpressionKind::Unquote(expression) => self.visit_unquote(expression, span),
+            ExpressionKind::AsTraitPath(path) => self.visit_as_trait_path(path, span),
+            ExpressionKind::TypePath(path) => self.visit_type_path(path, span),
+            _ => self.skip("other expression"),
+        };
+        self.stack.truncate(stack_size);
+        result
     }
 
-    fn visit_literal(&mut self, _: &Literal, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_literal(&mut self, literal: &Literal, span: Span) -> bool {
+        match literal {
+            Literal::Array(array) => self.visit_literal_array(array, span),
+            Literal::Slice(slice) => self.visit_literal_slice(slice, span),
+            _ => true,
+        }
     }
 
-    fn visit_literal_array(&mut self, _: &ArrayLiteral, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_literal_array(&mut self, array: &ArrayLiteral, span: Span) -> bool {
+        self.visit_array_literal(array, span)
     }
 
-    fn visit_literal_slice(&mut self, _: &ArrayLiteral, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_literal_slice(&mut self, slice: &ArrayLiteral, span: Span) -> bool {
+        self.visit_array_literal(slice, span)
     }
 
     fn visit_literal_bool(&mut self, _: bool, _: Span) {}
@@ -309,72 +674,165 @@ impl Visitor for Analyzer<'_> {
 
     fn visit_literal_unit(&mut self, _: Span) {}
 
-    fn visit_block_expression(&mut self, _: &BlockExpression, _: Option<Span>) -> bool {
-        todo!("Not implemented!")
+    fn visit_block_expression(&mut self, block: &BlockExpression, span: Option<Span>) -> bool {
+        if let Some(span) = span {
+            self.scopes().enter(ScopeKind::Block, span);
+        }
+        let result = block
+            .statements
+            .iter()
+            .all(|statement| self.visit_statement(statement));
+        if span.is_some() {
+            self.scopes().exit();
+        }
+        result
     }
 
-    fn visit_prefix_expression(&mut self, _: &PrefixExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_prefix_expression(&mut self, prefix: &PrefixExpression, _: Span) -> bool {
+        self.visit_expression(&prefix.rhs)
     }
 
-    fn visit_index_expression(&mut self, _: &IndexExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_index_expression(&mut self, index: &IndexExpression, _: Span) -> bool {
+        self.visit_expression(&index.collection) && self.visit_expression(&index.index)
     }
 
-    fn visit_call_expression(&mut self, _: &CallExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_call_expression(&mut self, call: &CallExpression, span: Span) -> bool {
+        if call.is_macro_call {
+            return self.skip("macro call");
+        }
+        let function_call = || FunctionCall {
+            call: call.clone(),
+            span,
+            file_id: Noir::expression_location(&call.func).1,
+        };
+
+        match &call.func.kind {
+            ExpressionKind::Variable(variable) => {
+                self.visit_path(variable);
+                match &mut self.context {
+                    None => panic!("Context not initialized!"),
+                    Some(context) => {
+                        if let Some(StackItem::Identifiers(identifiers)) = self.stack.last() {
+                            let entry = context
+                                .function_calls
+                                .entry(
+                                    identifiers
+                                        .iter()
+                                        .fold(String::new(), |acc, def| acc.add(&def.to_string())),
+                                )
+                                .or_insert(Vec::new());
+                            entry.push(function_call());
+                        }
+                        if let Some(callee) = context
+                            .symbols
+                            .resolve_function(&self.module_path, variable)
+                        {
+                            context
+                                .resolved_calls
+                                .entry(callee)
+                                .or_default()
+                                .push(function_call());
+                        }
+                    }
+                }
+            }
+            _ => {
+                self.skip("call of a computed function");
+            }
+        }
+
+        call.arguments
+            .iter()
+            .all(|argument| self.visit_expression(argument))
     }
 
-    fn visit_method_call_expression(&mut self, _: &MethodCallExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_method_call_expression(
+        &mut self,
+        method_call: &MethodCallExpression,
+        _: Span,
+    ) -> bool {
+        self.visit_expression(&method_call.object)
+            && method_call
+                .arguments
+                .iter()
+                .all(|argument| self.visit_expression(argument))
     }
 
-    fn visit_constructor_expression(&mut self, _: &ConstructorExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_constructor_expression(
+        &mut self,
+        constructor: &ConstructorExpression,
+        _: Span,
+    ) -> bool {
+        constructor
+            .fields
+            .iter()
+            .all(|(_, value)| self.visit_expression(value))
     }
 
-    fn visit_member_access_expression(&mut self, _: &MemberAccessExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_member_access_expression(
+        &mut self,
+        member_access: &MemberAccessExpression,
+        _: Span,
+    ) -> bool {
+        self.visit_expression(&member_access.lhs)
     }
 
-    fn visit_cast_expression(&mut self, _: &CastExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_cast_expression(&mut self, cast: &CastExpression, _: Span) -> bool {
+        self.visit_expression(&cast.lhs)
     }
 
-    fn visit_infix_expression(&mut self, _: &InfixExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_infix_expression(&mut self, infix: &InfixExpression, _: Span) -> bool {
+        self.visit_expression(&infix.lhs) && self.visit_expression(&infix.rhs)
     }
 
-    fn visit_if_expression(&mut self, _: &IfExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_if_expression(&mut self, if_expression: &IfExpression, _: Span) -> bool {
+        // Every branch is visited, unless disabled by configured feature flags beforehand
+        self.visit_expression(&if_expression.condition)
+            && self.visit_expression(&if_expression.consequence)
+            && if_expression
+                .alternative
+                .as_ref()
+                .is_none_or(|alternative| self.visit_expression(alternative))
     }
 
-    fn visit_match_expression(&mut self, _: &MatchExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_match_expression(&mut self, match_expression: &MatchExpression, _: Span) -> bool {
+        // Patterns are not visited, so that variant constructors are not taken for calls
+        self.visit_expression(&match_expression.expression)
+            && match_expression
+                .rules
+                .iter()
+                .all(|(_, branch)| self.visit_expression(branch))
     }
 
-    fn visit_tuple(&mut self, _: &[Expression], _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_tuple(&mut self, elements: &[Expression], _: Span) -> bool {
+        elements
+            .iter()
+            .all(|element| self.visit_expression(element))
     }
 
-    fn visit_parenthesized(&mut self, _: &Expression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_parenthesized(&mut self, expression: &Expression, _: Span) -> bool {
+        self.visit_expression(expression)
     }
 
     fn visit_unquote(&mut self, _: &Expression, _: Span) -> bool {
-        todo!("Not implemented!")
+        // Unquoted expressions are only meaningful within the quoted code around them
+        self.skip("quote")
     }
 
-    fn visit_comptime_expression(&mut self, _: &BlockExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_comptime_expression(&mut self, block: &BlockExpression, span: Span) -> bool {
+        self.visit_block_expression(block, Some(span))
     }
 
-    fn visit_unsafe_expression(&mut self, _: &UnsafeExpression, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_unsafe_expression(
+        &mut self,
+        unsafe_expression: &UnsafeExpression,
+        span: Span,
+    ) -> bool {
+        self.visit_block_expression(&unsafe_expression.block, Some(span))
     }
 
     fn visit_variable(&mut self, _: &Path, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_quote(&mut self, _: &Tokens) {}
@@ -385,78 +843,114 @@ impl Visitor for Analyzer<'_> {
 
     fn visit_error_expression(&mut self) {}
 
-    fn visit_lambda(&mut self, _: &Lambda, _: Span) -> bool {
-        todo!("Not implemented!")
-    }
-
-    fn visit_array_literal(&mut self, _: &ArrayLiteral, _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_lambda(&mut self, lambda: &Lambda, span: Span) -> bool {
+        let scopes = self.scopes();
+        scopes.enter(ScopeKind::Lambda, span);
+        for (parameter, _) in &lambda.parameters {
+            scopes.declare(parameter, BindingKind::Parameter, 0);
+        }
+        // Calls in the body of a lambda are attributed to the function defining it
+        let result = self.visit_expression(&lambda.body);
+        self.scopes().exit();
+        result
+    }
+
+    fn visit_array_literal(&mut self, array: &ArrayLiteral, span: Span) -> bool {
+        match array {
+            ArrayLiteral::Standard(elements) => self.visit_array_literal_standard(elements, span),
+            ArrayLiteral::Repeated {
+                repeated_element,
+                length,
+            } => self.visit_array_literal_repeated(repeated_element, length, span),
+        }
     }
 
-    fn visit_array_literal_standard(&mut self, _: &[Expression], _: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_array_literal_standard(&mut self, elements: &[Expression], _: Span) -> bool {
+        elements
+            .iter()
+            .all(|element| self.visit_expression(element))
     }
 
     fn visit_array_literal_repeated(
         &mut self,
-        _repeated_element: &Expression,
-        _length: &Expression,
+        repeated_element: &Expression,
+        length: &Expression,
         _: Span,
     ) -> bool {
-        todo!("Not implemented!")
+        self.visit_expression(repeated_element) && self.visit_expression(length)
     }
 
     fn visit_statement(&mut self, statement: &Statement) -> bool {
         match &statement.kind {
-            StatementKind::Let(_) => todo!("Not implemented!"),
+            StatementKind::Let(let_statement) => self.visit_let_statement(let_statement),
             StatementKind::Expression(expression) => self.visit_expression(expression),
-            StatementKind::Assign(_) => todo!("Not implemented!"),
-            StatementKind::For(_) => todo!("Not implemented!"),
-            StatementKind::Loop(_, _) => todo!("Not implemented!"),
-            StatementKind::While(_) => todo!("Not implemented!"),
-            StatementKind::Break => todo!("Not implemented!"),
-            StatementKind::Continue => todo!("Not implemented!"),
-            StatementKind::Comptime(_) => todo!("Not implemented!"),
-            StatementKind::Semi(_) => todo!("Not implemented!"),
-            StatementKind::Interned(_) => todo!("Not implemented!"),
-            StatementKind::Error => todo!("Not implemented!"),
+            StatementKind::Assign(assign) => self.visit_assign_statement(assign),
+            StatementKind::For(for_loop) => self.visit_for_loop_statement(for_loop),
+            StatementKind::Loop(body, _) => self.visit_loop_statement(body),
+            StatementKind::While(while_statement) => {
+                self.visit_while_statement(&while_statement.condition, &while_statement.body)
+            }
+            StatementKind::Break | StatementKind::Continue => true,
+            StatementKind::Comptime(statement) => self.visit_comptime_statement(statement),
+            StatementKind::Semi(expression) => self.visit_expression(expression),
+            StatementKind::Interned(_) => self.skip("interned statement"),
+            StatementKind::Error => self.skip("statement with parse errors"),
         }
     }
 
     fn visit_import(&mut self, _: &UseTree, _: Span, _visibility: ItemVisibility) -> bool {
-        todo!("Not implemented!")
+        // Imports are collected into the module graph when the context is created
+        true
     }
 
     fn visit_global(&mut self, _: &LetStatement, _: Span) -> bool {
-        todo!("Not implemented!")
+        // Like global items, see `visit_item`
+        true
     }
 
-    fn visit_let_statement(&mut self, _: &LetStatement) -> bool {
-        todo!("Not implemented!")
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        let result = self.visit_expression(&let_statement.expression);
+        // Variables are visible after their initializer, which may read the ones they shadow
+        let (span, _) = Noir::expression_location(&let_statement.expression);
+        self.scopes()
+            .declare(&let_statement.pattern, BindingKind::Local, span.end());
+        result
     }
 
-    fn visit_constrain_statement(&mut self, _: &ConstrainExpression) -> bool {
-        todo!("Not implemented!")
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        constrain
+            .arguments
+            .iter()
+            .all(|argument| self.visit_expression(argument))
     }
 
-    fn visit_assign_statement(&mut self, _: &AssignStatement) -> bool {
-        todo!("Not implemented!")
+    fn visit_assign_statement(&mut self, assign: &AssignStatement) -> bool {
+        self.visit_lvalue(&assign.lvalue) && self.visit_expression(&assign.expression)
     }
 
-    fn visit_for_loop_statement(&mut self, _: &ForLoopStatement) -> bool {
-        todo!("Not implemented!")
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        if !self.visit_for_range(&for_loop.range) {
+            return false;
+        }
+        let (span, _) = Noir::expression_location(&for_loop.block);
+        let scopes = self.scopes();
+        scopes.enter(ScopeKind::ForLoop, span);
+        scopes.declare_ident(&for_loop.identifier, BindingKind::Local, false, 0);
+        let result = self.visit_expression(&for_loop.block);
+        self.scopes().exit();
+        result
     }
 
-    fn visit_loop_statement(&mut self, _: &Expression) -> bool {
-        todo!("Not implemented!")
+    fn visit_loop_statement(&mut self, body: &Expression) -> bool {
+        self.visit_expression(body)
     }
 
-    fn visit_while_statement(&mut self, _condition: &Expression, _body: &Expression) -> bool {
-        todo!("Not implemented!")
+    fn visit_while_statement(&mut self, condition: &Expression, body: &Expression) -> bool {
+        self.visit_expression(condition) && self.visit_expression(body)
     }
 
-    fn visit_comptime_statement(&mut self, _: &Statement) -> bool {
-        todo!("Not implemented!")
+    fn visit_comptime_statement(&mut self, statement: &Statement) -> bool {
+        self.visit_statement(statement)
     }
 
     fn visit_break(&mut self) {}
@@ -467,45 +961,60 @@ impl Visitor for Analyzer<'_> {
 
     fn visit_error_statement(&mut self) {}
 
-    fn visit_lvalue(&mut self, _: &LValue) -> bool {
-        todo!("Not implemented!")
+    fn visit_lvalue(&mut self, lvalue: &LValue) -> bool {
+        // Only indices hold expressions
+        match lvalue {
+            LValue::MemberAccess { object, .. } => self.visit_lvalue(object),
+            LValue::Dereference(lvalue, _) => self.visit_lvalue(lvalue),
+            LValue::Index { array, index, .. } => {
+                self.visit_lvalue(array) && self.visit_expression(index)
+            }
+            _ => true,
+        }
     }
 
     fn visit_lvalue_ident(&mut self, _: &Ident) {}
 
     fn visit_lvalue_member_access(
         &mut self,
-        _object: &LValue,
+        object: &LValue,
         _field_name: &Ident,
         _span: Span,
     ) -> bool {
-        todo!("Not implemented!")
+        self.visit_lvalue(object)
     }
 
-    fn visit_lvalue_index(&mut self, _array: &LValue, _index: &Expression, _span: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_lvalue_index(&mut self, array: &LValue, index: &Expression, _span: Span) -> bool {
+        self.visit_lvalue(array) && self.visit_expression(index)
     }
 
-    fn visit_lvalue_dereference(&mut self, _lvalue: &LValue, _span: Span) -> bool {
-        todo!("Not implemented!")
+    fn visit_lvalue_dereference(&mut self, lvalue: &LValue, _span: Span) -> bool {
+        self.visit_lvalue(lvalue)
     }
 
     fn visit_lvalue_interned(&mut self, _id: InternedExpressionKind, _span: Span) {}
 
-    fn visit_for_range(&mut self, _: &ForRange) -> bool {
-        todo!("Not implemented!")
+    fn visit_for_range(&mut self, range: &ForRange) -> bool {
+        match range {
+            ForRange::Range(bounds) => {
+                self.visit_expression(&bounds.start) && self.visit_expression(&bounds.end)
+            }
+            ForRange::Array(array) => self.visit_expression(array),
+        }
     }
 
     fn visit_as_trait_path(&mut self, _: &AsTraitPath, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_type_path(&mut self, _: &TypePath, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_unresolved_type(&mut self, _: &UnresolvedType) -> bool {
-        todo!("Not implemented!")
+        // Types, patterns and attributes hold no code the lint rules analyze through the
+        // context
+        true
     }
 
     fn visit_array_type(
@@ -514,31 +1023,31 @@ impl Visitor for Analyzer<'_> {
         _: &UnresolvedType,
         _: Span,
     ) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_slice_type(&mut self, _: &UnresolvedType, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_parenthesized_type(&mut self, _: &UnresolvedType, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_named_type(&mut self, _: &Path, _: &GenericTypeArgs, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_trait_as_type(&mut self, _: &Path, _: &GenericTypeArgs, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_reference_type(&mut self, _: &UnresolvedType, _mutable: bool, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_tuple_type(&mut self, _: &[UnresolvedType], _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_function_type(
@@ -549,11 +1058,11 @@ impl Visitor for Analyzer<'_> {
         _unconstrained: bool,
         _span: Span,
     ) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_as_trait_path_type(&mut self, _: &AsTraitPath, _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_expression_type(&mut self, _: &UnresolvedTypeExpression, _: Span) {}
@@ -564,113 +1073,92 @@ impl Visitor for Analyzer<'_> {
         _: &UnresolvedType,
         _: Span,
     ) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
-    fn visit_string_type(&mut self, _: &UnresolvedTypeExpression, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_string_type(&mut self, _: &UnresolvedTypeExpression, _: Span) {}
 
-    fn visit_unspecified_type(&mut self, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_unspecified_type(&mut self, _: Span) {}
 
-    fn visit_quoted_type(&mut self, _: &QuotedType, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_quoted_type(&mut self, _: &QuotedType, _: Span) {}
 
-    fn visit_field_element_type(&mut self, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_field_element_type(&mut self, _: Span) {}
 
-    fn visit_integer_type(&mut self, _: Signedness, _: IntegerBitSize, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_integer_type(&mut self, _: Signedness, _: IntegerBitSize, _: Span) {}
 
-    fn visit_bool_type(&mut self, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_bool_type(&mut self, _: Span) {}
 
-    fn visit_unit_type(&mut self, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_unit_type(&mut self, _: Span) {}
 
-    fn visit_resolved_type(&mut self, _: QuotedTypeId, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_resolved_type(&mut self, _: QuotedTypeId, _: Span) {}
 
-    fn visit_interned_type(&mut self, _: InternedUnresolvedTypeData, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_interned_type(&mut self, _: InternedUnresolvedTypeData, _: Span) {}
 
-    fn visit_error_type(&mut self, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_error_type(&mut self, _: Span) {}
 
     fn visit_path(&mut self, path: &Path) {
+        // Calls through `crate::` and `super::` paths are only registered once resolved, and
+        // calls into dependencies are left to the dependency graph
         match &path.kind {
-            PathKind::Crate => todo!("Not implemented!"),
-            PathKind::Dep => todo!("Not implemented!"),
+            PathKind::Crate | PathKind::Super => {}
+            PathKind::Dep => {
+                self.skip("call through a dep path");
+            }
             PathKind::Plain => self.stack.push(StackItem::Identifiers(
                 path.segments
                     .iter()
                     .map(|segment| segment.ident.clone())
                     .collect(),
             )),
-            PathKind::Super => todo!("Not implemented!"),
         }
     }
 
     fn visit_generic_type_args(&mut self, _: &GenericTypeArgs) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_function_return_type(&mut self, _: &FunctionReturnType) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_trait_bound(&mut self, _: &TraitBound) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_unresolved_trait_constraint(&mut self, _: &UnresolvedTraitConstraint) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_pattern(&mut self, _: &Pattern) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
-    fn visit_identifier_pattern(&mut self, _: &Ident) {
-        todo!("Not implemented!")
-    }
+    fn visit_identifier_pattern(&mut self, _: &Ident) {}
 
     fn visit_mutable_pattern(&mut self, _: &Pattern, _: Span, _is_synthesized: bool) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_tuple_pattern(&mut self, _: &[Pattern], _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_struct_pattern(&mut self, _: &Path, _: &[(Ident, Pattern)], _: Span) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
-    fn visit_interned_pattern(&mut self, _: &InternedPattern, _: Span) {
-        todo!("Not implemented!")
-    }
+    fn visit_interned_pattern(&mut self, _: &InternedPattern, _: Span) {}
 
     fn visit_secondary_attribute(
         &mut self,
         _: &SecondaryAttribute,
         _target: AttributeTarget,
     ) -> bool {
-        todo!("Not implemented!")
+        true
     }
 
     fn visit_meta_attribute(&mut self, _: &MetaAttribute, _target: AttributeTarget) -> bool {
-        todo!("Not implemented!")
+        true
     }
 }
 
@@ -678,6 +1166,8 @@ impl Visitor for Analyzer<'_> {
 mod tests {
     use super::*;
     use crate::ast::parser::Parser;
+    use fm::FileId;
+    use std::path::Path;
 
     #[test]
     fn test_analyzer_can_be_created() {
@@ -710,6 +1200,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_analyzer_reports_lints_in_rule_order_with_threads() {
+        use crate::lints::cyclic_module_imports::CyclicModuleImports;
+        use crate::lints::god_module::GodModule;
+        use crate::lints::unused_function::UnusedFunction;
+
+        let source_code = r#"
+            mod a {
+                use crate::b::bar;
+                fn unused_in_a() {}
+            }
+            mod b {
+                use crate::a::unused_in_a;
+                pub fn bar() {}
+            }
+            fn unused() {}
+            fn main() {}
+            "#;
+        let lint_rules: Vec<Box<dyn LintRule>> = vec![
+            Box::new(UnusedFunction),
+            Box::new(CyclicModuleImports),
+            Box::new(GodModule::with_thresholds(1, 1)),
+        ];
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let sequential = Analyzer::new(&lint_rules).analyze(&root).unwrap();
+        let parallel = Analyzer::new(&lint_rules)
+            .with_threads(3)
+            .analyze(&root)
+            .unwrap();
+
+        assert!(!sequential.is_empty());
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn test_panicking_rule_is_reported_as_internal_error() {
+        use crate::diagnostics::internal_error::INTERNAL_ERROR_LINT;
+        use crate::lints::unused_function::UnusedFunction;
+
+        struct Panicking;
+        impl LintRule for Panicking {
+            fn name(&self) -> &'static str {
+                "panicking"
+            }
+            fn explanation(&self) -> &'static str {
+                "Always panics"
+            }
+            fn boxed_clone(&self) -> Box<dyn LintRule> {
+                Box::new(Panicking)
+            }
+            fn lint(&self, _context: &AstContext) -> Vec<Lint> {
+                panic!("index out of bounds")
+            }
+        }
+
+        let lint_rules: Vec<Box<dyn LintRule>> =
+            vec![Box::new(Panicking), Box::new(UnusedFunction)];
+        let root = Parser::parse_program_with_dummy_file("fn unused() {} fn main() {}").unwrap();
+        for threads in [1, 2] {
+            let lints = Analyzer::new(&lint_rules)
+                .with_threads(threads)
+                .analyze(&root)
+                .unwrap();
+
+            let names: Vec<&str> = lints.iter().map(|lint| lint.name).collect();
+            assert_eq!(names, vec![INTERNAL_ERROR_LINT, "unused-function"]);
+            assert_eq!(
+                lints[0].description,
+                "Rule 'panicking' panicked on this file (index out of bounds), so its findings are missing"
+            );
+        }
+    }
+
+    #[test]
+    fn test_low_memory_analysis_skips_whole_program_rules() {
+        use crate::lints::oversized_unsafe_block::OversizedUnsafeBlock;
+        use crate::lints::unused_function::UnusedFunction;
+
+        let source_code = r#"
+            unconstrained fn hint() {}
+            fn unused() {}
+            mod inner {
+                pub fn main() {
+                    // Safety: the hint is not used
+                    unsafe {
+                        let a = 1;
+                        hint();
+                    }
+                }
+            }
+            "#;
+        let lint_rules: Vec<Box<dyn LintRule>> =
+            vec![Box::new(UnusedFunction), Box::new(OversizedUnsafeBlock)];
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lints = Analyzer::new(&lint_rules)
+            .analyze_low_memory(&root)
+            .unwrap();
+
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].name, "oversized-unsafe-block");
+    }
+
     #[test]
     fn test_analyzer_adds_function_definitions_to_context() {
         let source_code = r#"
@@ -730,4 +1324,231 @@ mod tests {
 
         assert_eq!(context.function_definitions.len(), 2);
     }
+
+    #[test]
+    fn test_analyzer_computes_only_required_analyses() {
+        let source_code = r#"
+            fn foo() {}
+            fn main() { foo(); }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+
+        let mut analyzer = Analyzer::new(&[]);
+        analyzer.analyze(&root).expect("Should have passed");
+        let context = analyzer.context.expect("Analyzer should have the context");
+        assert_eq!(context.call_graph.functions().count(), 0);
+        assert_eq!(context.module_graph.modules().count(), 0);
+
+        let lint_rules: Vec<Box<dyn LintRule>> =
+            vec![Box::new(crate::lints::private_data_leak::PrivateDataLeak)];
+        let mut analyzer = Analyzer::new(&lint_rules);
+        analyzer.analyze(&root).expect("Should have passed");
+        let context = analyzer.context.expect("Analyzer should have the context");
+        assert_eq!(context.call_graph.callees_of("main"), ["foo"]);
+        assert_eq!(context.module_graph.modules().count(), 0);
+        assert!(context.control_flow.is_empty());
+    }
+
+    #[test]
+    fn test_analyzer_adds_unsafe_blocks_to_context() {
+        let source_code = r#"
+            unconstrained fn hint() {}
+            fn main() {
+                // Safety: the hint is not used
+                let x = unsafe { hint() };
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+
+        let mut analyzer = Analyzer::new(&[]);
+
+        assert!(
+            analyzer.analyze(&root).is_ok(),
+            "Analyzer should successfully traverse an unsafe block."
+        );
+
+        let context = analyzer.context.expect("Analyzer should have the context");
+
+        assert_eq!(context.unsafe_blocks.len(), 1);
+        assert_eq!(context.function_calls.len(), 1);
+    }
+
+    #[test]
+    fn test_analyzer_records_skipped_nodes() {
+        let source_code = r#"
+            fn helper(x: Field) -> Field { x }
+            fn main(x: Field) {
+                let double = |y| helper(y) * 2;
+                let pair = (helper(x), crate::helper(x) + dep::math::scale(x));
+                assert(double(pair.0) == pair.1);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[]);
+        analyzer
+            .analyze(&root)
+            .expect("Should have skipped unsupported nodes");
+
+        assert_eq!(
+            analyzer.coverage().skipped(),
+            vec![("call through a dep path", 1)]
+        );
+        // Lambdas and tuples are traversed, and calls through `crate::` paths resolved
+        let context = analyzer.context.expect("Analyzer should have the context");
+        assert_eq!(context.function_calls["helper"].len(), 2);
+        assert_eq!(context.resolved_calls["crate::helper"].len(), 3);
+    }
+
+    #[test]
+    fn test_analyzer_records_call_sites() {
+        let source_code = r#"
+            fn helper(x: Field) -> Field { x }
+            fn main(x: Field) {
+                assert(helper(x) == crate::helper(x + 1));
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[]);
+        analyzer.analyze(&root).expect("Should have passed");
+
+        let context = analyzer.context.expect("Analyzer should have the context");
+        let call_sites: Vec<&str> = context.resolved_calls["crate::helper"]
+            .iter()
+            .map(|call| &source_code[call.span.start() as usize..call.span.end() as usize])
+            .collect();
+        assert_eq!(call_sites, ["helper(x)", "crate::helper(x + 1)"]);
+    }
+
+    #[test]
+    fn test_analyzer_records_the_file_of_call_sites() {
+        let mut parser = Parser::default();
+        let mut file_ids = vec![];
+        for (file, source_code) in [
+            ("main.nr", "fn helper() {}\nfn main() { helper(); }"),
+            ("other.nr", "fn helper() {}\nfn other() { helper(); }"),
+        ] {
+            let root = parser.parse_source(Path::new(file), source_code).unwrap();
+            let mut analyzer = Analyzer::new(&[]);
+            analyzer.analyze(&root).expect("Should have passed");
+
+            let context = analyzer.context.expect("Analyzer should have the context");
+            let file_id = context.function_calls["helper"][0].file_id;
+            assert_eq!(parser.file_manager().path(file_id), Some(Path::new(file)));
+            file_ids.push(file_id);
+        }
+        assert_ne!(file_ids[0], file_ids[1]);
+        assert!(!file_ids.contains(&FileId::dummy()));
+    }
+
+    #[test]
+    fn test_analyzer_traverses_every_kind_of_node() {
+        let source_code = r#"
+            use std::hash::pedersen_hash;
+
+            global SIZE: u32 = 4;
+
+            type Pair = (Field, Field);
+
+            struct Point {
+                x: Field,
+                y: Field,
+            }
+
+            trait Shape {
+                fn area(self) -> Field;
+                fn scaled(self, factor: Field) -> Field {
+                    self.area() * factor
+                }
+            }
+
+            impl Point {
+                fn new(x: Field, y: Field) -> Self {
+                    Point { x, y }
+                }
+            }
+
+            impl Shape for Point {
+                fn area(self) -> Field {
+                    self.x * self.y
+                }
+            }
+
+            mod utils {
+                pub fn square(x: Field) -> Field {
+                    x * x
+                }
+            }
+
+            unconstrained fn hint(x: Field) -> Field {
+                let mut result = 0;
+                let mut i = 0;
+                while i < 3 {
+                    result += x;
+                    i += 1;
+                }
+                loop {
+                    if result != 0 {
+                        break;
+                    }
+                    result = 1;
+                }
+                result
+            }
+
+            fn main(x: Field, mut values: [Field; SIZE]) -> pub Field {
+                let point = Point::new(x, utils::square(x));
+                let pair: Pair = (point.x, point.area());
+                let (a, b) = pair;
+                let Point { x: px, y: _ } = point;
+                for i in 0..SIZE {
+                    values[i] = values[i] + a;
+                }
+                values[utils::square(1) as u32] = b;
+                let repeated = [utils::square(px); 2];
+                let slice = &[a, b];
+                let closure = |y: Field| y + utils::square(y);
+                // Safety: checked below
+                let hinted = unsafe { hint(x) };
+                assert(hinted == x * 3, "hint mismatch");
+                assert_eq(slice.len(), 2);
+                let text = f"{x}";
+                comptime {
+                    let _ = 1 + 2;
+                }
+                let _ = text;
+                if a == b {
+                    closure(repeated[0])
+                } else {
+                    pedersen_hash([a, b]) + point.scaled(2) + (-x) + (x as u8) as Field
+                }
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lints: Vec<Box<dyn LintRule>> = vec![
+            Box::new(crate::lints::unused_function::UnusedFunction),
+            Box::new(crate::lints::oversized_unsafe_block::OversizedUnsafeBlock),
+            Box::new(crate::lints::call_chain_depth::CallChainDepth::default()),
+        ];
+        let mut analyzer = Analyzer::new(&lints);
+        let result = analyzer
+            .analyze(&root)
+            .expect("Should have traversed the program");
+
+        assert!(
+            result
+                .iter()
+                .all(|lint| !lint.description.contains("panicked")),
+            "No lint rule should panic: {result:?}"
+        );
+        assert!(analyzer.coverage().is_complete());
+        let context = analyzer.context.expect("Analyzer should have the context");
+        assert!(context.function_definitions.contains_key("hint"));
+        assert!(context.function_calls.contains_key("hint"));
+        assert!(context.function_calls.contains_key("pedersen_hash"));
+    }
 }
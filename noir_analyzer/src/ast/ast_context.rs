@@ -1,24 +1,135 @@
+use crate::analysis::call_graph::CallGraph;
+use crate::analysis::cfg::{self, ControlFlowGraph};
+use crate::analysis::dependencies::DependencyGraph;
+use crate::analysis::module_graph::ModuleGraph;
+use crate::analysis::passes::{self, Analysis};
+use crate::analysis::private_taint::PrivateTaint;
+use crate::analysis::resolution::SymbolTable;
+use crate::ast::scopes::ScopeTree;
+use fm::FileId;
 use noirc_frontend::ParsedModule;
-use noirc_frontend::ast::{CallExpression, FunctionDefinition};
-use std::collections::HashMap;
+use noirc_frontend::ast::{BlockExpression, CallExpression, FunctionDefinition};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::BTreeMap;
 
 /// Stores all collected data from the AST traversal.
 pub struct AstContext<'ast> {
     /// References the parsed module, ensuring data consistency.
     pub parsed_module: &'ast ParsedModule,
 
-    /// Stores function definitions (name → AST node).
-    pub function_definitions: HashMap<String, FunctionDefinition>, // TODO  try to implement with references
-    pub function_calls: HashMap<String, Vec<Box<CallExpression>>>,
+    /// Stores function definitions (name → AST node). Indexes are ordered by name, so that
+    /// rules iterating them report lints in the same order on every run.
+    pub function_definitions: BTreeMap<String, FunctionDefinition>, // TODO  try to implement with references
+    /// Calls by the path written at the call site, segments concatenated. Calls through
+    /// `crate::`, `super::` and `dep::` paths are left out.
+    pub function_calls: BTreeMap<String, Vec<FunctionCall>>,
+
+    /// Calls to the functions of the program, by fully-qualified path of the callee.
+    pub resolved_calls: BTreeMap<String, Vec<FunctionCall>>,
+
+    /// Functions and imports of each module, for resolving paths.
+    pub symbols: SymbolTable,
+
+    /// Scopes of the traversed code, with the variables they declare.
+    pub(crate) scopes: ScopeTree,
+
+    /// Stores every `unsafe { }` block encountered, in traversal order.
+    pub unsafe_blocks: Vec<UnsafeBlock>,
+
+    /// Import dependencies between the modules of the parsed program. This and the following
+    /// analyses are empty unless computed, see [`Analysis`].
+    pub module_graph: ModuleGraph,
+
+    /// Calls between the functions and methods of the parsed program.
+    pub call_graph: CallGraph,
+
+    /// Control-flow graph of each function and method, keyed like the nodes of the call
+    /// graph.
+    pub control_flow: BTreeMap<String, ControlFlowGraph>,
+
+    /// Flows of the private inputs of `main` through the functions of the program, and the
+    /// sinks they reach.
+    pub private_taint: PrivateTaint,
+
+    /// Source of the parsed program, when known, for lints depending on tokens and comments
+    /// that the AST does not keep.
+    pub source: Option<&'ast str>,
+
+    /// Dependencies of the package of the program, when resolved, for lints following calls
+    /// into them.
+    pub dependencies: Option<&'ast DependencyGraph>,
+}
+
+/// A call to a function together with its location in the source.
+pub struct FunctionCall {
+    /// The call, with the function called and its arguments.
+    pub call: CallExpression,
+    /// Span of the whole call expression, arguments included.
+    pub span: Span,
+    /// The file containing the call.
+    pub file_id: FileId,
+}
+
+/// An `unsafe { }` block together with its location in the source.
+pub struct UnsafeBlock {
+    /// The statements wrapped by the block.
+    pub block: BlockExpression,
+    /// Span of the whole `unsafe { ... }` expression.
+    pub span: Span,
+    /// The file containing the block.
+    pub file_id: FileId,
 }
 
 impl<'ast> AstContext<'ast> {
-    /// Creates a new instance, linking it to the given `ParsedModule`.
+    /// Creates a new instance, linking it to the given `ParsedModule`, with every analysis
+    /// computed.
     pub fn new(parsed_module: &'ast ParsedModule) -> Self {
+        Self::with_analyses(parsed_module, &Analysis::ALL)
+    }
+
+    /// Creates a new instance, linking it to the given `ParsedModule`, with the given
+    /// analyses and those they depend on computed. The others are left empty.
+    pub fn with_analyses(parsed_module: &'ast ParsedModule, analyses: &[Analysis]) -> Self {
+        let mut context = Self::for_function(parsed_module);
+        context.symbols = SymbolTable::build(parsed_module);
+        for analysis in passes::schedule(analyses.iter().copied()) {
+            match analysis {
+                Analysis::ModuleGraph => context.module_graph = ModuleGraph::build(parsed_module),
+                Analysis::CallGraph => context.call_graph = CallGraph::build(parsed_module),
+                Analysis::ControlFlow => {
+                    context.control_flow = cfg::control_flow_graphs(parsed_module)
+                }
+                Analysis::PrivateTaint => {
+                    context.private_taint = PrivateTaint::build(parsed_module, &context.call_graph)
+                }
+            }
+        }
+        context
+    }
+
+    /// Creates an instance without whole-program indexes such as the module graph, for
+    /// analyzing a single function of the given `ParsedModule`.
+    pub fn for_function(parsed_module: &'ast ParsedModule) -> Self {
         Self {
             parsed_module,
-            function_definitions: HashMap::new(),
-            function_calls: HashMap::new(),
+            function_definitions: BTreeMap::new(),
+            function_calls: BTreeMap::new(),
+            resolved_calls: BTreeMap::new(),
+            symbols: SymbolTable::default(),
+            scopes: ScopeTree::default(),
+            unsafe_blocks: Vec::new(),
+            module_graph: ModuleGraph::default(),
+            call_graph: CallGraph::default(),
+            control_flow: BTreeMap::new(),
+            private_taint: PrivateTaint::default(),
+            source: None,
+            dependencies: None,
         }
     }
+
+    /// Returns the scopes of the traversed code, for looking up the variable a name refers
+    /// to at a given location.
+    pub fn scopes(&self) -> &ScopeTree {
+        &self.scopes
+    }
 }
@@ -0,0 +1,44 @@
+//! # Coverage
+//!
+//! The analyzer does not descend into every kind of AST node. Nodes it cannot analyze, such
+//! as quoted code or calls through `dep::` paths, are skipped, so lint rules do not see the
+//! calls and constraints they contain. `Coverage` counts the skipped nodes by kind, so that users know
+//! the blind spots of a run on their code and maintainers know which nodes to support next.
+
+use std::collections::BTreeMap;
+
+/// Number of AST nodes skipped by the analyzer, by node kind.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Coverage {
+    skipped: BTreeMap<&'static str, usize>,
+}
+
+impl Coverage {
+    /// Records a skipped node of the given kind.
+    pub fn record(&mut self, kind: &'static str) {
+        *self.skipped.entry(kind).or_default() += 1;
+    }
+
+    /// Adds the nodes skipped in another run.
+    pub fn merge(&mut self, other: &Coverage) {
+        for (kind, count) in &other.skipped {
+            *self.skipped.entry(kind).or_default() += count;
+        }
+    }
+
+    /// Returns whether every encountered node was analyzed.
+    pub fn is_complete(&self) -> bool {
+        self.skipped.is_empty()
+    }
+
+    /// Returns the kinds of skipped nodes with their counts, most frequent first.
+    pub fn skipped(&self) -> Vec<(&'static str, usize)> {
+        let mut skipped: Vec<(&'static str, usize)> = self
+            .skipped
+            .iter()
+            .map(|(kind, count)| (*kind, *count))
+            .collect();
+        skipped.sort_by(|a, b| b.1.cmp(&a.1));
+        skipped
+    }
+}
@@ -0,0 +1,160 @@
+//! # Feature Flags
+//!
+//! Projects often gate code behind boolean globals acting as feature flags, such as
+//! `if DEBUG { ... }` or `comptime if USE_POSEIDON { ... }`. By default the analyzer reads
+//! every branch, so lints also see code that only compiles under some configurations.
+//!
+//! When flag values are configured, the branches they disable are removed from the parsed
+//! program before analysis, so that lints analyze the program of that configuration. Only
+//! conditions consisting of a configured flag, possibly negated or parenthesized, select a
+//! branch; branches nested in other expressions than blocks, `if`s and loops are kept.
+
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    BlockExpression, Expression, ExpressionKind, StatementKind, TraitImplItemKind, UnaryOp,
+};
+use noirc_frontend::parser::ItemKind;
+use std::collections::BTreeMap;
+
+/// Removes the branches disabled by the given flag values from every function body.
+pub fn select_branches(parsed_module: &mut ParsedModule, flags: &BTreeMap<String, bool>) {
+    if flags.is_empty() {
+        return;
+    }
+
+    for item in &mut parsed_module.items {
+        match &mut item.kind {
+            ItemKind::Submodules(submodule) => select_branches(&mut submodule.contents, flags),
+            ItemKind::Function(function) => select_in_block(&mut function.def.body, flags),
+            ItemKind::Impl(type_impl) => {
+                for (method, _) in &mut type_impl.methods {
+                    select_in_block(&mut method.item.def.body, flags);
+                }
+            }
+            ItemKind::TraitImpl(trait_impl) => {
+                for item in &mut trait_impl.items {
+                    if let TraitImplItemKind::Function(function) = &mut item.item.kind {
+                        select_in_block(&mut function.def.body, flags);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn select_in_block(block: &mut BlockExpression, flags: &BTreeMap<String, bool>) {
+    for statement in &mut block.statements {
+        select_in_statement(&mut statement.kind, flags);
+    }
+}
+
+fn select_in_statement(statement: &mut StatementKind, flags: &BTreeMap<String, bool>) {
+    match statement {
+        StatementKind::Let(let_statement) => {
+            select_in_expression(&mut let_statement.expression, flags)
+        }
+        StatementKind::Expression(expression) | StatementKind::Semi(expression) => {
+            select_in_expression(expression, flags)
+        }
+        StatementKind::Assign(assign) => select_in_expression(&mut assign.expression, flags),
+        StatementKind::For(for_loop) => select_in_expression(&mut for_loop.block, flags),
+        StatementKind::Comptime(statement) => select_in_statement(&mut statement.kind, flags),
+        _ => {}
+    }
+}
+
+fn select_in_expression(expression: &mut Expression, flags: &BTreeMap<String, bool>) {
+    if let ExpressionKind::If(if_expression) = &expression.kind
+        && let Some(enabled) = flag_value(&if_expression.condition, flags)
+    {
+        // An `if` without `else` evaluates to an empty block when disabled
+        let branch = if enabled {
+            Some(if_expression.consequence.clone())
+        } else {
+            if_expression.alternative.clone()
+        };
+        match branch {
+            Some(branch) => *expression = branch,
+            None => expression.kind = ExpressionKind::Block(BlockExpression { statements: vec![] }),
+        }
+    }
+
+    match &mut expression.kind {
+        ExpressionKind::If(if_expression) => {
+            select_in_expression(&mut if_expression.consequence, flags);
+            if let Some(alternative) = &mut if_expression.alternative {
+                select_in_expression(alternative, flags);
+            }
+        }
+        ExpressionKind::Block(block) | ExpressionKind::Comptime(block, _) => {
+            select_in_block(block, flags)
+        }
+        ExpressionKind::Parenthesized(expression) => select_in_expression(expression, flags),
+        _ => {}
+    }
+}
+
+/// Returns the value of a condition made of a configured flag.
+fn flag_value(condition: &Expression, flags: &BTreeMap<String, bool>) -> Option<bool> {
+    match &condition.kind {
+        ExpressionKind::Variable(path) if path.segments.len() == 1 => {
+            flags.get(&path.segments[0].ident.to_string()).copied()
+        }
+        ExpressionKind::Prefix(prefix) if matches!(prefix.operator, UnaryOp::Not) => {
+            flag_value(&prefix.rhs, flags).map(|value| !value)
+        }
+        ExpressionKind::Parenthesized(expression) => flag_value(expression, flags),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::lints::unused_function::UnusedFunction;
+
+    const SOURCE: &str = r#"
+        global DEBUG: bool = true;
+
+        fn log_value(x: Field) -> Field { x }
+        fn release_value(x: Field) -> Field { x + 1 }
+
+        pub fn main(x: Field) {
+            if DEBUG {
+                let _ = log_value(x);
+            } else {
+                let _ = release_value(x);
+            }
+        }
+        "#;
+
+    fn unused_functions(flags: &[(&str, bool)]) -> Vec<String> {
+        let flags = flags
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        let mut root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        select_branches(&mut root, &flags);
+
+        let lint = Box::new(UnusedFunction);
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+        result.into_iter().map(|lint| lint.description).collect()
+    }
+
+    #[test]
+    fn test_every_branch_is_analyzed_by_default() {
+        assert!(unused_functions(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_disabled_branches_are_removed() {
+        let unused = unused_functions(&[("DEBUG", false)]);
+
+        assert_eq!(unused.len(), 1);
+        assert!(unused[0].contains("log_value"));
+    }
+}
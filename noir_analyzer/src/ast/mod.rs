@@ -5,4 +5,8 @@
 
 pub mod analyzer;
 pub mod ast_context;
+pub mod coverage;
+pub mod flags;
 pub mod parser;
+pub mod scopes;
+pub mod trivia;
@@ -1,32 +1,68 @@
 use crate::ast::analyzer::AnalyzerError;
-use fm::FileId;
+use crate::frontend::Noir;
+use fm::{FileId, FileManager};
 use noirc_frontend::ParsedModule;
 use std::fs;
 use std::path::Path;
 
-pub struct Parser {}
+/// Parses Noir source files, registering each one in a file manager so that the spans and
+/// file IDs of the parsed modules tell their files apart.
+pub struct Parser {
+    file_manager: FileManager,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new(Path::new(""))
+    }
+}
 
 impl Parser {
-    pub fn parse_program_with_dummy_file(src: &str) -> Result<ParsedModule, AnalyzerError> {
-        let (ast_root, errors) = noirc_frontend::parse_program_with_dummy_file(src);
-        if !errors.is_empty() {
-            return Err(AnalyzerError::ParsingError(errors));
+    /// Creates a parser registering files relative to `root`.
+    pub fn new(root: &Path) -> Self {
+        Self {
+            file_manager: FileManager::new(root),
         }
-        Ok(ast_root)
     }
 
-    pub fn parse_file(file_path: &Path) -> Result<ParsedModule, AnalyzerError> {
-        // Read the file contents
+    /// Parses a snippet that is not read from a file, such as the source of a test.
+    pub fn parse_program_with_dummy_file(src: &str) -> Result<ParsedModule, AnalyzerError> {
+        Self::parse_program(src, FileId::dummy())
+    }
+
+    /// Parses the source of a file, registering it under `file_path`. A file parsed again
+    /// keeps the ID it was first registered with.
+    pub fn parse_source(
+        &mut self,
+        file_path: &Path,
+        source: &str,
+    ) -> Result<ParsedModule, AnalyzerError> {
+        let file_id = self
+            .file_manager
+            .add_file_with_source(file_path, source.to_string())
+            .ok_or_else(|| {
+                AnalyzerError::GenericError(format!("Could not register {file_path:?}"))
+            })?;
+        Self::parse_program(source, file_id)
+    }
+
+    /// Reads and parses a file, registering it under `file_path`.
+    pub fn parse_file(&mut self, file_path: &Path) -> Result<ParsedModule, AnalyzerError> {
         let source = fs::read_to_string(file_path)
             .map_err(|e| AnalyzerError::FileReadError(file_path.to_path_buf(), e.to_string()))?;
+        self.parse_source(file_path, &source)
+    }
 
-        // Parse the file using a proper file ID
-        let (ast_root, errors) = noirc_frontend::parse_program(&source, FileId::dummy());
+    /// Returns the files parsed so far, to map the file IDs of spans back to their paths.
+    pub fn file_manager(&self) -> &FileManager {
+        &self.file_manager
+    }
 
+    fn parse_program(source: &str, file_id: FileId) -> Result<ParsedModule, AnalyzerError> {
+        let (ast_root, errors) = Noir::parse_program(source, file_id);
         if !errors.is_empty() {
             return Err(AnalyzerError::ParsingError(errors));
         }
-
         Ok(ast_root)
     }
 }
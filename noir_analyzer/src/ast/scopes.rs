@@ -0,0 +1,286 @@
+//! # Scopes
+//!
+//! The scope tree of a program, built by the analyzer while it traverses the AST: modules
+//! declare globals, functions and lambdas their parameters, `for` loops their index and blocks
+//! the variables of their `let` statements. Lints resolve the variable a name refers to at a
+//! given location with [`ScopeTree::lookup`], which follows shadowing, rather than matching
+//! names across the whole function.
+//!
+//! Globals and parameters are visible in their whole scope, while a local variable is visible
+//! after the statement declaring it, so that `let x = x + 1` reads the previous `x`. Patterns
+//! of `match` arms are not expressions the analyzer descends into, so they declare nothing.
+
+use noirc_frontend::ast::{Ident, Pattern};
+use noirc_frontend::hir::resolution::errors::Span;
+
+/// Index of a scope in its tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScopeId(usize);
+
+/// Index of a binding in its tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BindingId(usize);
+
+/// What introduces a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    Module,
+    Function,
+    Lambda,
+    ForLoop,
+    Block,
+}
+
+/// What a name is bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    Global,
+    Parameter,
+    Local,
+}
+
+/// A lexical scope, covering a span of its file.
+#[derive(Debug)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub parent: Option<ScopeId>,
+    pub span: Span,
+    /// Bindings declared directly in this scope, in declaration order.
+    bindings: Vec<BindingId>,
+}
+
+/// A name declared in a scope.
+#[derive(Debug)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    pub is_mutable: bool,
+    pub scope: ScopeId,
+    /// Span of the declared identifier.
+    pub span: Span,
+    /// Offset from which the binding is visible.
+    pub visible_from: u32,
+    /// Binding with the same name this one hides from the rest of its scope, if any.
+    pub shadows: Option<BindingId>,
+}
+
+/// The scopes and bindings of a program, in traversal order.
+#[derive(Debug, Default)]
+pub struct ScopeTree {
+    scopes: Vec<Scope>,
+    bindings: Vec<Binding>,
+    /// Scope being populated during the traversal.
+    current: Option<ScopeId>,
+}
+
+impl ScopeTree {
+    /// Opens a scope nested in the current one.
+    pub(crate) fn enter(&mut self, kind: ScopeKind, span: Span) {
+        self.scopes.push(Scope {
+            kind,
+            parent: self.current,
+            span,
+            bindings: Vec::new(),
+        });
+        self.current = Some(ScopeId(self.scopes.len() - 1));
+    }
+
+    /// Closes the current scope.
+    pub(crate) fn exit(&mut self) {
+        self.current = self
+            .current
+            .and_then(|ScopeId(index)| self.scopes[index].parent);
+    }
+
+    /// Declares the variables bound by a pattern in the current scope, visible from the
+    /// given offset.
+    pub(crate) fn declare(&mut self, pattern: &Pattern, kind: BindingKind, visible_from: u32) {
+        let mut idents = vec![];
+        pattern_idents(pattern, false, &mut idents);
+        for (ident, is_mutable) in idents {
+            self.declare_ident(ident, kind, is_mutable, visible_from);
+        }
+    }
+
+    /// Declares a variable in the current scope, visible from the given offset.
+    pub(crate) fn declare_ident(
+        &mut self,
+        ident: &Ident,
+        kind: BindingKind,
+        is_mutable: bool,
+        visible_from: u32,
+    ) {
+        let Some(scope) = self.current else {
+            return;
+        };
+        let name = ident.to_string();
+        let span = ident.span();
+        let shadows = self
+            .visible(scope, span.start())
+            .find(|binding| self.bindings[binding.0].name == name);
+        self.bindings.push(Binding {
+            name,
+            kind,
+            is_mutable,
+            scope,
+            span,
+            visible_from,
+            shadows,
+        });
+        let binding = BindingId(self.bindings.len() - 1);
+        self.scopes[scope.0].bindings.push(binding);
+    }
+
+    /// Returns the binding a name refers to at the given location, if it is declared in an
+    /// enclosing scope.
+    pub fn lookup(&self, name: &str, span: Span) -> Option<&Binding> {
+        let scope = self.innermost(span)?;
+        self.visible(scope, span.start())
+            .map(|binding| &self.bindings[binding.0])
+            .find(|binding| binding.name == name)
+    }
+
+    /// Returns the innermost scope containing the given location.
+    pub fn innermost(&self, span: Span) -> Option<ScopeId> {
+        // Scopes are stored in traversal order, so the last one containing the location is
+        // nested in all the others that do
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.span.start() <= span.start() && span.end() <= scope.span.end())
+            .map(ScopeId)
+    }
+
+    /// Returns a scope of the tree.
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    /// Returns a binding of the tree.
+    pub fn binding(&self, id: BindingId) -> &Binding {
+        &self.bindings[id.0]
+    }
+
+    /// Returns every binding of the program, in declaration order.
+    pub fn bindings(&self) -> impl Iterator<Item = &Binding> {
+        self.bindings.iter()
+    }
+
+    /// Returns the bindings declared directly in a scope, in declaration order.
+    pub fn bindings_of(&self, id: ScopeId) -> impl Iterator<Item = &Binding> {
+        self.scopes[id.0]
+            .bindings
+            .iter()
+            .map(|binding| &self.bindings[binding.0])
+    }
+
+    /// Returns the bindings visible at an offset of a scope, innermost and latest first.
+    fn visible(&self, scope: ScopeId, offset: u32) -> impl Iterator<Item = BindingId> + '_ {
+        let scopes = std::iter::successors(Some(scope), |scope| self.scopes[scope.0].parent);
+        scopes.flat_map(move |scope| {
+            self.scopes[scope.0]
+                .bindings
+                .iter()
+                .rev()
+                .copied()
+                .filter(move |binding| self.bindings[binding.0].visible_from <= offset)
+        })
+    }
+}
+
+/// Collects the identifiers bound by a pattern, with whether they are mutable.
+pub(crate) fn pattern_idents<'a>(
+    pattern: &'a Pattern,
+    is_mutable: bool,
+    idents: &mut Vec<(&'a Ident, bool)>,
+) {
+    match pattern {
+        Pattern::Identifier(ident) => idents.push((ident, is_mutable)),
+        Pattern::Mutable(pattern, ..) => pattern_idents(pattern, true, idents),
+        Pattern::Tuple(patterns, _) => {
+            for pattern in patterns {
+                pattern_idents(pattern, is_mutable, idents);
+            }
+        }
+        Pattern::Struct(_, fields, _) => {
+            for (_, pattern) in fields {
+                pattern_idents(pattern, is_mutable, idents);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    /// Returns the span of the `occurrence`-th occurrence of `needle` in the source.
+    fn span_of(source: &str, needle: &str, occurrence: usize) -> Span {
+        let start = source.match_indices(needle).nth(occurrence).unwrap().0;
+        Span::from(start as u32..(start + needle.len()) as u32)
+    }
+
+    #[test]
+    fn test_lookup_follows_shadowing_and_nesting() {
+        let source = "global LIMIT: u32 = 10;\n\nfn main(x: u32, mut y: u32) {\n    let x = x + 1;\n    for i in 0..LIMIT {\n        let (x, z) = (i, x);\n        y += x + z;\n    }\n    let add = |x: u32| x + y;\n    assert(add(x) < LIMIT);\n}\n";
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[]);
+        analyzer.analyze(&root).expect("Should have passed");
+        let context = analyzer.context.expect("Analyzer should have the context");
+        let scopes = context.scopes();
+
+        let declaration = |name: &str, occurrence: usize| {
+            scopes
+                .lookup(name, span_of(source, name, occurrence))
+                .map(|binding| (binding.kind, binding.span.start()))
+        };
+        let at = |name: &str, occurrence: usize| span_of(source, name, occurrence).start();
+
+        // The initializer of the local `x` reads the parameter
+        assert_eq!(
+            declaration("x", 2),
+            Some((BindingKind::Parameter, at("x", 0)))
+        );
+        assert_eq!(
+            declaration("LIMIT", 1),
+            Some((BindingKind::Global, at("LIMIT", 0)))
+        );
+        // The tuple element shadows the local from the next statement of the loop on
+        assert_eq!(declaration("x", 4), Some((BindingKind::Local, at("x", 1))));
+        assert_eq!(declaration("x", 5), Some((BindingKind::Local, at("x", 3))));
+        // The lambda parameter, then the local again once out of the lambda
+        assert_eq!(
+            declaration("x", 7),
+            Some((BindingKind::Parameter, at("x", 6)))
+        );
+        assert_eq!(declaration("x", 8), Some((BindingKind::Local, at("x", 1))));
+        assert!(scopes.lookup("i", span_of(source, "assert", 0)).is_none());
+
+        let shadowing: Vec<(&str, bool)> = scopes
+            .bindings()
+            .map(|binding| (binding.name.as_str(), binding.shadows.is_some()))
+            .collect();
+        assert_eq!(
+            shadowing,
+            [
+                ("LIMIT", false),
+                ("x", false),
+                ("y", false),
+                ("x", true),
+                ("i", false),
+                ("x", true),
+                ("z", false),
+                ("x", true),
+                ("add", false),
+            ]
+        );
+        assert!(
+            scopes
+                .bindings()
+                .any(|binding| binding.name == "y" && binding.is_mutable)
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! # Trivia Module
+//!
+//! The parser drops whitespace, comments and some punctuation, such as the `;` ending an
+//! expression statement. These helpers recover token positions from the source, for lints
+//! depending on tokens the AST does not keep.
+
+/// Returns the byte offset of the first token at or after `offset`, skipping whitespace and
+/// comments.
+pub fn next_token(source: &str, mut offset: usize) -> Option<usize> {
+    loop {
+        let rest = source.get(offset..)?;
+        if rest.starts_with("//") {
+            offset += rest.find('\n').map_or(rest.len(), |newline| newline + 1);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            offset += comment.find("*/")? + 4;
+        } else {
+            let character = rest.chars().next()?;
+            if !character.is_whitespace() {
+                return Some(offset);
+            }
+            offset += character.len_utf8();
+        }
+    }
+}
+
+/// Extends the byte range `start..end` to the whole lines containing it when the rest of
+/// these lines is whitespace, so that removing the range does not leave a blank line.
+pub fn expand_to_lines(source: &str, start: usize, end: usize) -> (usize, usize) {
+    let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+    let line_end = source[end..]
+        .find('\n')
+        .map_or(source.len(), |newline| end + newline + 1);
+
+    if source[line_start..start].trim().is_empty() && source[end..line_end].trim().is_empty() {
+        (line_start, line_end)
+    } else {
+        (start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_token_skips_whitespace_and_comments() {
+        let source = "x == 5 // checked\n  /* really */ ;";
+
+        assert_eq!(next_token(source, 6), Some(source.len() - 1));
+        assert_eq!(next_token(source, 0), Some(0));
+    }
+
+    #[test]
+    fn test_next_token_at_end_of_source() {
+        assert_eq!(next_token("x   ", 1), None);
+        assert_eq!(next_token("x /* open", 1), None);
+    }
+
+    #[test]
+    fn test_expand_to_lines_only_extends_lone_ranges() {
+        let source = "a\n    use x;\nb; use y;\n";
+
+        assert_eq!(expand_to_lines(source, 6, 12), (2, 13));
+        assert_eq!(expand_to_lines(source, 16, 22), (16, 22));
+    }
+}
@@ -0,0 +1,258 @@
+//! # Baseline
+//!
+//! A baseline records findings that were accepted at some point, so that `check` only
+//! reports new ones. This makes it possible to adopt the analyzer on an existing codebase
+//! without fixing every finding first.
+//!
+//! Baselines are JSON files of the form:
+//! ```json
+//! {
+//!   "version": 1,
+//!   "entries": [
+//!     { "rule": "unused-function", "file": "src/main.nr", "line": 4, "message": "Function 'foo' is unused", "fingerprint": "3f1c8e0a9b2d4c57" }
+//!   ]
+//! }
+//! ```
+//! Entries match findings by rule, file and message, or by the fingerprint of the finding,
+//! which also survives rewording the message. Lines are informational only, so baselined
+//! findings stay suppressed when surrounding code moves.
+
+pub mod nargo;
+
+use crate::diagnostics::finding::{Finding, relative_file};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Default location of the baseline file, relative to the workspace root.
+pub const BASELINE_FILE_NAME: &str = "noir-analyzer-baseline.json";
+
+/// Version of the baseline format written by this crate.
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum BaselineError {
+    #[error("Failed to read baseline {0:?}: {1}")]
+    FileReadError(PathBuf, String),
+    #[error("Failed to write baseline {0:?}: {1}")]
+    FileWriteError(PathBuf, String),
+    #[error("Failed to parse baseline: {0}")]
+    ParseError(String),
+    #[error("Unsupported baseline version {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// An accepted finding.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Name of the lint rule that reported the finding.
+    pub rule: String,
+    /// File of the finding, relative to the workspace root and using forward slashes.
+    pub file: String,
+    /// Line of the finding when it was accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Description of the finding.
+    pub message: String,
+    /// Fingerprint of the finding, see `Finding::fingerprint`. Entries imported from other
+    /// tools have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+}
+
+/// A set of accepted findings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Baseline {
+    /// Creates a baseline from the given entries, sorted for stable output.
+    pub fn new(mut entries: Vec<BaselineEntry>) -> Self {
+        entries.sort();
+        entries.dedup();
+        Self {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    /// Creates a baseline accepting every given finding.
+    pub fn from_findings(findings: &[Finding], root: &Path) -> Self {
+        Self::new(
+            findings
+                .iter()
+                .map(|finding| BaselineEntry {
+                    rule: finding.lint.name.to_string(),
+                    file: relative_file(&finding.file_path, root),
+                    line: finding.line,
+                    message: finding.lint.description.clone(),
+                    fingerprint: Some(finding.fingerprint(root)),
+                })
+                .collect(),
+        )
+    }
+
+    /// Parses a baseline from its JSON representation.
+    pub fn parse(content: &str) -> Result<Self, BaselineError> {
+        let baseline: Self =
+            serde_json::from_str(content).map_err(|e| BaselineError::ParseError(e.to_string()))?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(BaselineError::UnsupportedVersion(baseline.version));
+        }
+        Ok(baseline)
+    }
+
+    /// Loads a baseline from a file.
+    pub fn load(path: &Path) -> Result<Self, BaselineError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| BaselineError::FileReadError(path.to_path_buf(), e.to_string()))?;
+        Self::parse(&content)
+    }
+
+    /// Writes the baseline to a file.
+    pub fn save(&self, path: &Path) -> Result<(), BaselineError> {
+        let content = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, content + "\n")
+            .map_err(|e| BaselineError::FileWriteError(path.to_path_buf(), e.to_string()))
+    }
+
+    /// Returns whether the finding was accepted by the baseline.
+    pub fn contains(&self, finding: &Finding, root: &Path) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.matches(finding, root))
+    }
+
+    /// Returns the entries matching none of the given findings: accepted findings that were
+    /// fixed since.
+    pub fn unmatched<'a>(&'a self, findings: &[Finding], root: &Path) -> Vec<&'a BaselineEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| !findings.iter().any(|finding| entry.matches(finding, root)))
+            .collect()
+    }
+}
+
+impl BaselineEntry {
+    /// Returns whether the entry accepts the finding.
+    fn matches(&self, finding: &Finding, root: &Path) -> bool {
+        self.fingerprint
+            .as_ref()
+            .is_some_and(|fingerprint| *fingerprint == finding.fingerprint(root))
+            || (self.rule == finding.lint.name
+                && self.file == relative_file(&finding.file_path, root)
+                && self.message == finding.lint.description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::lint::{Confidence, Lint, Severity};
+    use noirc_frontend::hir::resolution::errors::Span;
+
+    fn finding(file_path: &str, description: &str) -> Finding {
+        let lint = Lint {
+            name: "unused-function",
+            severity: Severity::Warning,
+            confidence: Confidence::High,
+            description: description.to_string(),
+            message: None,
+            span: None,
+            file_id: None,
+            suggestion: None,
+        };
+        Finding::new(lint, Path::new(file_path), "")
+    }
+
+    #[test]
+    fn test_baseline_matches_findings_by_rule_file_and_message() {
+        let root = Path::new("workspace");
+        let baseline = Baseline::from_findings(
+            &[finding("workspace/src/main.nr", "Function 'foo' is unused")],
+            root,
+        );
+
+        assert_eq!(baseline.entries[0].file, "src/main.nr");
+        assert!(baseline.contains(
+            &finding("workspace/src/main.nr", "Function 'foo' is unused"),
+            root
+        ));
+        assert!(!baseline.contains(
+            &finding("workspace/src/main.nr", "Function 'bar' is unused"),
+            root
+        ));
+        assert!(!baseline.contains(
+            &finding("workspace/src/lib.nr", "Function 'foo' is unused"),
+            root
+        ));
+    }
+
+    #[test]
+    fn test_baseline_matches_reworded_findings_by_fingerprint() {
+        let root = Path::new("workspace");
+        let reported = |description: &str| {
+            let mut lint = finding("workspace/src/main.nr", description).lint;
+            lint.span = Some(Span::from(3..6));
+            Finding::new(lint, Path::new("workspace/src/main.nr"), "fn foo() {}\n")
+        };
+        let baseline = Baseline::from_findings(&[reported("Function 'foo' is unused")], root);
+
+        assert!(baseline.contains(&reported("Function 'foo' is never called"), root));
+    }
+
+    #[test]
+    fn test_unmatched_entries_are_the_fixed_findings() {
+        let root = Path::new("workspace");
+        let baseline = Baseline::from_findings(
+            &[
+                finding("workspace/src/main.nr", "Function 'foo' is unused"),
+                finding("workspace/src/main.nr", "Function 'bar' is unused"),
+            ],
+            root,
+        );
+
+        let unmatched = baseline.unmatched(
+            &[finding("workspace/src/main.nr", "Function 'foo' is unused")],
+            root,
+        );
+
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].message, "Function 'bar' is unused");
+    }
+
+    #[test]
+    fn test_baseline_round_trips_through_json() {
+        let baseline = Baseline::new(vec![BaselineEntry {
+            rule: "unused-function".to_string(),
+            file: "src/main.nr".to_string(),
+            line: Some(4),
+            message: "Function 'foo' is unused".to_string(),
+            fingerprint: Some("3f1c8e0a9b2d4c57".to_string()),
+        }]);
+
+        let content = serde_json::to_string(&baseline).unwrap();
+
+        assert_eq!(Baseline::parse(&content).unwrap(), baseline);
+    }
+
+    #[test]
+    fn test_baseline_rejects_unknown_versions() {
+        assert!(matches!(
+            Baseline::parse(r#"{ "version": 2, "entries": [] }"#),
+            Err(BaselineError::UnsupportedVersion(2))
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+//! # Nargo Warning Import
+//!
+//! Converts the warnings printed by `nargo compile` (or `nargo check`) into baseline
+//! entries, so that teams migrating to the analyzer can start from the warnings they
+//! already accepted.
+//!
+//! Nargo reports warnings as:
+//! ```text
+//! warning: unused function foo
+//!   ┌─ src/main.nr:1:4
+//!   │
+//! 1 │ fn foo() {}
+//!   │    --- unused function
+//! ```
+//! Warnings also reported by one of the analyzer's lints are converted to that lint, so
+//! the baseline suppresses it. Every other warning is kept under the `nargo` rule.
+
+use crate::baseline::BaselineEntry;
+
+/// Rule of imported warnings that do not correspond to an analyzer lint.
+pub const NARGO_RULE: &str = "nargo";
+
+/// Parses `nargo` output, returning one baseline entry per located warning.
+///
+/// Errors, notes and warnings without a source location are skipped. ANSI color codes are
+/// stripped, so colored output can be imported as-is.
+pub fn parse_warnings(output: &str) -> Vec<BaselineEntry> {
+    let mut entries = vec![];
+    let mut pending: Option<String> = None;
+
+    for line in output.lines() {
+        let line = strip_ansi_codes(line);
+        let trimmed = line.trim();
+
+        if let Some(message) = trimmed.strip_prefix("warning: ") {
+            pending = Some(message.trim().to_string());
+        } else if trimmed.starts_with("error") {
+            pending = None;
+        } else if let Some(location) = trimmed.strip_prefix("┌─ ") {
+            let Some(message) = pending.take() else {
+                continue;
+            };
+            let Some((file, line)) = parse_location(location) else {
+                continue;
+            };
+
+            let (rule, message) = convert_warning(&message);
+            entries.push(BaselineEntry {
+                rule,
+                file,
+                line: Some(line),
+                message,
+                fingerprint: None,
+            });
+        }
+    }
+
+    entries
+}
+
+/// Maps a nargo warning message to the matching analyzer lint and description.
+fn convert_warning(message: &str) -> (String, String) {
+    match message.strip_prefix("unused function ") {
+        Some(name) => (
+            "unused-function".to_string(),
+            format!("Function '{name}' is unused"),
+        ),
+        None => (NARGO_RULE.to_string(), message.to_string()),
+    }
+}
+
+/// Parses a `path:line:column` location.
+fn parse_location(location: &str) -> Option<(String, usize)> {
+    let mut parts = location.trim().rsplitn(3, ':');
+    let _column = parts.next()?;
+    let line = parts.next()?.parse().ok()?;
+    let file = parts.next()?;
+
+    let file = file.strip_prefix("./").unwrap_or(file);
+    Some((file.replace('\\', "/"), line))
+}
+
+/// Removes ANSI escape sequences (e.g. colors) from a line.
+fn strip_ansi_codes(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the control sequence up to its final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            output.push(c);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "\
+warning: unused function foo
+  ┌─ src/main.nr:1:4
+  │
+1 │ fn foo() {}
+  │    --- unused function
+  │
+
+\x1b[33mwarning\x1b[0m: unused variable x
+  ┌─ ./src/utils.nr:3:9
+  │
+3 │     let x = 1;
+  │         - unused variable
+  │
+
+error: Expected a ; separating these two statements
+  ┌─ src/lib.nr:2:5
+  │
+";
+
+    #[test]
+    fn test_parse_warnings_converts_located_warnings() {
+        let entries = parse_warnings(OUTPUT);
+
+        assert_eq!(
+            entries,
+            vec![
+                BaselineEntry {
+                    rule: "unused-function".to_string(),
+                    file: "src/main.nr".to_string(),
+                    line: Some(1),
+                    message: "Function 'foo' is unused".to_string(),
+                    fingerprint: None,
+                },
+                BaselineEntry {
+                    rule: NARGO_RULE.to_string(),
+                    file: "src/utils.nr".to_string(),
+                    line: Some(3),
+                    message: "unused variable x".to_string(),
+                    fingerprint: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_ansi_codes() {
+        assert_eq!(
+            strip_ansi_codes("\x1b[1;33mwarning\x1b[0m: x"),
+            "warning: x"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! # Blame Enrichment
+//!
+//! Post-processing stage annotating findings with `git blame` data for the reported line
+//! (commit, author and age), so findings can be routed to the people owning the code.
+//!
+//! Every file is blamed once, no matter how many findings it has. Lines that are not
+//! committed yet, and files outside of a git repository, are left without blame.
+
+use crate::diagnostics::finding::Finding;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Last change of a source line according to `git blame`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Blame {
+    /// Hash of the commit that last changed the line.
+    pub commit: String,
+    /// Name of the commit author.
+    pub author: String,
+    /// Email of the commit author.
+    pub author_email: String,
+    /// Author timestamp, in seconds since the Unix epoch.
+    pub author_time: u64,
+    /// Days elapsed between the author timestamp and the analysis.
+    pub age_days: u64,
+}
+
+/// Annotates findings with blame data, caching the blame of every file.
+pub struct Blamer {
+    now: u64,
+    files: BTreeMap<PathBuf, Vec<Option<Blame>>>,
+}
+
+impl Default for Blamer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Blamer {
+    /// Creates a blamer computing ages relative to the current time.
+    pub fn new() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+
+        Self {
+            now,
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Fills in the blame of every finding that has a line.
+    pub fn enrich(&mut self, findings: &mut [Finding]) {
+        for finding in findings {
+            if let Some(line) = finding.line {
+                finding.blame = self.blame(&finding.file_path, line);
+            }
+        }
+    }
+
+    /// Returns the blame of a 1-based line of the given file.
+    pub fn blame(&mut self, path: &Path, line: usize) -> Option<Blame> {
+        let now = self.now;
+        let lines = self
+            .files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| blame_file(path, now));
+
+        lines.get(line.checked_sub(1)?).cloned().flatten()
+    }
+}
+
+/// Runs `git blame` on a whole file, returning one entry per line.
+fn blame_file(path: &Path, now: u64) -> Vec<Option<Blame>> {
+    let (Some(directory), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return vec![];
+    };
+    let directory = if directory.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        directory
+    };
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(["blame", "--line-porcelain", "--"])
+        .arg(file_name)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_line_porcelain(&String::from_utf8_lossy(&output.stdout), now)
+        }
+        _ => vec![],
+    }
+}
+
+/// Parses the output of `git blame --line-porcelain`, returning one entry per line.
+///
+/// Lines that are not committed yet have no blame.
+pub fn parse_line_porcelain(output: &str, now: u64) -> Vec<Option<Blame>> {
+    let mut lines = vec![];
+    let mut current: Option<Blame> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            // The source line itself closes the entry
+            lines.push(
+                current
+                    .take()
+                    .filter(|blame| blame.commit.bytes().any(|byte| byte != b'0')),
+            );
+            continue;
+        }
+
+        let Some(blame) = current.as_mut() else {
+            let commit = line.split_whitespace().next().unwrap_or_default();
+            current = Some(Blame {
+                commit: commit.to_string(),
+                author: String::new(),
+                author_email: String::new(),
+                author_time: 0,
+                age_days: 0,
+            });
+            continue;
+        };
+
+        if let Some(author) = line.strip_prefix("author ") {
+            blame.author = author.to_string();
+        } else if let Some(email) = line.strip_prefix("author-mail ") {
+            blame.author_email = email.trim_matches(['<', '>']).to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            blame.author_time = time.parse().unwrap_or_default();
+            blame.age_days = now.saturating_sub(blame.author_time) / SECONDS_PER_DAY;
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORCELAIN: &str = "\
+4b825dc642cb6eb9a060e54bf8d69288fbee4904 1 1 1
+author Alice
+author-mail <alice@example.com>
+author-time 1700000000
+author-tz +0000
+committer Alice
+committer-mail <alice@example.com>
+committer-time 1700000000
+committer-tz +0000
+summary Add main
+filename src/main.nr
+\tfn main() {}
+0000000000000000000000000000000000000000 2 2 1
+author Not Committed Yet
+author-mail <not.committed.yet>
+author-time 1710000000
+author-tz +0000
+committer Not Committed Yet
+committer-mail <not.committed.yet>
+committer-time 1710000000
+committer-tz +0000
+summary Version of src/main.nr from src/main.nr
+filename src/main.nr
+\tfn helper() {}
+";
+
+    #[test]
+    fn test_parse_line_porcelain_reads_committed_lines() {
+        let now = 1700000000 + 10 * SECONDS_PER_DAY;
+        let lines = parse_line_porcelain(PORCELAIN, now);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            Some(Blame {
+                commit: "4b825dc642cb6eb9a060e54bf8d69288fbee4904".to_string(),
+                author: "Alice".to_string(),
+                author_email: "alice@example.com".to_string(),
+                author_time: 1700000000,
+                age_days: 10,
+            })
+        );
+        assert_eq!(lines[1], None);
+    }
+
+    #[test]
+    fn test_blamer_ignores_files_outside_git() {
+        let mut blamer = Blamer::new();
+
+        assert_eq!(blamer.blame(Path::new("/nonexistent/src/main.nr"), 1), None);
+    }
+}
@@ -0,0 +1,246 @@
+//! # Changed Lines
+//!
+//! Determines which lines of a workspace changed since a git reference, so that findings
+//! can be restricted to the code touched by a change (e.g. in a pull request) while the
+//! whole project is still analyzed for context.
+//!
+//! Changes are read from `git diff --unified=0` against the reference, which includes
+//! uncommitted modifications. Untracked files are considered changed in their entirety.
+
+use crate::diagnostics::lint::Lint;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ChangesError {
+    #[error("Failed to run git: {0}")]
+    GitUnavailable(String),
+    #[error("git {0} failed: {1}")]
+    GitFailed(String, String),
+}
+
+/// Lines changed in a single file.
+#[derive(Debug, Clone, PartialEq)]
+enum FileChanges {
+    /// The file is new, every line counts as changed.
+    All,
+    /// Inclusive, 1-based line ranges.
+    Lines(Vec<(usize, usize)>),
+}
+
+/// The lines changed in a workspace, keyed by paths relative to the workspace root.
+#[derive(Debug, Clone, Default)]
+pub struct ChangedLines {
+    root: PathBuf,
+    files: BTreeMap<PathBuf, FileChanges>,
+}
+
+impl ChangedLines {
+    /// Collects the lines changed below `root` since the given git reference.
+    pub fn since(root: &Path, git_ref: &str) -> Result<Self, ChangesError> {
+        // A manifest given as a bare file name has an empty parent directory
+        let root = if root.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            root
+        };
+
+        let diff = run_git(
+            root,
+            &[
+                "diff",
+                "--relative",
+                "--no-prefix",
+                "--no-color",
+                "--no-ext-diff",
+                "--unified=0",
+                git_ref,
+            ],
+        )?;
+        let mut changes = Self::parse_diff(&diff);
+        changes.root = root.to_path_buf();
+
+        let untracked = run_git(root, &["ls-files", "--others", "--exclude-standard"])?;
+        for path in untracked.lines().filter(|line| !line.is_empty()) {
+            changes.files.insert(PathBuf::from(path), FileChanges::All);
+        }
+
+        Ok(changes)
+    }
+
+    /// Parses the output of `git diff --no-prefix --unified=0`.
+    pub fn parse_diff(diff: &str) -> Self {
+        let mut files: BTreeMap<PathBuf, FileChanges> = BTreeMap::new();
+        let mut current: Option<PathBuf> = None;
+
+        for line in diff.lines() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                // Deleted files have no new side to report findings on
+                current = (path != "/dev/null").then(|| PathBuf::from(path));
+            } else if let Some(hunk) = line.strip_prefix("@@ ") {
+                let (Some(path), Some((start, count))) = (&current, parse_hunk_header(hunk)) else {
+                    continue;
+                };
+
+                // A pure deletion touches the lines around the removed ones
+                let range = if count == 0 {
+                    (start.max(1), start + 1)
+                } else {
+                    (start, start + count - 1)
+                };
+
+                let entry = files
+                    .entry(path.clone())
+                    .or_insert_with(|| FileChanges::Lines(Vec::new()));
+                if let FileChanges::Lines(ranges) = entry {
+                    ranges.push(range);
+                }
+            }
+        }
+
+        Self {
+            root: PathBuf::new(),
+            files,
+        }
+    }
+
+    /// Returns whether the given file has any changes.
+    pub fn is_file_changed(&self, path: &Path) -> bool {
+        self.files.contains_key(self.relative(path))
+    }
+
+    /// Returns whether any line of the inclusive, 1-based range `start..=end` changed.
+    pub fn intersects(&self, path: &Path, start: usize, end: usize) -> bool {
+        match self.files.get(self.relative(path)) {
+            Some(FileChanges::All) => true,
+            Some(FileChanges::Lines(ranges)) => ranges
+                .iter()
+                .any(|(changed_start, changed_end)| start <= *changed_end && *changed_start <= end),
+            None => false,
+        }
+    }
+
+    /// Keeps the lints of `path` whose span intersects the changed lines.
+    ///
+    /// Lints without a span are kept when the file changed at all.
+    pub fn retain_changed(&self, lints: Vec<Lint>, path: &Path, source: &str) -> Vec<Lint> {
+        lints
+            .into_iter()
+            .filter(|lint| match &lint.span {
+                Some(span) => self.intersects(
+                    path,
+                    line_of(source, span.start()),
+                    line_of(source, span.end()),
+                ),
+                None => self.is_file_changed(path),
+            })
+            .collect()
+    }
+
+    fn relative<'path>(&self, path: &'path Path) -> &'path Path {
+        path.strip_prefix(&self.root).unwrap_or(path)
+    }
+}
+
+/// Parses the new-side `start,count` of a hunk header such as `-3,2 +4,5 @@ fn main() {`.
+fn parse_hunk_header(hunk: &str) -> Option<(usize, usize)> {
+    let new_side = hunk
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+
+    match new_side.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((new_side.parse().ok()?, 1)),
+    }
+}
+
+/// Returns the 1-based line containing the given byte offset.
+pub(crate) fn line_of(source: &str, byte_offset: u32) -> usize {
+    let end = (byte_offset as usize).min(source.len());
+    source.as_bytes()[..end]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+        + 1
+}
+
+fn run_git(root: &Path, args: &[&str]) -> Result<String, ChangesError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["-c", "core.quotePath=false"])
+        .args(args)
+        .output()
+        .map_err(|e| ChangesError::GitUnavailable(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(ChangesError::GitFailed(
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git src/main.nr src/main.nr
+index 1111111..2222222 100644
+--- src/main.nr
++++ src/main.nr
+@@ -3 +3 @@ fn main() {
+-    let x = 1;
++    let x = 2;
+@@ -10,0 +11,3 @@ fn helper() {
++fn added() {
++    assert(true);
++}
+@@ -20,2 +22,0 @@ fn removed() {
+-fn gone() {}
+-fn also_gone() {}
+diff --git src/old.nr src/old.nr
+deleted file mode 100644
+--- src/old.nr
++++ /dev/null
+@@ -1 +0,0 @@
+-fn old() {}
+";
+
+    #[test]
+    fn test_parse_diff_collects_changed_ranges() {
+        let changes = ChangedLines::parse_diff(DIFF);
+        let main = Path::new("src/main.nr");
+
+        assert!(changes.intersects(main, 3, 3));
+        assert!(changes.intersects(main, 1, 12));
+        assert!(changes.intersects(main, 13, 15));
+        assert!(changes.intersects(main, 23, 23));
+        assert!(!changes.intersects(main, 4, 10));
+        assert!(!changes.intersects(main, 24, 30));
+        assert!(!changes.is_file_changed(Path::new("src/old.nr")));
+    }
+
+    #[test]
+    fn test_paths_are_resolved_relative_to_root() {
+        let mut changes = ChangedLines::parse_diff(DIFF);
+        changes.root = PathBuf::from("workspace");
+
+        assert!(changes.is_file_changed(Path::new("workspace/src/main.nr")));
+        assert!(!changes.is_file_changed(Path::new("workspace/src/lib.nr")));
+    }
+
+    #[test]
+    fn test_line_of_counts_newlines() {
+        let source = "fn main() {\n    let x = 1;\n}\n";
+
+        assert_eq!(line_of(source, 0), 1);
+        assert_eq!(line_of(source, 12), 2);
+        assert_eq!(line_of(source, 100), 4);
+    }
+}
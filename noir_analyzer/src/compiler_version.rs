@@ -0,0 +1,133 @@
+//! # Compiler Version
+//!
+//! Packages declare the Noir compiler they target with `compiler_version` in `Nargo.toml`.
+//! The capability table below records which language features each Noir release introduced,
+//! so the analyzer can report syntax the targeted compiler does not understand and skip the
+//! lints about features it does not have.
+
+use crate::ast::ast_context::AstContext;
+use fm::FileId;
+use noirc_frontend::hir::resolution::errors::Span;
+use std::fmt;
+use std::str::FromStr;
+
+/// A Noir compiler release, ignoring pre-release tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NoirVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+/// A language feature introduced by a Noir release.
+pub struct Capability {
+    /// Human-readable name of the feature.
+    pub name: &'static str,
+    /// First release supporting the feature.
+    pub since: NoirVersion,
+    /// Lints that only apply to code using the feature.
+    pub lints: &'static [&'static str],
+    /// Returns the location of every use of the feature in the analyzed program.
+    pub find: fn(&AstContext) -> Vec<(Span, FileId)>,
+}
+
+/// Language features the analyzer knows the introducing release of.
+pub const CAPABILITIES: &[Capability] = &[Capability {
+    name: "unsafe blocks",
+    since: NoirVersion::new(0, 39, 0),
+    lints: &["oversized-unsafe-block"],
+    find: find_unsafe_blocks,
+}];
+
+impl NoirVersion {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Returns whether this release has the given feature.
+    pub fn supports(&self, capability: &Capability) -> bool {
+        *self >= capability.since
+    }
+
+    /// Returns whether the lint applies to programs targeting this release, i.e. whether
+    /// this release has every feature the lint is about.
+    pub fn is_lint_relevant(&self, lint_name: &str) -> bool {
+        CAPABILITIES
+            .iter()
+            .filter(|capability| capability.lints.contains(&lint_name))
+            .all(|capability| self.supports(capability))
+    }
+}
+
+impl fmt::Display for NoirVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Parses a version or the `compiler_version` requirement of `Nargo.toml`, such as
+/// `>=0.36.0` or `1.0.0-beta.3`. The lowest release accepted by the requirement is returned.
+impl FromStr for NoirVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let requirement = s.split(',').next().unwrap_or_default();
+        let version = requirement
+            .trim()
+            .trim_start_matches(['>', '<', '=', '^', '~'])
+            .trim_start();
+        let version = version.split(['-', '+']).next().unwrap_or_default();
+
+        let mut parts = version.split('.').map(|part| part.parse::<u64>());
+        let mut next_part = || match parts.next() {
+            Some(Ok(part)) => Ok(part),
+            None => Ok(0),
+            Some(Err(_)) => Err(format!("Invalid Noir version {s:?}")),
+        };
+        let major = next_part()?;
+        let minor = next_part()?;
+        let patch = next_part()?;
+        if version.is_empty() || parts.next().is_some() {
+            return Err(format!("Invalid Noir version {s:?}"));
+        }
+
+        Ok(Self::new(major, minor, patch))
+    }
+}
+
+fn find_unsafe_blocks(context: &AstContext) -> Vec<(Span, FileId)> {
+    context
+        .unsafe_blocks
+        .iter()
+        .map(|unsafe_block| (unsafe_block.span, unsafe_block.file_id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_compiler_version_requirements() {
+        assert_eq!("0.36.0".parse(), Ok(NoirVersion::new(0, 36, 0)));
+        assert_eq!(">=0.30.0".parse(), Ok(NoirVersion::new(0, 30, 0)));
+        assert_eq!(">= 0.31, <0.40".parse(), Ok(NoirVersion::new(0, 31, 0)));
+        assert_eq!("1.0.0-beta.3".parse(), Ok(NoirVersion::new(1, 0, 0)));
+        assert!("latest".parse::<NoirVersion>().is_err());
+        assert!("1.2.3.4".parse::<NoirVersion>().is_err());
+    }
+
+    #[test]
+    fn test_lints_about_newer_features_are_irrelevant() {
+        let old = NoirVersion::new(0, 30, 0);
+        let new = NoirVersion::new(1, 0, 0);
+
+        assert!(!old.is_lint_relevant("oversized-unsafe-block"));
+        assert!(new.is_lint_relevant("oversized-unsafe-block"));
+        assert!(old.is_lint_relevant("unused-function"));
+    }
+}
@@ -0,0 +1,778 @@
+//! # Analyzer Configuration
+//!
+//! This module loads the `noir-analyzer.toml` file found at the workspace root. The
+//! configuration controls the level each lint is reported at:
+//!
+//! ```toml
+//! [lints]
+//! unused-function = "warn"
+//! god-module = "allow"
+//! oversized-unsafe-block = "deny"
+//! ```
+//!
+//! Lints without an entry keep the severity they were emitted with. Opt-in lints only run
+//! when set to `warn` or `deny`.
+//!
+//! Lints taking options, such as limits or allow-lists, are configured with a table instead,
+//! whose `level` key is optional:
+//!
+//! ```toml
+//! [lints.call-chain-depth]
+//! level = "deny"
+//! max-depth = 12
+//! ```
+//!
+//! Source files can be skipped with globs relative to the workspace root:
+//!
+//! ```toml
+//! exclude = ["src/generated/**"]
+//! ```
+//!
+//! File locations are printed relative to the workspace root by default. They can be printed
+//! as absolute paths, or as `file:line:col` tokens that terminals and editors link to:
+//!
+//! ```toml
+//! locations = "clickable"
+//! ```
+//!
+//! Lint descriptions can be reworded, e.g. to link to internal guidelines. Templates use the
+//! `{name}` placeholders of the default message they replace:
+//!
+//! ```toml
+//! [messages]
+//! unused-function = "Function '{function}' is dead code, see https://wiki.example.com/noir"
+//! ```
+//!
+//! Boolean globals used as feature flags can be given a value, so that only the branches
+//! enabled by these values are analyzed. Without values, every branch is analyzed:
+//!
+//! ```toml
+//! [flags]
+//! DEBUG = false
+//! ```
+//!
+//! The main limit of a lint can also be set under `[thresholds]`, keyed by lint name. It is
+//! given to the lint as its `threshold` option, so setting the same limit in both places is
+//! an error:
+//!
+//! ```toml
+//! [thresholds]
+//! call-chain-depth = 12
+//! ```
+//!
+//! When code generated by macros is analyzed (`check --expand`), its findings can be
+//! silenced while keeping those of the source:
+//!
+//! ```toml
+//! silence_generated = true
+//! ```
+//!
+//! The findings of `check` can be posted to a webhook, see `crate::webhook`:
+//!
+//! ```toml
+//! [webhook]
+//! url = "https://hooks.example.com/noir-analyzer"
+//! ```
+//!
+//! Any key can be overridden without editing the file, e.g. per CI job, with `NOIR_ANALYZER_*`
+//! environment variables and `key=value` overrides, which take precedence over the file in
+//! that order. Keys of a table are written after its name, e.g. `thresholds.call-chain-depth=12`
+//! or `NOIR_ANALYZER_THRESHOLDS__CALL_CHAIN_DEPTH=12`, and options after the name of their
+//! lint, e.g. `lints.god-module.max-items=80`. Values are TOML, and are read as strings when
+//! they are not valid TOML, so that `lints.god-module=allow` needs no quotes.
+
+use crate::diagnostics::lint::Lint;
+use crate::diagnostics::message::MessageCatalog;
+use crate::diagnostics::reporter::LocationStyle;
+use crate::diagnostics::severity_policy::SeverityPolicy;
+use crate::lints::config_file::{CONFIG_KEYS, did_you_mean};
+use crate::lints::lint_rule::{LintRule, RuleConfigError};
+use crate::lints::registry::LintRegistry;
+use crate::webhook::WebhookConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Name of the configuration file looked up in the workspace root.
+pub const CONFIG_FILE_NAME: &str = "noir-analyzer.toml";
+
+/// Prefix of the environment variables overriding configuration keys.
+pub const ENV_PREFIX: &str = "NOIR_ANALYZER_";
+
+/// Levels a lint can be configured with.
+pub const LINT_LEVELS: &[&str] = &["allow", "warn", "deny"];
+
+/// Level at which a lint is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The lint is not reported.
+    Allow,
+    /// The lint is reported as a warning.
+    Warn,
+    /// The lint is reported as an error.
+    Deny,
+}
+
+impl TryFrom<String> for LintLevel {
+    type Error = String;
+
+    fn try_from(level: String) -> Result<Self, Self::Error> {
+        match level.as_str() {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "deny" => Ok(Self::Deny),
+            _ => Err(format!(
+                "unknown level {level:?}, expected \"allow\", \"warn\" or \"deny\"{}",
+                did_you_mean(&level, LINT_LEVELS)
+            )),
+        }
+    }
+}
+
+impl From<LintLevel> for toml::Value {
+    fn from(level: LintLevel) -> Self {
+        let level = match level {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+        };
+        toml::Value::String(level.to_string())
+    }
+}
+
+/// Entry of a lint under `[lints]`: either a level, or a table of options with an optional
+/// `level` key.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "toml::Value", into = "toml::Value")]
+pub struct LintSetting {
+    pub level: Option<LintLevel>,
+    /// Options of the lint, without the `level` key.
+    pub options: toml::Table,
+}
+
+impl TryFrom<toml::Value> for LintSetting {
+    type Error = String;
+
+    fn try_from(value: toml::Value) -> Result<Self, Self::Error> {
+        match value {
+            toml::Value::String(level) => Ok(Self {
+                level: Some(LintLevel::try_from(level)?),
+                options: toml::Table::new(),
+            }),
+            toml::Value::Table(mut options) => {
+                let level = match options.remove("level") {
+                    Some(toml::Value::String(level)) => Some(LintLevel::try_from(level)?),
+                    Some(level) => return Err(format!("invalid level {level}, expected a string")),
+                    None => None,
+                };
+                Ok(Self { level, options })
+            }
+            value => Err(format!(
+                "expected a level or a table of options, found {}",
+                value.type_str()
+            )),
+        }
+    }
+}
+
+impl From<LintSetting> for toml::Value {
+    fn from(setting: LintSetting) -> Self {
+        match setting.level {
+            Some(level) if setting.options.is_empty() => level.into(),
+            level => {
+                let mut table = setting.options;
+                if let Some(level) = level {
+                    table.insert("level".to_string(), level.into());
+                }
+                toml::Value::Table(table)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Opening {0:?} with error: {1:?}")]
+    FileReadError(PathBuf, String),
+    #[error("Invalid configuration: {0}")]
+    ParseError(String),
+    #[error("Invalid configuration override {0:?}: {1}")]
+    InvalidOverride(String, String),
+    #[error(transparent)]
+    RuleOptions(#[from] RuleConfigError),
+}
+
+/// A configuration key set outside of the configuration file, from the environment or the
+/// command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigOverride {
+    /// Top-level key, e.g. `thresholds`.
+    pub key: String,
+    /// Key within the table of `key`, e.g. `call-chain-depth`, if any.
+    pub entry: Option<String>,
+    pub value: toml::Value,
+}
+
+impl ConfigOverride {
+    /// Parses a `key=value` override, e.g. `thresholds.call-chain-depth=12`. Only the first
+    /// `.` separates the table from its entry, so that message identifiers can be overridden
+    /// too, e.g. `messages.redundant-import.self=...`.
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let invalid = |reason: String| ConfigError::InvalidOverride(text.to_string(), reason);
+        let (path, value) = text
+            .split_once('=')
+            .ok_or_else(|| invalid("expected key=value".to_string()))?;
+        let (key, entry) = match path.trim().split_once('.') {
+            Some((key, entry)) => (key, Some(entry.to_string())),
+            None => (path.trim(), None),
+        };
+        Self::new(key, entry, value.trim()).map_err(invalid)
+    }
+
+    /// Reads the overrides from the `NOIR_ANALYZER_*` environment variables, given as name
+    /// and value pairs. The rest of the name is the key, lowercased, with `__` separating a
+    /// table from its entry, whose `_` stand for `-` as in lint names and `__` for `.`, e.g.
+    /// `NOIR_ANALYZER_LINTS__GOD_MODULE__MAX_ITEMS=80`. Feature flags keep their case.
+    pub fn from_env(
+        variables: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Vec<Self>, ConfigError> {
+        let mut overrides = vec![];
+        for (name, value) in variables {
+            let Some(path) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let (key, entry) = match path.split_once("__") {
+                Some((key, entry)) => (key.to_lowercase(), Some(entry)),
+                None => (path.to_lowercase(), None),
+            };
+            let entry = entry.map(|entry| {
+                if key == "flags" {
+                    entry.to_string()
+                } else {
+                    entry.to_lowercase().replace("__", ".").replace('_', "-")
+                }
+            });
+            let config_override = Self::new(&key, entry, &value)
+                .map_err(|reason| ConfigError::InvalidOverride(name.clone(), reason))?;
+            overrides.push(config_override);
+        }
+        Ok(overrides)
+    }
+
+    fn new(key: &str, entry: Option<String>, value: &str) -> Result<Self, String> {
+        if !CONFIG_KEYS.contains(&key) {
+            return Err(format!(
+                "unknown configuration key '{key}'{}",
+                did_you_mean(key, CONFIG_KEYS)
+            ));
+        }
+        let value = toml::from_str::<toml::Table>(&format!("value = {value}"))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or_else(|| toml::Value::String(value.to_string()));
+        Ok(Self {
+            key: key.to_string(),
+            entry,
+            value,
+        })
+    }
+
+    /// Sets the key in a configuration table.
+    fn apply(&self, table: &mut toml::Table) -> Result<(), ConfigError> {
+        let Some(entry) = &self.entry else {
+            table.insert(self.key.clone(), self.value.clone());
+            return Ok(());
+        };
+        let section = table
+            .entry(self.key.clone())
+            .or_insert(toml::Value::Table(toml::Table::new()));
+        let toml::Value::Table(section) = section else {
+            return Err(ConfigError::InvalidOverride(
+                format!("{}.{entry}", self.key),
+                format!("'{}' is not a table", self.key),
+            ));
+        };
+        if self.key != "lints" {
+            section.insert(entry.clone(), self.value.clone());
+            return Ok(());
+        }
+
+        // Levels and options of a lint are merged into its table, so that overriding one
+        // keeps the others
+        let (lint_name, option) = entry.split_once('.').unwrap_or((entry.as_str(), "level"));
+        let setting = section
+            .entry(lint_name.to_string())
+            .or_insert(toml::Value::Table(toml::Table::new()));
+        if let toml::Value::String(level) = setting {
+            let level = toml::Value::String(level.clone());
+            *setting = toml::Value::Table(toml::Table::from_iter([("level".to_string(), level)]));
+        }
+        let toml::Value::Table(options) = setting else {
+            return Err(ConfigError::InvalidOverride(
+                format!("{}.{entry}", self.key),
+                format!("'{}.{lint_name}' is not a table", self.key),
+            ));
+        };
+        options.insert(option.to_string(), self.value.clone());
+        Ok(())
+    }
+}
+
+/// Analyzer settings loaded from `noir-analyzer.toml`.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    /// Levels and options of the lints, keyed by lint name.
+    #[serde(default)]
+    pub lints: BTreeMap<String, LintSetting>,
+    /// Globs of source files skipped during analysis, relative to the workspace root.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Values of the globals used as feature flags, keyed by global name.
+    #[serde(default)]
+    pub flags: BTreeMap<String, bool>,
+    /// How file locations are printed in human-readable reports.
+    #[serde(default)]
+    pub locations: LocationStyle,
+    /// Message templates overriding the default lint descriptions, keyed by message id.
+    #[serde(default)]
+    pub messages: MessageCatalog,
+    /// Limits of the lints comparing against one, keyed by lint name.
+    #[serde(default)]
+    pub thresholds: BTreeMap<String, usize>,
+    /// Whether the findings in code generated by macros are dropped.
+    #[serde(default)]
+    pub silence_generated: bool,
+    /// Webhook the findings of `check` are posted to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<WebhookConfig>,
+}
+
+impl Config {
+    /// Parses a configuration from TOML source.
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        Self::parse_with_overrides(content, &[])
+    }
+
+    /// Parses a configuration from TOML source, with the overrides applied over it in order.
+    pub fn parse_with_overrides(
+        content: &str,
+        overrides: &[ConfigOverride],
+    ) -> Result<Self, ConfigError> {
+        let config: Self = if overrides.is_empty() {
+            // Errors keep their location in the file
+            toml::from_str(content)
+        } else {
+            let mut table: toml::Table =
+                toml::from_str(content).map_err(|e| ConfigError::ParseError(e.to_string()))?;
+            for config_override in overrides {
+                config_override.apply(&mut table)?;
+            }
+            toml::Value::Table(table).try_into()
+        }
+        .map_err(|e| ConfigError::ParseError(e.to_string()))?;
+        config
+            .messages
+            .validate()
+            .map_err(ConfigError::ParseError)?;
+        Ok(config)
+    }
+
+    /// Loads the configuration file at the given path.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ConfigError::FileReadError(path.to_path_buf(), e.to_string()))?;
+        Self::parse(&content)
+    }
+
+    /// Loads `noir-analyzer.toml` from the given directory, falling back to the default
+    /// configuration when the file does not exist.
+    pub fn load_from_dir(dir: &Path) -> Result<Self, ConfigError> {
+        let path = dir.join(CONFIG_FILE_NAME);
+        if path.exists() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Loads `noir-analyzer.toml` from the given directory like `load_from_dir`, with the
+    /// overrides applied over it in order.
+    pub fn load_with_overrides(
+        dir: &Path,
+        overrides: &[ConfigOverride],
+    ) -> Result<Self, ConfigError> {
+        let path = dir.join(CONFIG_FILE_NAME);
+        let content = if path.exists() {
+            fs::read_to_string(&path)
+                .map_err(|e| ConfigError::FileReadError(path.clone(), e.to_string()))?
+        } else {
+            String::new()
+        };
+        Self::parse_with_overrides(&content, overrides)
+    }
+
+    /// Renders the configuration as TOML, defaults included.
+    pub fn to_toml(&self) -> String {
+        toml::Value::try_from(self)
+            .and_then(|value| toml::to_string_pretty(&value))
+            .expect("Configurations serialize to TOML")
+    }
+
+    /// Returns the configured level of a lint, if any.
+    pub fn level(&self, lint_name: &str) -> Option<LintLevel> {
+        self.lints.get(lint_name).and_then(|setting| setting.level)
+    }
+
+    /// Returns the configured limit of a lint, if any.
+    pub fn threshold(&self, lint_name: &str) -> Option<usize> {
+        self.thresholds.get(lint_name).copied()
+    }
+
+    /// Returns the configured options of a lint, with its limit under `[thresholds]` as the
+    /// `threshold` option, if any is set.
+    pub fn options(&self, lint_name: &str) -> Option<toml::Value> {
+        let mut options = self
+            .lints
+            .get(lint_name)
+            .map(|setting| setting.options.clone())
+            .unwrap_or_default();
+        if let Some(threshold) = self.threshold(lint_name) {
+            options.insert(
+                "threshold".to_string(),
+                toml::Value::Integer(threshold as i64),
+            );
+        }
+        (!options.is_empty()).then_some(toml::Value::Table(options))
+    }
+
+    /// Configures lint rules with their options, failing on options a rule does not take.
+    pub fn configure(&self, lint_rules: &mut [Box<dyn LintRule>]) -> Result<(), ConfigError> {
+        for lint_rule in lint_rules {
+            if let Some(options) = self.options(lint_rule.name()) {
+                lint_rule.configure(options)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether a lint rule should run: opt-in rules only run when configured with a
+    /// `warn` or `deny` level.
+    pub fn enables(&self, lint_rule: &dyn LintRule) -> bool {
+        lint_rule.enabled_by_default()
+            || matches!(
+                self.level(lint_rule.name()),
+                Some(LintLevel::Warn | LintLevel::Deny)
+            )
+    }
+
+    /// Enables the opt-in rules of a registry configured with a `warn` or `deny` level.
+    pub fn enable_configured(&self, registry: &mut LintRegistry) {
+        for (name, setting) in &self.lints {
+            if matches!(setting.level, Some(LintLevel::Warn | LintLevel::Deny)) {
+                // Unknown names are reported by the validation of the configuration
+                let _ = registry.enable(name);
+            }
+        }
+    }
+
+    /// Returns the severity policy applying the configured levels.
+    pub fn severity_policy(&self) -> SeverityPolicy {
+        self.lints
+            .iter()
+            .filter_map(|(rule, setting)| Some((rule, setting.level?)))
+            .fold(SeverityPolicy::new(), |policy, (rule, level)| {
+                policy.with_level(rule, level)
+            })
+    }
+
+    /// Applies the configured levels, dropping allowed lints and adjusting severities, and
+    /// renders descriptions with the configured message templates.
+    pub fn apply(&self, lints: Vec<Lint>) -> Vec<Lint> {
+        let mut lints = self.severity_policy().apply(lints);
+        for lint in &mut lints {
+            if let Some(message) = &lint.message {
+                lint.description = self.messages.render(message);
+            }
+        }
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::ast_context::AstContext;
+    use crate::diagnostics::lint::{Confidence, Severity};
+    use crate::diagnostics::message::Message;
+    use crate::lints::call_chain_depth::CallChainDepth;
+
+    fn lint(name: &'static str) -> Lint {
+        Lint {
+            name,
+            severity: Severity::Warning,
+            confidence: Confidence::High,
+            description: String::new(),
+            message: None,
+            span: None,
+            file_id: None,
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn test_config_parses_lint_levels() {
+        let config = Config::parse(
+            r#"
+            [lints]
+            unused-function = "allow"
+            god-module = "deny"
+            "#,
+        )
+        .expect("Should have parsed");
+
+        assert_eq!(config.level("unused-function"), Some(LintLevel::Allow));
+        assert_eq!(config.level("god-module"), Some(LintLevel::Deny));
+        assert_eq!(config.level("oversized-unsafe-block"), None);
+    }
+
+    #[test]
+    fn test_config_parses_lint_options() {
+        let config = Config::parse(
+            r#"
+            [lints.god-module]
+            level = "deny"
+            max-fan-in = 20
+
+            [lints.stdlib-shadowing]
+            allow = ["println"]
+
+            [thresholds]
+            god-module = 80
+            "#,
+        )
+        .expect("Should have parsed");
+
+        assert_eq!(config.level("god-module"), Some(LintLevel::Deny));
+        assert_eq!(config.level("stdlib-shadowing"), None);
+        let options: toml::Value = toml::from_str("max-fan-in = 20\nthreshold = 80").unwrap();
+        assert_eq!(config.options("god-module"), Some(options));
+        assert_eq!(config.options("unused-function"), None);
+        assert!(Config::parse("[lints.god-module]\nlevel = \"warm\"").is_err());
+    }
+
+    #[test]
+    fn test_config_parses_exclude_globs() {
+        let config = Config::parse(r#"exclude = ["src/generated/**", "vendor/**"]"#)
+            .expect("Should have parsed");
+
+        assert_eq!(config.exclude, vec!["src/generated/**", "vendor/**"]);
+        assert!(config.lints.is_empty());
+    }
+
+    #[test]
+    fn test_config_parses_flag_values() {
+        let config = Config::parse("[flags]\nDEBUG = false\nUSE_POSEIDON = true")
+            .expect("Should have parsed");
+
+        assert_eq!(config.flags.get("DEBUG"), Some(&false));
+        assert_eq!(config.flags.get("USE_POSEIDON"), Some(&true));
+    }
+
+    #[test]
+    fn test_config_parses_thresholds() {
+        let config =
+            Config::parse("[thresholds]\ncall-chain-depth = 12").expect("Should have parsed");
+
+        assert_eq!(config.threshold("call-chain-depth"), Some(12));
+        assert_eq!(config.threshold("god-module"), None);
+    }
+
+    #[test]
+    fn test_config_parses_generated_code_switch() {
+        let config = Config::parse("silence_generated = true").expect("Should have parsed");
+        assert!(config.silence_generated);
+        assert!(!Config::default().silence_generated);
+    }
+
+    #[test]
+    fn test_config_parses_location_style() {
+        let config = Config::parse("locations = \"clickable\"").expect("Should have parsed");
+
+        assert_eq!(config.locations, LocationStyle::Clickable);
+        assert_eq!(Config::default().locations, LocationStyle::Relative);
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_levels() {
+        assert!(Config::parse("[lints]\nunused-function = \"loud\"").is_err());
+        let error = Config::parse("[lints]\nunused-function = \"warm\"").unwrap_err();
+        assert!(error.to_string().contains("did you mean 'warn'?"));
+    }
+
+    #[test]
+    fn test_config_applies_levels() {
+        let config = Config::parse(
+            r#"
+            [lints]
+            unused-function = "allow"
+            god-module = "deny"
+            "#,
+        )
+        .unwrap();
+
+        let result = config.apply(vec![
+            lint("unused-function"),
+            lint("god-module"),
+            lint("oversized-unsafe-block"),
+        ]);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].name, "god-module");
+        assert_eq!(result[0].severity, Severity::Error);
+        assert_eq!(result[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_config_applies_message_templates() {
+        let config = Config::parse(
+            r#"
+            [messages]
+            unused-function = "'{function}' is dead code, see https://wiki.example.com/noir"
+            "#,
+        )
+        .unwrap();
+
+        let mut unused = lint("unused-function");
+        unused.message = Some(Message::new("unused-function").with("function", "helper"));
+        let result = config.apply(vec![unused]);
+
+        assert_eq!(
+            result[0].description,
+            "'helper' is dead code, see https://wiki.example.com/noir"
+        );
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_placeholders() {
+        assert!(Config::parse("[messages]\nunused-function = \"{name} is unused\"").is_err());
+    }
+
+    #[test]
+    fn test_overrides_take_precedence_over_the_file() {
+        let env = [
+            ("NOIR_ANALYZER_LINTS__GOD_MODULE", "deny"),
+            ("NOIR_ANALYZER_FLAGS__DEBUG", "true"),
+            ("NOIR_ANALYZER_SILENCE_GENERATED", "true"),
+            ("HOME", "/root"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        let mut overrides = ConfigOverride::from_env(env).unwrap();
+        overrides.push(ConfigOverride::parse("thresholds.call-chain-depth=12").unwrap());
+        overrides.push(ConfigOverride::parse("lints.god-module=warn").unwrap());
+
+        let config = Config::parse_with_overrides(
+            "[lints]\ngod-module = \"allow\"\nunused-function = \"allow\"\n",
+            &overrides,
+        )
+        .unwrap();
+
+        assert_eq!(config.level("god-module"), Some(LintLevel::Warn));
+        assert_eq!(config.level("unused-function"), Some(LintLevel::Allow));
+        assert_eq!(config.flags.get("DEBUG"), Some(&true));
+        assert_eq!(config.threshold("call-chain-depth"), Some(12));
+        assert!(config.silence_generated);
+        assert!(config.to_toml().contains("god-module = \"warn\""));
+    }
+
+    #[test]
+    fn test_overrides_of_unknown_keys_are_rejected() {
+        let error = ConfigOverride::parse("threshold.call-chain-depth=12").unwrap_err();
+        assert!(error.to_string().contains("did you mean 'thresholds'?"));
+        assert!(ConfigOverride::parse("lints.god-module").is_err());
+    }
+
+    #[test]
+    fn test_overrides_merge_lint_options() {
+        let env = [("NOIR_ANALYZER_LINTS__GOD_MODULE__MAX_FAN_IN", "20")]
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+        let mut overrides = ConfigOverride::from_env(env).unwrap();
+        overrides.push(ConfigOverride::parse("lints.god-module.max-items=80").unwrap());
+
+        let config =
+            Config::parse_with_overrides("[lints]\ngod-module = \"deny\"\n", &overrides).unwrap();
+
+        assert_eq!(config.level("god-module"), Some(LintLevel::Deny));
+        let options: toml::Value = toml::from_str("max-fan-in = 20\nmax-items = 80").unwrap();
+        assert_eq!(config.options("god-module"), Some(options));
+    }
+
+    #[test]
+    fn test_config_configures_rules_with_options() {
+        let mut lint_rules: Vec<Box<dyn LintRule>> =
+            vec![Box::new(CallChainDepth::default()), Box::new(OptInRule)];
+        let config = Config::parse("[lints.call-chain-depth]\nmax-depth = 12").unwrap();
+        assert!(config.configure(&mut lint_rules).is_ok());
+
+        let config = Config::parse("[lints.call-chain-depth]\nmax-dept = 12").unwrap();
+        let error = config.configure(&mut lint_rules).unwrap_err();
+        assert!(error.to_string().contains("unknown field `max-dept`"));
+
+        let config = Config::parse("[thresholds]\nopt-in-rule = 3").unwrap();
+        let error = config.configure(&mut lint_rules).unwrap_err();
+        assert_eq!(error.to_string(), "Lint 'opt-in-rule' takes no options");
+    }
+
+    struct OptInRule;
+
+    impl LintRule for OptInRule {
+        fn name(&self) -> &'static str {
+            "opt-in-rule"
+        }
+
+        fn explanation(&self) -> &'static str {
+            ""
+        }
+
+        fn boxed_clone(&self) -> Box<dyn LintRule> {
+            Box::new(OptInRule)
+        }
+
+        fn enabled_by_default(&self) -> bool {
+            false
+        }
+
+        fn lint(&self, _: &AstContext) -> Vec<Lint> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_config_enables_opt_in_rules_with_a_level() {
+        assert!(!Config::default().enables(&OptInRule));
+        assert!(
+            !Config::parse("[lints]\nopt-in-rule = \"allow\"")
+                .unwrap()
+                .enables(&OptInRule)
+        );
+        assert!(
+            Config::parse("[lints]\nopt-in-rule = \"warn\"")
+                .unwrap()
+                .enables(&OptInRule)
+        );
+    }
+
+    #[test]
+    fn test_config_enables_opt_in_rules_of_the_registry() {
+        let config = Config::parse(
+            "[lints]\nimport-order = \"warn\"\nstruct-field-order = \"allow\"\nno-such-lint = \"deny\"",
+        )
+        .unwrap();
+        let mut registry = LintRegistry::builtin();
+        config.enable_configured(&mut registry);
+        assert!(registry.is_enabled("import-order"));
+        assert!(!registry.is_enabled("struct-field-order"));
+    }
+}
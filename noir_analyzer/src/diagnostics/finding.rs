@@ -0,0 +1,215 @@
+//! # Finding Module
+//!
+//! A `Finding` is a lint resolved against the file it was reported in. Machine-readable
+//! reports and post-processing stages (such as blame enrichment) work on findings, since
+//! they need file paths and line numbers rather than byte spans.
+
+use crate::blame::Blame;
+use crate::diagnostics::lint::Lint;
+use std::path::{Path, PathBuf};
+
+/// Initial value of the FNV-1a hashes of fingerprints.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// Multiplier of the FNV-1a hashes of fingerprints.
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// A lint together with its location in a source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// The reported lint.
+    pub lint: Lint,
+    /// File the lint was reported in.
+    pub file_path: PathBuf,
+    /// 1-based line of the start of the lint span, if it has one.
+    pub line: Option<usize>,
+    /// 1-based column of the start of the lint span, if it has one.
+    pub column: Option<usize>,
+    /// Who last changed the reported line, filled in by blame enrichment.
+    pub blame: Option<Blame>,
+    /// Circuit lints reported on the opcodes compiled from the lint span, merged into the
+    /// finding when both the source and the circuit are analyzed.
+    pub circuit_evidence: Vec<Lint>,
+    /// Hash of the reported source line without whitespace, or of the description for
+    /// lints without a span.
+    pub context_hash: u64,
+}
+
+impl Finding {
+    /// Resolves the lint span against the source of the file it was reported in.
+    pub fn new(lint: Lint, file_path: &Path, source: &str) -> Self {
+        let location = lint.span.map(|span| line_column(source, span.start()));
+        let context_hash = match location {
+            Some((line, _)) => {
+                let line = source.lines().nth(line - 1).unwrap_or_default();
+                line.split_whitespace()
+                    .fold(FNV_OFFSET_BASIS, |hash, word| fnv1a(hash, word.as_bytes()))
+            }
+            None => fnv1a(FNV_OFFSET_BASIS, lint.description.as_bytes()),
+        };
+
+        Self {
+            lint,
+            file_path: file_path.to_path_buf(),
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column),
+            blame: None,
+            circuit_evidence: vec![],
+            context_hash,
+        }
+    }
+
+    /// Returns an identifier of the finding that survives code moving around: a hash of its
+    /// rule, of its file relative to the workspace root and of its context. Findings on
+    /// identical lines of a file share their fingerprint.
+    pub fn fingerprint(&self, root: &Path) -> String {
+        let file = relative_file(&self.file_path, root);
+        let parts: [&[u8]; 3] = [
+            self.lint.name.as_bytes(),
+            file.as_bytes(),
+            &self.context_hash.to_le_bytes(),
+        ];
+        // Parts are separated by a zero byte, so that they cannot run into each other
+        let hash = parts.into_iter().fold(FNV_OFFSET_BASIS, |hash, bytes| {
+            fnv1a(fnv1a(hash, bytes), &[0])
+        });
+        format!("{hash:016x}")
+    }
+}
+
+/// Renders a path relative to the workspace root, using forward slashes.
+pub(crate) fn relative_file(path: &Path, root: &Path) -> String {
+    let path = path.strip_prefix(root).unwrap_or(path);
+    let path = path.strip_prefix(".").unwrap_or(path);
+    path.display().to_string().replace('\\', "/")
+}
+
+/// Continues a 64-bit FNV-1a hash with the given bytes. Unlike the hashers of the standard
+/// library, it is stable across Rust releases and platforms.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Sorts findings by file, location, rule and description, so that reports do not depend on
+/// the order in which rules and files were analyzed. The sort is stable: identical findings
+/// keep their order.
+pub fn sort_findings(findings: &mut [Finding]) {
+    let key = |finding: &Finding| {
+        (
+            finding.file_path.clone(),
+            finding.line,
+            finding.column,
+            finding.lint.name,
+            finding.lint.description.clone(),
+        )
+    };
+    findings.sort_by_cached_key(key);
+}
+
+/// Returns the 1-based (line, column) of a byte offset in the source.
+fn line_column(source: &str, byte_offset: u32) -> (usize, usize) {
+    let offset = (byte_offset as usize).min(source.len());
+    let before = &source.as_bytes()[..offset];
+    let line_start = before
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let line = before.iter().filter(|byte| **byte == b'\n').count() + 1;
+
+    (line, offset - line_start + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::lint::{Confidence, Severity};
+    use noirc_frontend::hir::resolution::errors::Span;
+
+    #[test]
+    fn test_finding_resolves_line_and_column() {
+        let source = "fn main() {}\nfn unused() {}\n";
+        let lint = Lint {
+            name: "unused-function",
+            severity: Severity::Warning,
+            confidence: Confidence::High,
+            description: "Function 'unused' is unused".to_string(),
+            message: None,
+            span: Some(Span::from(16..22)),
+            file_id: None,
+            suggestion: None,
+        };
+
+        let finding = Finding::new(lint, Path::new("src/main.nr"), source);
+
+        assert_eq!(finding.line, Some(2));
+        assert_eq!(finding.column, Some(4));
+        assert!(finding.blame.is_none());
+    }
+
+    #[test]
+    fn test_fingerprints_survive_moved_and_reindented_lines() {
+        let lint = || Lint {
+            name: "unused-function",
+            severity: Severity::Warning,
+            confidence: Confidence::High,
+            description: "Function 'unused' is unused".to_string(),
+            message: None,
+            span: Some(Span::from(16..22)),
+            file_id: None,
+            suggestion: None,
+        };
+        let root = Path::new("workspace");
+        let file_path = Path::new("workspace/src/main.nr");
+        let finding = Finding::new(lint(), file_path, "fn main() {}\nfn unused() {}\n");
+
+        let mut moved_lint = lint();
+        moved_lint.span = Some(Span::from(21..27));
+        let moved = Finding::new(
+            moved_lint,
+            file_path,
+            "\nfn main() {}\n\n  fn  unused() {}\n",
+        );
+        assert_eq!(moved.line, Some(4));
+        assert_eq!(moved.fingerprint(root), finding.fingerprint(root));
+        assert_eq!(finding.fingerprint(root).len(), 16);
+
+        let other_file = Finding::new(
+            lint(),
+            Path::new("workspace/src/lib.nr"),
+            "fn main() {}\nfn unused() {}\n",
+        );
+        assert_ne!(other_file.fingerprint(root), finding.fingerprint(root));
+    }
+
+    #[test]
+    fn test_sorted_report_is_identical_across_runs() {
+        use crate::ast::analyzer::Analyzer;
+        use crate::ast::parser::Parser;
+        use crate::diagnostics::reporter::Reporter;
+        use crate::lints::lint_rule::LintRule;
+        use crate::lints::unused_function::UnusedFunction;
+
+        let source = (0..20)
+            .map(|index| format!("fn unused_{index}() {{}}\n"))
+            .chain(["fn main() {}\n".to_string()])
+            .collect::<String>();
+        let root = Parser::parse_program_with_dummy_file(&source).unwrap();
+        let lint_rules: Vec<Box<dyn LintRule>> = vec![Box::new(UnusedFunction)];
+        let report = || {
+            let lints = Analyzer::new(&lint_rules).analyze(&root).unwrap();
+            let mut findings: Vec<Finding> = lints
+                .into_iter()
+                .map(|lint| Finding::new(lint, Path::new("src/main.nr"), &source))
+                .collect();
+            sort_findings(&mut findings);
+            Reporter::json_report(&findings, Path::new(""))
+        };
+
+        let first = report();
+        for _ in 0..10 {
+            assert_eq!(report(), first);
+        }
+        assert!(first.find("unused_2").unwrap() < first.find("unused_10").unwrap());
+    }
+}
@@ -0,0 +1,52 @@
+//! # Internal Errors
+//!
+//! A bug in a lint rule, or in the analyzer itself, should not abort the analysis of a
+//! whole workspace. Panics are caught per rule and per file, and reported as
+//! `analyzer-internal-error` lints naming the rule that failed, so that the other rules and
+//! files are still analyzed and the bug can be reported with its context.
+
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use std::any::Any;
+
+/// Name of the lints reporting internal errors.
+pub const INTERNAL_ERROR_LINT: &str = "analyzer-internal-error";
+
+/// Returns the message a panic was raised with.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Reports a lint rule that panicked while analyzing a file.
+pub fn rule_panicked(rule: &str, payload: &(dyn Any + Send)) -> Lint {
+    internal_error(
+        Message::new("analyzer-internal-error.rule")
+            .with("rule", rule)
+            .with("panic", panic_message(payload)),
+    )
+}
+
+/// Reports an analysis of a file that panicked outside of the lint rules, e.g. while
+/// traversing the program.
+pub fn analysis_panicked(payload: &(dyn Any + Send)) -> Lint {
+    internal_error(
+        Message::new("analyzer-internal-error.analysis").with("panic", panic_message(payload)),
+    )
+}
+
+fn internal_error(message: Message) -> Lint {
+    Lint {
+        name: INTERNAL_ERROR_LINT,
+        severity: Severity::Warning,
+        confidence: Confidence::High,
+        description: message.to_string(),
+        message: Some(message),
+        span: None,
+        file_id: None,
+        suggestion: None,
+    }
+}
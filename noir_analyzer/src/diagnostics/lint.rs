@@ -5,8 +5,12 @@
 //!
 //! ## Overview
 //! - Each lint has a unique name, severity level, and description.
+//! - Descriptions are rendered from a `Message` of the message catalog, so that deployments
+//!   can override their wording.
 //! - Lints may also include a location (span) to point to specific code locations.
+//! - Lints may carry a `Suggestion` describing how to fix the issue.
 //! - The `Severity` enum categorizes lints as warnings or errors.
+//! - The `Confidence` enum states how likely a lint is to be a true positive.
 //!
 //! ## Future Improvements
 //! - Support for configurable lint levels.
@@ -14,8 +18,13 @@
 //! - Integration with an error-reporting framework.
 //!
 
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::Suggestion;
 use fm::FileId;
 use noirc_frontend::hir::resolution::errors::Span;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// Represents a static analysis lint detected in Noir code.
 #[derive(Debug, Clone, PartialEq)]
@@ -24,19 +33,68 @@ pub struct Lint {
     pub name: &'static str,
     /// Severity level of the lint.
     pub severity: Severity,
+    /// How certain the rule is that the issue is real.
+    pub confidence: Confidence,
     /// Human-readable description of the issue.
     pub description: String,
+    /// Catalog message the description was rendered from, rendered again when the
+    /// configuration overrides its template.
+    pub message: Option<Message>,
     /// Optional span where the lint applies.
     pub span: Option<Span>,
     /// The file where this lint occurs.
     pub file_id: Option<FileId>,
+    /// Optional suggested fix for the issue.
+    pub suggestion: Option<Suggestion>,
 }
 
-/// Defines the severity levels for lints.
-#[derive(Debug, Clone, PartialEq)]
+/// Defines the severity levels for lints, from the least to the most severe.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// Indicates a non-critical issue that may require attention.
     Warning,
     /// Indicates a serious issue that could lead to incorrect behavior.
     Error,
 }
+
+/// How certain a lint rule is that a reported issue is real.
+///
+/// Rules that only report what they can prove from the code (sound with respect to the
+/// issue they describe) use `High`. Heuristic rules that trade precision for coverage,
+/// e.g. by assuming the worst about code they cannot resolve, use `Medium` or `Low`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// The issue is likely a false positive and needs review.
+    Low,
+    /// The issue relies on heuristics and may be a false positive.
+    Medium,
+    /// The issue is derived directly from the code.
+    High,
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Confidence::Low => "low",
+            Confidence::Medium => "medium",
+            Confidence::High => "high",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Confidence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Confidence::Low),
+            "medium" => Ok(Confidence::Medium),
+            "high" => Ok(Confidence::High),
+            _ => Err(format!(
+                "Unknown confidence {s:?}, expected low, medium or high"
+            )),
+        }
+    }
+}
@@ -0,0 +1,526 @@
+//! # Message Module
+//!
+//! Lint descriptions are rendered from a catalog of message templates rather than written
+//! inline by each rule. A rule reports a `Message`, made of a message identifier and named
+//! arguments, and the catalog substitutes the arguments for the `{name}` placeholders of the
+//! template registered under that identifier.
+//!
+//! Deployments can override templates from the configuration file, e.g. to use internal
+//! phrasing or link to an internal wiki:
+//!
+//! ```toml
+//! [messages]
+//! unused-function = "Function '{function}' is dead code, see https://wiki.example.com/noir"
+//! ```
+//!
+//! Messages are identified by the name of the lint reporting them, followed by a suffix
+//! when a lint reports several kinds of messages, e.g. `redundant-import.self`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Default templates, keyed by message identifier.
+const DEFAULT_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "analyzer-internal-error.analysis",
+        "The analyzer panicked on this file ({panic}), so some rules did not run",
+    ),
+    (
+        "analyzer-internal-error.rule",
+        "Rule '{rule}' panicked on this file ({panic}), so its findings are missing",
+    ),
+    (
+        "black-box-input-range.missing",
+        "{count} of the {inputs} inputs of the {function} call at opcode {opcode} of '{circuit}' have no range constraint, although it expects {bits}-bit values, so the prover can pass any field element",
+    ),
+    (
+        "black-box-input-range.too-wide",
+        "Inputs of the {function} call at opcode {opcode} of '{circuit}' are only constrained to {actual} bits, although it expects {bits}-bit values",
+    ),
+    (
+        "bool-field-cast.flag",
+        "Is-zero comparison is cast to `Field` in '{function}'; it costs an inverse hint and two constraints however the 0/1 value is written",
+    ),
+    (
+        "bool-field-cast.multiplier",
+        "Boolean '{flag}' is used as a 0/1 multiplier in '{function}'; an `if` expression states the selection for the same single multiplication",
+    ),
+    (
+        "bool-field-cast.round-trip",
+        "Boolean cast to `Field` is compared with 0 or 1 in '{function}', which costs an is-zero check that using the `bool` avoids",
+    ),
+    (
+        "call-chain-depth",
+        "Call chain from '{entry_point}' is {depth} calls deep, more than the limit of {max}: {chain}",
+    ),
+    (
+        "conditional-assignment",
+        "Variable '{variable}' of function '{function}' is only assigned when `{condition}` holds, otherwise its placeholder value {initial} reaches {usage}",
+    ),
+    (
+        "contract-function-visibility.contradictory",
+        "Contract function '{function}' is marked both #[{first}] and #[{second}]",
+    ),
+    (
+        "contract-function-visibility.missing",
+        "Contract function '{function}' has no #[private], #[public] or #[utility] attribute, so its execution context depends on the Aztec version",
+    ),
+    (
+        "cyclic-module-imports",
+        "Modules import each other in a cycle: {cycle}",
+    ),
+    (
+        "deep-constraint-expression",
+        "Assertion in function '{function}' nests {depth} operators, more than the limit of {max}; name intermediate values with `let` to keep its constraints reviewable",
+    ),
+    (
+        "dependency-hygiene.case-duplicate",
+        "Dependency '{dependency}' differs from '{other}' only in case",
+    ),
+    (
+        "dependency-hygiene.outside-workspace",
+        "Path dependency '{dependency}' at {path} is outside the workspace root",
+    ),
+    (
+        "dependency-hygiene.unpinned",
+        "Dependency '{dependency}' on {url} has no tag or rev, so it follows the default branch",
+    ),
+    (
+        "duplicate-public-function",
+        "Public function '{function}' in package '{package}' duplicates the one in package '{original_package}'; consider moving it into a shared dependency",
+    ),
+    (
+        "empty-block.else",
+        "Empty `else` block in '{function}'",
+    ),
+    (
+        "empty-block.loop",
+        "Empty `{loop}` loop in '{function}'",
+    ),
+    (
+        "empty-block.unsafe",
+        "Empty `unsafe` block in '{function}'",
+    ),
+    (
+        "field-accumulation-overflow",
+        "Field '{accumulator}' accumulates products of up to {term_bits} bits over {iterations} iterations, and can wrap around the field modulus from iteration {wrap_iteration}",
+    ),
+    (
+        "field-modulo",
+        "'%' on a Field in '{function}' is compared with an integer; cast the operands to an integer type for integer modulo",
+    ),
+    (
+        "first-element-comparison",
+        "Assertion in function '{function}' only compares the first elements of '{lhs}' and '{rhs}', although '{whole}' is used as a whole value",
+    ),
+    (
+        "function-naming",
+        "Function '{function}' is not in snake_case; rename it to '{snake_case}'",
+    ),
+    ("generated-code", "{finding} (in code generated by {macro})"),
+    (
+        "god-module.fan-in",
+        "Module '{module}' is imported by {fan_in} modules (limit {max_fan_in}); consider splitting it",
+    ),
+    (
+        "god-module.items",
+        "Module '{module}' declares {items} items (limit {max_items}); consider splitting it",
+    ),
+    (
+        "god-module.items-and-fan-in",
+        "Module '{module}' declares {items} items (limit {max_items}) and is imported by {fan_in} modules (limit {max_fan_in}); consider splitting it",
+    ),
+    (
+        "hint-drift",
+        "Unconstrained function '{hint}' mirrors '{function}', but only {similarity}% of their tokens match, below {min}%; check that it still computes what '{function}' constrains",
+    ),
+    (
+        "import-order",
+        "Imports of '{module}' are not grouped and sorted",
+    ),
+    (
+        "inconsistent-variant-handling",
+        "Variant '{variant}' is handled without constraints, unlike '{parallel_variant}' which carries the same payload",
+    ),
+    (
+        "integer-literal-overflow",
+        "Integer literal {value} does not fit in {type}",
+    ),
+    (
+        "invalid-config.invalid-level",
+        "Invalid level {level} for lint '{lint}', expected \"allow\", \"warn\" or \"deny\"{hint}",
+    ),
+    (
+        "invalid-config.invalid-threshold",
+        "Threshold of '{lint}' must be a positive integer, found {value}",
+    ),
+    (
+        "invalid-config.no-threshold",
+        "Lint '{lint}' takes no threshold{hint}",
+    ),
+    (
+        "invalid-config.unknown-key",
+        "Unknown configuration key '{key}' is ignored{hint}",
+    ),
+    (
+        "invalid-config.unknown-lint",
+        "Unknown lint '{lint}' in [{section}] is ignored{hint}",
+    ),
+    (
+        "large-struct",
+        "Struct '{struct}' flattens to {total} field elements (limit {limit}); per field: {breakdown}",
+    ),
+    (
+        "manifest-missing-compiler-version",
+        "Package does not declare the Noir compiler it targets with `compiler_version`",
+    ),
+    (
+        "manifest-missing-entry",
+        "Entry point '{entry}' does not exist",
+    ),
+    (
+        "manifest-missing-version",
+        "Package does not declare a `version`",
+    ),
+    (
+        "manifest-unknown-key",
+        "Key '{key}' of {section} is not a manifest key, and is ignored by nargo",
+    ),
+    (
+        "manifest-wildcard-compiler-version",
+        "compiler_version '{requirement}' accepts any compiler release; pin the versions the package was tested with",
+    ),
+    (
+        "needless-visibility",
+        "Function '{function}' is {visibility} in a binary package, where nothing else can use it",
+    ),
+    (
+        "oversized-entry-point",
+        "Entry function '{function}' has {statements} statements, more than the limit of {max}; delegate to named helpers, e.g. for {outline}",
+    ),
+    (
+        "oversized-unsafe-block",
+        "Unsafe block spans {statements} statements but only {required} of them call unconstrained code",
+    ),
+    (
+        "private-assert-message",
+        "Message of an assertion in '{function}' interpolates private value '{value}', which failing proofs disclose",
+    ),
+    (
+        "private-data-leak.print",
+        "Private input '{input}' is printed in '{function}'",
+    ),
+    (
+        "private-data-leak.public-output",
+        "Private input '{input}' is returned by 'main' as a public output",
+    ),
+    (
+        "private-data-leak.unconstrained",
+        "Private input '{input}' is passed to unconstrained function '{callee}' in '{function}'",
+    ),
+    (
+        "prover-input-mismatch.array-length",
+        "{file} gives '{input}' {length} elements, but its type is `{type}`",
+    ),
+    (
+        "prover-input-mismatch.missing-field",
+        "{file} is missing field '{input}'",
+    ),
+    (
+        "prover-input-mismatch.missing-input",
+        "{file} is missing input '{input}' of main",
+    ),
+    (
+        "prover-input-mismatch.missing-public-input",
+        "{file} is missing public input '{input}' of main",
+    ),
+    (
+        "prover-input-mismatch.private-input",
+        "{file} discloses private input '{input}' of main",
+    ),
+    (
+        "prover-input-mismatch.string-length",
+        "{file} gives '{input}' {length} bytes, but its type is `{type}`",
+    ),
+    (
+        "prover-input-mismatch.unknown-entry",
+        "{file} has entry '{input}', which is not a parameter of main",
+    ),
+    (
+        "prover-input-mismatch.unknown-field",
+        "{file} has entry '{input}', which is not a field of '{struct}'",
+    ),
+    (
+        "prover-input-mismatch.wrong-type",
+        "{file} gives '{input}' a {value_type} value, but its type is `{type}`",
+    ),
+    (
+        "public-only-constraint",
+        "Assertion in private function '{function}' only depends on constants and public inputs",
+    ),
+    (
+        "redundant-equality",
+        "Assertion that {lhs} == {rhs} in function '{function}' is implied by earlier assertions: {chain}",
+    ),
+    (
+        "redundant-import.duplicate",
+        "{imports} is already imported in '{module}'",
+    ),
+    (
+        "redundant-import.duplicates",
+        "{imports} are already imported in '{module}'",
+    ),
+    (
+        "redundant-import.self",
+        "Import of '{path}' refers to the module it appears in",
+    ),
+    (
+        "repeated-linear-combination",
+        "Linear combination {combination} appears in {count} assertions of '{circuit}' (opcodes {opcodes}); binding it to an intermediate witness would save about {saved} terms",
+    ),
+    (
+        "shadowed-trait-method",
+        "Method '{type}::{method}' shadows the method of a trait implemented for '{type}'",
+    ),
+    (
+        "single-instantiation-generic",
+        "Generic function '{function}' is only called with {type_arguments}",
+    ),
+    (
+        "stdlib-shadowing.function",
+        "Function '{function}' shadows '{std_path}', so calls to it in its module bind to it instead of the standard library",
+    ),
+    (
+        "stdlib-shadowing.import",
+        "Import of '{path}' shadows '{std_path}', so calls to '{name}' bind to it instead of the standard library",
+    ),
+    (
+        "struct-field-order",
+        "Fields of this '{struct}' constructor are not in declaration order ({order})",
+    ),
+    (
+        "trailing-semicolon",
+        "Trailing semicolon drops the result of the final comparison in '{function}'",
+    ),
+    (
+        "truncating-cast",
+        "Cast of a '{from}' value to '{to}' in '{function}' drops its high bits",
+    ),
+    (
+        "unchecked-public-function",
+        "Public function '{function}' returns a computation of its inputs without asserting anything",
+    ),
+    (
+        "unchecked-slice-conversion",
+        "Slice '{slice}' is converted to an array in '{function}' without asserting its length",
+    ),
+    (
+        "unconstrained-parameter",
+        "Parameter '{parameter}' of '{function}' only receives unconstrained values from {callers}, and no assertion of '{function}' references it; constrain it, or mark '{function}' unconstrained",
+    ),
+    (
+        "unconstrained-struct-field",
+        "Field '{field}' of the struct returned by '{function}' comes from unconstrained code and is never constrained",
+    ),
+    (
+        "undocumented-numeric-generic",
+        "Public function '{function}' neither documents nor asserts the valid range of numeric generic '{generic}'",
+    ),
+    (
+        "unfulfilled-expectation",
+        "Expected a '{lint}' finding on line {line}, but none was reported",
+    ),
+    (
+        "unsupported-syntax",
+        "{syntax} require Noir {since}, but the package targets Noir {version}",
+    ),
+    ("unused-function", "Function '{function}' is unused"),
+    (
+        "unused-function.import",
+        "Import only brings the unused function(s) {functions} into scope",
+    ),
+    ("unused-import", "Imported name(s) {names} are never used"),
+];
+
+/// A diagnostic message: the identifier of its template and the values of its placeholders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    /// Identifier of the template in the message catalog.
+    pub id: &'static str,
+    /// Values of the placeholders, keyed by placeholder name.
+    pub arguments: Vec<(&'static str, String)>,
+}
+
+impl Message {
+    /// Creates a message without arguments.
+    pub fn new(id: &'static str) -> Self {
+        Self {
+            id,
+            arguments: vec![],
+        }
+    }
+
+    /// Sets the value of a placeholder.
+    pub fn with(mut self, name: &'static str, value: impl fmt::Display) -> Self {
+        self.arguments.push((name, value.to_string()));
+        self
+    }
+}
+
+/// Renders the message with the default templates.
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&MessageCatalog::default().render(self))
+    }
+}
+
+/// Message templates, overriding the default ones, keyed by message identifier.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct MessageCatalog {
+    overrides: BTreeMap<String, String>,
+}
+
+impl MessageCatalog {
+    /// Creates a catalog overriding the given templates.
+    pub fn new(overrides: BTreeMap<String, String>) -> Self {
+        Self { overrides }
+    }
+
+    /// Returns the default template of a message.
+    pub fn default_template(id: &str) -> Option<&'static str> {
+        DEFAULT_TEMPLATES
+            .iter()
+            .find(|(template_id, _)| *template_id == id)
+            .map(|(_, template)| *template)
+    }
+
+    /// Returns the template of a message, preferring overrides to the default templates.
+    pub fn template(&self, id: &str) -> Option<&str> {
+        self.overrides
+            .get(id)
+            .map(String::as_str)
+            .or_else(|| Self::default_template(id))
+    }
+
+    /// Renders a message, substituting its arguments for the placeholders of its template.
+    /// Messages without a template render as their identifier.
+    pub fn render(&self, message: &Message) -> String {
+        let Some(template) = self.template(message.id) else {
+            return message.id.to_string();
+        };
+
+        let mut output = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let argument = after.find('}').and_then(|end| {
+                let name = &after[..end];
+                message
+                    .arguments
+                    .iter()
+                    .find(|(argument, _)| *argument == name)
+                    .map(|(_, value)| (value, end))
+            });
+            match argument {
+                Some((value, end)) => {
+                    output.push_str(value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    output.push('{');
+                    rest = after;
+                }
+            }
+        }
+        output.push_str(rest);
+        output
+    }
+
+    /// Checks that every override replaces a known message and only uses the placeholders of
+    /// its default template.
+    pub fn validate(&self) -> Result<(), String> {
+        for (id, template) in &self.overrides {
+            let Some(default_template) = Self::default_template(id) else {
+                return Err(format!("Unknown message {id:?}"));
+            };
+            let known: Vec<&str> = placeholders(default_template).collect();
+            if let Some(unknown) = placeholders(template).find(|name| !known.contains(name)) {
+                return Err(format!(
+                    "Message {id:?} uses unknown placeholder {{{unknown}}}, expected one of {}",
+                    known
+                        .iter()
+                        .map(|name| format!("{{{name}}}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the names of the `{name}` placeholders of a template.
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template.split('{').skip(1).filter_map(|part| {
+        let name = &part[..part.find('}')?];
+        let is_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        is_name.then_some(name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_renders_default_template() {
+        let message = Message::new("shadowed-trait-method")
+            .with("type", "Point")
+            .with("method", "hash");
+
+        assert_eq!(
+            message.to_string(),
+            "Method 'Point::hash' shadows the method of a trait implemented for 'Point'"
+        );
+    }
+
+    #[test]
+    fn test_catalog_renders_overrides() {
+        let catalog = MessageCatalog::new(BTreeMap::from([(
+            "unused-function".to_string(),
+            "'{function}' is dead code, see {wiki}".to_string(),
+        )]));
+        let message = Message::new("unused-function").with("function", "helper");
+
+        assert_eq!(
+            catalog.render(&message),
+            "'helper' is dead code, see {wiki}"
+        );
+        assert!(catalog.validate().is_err());
+    }
+
+    #[test]
+    fn test_catalog_rejects_unknown_messages() {
+        let catalog = MessageCatalog::new(BTreeMap::from([("unknown".to_string(), String::new())]));
+
+        assert_eq!(
+            catalog.validate(),
+            Err("Unknown message \"unknown\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_default_templates_are_sorted_and_unique() {
+        assert!(
+            DEFAULT_TEMPLATES
+                .windows(2)
+                .all(|pair| pair[0].0 < pair[1].0)
+        );
+    }
+}
@@ -1,2 +1,7 @@
+pub mod finding;
+pub mod internal_error;
 pub mod lint;
+pub mod message;
 pub mod reporter;
+pub mod severity_policy;
+pub mod suggestion;
@@ -1,6 +1,64 @@
-use crate::diagnostics::lint::{Lint, Severity};
+use crate::ast::coverage::Coverage;
+use crate::diagnostics::finding::Finding;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::health::Category;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Schema of the SARIF logs emitted by `Reporter::sarif_report`.
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Key of the finding fingerprints in the `partialFingerprints` of SARIF results, versioned
+/// in case the way fingerprints are computed changes.
+const SARIF_FINGERPRINT: &str = "noirAnalyzerFingerprint/v1";
+
+/// How file locations are printed in human-readable reports.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LocationStyle {
+    /// Paths relative to the workspace root.
+    #[default]
+    Relative,
+    /// Absolute paths.
+    Absolute,
+    /// Paths relative to the workspace root, printed as an uncolored `file:line:col` token
+    /// that terminals and editors turn into a link.
+    Clickable,
+}
+
+impl LocationStyle {
+    /// Returns the path to print for a file of the workspace with the given root.
+    pub fn display_path(&self, file_path: &Path, root: &Path) -> PathBuf {
+        match self {
+            LocationStyle::Relative | LocationStyle::Clickable => file_path
+                .strip_prefix(root)
+                .unwrap_or(file_path)
+                .to_path_buf(),
+            LocationStyle::Absolute => {
+                std::fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf())
+            }
+        }
+    }
+}
+
+impl FromStr for LocationStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "relative" => Ok(LocationStyle::Relative),
+            "absolute" => Ok(LocationStyle::Absolute),
+            "clickable" => Ok(LocationStyle::Clickable),
+            _ => Err(format!(
+                "Unknown location style {s:?}, expected relative, absolute or clickable"
+            )),
+        }
+    }
+}
 
 /// Handles reporting of lints detected during analysis.
 pub struct Reporter;
@@ -8,47 +66,356 @@ pub struct Reporter;
 impl Reporter {
     /// Pretty-prints lints in a structured and colorful format.
     pub fn pretty_report(lints: &[Lint], file_path: &Path) -> String {
+        Self::pretty_report_with_locations(
+            lints,
+            file_path,
+            Path::new(""),
+            LocationStyle::default(),
+        )
+    }
+
+    /// Pretty-prints lints, printing the location of `file_path` in the given style relative
+    /// to the workspace `root`.
+    pub fn pretty_report_with_locations(
+        lints: &[Lint],
+        file_path: &Path,
+        root: &Path,
+        style: LocationStyle,
+    ) -> String {
+        let display_path = style.display_path(file_path, root);
         let mut output = String::new();
 
         for lint in lints {
-            let severity_label = match lint.severity {
-                Severity::Error => "\x1b[1;31merror\x1b[0m",   // Bright Red (bold)
-                Severity::Warning => "\x1b[1;33mwarning\x1b[0m", // Bright Yellow (bold)
-            };
+            write_pretty_lint(&mut output, lint, file_path, &display_path, style);
+            writeln!(output).unwrap(); // Blank line for spacing
+        }
+
+        output
+    }
+
+    /// Pretty-prints the findings of a file like `pretty_report_with_locations`. Findings
+    /// merging circuit lints list them as circuit-level evidence below the source-level one.
+    pub fn pretty_findings_report(
+        findings: &[Finding],
+        file_path: &Path,
+        root: &Path,
+        style: LocationStyle,
+    ) -> String {
+        let display_path = style.display_path(file_path, root);
+        let mut output = String::new();
+
+        for finding in findings {
+            write_pretty_lint(&mut output, &finding.lint, file_path, &display_path, style);
+            if !finding.circuit_evidence.is_empty() {
+                writeln!(output, "  \x1b[1;35msource-level evidence\x1b[0m: [{}] above", finding.lint.name).unwrap();
+                writeln!(output, "  \x1b[1;35mcircuit-level evidence\x1b[0m:").unwrap();
+                for lint in &finding.circuit_evidence {
+                    writeln!(output, " \x1b[1;37m|\x1b[0m [{}] {}", lint.name, lint.description).unwrap();
+                }
+            }
+            writeln!(output).unwrap(); // Blank line for spacing
+        }
+
+        output
+    }
+
+    /// Renders findings as single lines of the form `path:line:col: severity: message [rule]`,
+    /// as printed by compilers and `grep`.
+    pub fn short_report(findings: &[Finding], root: &Path, style: LocationStyle) -> String {
+        let mut output = String::new();
+        for finding in findings {
+            let mut location = style
+                .display_path(&finding.file_path, root)
+                .display()
+                .to_string();
+            if let (Some(line), Some(column)) = (finding.line, finding.column) {
+                write!(location, ":{line}:{column}").unwrap();
+            }
+            writeln!(
+                output,
+                "{location}: {}: {} [{}]",
+                severity_name(&finding.lint.severity),
+                finding.lint.description,
+                finding.lint.name
+            )
+            .unwrap();
+        }
+        output
+    }
+
+    /// Lists the kinds of AST nodes skipped by the analyzer, most frequent first.
+    pub fn coverage_report(coverage: &Coverage) -> String {
+        if coverage.is_complete() {
+            return "Coverage: every encountered node was analyzed\n".to_string();
+        }
+
+        let mut output =
+            String::from("Coverage: lint rules did not see the contents of these skipped nodes\n");
+        for (kind, count) in coverage.skipped() {
+            writeln!(output, "  {count:>6}  {kind}").unwrap();
+        }
+        output
+    }
+
+    /// Renders findings as a JSON document of the form `{ "findings": [...] }`, with the
+    /// fingerprints of the findings of the workspace with the given root.
+    pub fn json_report(findings: &[Finding], root: &Path) -> String {
+        serde_json::to_string_pretty(&Self::json_value(findings, root)).unwrap()
+    }
+
+    /// Returns the JSON document rendered by `json_report`.
+    pub fn json_value(findings: &[Finding], root: &Path) -> Value {
+        let findings: Vec<Value> = findings
+            .iter()
+            .map(|finding| finding_to_json(finding, root))
+            .collect();
+        json!({ "findings": findings })
+    }
 
-            // Print severity and lint name
-            writeln!(output, "{}: \x1b[1m{}\x1b[0m", severity_label, lint.description).unwrap();
+    /// Renders findings as a Markdown summary for pull request comments: a table of the new
+    /// findings and of the baseline findings fixed since, when known, their counts by
+    /// category, and the findings of each file in a collapsible section.
+    pub fn markdown_report(
+        findings: &[Finding],
+        fixed: Option<usize>,
+        root: &Path,
+        style: LocationStyle,
+    ) -> String {
+        let mut output = String::from("### noir-analyzer\n\n");
+        writeln!(output, "| | Findings |\n|---|---:|").unwrap();
+        writeln!(output, "| New | {} |", findings.len()).unwrap();
+        let fixed = fixed.map_or("-".to_string(), |fixed| fixed.to_string());
+        writeln!(output, "| Fixed | {fixed} |").unwrap();
 
-            if let Some(span) = &lint.span {
-                let (line, column) = get_line_column(file_path, span.start());
+        if findings.is_empty() {
+            return output;
+        }
+
+        let mut categories: BTreeMap<Category, usize> = BTreeMap::new();
+        let mut files: BTreeMap<PathBuf, Vec<&Finding>> = BTreeMap::new();
+        for finding in findings {
+            *categories
+                .entry(Category::of(finding.lint.name))
+                .or_default() += 1;
+            files
+                .entry(style.display_path(&finding.file_path, root))
+                .or_default()
+                .push(finding);
+        }
 
-                // Print file location with colored path and line/column
+        writeln!(output, "\n| Category | Findings |\n|---|---:|").unwrap();
+        for (category, count) in categories {
+            writeln!(output, "| {} | {count} |", category.as_str()).unwrap();
+        }
+
+        for (path, findings) in files {
+            writeln!(
+                output,
+                "\n<details>\n<summary><code>{}</code>: {} finding(s)</summary>\n",
+                path.display(),
+                findings.len()
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "| Line | Severity | Rule | Message |\n|---:|---|---|---|"
+            )
+            .unwrap();
+            for finding in findings {
+                let line = finding.line.map_or(String::new(), |line| line.to_string());
+                // Fingerprints are hidden, for bots to match findings across comments
                 writeln!(
                     output,
-                    "  --> \x1b[1;36m{}:\x1b[1;34m{}:{}\x1b[0m",
-                    file_path.display(),
-                    line,
-                    column
+                    "| {line} | {} | `{}` | {} <!-- fingerprint: {} --> |",
+                    severity_name(&finding.lint.severity),
+                    finding.lint.name,
+                    markdown_cell(&finding.lint.description),
+                    finding.fingerprint(root)
                 )
-                    .unwrap();
+                .unwrap();
+            }
+            writeln!(output, "\n</details>").unwrap();
+        }
+        output
+    }
 
-                // Extract the source line (if available)
-                if let Some(source_line) = get_source_line(file_path, line) {
-                    writeln!(output, " \x1b[1;37m| {}\x1b[0m", source_line.trim()).unwrap();
+    /// Renders findings as a SARIF 2.1.0 log, understood by code scanning services.
+    ///
+    /// The confidence of each finding is mapped to the SARIF `rank`, and its fingerprint to
+    /// the `partialFingerprints` of the result. Blame data and circuit evidence, when
+    /// present, are stored in the `properties` bag of each result.
+    pub fn sarif_report(findings: &[Finding], root: &Path) -> String {
+        let rules: BTreeSet<&str> = findings.iter().map(|finding| finding.lint.name).collect();
+        let rules: Vec<Value> = rules.into_iter().map(|name| json!({ "id": name })).collect();
 
-                    // Generate caret under the issue with red color
-                    let padding = column - 1; // Convert to 0-based index
-                    writeln!(output, " \x1b[1;37m{} \x1b[1;31m^\x1b[0m", " ".repeat(padding)).unwrap();
+        let results: Vec<Value> = findings
+            .iter()
+            .map(|finding| {
+                let mut physical_location = json!({
+                    "artifactLocation": { "uri": path_uri(&finding.file_path) },
+                });
+                if let (Some(line), Some(column)) = (finding.line, finding.column) {
+                    physical_location["region"] =
+                        json!({ "startLine": line, "startColumn": column });
                 }
-            }
 
-            writeln!(output).unwrap(); // Blank line for spacing
+                let mut result = json!({
+                    "ruleId": finding.lint.name,
+                    "level": severity_name(&finding.lint.severity),
+                    "rank": confidence_rank(finding.lint.confidence),
+                    "message": { "text": finding.lint.description },
+                    "locations": [{ "physicalLocation": physical_location }],
+                    "partialFingerprints": { SARIF_FINGERPRINT: finding.fingerprint(root) },
+                });
+                if let Some(blame) = &finding.blame {
+                    result["properties"]["blame"] = json!(blame);
+                }
+                if !finding.circuit_evidence.is_empty() {
+                    result["properties"]["circuitEvidence"] = circuit_evidence_to_json(finding);
+                }
+                result
+            })
+            .collect();
+
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "noir-analyzer",
+                        "informationUri": "https://github.com/walnuthq/noir-static-analyzer",
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        });
+        serde_json::to_string_pretty(&log).unwrap()
+    }
+}
+
+/// Escapes the characters of a Markdown table cell that would end the cell or the row.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Writes the description, source location and suggestion of a lint in the pretty format.
+fn write_pretty_lint(
+    output: &mut String,
+    lint: &Lint,
+    file_path: &Path,
+    display_path: &Path,
+    style: LocationStyle,
+) {
+    let severity_label = match lint.severity {
+        Severity::Error => "\x1b[1;31merror\x1b[0m",   // Bright Red (bold)
+        Severity::Warning => "\x1b[1;33mwarning\x1b[0m", // Bright Yellow (bold)
+    };
+
+    // Print severity and lint name
+    writeln!(output, "{}: \x1b[1m{}\x1b[0m", severity_label, lint.description).unwrap();
+
+    if let Some(span) = &lint.span {
+        let (line, column) = get_line_column(file_path, span.start());
+
+        // Print file location with colored path and line/column
+        if style == LocationStyle::Clickable {
+            writeln!(output, "  --> {}:{line}:{column}", display_path.display()).unwrap();
+        } else {
+            writeln!(
+                output,
+                "  --> \x1b[1;36m{}:\x1b[1;34m{}:{}\x1b[0m",
+                display_path.display(),
+                line,
+                column
+            )
+            .unwrap();
         }
 
-        output
+        // Extract the source line (if available)
+        if let Some(source_line) = get_source_line(file_path, line) {
+            writeln!(output, " \x1b[1;37m| {}\x1b[0m", source_line.trim()).unwrap();
+
+            // Generate caret under the issue with red color
+            let padding = column - 1; // Convert to 0-based index
+            writeln!(output, " \x1b[1;37m{} \x1b[1;31m^\x1b[0m", " ".repeat(padding)).unwrap();
+        }
+    }
+
+    if let Some(suggestion) = &lint.suggestion {
+        // Print the suggested fix in green
+        writeln!(output, "  \x1b[1;32mhelp\x1b[0m: {}", suggestion.message).unwrap();
+        writeln!(output, " \x1b[1;37m| \x1b[32m{}\x1b[0m", suggestion.replacement).unwrap();
+        if !suggestion.other_edits.is_empty() {
+            writeln!(output, " \x1b[1;37m| \x1b[32m(and {} other edit(s))\x1b[0m", suggestion.other_edits.len()).unwrap();
+        }
+    }
+}
+
+/// Converts a finding into the JSON object used by `Reporter::json_report`. Circuit
+/// evidence is only included when the finding has some.
+fn finding_to_json(finding: &Finding, root: &Path) -> Value {
+    let lint = &finding.lint;
+    let mut value = json!({
+        "name": lint.name,
+        "severity": severity_name(&lint.severity),
+        "confidence": lint.confidence.to_string(),
+        "description": lint.description,
+        "file": path_uri(&finding.file_path),
+        "line": finding.line,
+        "column": finding.column,
+        "span": lint.span.map(|span| json!({ "start": span.start(), "end": span.end() })),
+        "suggestion": lint.suggestion.as_ref().map(|suggestion| json!({
+            "message": suggestion.message,
+            "replacement": suggestion.replacement,
+            "applicability": suggestion.applicability.to_string(),
+        })),
+        "blame": finding.blame,
+        "fingerprint": finding.fingerprint(root),
+    });
+    if !finding.circuit_evidence.is_empty() {
+        value["circuit_evidence"] = circuit_evidence_to_json(finding);
+    }
+    value
+}
+
+/// Converts the circuit lints merged into a finding into a JSON array.
+fn circuit_evidence_to_json(finding: &Finding) -> Value {
+    finding
+        .circuit_evidence
+        .iter()
+        .map(|lint| json!({
+            "name": lint.name,
+            "severity": severity_name(&lint.severity),
+            "confidence": lint.confidence.to_string(),
+            "description": lint.description,
+        }))
+        .collect()
+}
+
+/// Returns the lowercase name of a severity, as used by machine-readable reports.
+fn severity_name(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::Error => "error",
     }
 }
 
+/// Maps a confidence to a SARIF rank, from 0 (lowest priority) to 100.
+fn confidence_rank(confidence: Confidence) -> f64 {
+    match confidence {
+        Confidence::High => 90.0,
+        Confidence::Medium => 60.0,
+        Confidence::Low => 30.0,
+    }
+}
+
+/// Renders a path with forward slashes, as expected in URIs.
+fn path_uri(path: &Path) -> String {
+    path.display().to_string().replace('\\', "/")
+}
+
 /// Extracts (line, column) from a file given a byte position.
 fn get_line_column(file_path: &Path, byte_offset: u32) -> (usize, usize) {
     if let Ok(contents) = std::fs::read_to_string(file_path) {
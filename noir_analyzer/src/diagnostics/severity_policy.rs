@@ -0,0 +1,116 @@
+//! # Severity Policy Module
+//!
+//! A `SeverityPolicy` decides at which severity each lint is reported, or whether it is
+//! reported at all. The configuration file builds one from its `[lints]` levels, and
+//! embedders such as language servers or CI bots can build their own programmatically and
+//! hand it to the `Analyzer`:
+//!
+//! ```ignore
+//! let policy = SeverityPolicy::new()
+//!     .with_level("unused-function", LintLevel::Allow)
+//!     .with_hook(|lint| (lint.confidence == Confidence::High).then_some(lint.severity.clone()));
+//! let lints = Analyzer::new(&rules).with_severity_policy(policy).analyze(&root)?;
+//! ```
+
+use crate::config::LintLevel;
+use crate::diagnostics::lint::{Lint, Severity};
+use std::collections::HashMap;
+
+/// Callback deciding the severity of a lint, or `None` to drop it.
+pub type SeverityHook = dyn Fn(&Lint) -> Option<Severity> + Send + Sync;
+
+/// Maps lints to the severity they are reported at.
+#[derive(Default)]
+pub struct SeverityPolicy {
+    levels: HashMap<String, LintLevel>,
+    hook: Option<Box<SeverityHook>>,
+}
+
+impl SeverityPolicy {
+    /// Creates a policy reporting every lint at the severity chosen by its rule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reports the lints of the given rule at a level.
+    pub fn with_level(mut self, rule: &str, level: LintLevel) -> Self {
+        self.levels.insert(rule.to_string(), level);
+        self
+    }
+
+    /// Invokes `hook` on every lint once its rule level was applied. The hook returns the
+    /// severity to report the lint at, or `None` to drop the lint.
+    pub fn with_hook(
+        mut self,
+        hook: impl Fn(&Lint) -> Option<Severity> + Send + Sync + 'static,
+    ) -> Self {
+        self.hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Returns the level configured for a rule, if any.
+    pub fn level(&self, rule: &str) -> Option<LintLevel> {
+        self.levels.get(rule).copied()
+    }
+
+    /// Applies the policy, dropping allowed lints and adjusting severities.
+    pub fn apply(&self, lints: Vec<Lint>) -> Vec<Lint> {
+        lints
+            .into_iter()
+            .filter_map(|mut lint| {
+                match self.level(lint.name) {
+                    Some(LintLevel::Allow) => return None,
+                    Some(LintLevel::Warn) => lint.severity = Severity::Warning,
+                    Some(LintLevel::Deny) => lint.severity = Severity::Error,
+                    None => {}
+                }
+                if let Some(hook) = &self.hook {
+                    lint.severity = hook(&lint)?;
+                }
+                Some(lint)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::lint::Confidence;
+
+    fn lint(name: &'static str, confidence: Confidence) -> Lint {
+        Lint {
+            name,
+            severity: Severity::Warning,
+            confidence,
+            description: String::new(),
+            message: None,
+            span: None,
+            file_id: None,
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn test_policy_applies_levels_then_hook() {
+        let policy = SeverityPolicy::new()
+            .with_level("unused-function", LintLevel::Allow)
+            .with_level("god-module", LintLevel::Deny)
+            .with_hook(|lint| match lint.confidence {
+                Confidence::Low => None,
+                _ => Some(lint.severity.clone()),
+            });
+
+        let result = policy.apply(vec![
+            lint("unused-function", Confidence::High),
+            lint("god-module", Confidence::High),
+            lint("field-modulo", Confidence::Low),
+            lint("field-modulo", Confidence::Medium),
+        ]);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].severity, Severity::Error);
+        assert_eq!(result[1].confidence, Confidence::Medium);
+        assert_eq!(result[1].severity, Severity::Warning);
+    }
+}
@@ -0,0 +1,68 @@
+//! # Suggestion Module
+//!
+//! Suggestions are optional source edits attached to a lint, describing how the reported
+//! issue could be resolved. A suggestion replaces one span of the file, and possibly others
+//! along with it, such as the calls of a renamed function.
+//!
+//! Each suggestion states how confident the lint is that applying it is correct, following
+//! the applicability levels of rustc. The fix engine only applies machine-applicable
+//! suggestions unless risky fixes are explicitly allowed.
+
+use noirc_frontend::hir::resolution::errors::Span;
+use std::fmt;
+
+/// How safe it is to apply a suggestion without human review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended and can be applied automatically.
+    MachineApplicable,
+    /// The suggestion may be what the user intended, but it is uncertain. It should compile
+    /// but might change behavior or formatting.
+    MaybeIncorrect,
+    /// The suggestion contains placeholders (e.g. `(...)`) that must be filled in by hand.
+    HasPlaceholders,
+}
+
+impl Applicability {
+    /// Returns whether the fix engine applies suggestions at this level.
+    pub fn is_applied(self, allow_risky: bool) -> bool {
+        allow_risky || self == Applicability::MachineApplicable
+    }
+}
+
+impl fmt::Display for Applicability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Applicability::MachineApplicable => "machine-applicable",
+            Applicability::MaybeIncorrect => "maybe-incorrect",
+            Applicability::HasPlaceholders => "has-placeholders",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A proposed replacement for a region of source code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    /// Human-readable explanation of the proposed change.
+    pub message: String,
+    /// Span of the code that should be replaced.
+    pub span: Span,
+    /// Source text to put in place of the span.
+    pub replacement: String,
+    /// How safe it is to apply the replacement automatically.
+    pub applicability: Applicability,
+    /// Further replacements in the same file, applied or skipped along with the main one.
+    pub other_edits: Vec<(Span, String)>,
+}
+
+impl Suggestion {
+    /// Returns the replacements of the suggestion, the main one first.
+    pub fn edits(&self) -> impl Iterator<Item = (Span, &str)> {
+        std::iter::once((self.span, self.replacement.as_str())).chain(
+            self.other_edits
+                .iter()
+                .map(|(span, replacement)| (*span, replacement.as_str())),
+        )
+    }
+}
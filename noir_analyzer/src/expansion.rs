@@ -0,0 +1,300 @@
+//! # Generated Code
+//!
+//! Macros, i.e. attributes running `comptime` functions such as `#[derive(Eq)]` or the
+//! `#[aztec]` contract macros, generate items that are not part of the source and are
+//! therefore not analyzed. This module reads the expanded program printed by
+//! `nargo expand`, and attributes the lints found in it to the source.
+//!
+//! The expanded program is analyzed in addition to the source: lints also reported on the
+//! source are dropped, and the others are reported on the macro invocation of the source
+//! item they were generated next to, i.e. the item of the same name, or the closest
+//! preceding one for generated items. Their description notes that they are in generated
+//! code.
+
+use crate::diagnostics::lint::Lint;
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::{Item, ItemKind};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ExpansionError {
+    #[error("Failed to run nargo: {0}")]
+    NargoUnavailable(String),
+    #[error("`nargo expand` failed: {0}")]
+    NargoFailed(String),
+}
+
+/// Attributes applied by the compiler itself, which do not invoke a macro.
+const BUILTIN_ATTRIBUTES: &[&str] = &[
+    "abi",
+    "allow",
+    "builtin",
+    "contract_library_method",
+    "deprecated",
+    "export",
+    "field",
+    "fold",
+    "foreign",
+    "inline_always",
+    "must_use",
+    "no_predicates",
+    "oracle",
+    "recursive",
+    "test",
+    "use_callers_scope",
+    "varargs",
+];
+
+/// Returns the expanded program of a package, as printed by `nargo expand`.
+pub fn expand(package_dir: &Path, package: &str) -> Result<String, ExpansionError> {
+    let output = Command::new("nargo")
+        .arg("expand")
+        .arg("--program-dir")
+        .arg(package_dir)
+        .args(["--package", package])
+        .output()
+        .map_err(|e| ExpansionError::NargoUnavailable(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(ExpansionError::NargoFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Returns the lints of the expanded program that are in generated code, reported on the
+/// source.
+///
+/// `lints` are the lints found on the source, and `expanded_lints` those found on the
+/// expanded program. Lints in modules declared in other files are dropped, as they are
+/// reported with these files.
+pub fn generated_lints(
+    source: &str,
+    parsed_module: &ParsedModule,
+    lints: &[Lint],
+    expanded_module: &ParsedModule,
+    expanded_lints: Vec<Lint>,
+) -> Vec<Lint> {
+    let reported: BTreeSet<(&str, &str)> = lints
+        .iter()
+        .map(|lint| (lint.name, lint.description.as_str()))
+        .collect();
+
+    expanded_lints
+        .into_iter()
+        .filter(|lint| !reported.contains(&(lint.name, lint.description.as_str())))
+        .filter_map(|lint| {
+            let span = lint.span?;
+            let path = item_path(expanded_module, span);
+            let origin = origin(parsed_module, &path)?;
+            let invocation = macro_invocation(source, origin);
+            let message = Message::new("generated-code")
+                .with("finding", &lint.description)
+                .with(
+                    "macro",
+                    invocation.map_or("a macro".to_string(), |(_, name)| format!("`{name}`")),
+                );
+            Some(Lint {
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(invocation.map_or(Noir::item_location(origin).0, |(span, _)| span)),
+                file_id: Some(Noir::item_location(origin).1),
+                // Fixes would apply to the expanded program, not to the source
+                suggestion: None,
+                ..lint
+            })
+        })
+        .collect()
+}
+
+/// One level of the path to an item: its key, and the keys of its preceding siblings,
+/// closest first.
+type PathSegment = (String, Vec<String>);
+
+/// Returns the path of nested items containing the given span.
+fn item_path(parsed_module: &ParsedModule, span: Span) -> Vec<PathSegment> {
+    let mut path = vec![];
+    let mut items = &parsed_module.items;
+    'levels: loop {
+        for (index, item) in items.iter().enumerate() {
+            let (item_span, _) = Noir::item_location(item);
+            if item_span.start() > span.start() || span.end() > item_span.end() {
+                continue;
+            }
+            let preceding = items[..index].iter().rev().map(item_key).collect();
+            path.push((item_key(item), preceding));
+            if let ItemKind::Submodules(submodule) = &item.kind {
+                items = &submodule.contents.items;
+                continue 'levels;
+            }
+            break;
+        }
+        return path;
+    }
+}
+
+/// Returns the source item a generated item originates from: the item with the same path,
+/// or the closest preceding one at the first level that does not exist in the source.
+fn origin<'ast>(parsed_module: &'ast ParsedModule, path: &[PathSegment]) -> Option<&'ast Item> {
+    let mut items = &parsed_module.items;
+    let mut origin = None;
+    for (key, preceding) in path {
+        let Some(item) = find_item(items, key) else {
+            return preceding
+                .iter()
+                .find_map(|key| find_item(items, key))
+                .or(origin);
+        };
+        match &item.kind {
+            ItemKind::Submodules(submodule) => items = &submodule.contents.items,
+            // The module is in another file, analyzed separately
+            ItemKind::ModuleDecl(_) => return None,
+            _ => return Some(item),
+        }
+        origin = Some(item);
+    }
+    origin
+}
+
+fn find_item<'ast>(items: &'ast [Item], key: &str) -> Option<&'ast Item> {
+    items.iter().find(|item| item_key(item) == key)
+}
+
+/// Identifies an item among its siblings, in both the source and the expanded program.
+fn item_key(item: &Item) -> String {
+    match &item.kind {
+        ItemKind::Function(function) => format!("fn {}", function.name()),
+        ItemKind::Submodules(submodule) => format!("mod {}", submodule.name),
+        ItemKind::ModuleDecl(declaration) => format!("mod {}", declaration.ident),
+        ItemKind::Struct(noir_struct) => format!("struct {}", noir_struct.name),
+        // E.g. `impl Eq for Point`
+        kind => kind
+            .to_string()
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.starts_with("#[") && !line.starts_with("#!["))
+            .unwrap_or_default()
+            .trim_end_matches('{')
+            .trim_end()
+            .to_string(),
+    }
+}
+
+/// Returns the span and text of the first macro attribute of a source item, e.g.
+/// `#[derive(Eq)]`.
+fn macro_invocation<'a>(source: &'a str, item: &Item) -> Option<(Span, &'a str)> {
+    let (span, _) = Noir::item_location(item);
+    let item_start = (span.start() as usize).min(source.len());
+    // Attributes may be outside of the item span, on the lines before it
+    let mut start = source[..item_start]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    while start > 0 {
+        let previous = source[..start - 1].rfind('\n').map_or(0, |index| index + 1);
+        if !source[previous..start].trim_start().starts_with("#[") {
+            break;
+        }
+        start = previous;
+    }
+
+    let mut offset = start;
+    for line in source[start..].lines() {
+        let attribute = line.trim();
+        let line_start = offset;
+        offset += line.len() + 1;
+        if !attribute.starts_with("#[") {
+            if line_start >= item_start {
+                break;
+            }
+            continue;
+        }
+        let name: String = attribute[2..]
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == ':')
+            .collect();
+        if name.is_empty() || BUILTIN_ATTRIBUTES.contains(&name.as_str()) {
+            continue;
+        }
+        let attribute_start = line_start + (line.len() - line.trim_start().len());
+        let span = Span::from(attribute_start as u32..(attribute_start + attribute.len()) as u32);
+        return Some((span, attribute));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::lints::lint_rule::LintRule;
+    use crate::lints::unused_function::UnusedFunction;
+
+    #[test]
+    fn test_lints_in_generated_items_are_reported_on_the_macro() {
+        let source = r#"
+#[derive(Eq)]
+struct Point { x: Field }
+
+#[test]
+fn test_point() {}
+
+fn main(p: Point) { assert(p.x != 0); }
+"#;
+        let expanded = r#"
+struct Point { x: Field }
+
+fn point_helper() {}
+
+#[test]
+fn test_point() {}
+
+fn unused() {}
+
+fn main(p: Point) { assert(p.x != 0); }
+"#;
+
+        let parsed_module = Parser::parse_program_with_dummy_file(source).unwrap();
+        let expanded_module = Parser::parse_program_with_dummy_file(expanded).unwrap();
+        let lint_rules: Vec<Box<dyn LintRule>> = vec![Box::new(UnusedFunction)];
+        let lints = Analyzer::new(&lint_rules).analyze(&parsed_module).unwrap();
+        let expanded_lints = Analyzer::new(&lint_rules)
+            .analyze(&expanded_module)
+            .unwrap();
+
+        let generated = generated_lints(
+            source,
+            &parsed_module,
+            &lints,
+            &expanded_module,
+            expanded_lints,
+        );
+
+        assert_eq!(generated.len(), 2);
+        let derive_span = source.find("#[derive(Eq)]").unwrap() as u32;
+        assert_eq!(
+            generated[0].span,
+            Some(Span::from(derive_span..derive_span + 13))
+        );
+        assert!(
+            generated[0]
+                .description
+                .ends_with("(in code generated by `#[derive(Eq)]`)")
+        );
+        // Generated after a test function, whose attribute does not invoke a macro
+        let test_span = source.find("#[test]").unwrap() as u32;
+        assert!(generated[1].span.unwrap().start() >= test_span);
+        assert!(
+            generated[1]
+                .description
+                .ends_with("(in code generated by a macro)")
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! # Expected Findings
+//!
+//! `// analyzer-expect: lint-name` comments mark findings as intentional, for example in
+//! documentation and example packages showing what a lint reports. A directive written at the
+//! end of a line of code applies to that line; a directive on its own line applies to the
+//! next line. Several lints can be listed, separated by commas.
+//!
+//! Expected findings are suppressed. Directives matching no finding are reported with the
+//! `unfulfilled-expectation` lint, which makes directives usable as self-tests of the lints.
+
+use crate::changes::line_of;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use noirc_frontend::hir::resolution::errors::Span;
+
+/// Name of the lint reporting directives that matched no finding.
+pub const UNFULFILLED_EXPECTATION: &str = "unfulfilled-expectation";
+
+const DIRECTIVE: &str = "analyzer-expect:";
+
+/// A lint expected on a line by an `analyzer-expect` directive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expectation {
+    /// Name of the expected lint.
+    pub lint_name: String,
+    /// 1-based line the directive applies to.
+    pub line: usize,
+    /// Span of the directive comment.
+    pub span: Span,
+}
+
+/// The `analyzer-expect` directives of a source file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Expectations {
+    pub expectations: Vec<Expectation>,
+}
+
+impl Expectations {
+    /// Collects the directives of a source file.
+    pub fn parse(source: &str) -> Self {
+        let mut expectations = vec![];
+        let mut line_start = 0;
+
+        for (index, line) in source.split('\n').enumerate() {
+            if let Some(comment) = line.find("//") {
+                let text = line[comment + 2..].trim_start();
+                if let Some(lint_names) = text.strip_prefix(DIRECTIVE) {
+                    let is_trailing = !line[..comment].trim().is_empty();
+                    let line_number = if is_trailing { index + 1 } else { index + 2 };
+                    let start = (line_start + comment) as u32;
+                    let span = Span::from(start..(line_start + line.len()) as u32);
+
+                    expectations.extend(
+                        lint_names
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|lint_name| !lint_name.is_empty())
+                            .map(|lint_name| Expectation {
+                                lint_name: lint_name.to_string(),
+                                line: line_number,
+                                span,
+                            }),
+                    );
+                }
+            }
+            line_start += line.len() + 1;
+        }
+
+        Self { expectations }
+    }
+
+    /// Removes the expected lints, and reports the directives that matched no lint.
+    pub fn apply(&self, lints: Vec<Lint>, source: &str) -> Vec<Lint> {
+        let mut fulfilled = vec![false; self.expectations.len()];
+
+        let mut reported: Vec<Lint> = lints
+            .into_iter()
+            .filter(|lint| {
+                let Some(span) = lint.span else {
+                    return true;
+                };
+                let line = line_of(source, span.start());
+                let mut expected = false;
+                for (index, expectation) in self.expectations.iter().enumerate() {
+                    if expectation.lint_name == lint.name && expectation.line == line {
+                        fulfilled[index] = true;
+                        expected = true;
+                    }
+                }
+                !expected
+            })
+            .collect();
+
+        for (expectation, fulfilled) in self.expectations.iter().zip(fulfilled) {
+            if !fulfilled {
+                let message = Message::new(UNFULFILLED_EXPECTATION)
+                    .with("lint", &expectation.lint_name)
+                    .with("line", expectation.line);
+                reported.push(Lint {
+                    name: UNFULFILLED_EXPECTATION,
+                    severity: Severity::Warning,
+                    confidence: Confidence::High,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(expectation.span),
+                    file_id: None,
+                    suggestion: None,
+                });
+            }
+        }
+
+        reported
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::lints::unused_function::UnusedFunction;
+
+    #[test]
+    fn test_parse_trailing_and_standalone_directives() {
+        let source = "fn main() {}\n// analyzer-expect: unused-function\nfn a() {}\nfn b() {} // analyzer-expect: x, y\n";
+
+        let expectations = Expectations::parse(source).expectations;
+
+        assert_eq!(expectations.len(), 3);
+        assert_eq!(expectations[0].lint_name, "unused-function");
+        assert_eq!(expectations[0].line, 3);
+        assert_eq!(expectations[0].span, Span::from(13..48));
+        assert_eq!(expectations[1].lint_name, "x");
+        assert_eq!(expectations[1].line, 4);
+        assert_eq!(expectations[2].lint_name, "y");
+    }
+
+    #[test]
+    fn test_expected_findings_are_suppressed() {
+        let lint = Box::new(UnusedFunction);
+        let source = r#"
+            // analyzer-expect: unused-function
+            fn helper() {}
+
+            pub fn main() {}
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let lints = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(lints.len(), 1);
+        assert!(Expectations::parse(source).apply(lints, source).is_empty());
+    }
+
+    #[test]
+    fn test_unfulfilled_expectations_are_reported() {
+        let source = "fn main() {} // analyzer-expect: unused-function\n";
+
+        let lints = Expectations::parse(source).apply(vec![], source);
+
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].name, UNFULFILLED_EXPECTATION);
+        assert_eq!(
+            lints[0].description,
+            "Expected a 'unused-function' finding on line 1, but none was reported"
+        );
+        assert_eq!(lints[0].span, Some(Span::from(13..48)));
+    }
+}
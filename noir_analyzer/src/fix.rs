@@ -0,0 +1,304 @@
+//! # Fix Engine
+//!
+//! Applies the suggestions attached to lints to a source file.
+//!
+//! Like `rustfix`, only `MachineApplicable` suggestions are applied by default, so that
+//! automation never rewrites code in ways that need review. Risky suggestions
+//! (`MaybeIncorrect` and `HasPlaceholders`) are only applied when explicitly allowed.
+//! Suggestions that are not applied are reported with the reason they were skipped.
+//!
+//! When several suggestions edit overlapping regions of a file, applying all of them would
+//! corrupt the source. The engine instead applies the largest subset of non-overlapping
+//! suggestions and skips the others, naming the applied suggestion they conflict with. The
+//! edits of a suggestion that makes several are applied or skipped together.
+
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::fmt;
+
+/// Why a suggestion was not applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    /// The suggestion is not machine-applicable and risky fixes are not allowed.
+    Risky(Applicability),
+    /// The suggestion overlaps the applied suggestion with the given message.
+    Conflict(String),
+    /// An identical suggestion is already applied.
+    Duplicate,
+    /// The suggestion span does not fit in the source.
+    OutOfBounds,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Risky(applicability) => {
+                write!(
+                    f,
+                    "the fix is {applicability} and risky fixes are not allowed"
+                )
+            }
+            SkipReason::Conflict(message) => {
+                write!(f, "the fix overlaps the applied fix '{message}'")
+            }
+            SkipReason::Duplicate => write!(f, "an identical fix is already applied"),
+            SkipReason::OutOfBounds => write!(f, "the fix does not match the source"),
+        }
+    }
+}
+
+/// A suggestion that was not applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedFix {
+    pub suggestion: Suggestion,
+    pub reason: SkipReason,
+}
+
+/// Result of applying suggestions to a source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixOutcome {
+    /// The rewritten source.
+    pub source: String,
+    /// Suggestions applied to the source, in source order.
+    pub applied: Vec<Suggestion>,
+    /// Suggestions left out, with the reason why.
+    pub skipped: Vec<SkippedFix>,
+}
+
+/// Applies suggestions to source files.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fixer {
+    allow_risky: bool,
+}
+
+impl Fixer {
+    /// Creates a fixer. Suggestions that are not machine-applicable are only applied when
+    /// `allow_risky` is set.
+    pub fn new(allow_risky: bool) -> Self {
+        Self { allow_risky }
+    }
+
+    /// Applies the suggestions to the source they were computed from.
+    pub fn apply(&self, source: &str, suggestions: Vec<Suggestion>) -> FixOutcome {
+        let mut candidates = vec![];
+        let mut skipped = vec![];
+
+        for suggestion in suggestions {
+            if !suggestion.applicability.is_applied(self.allow_risky) {
+                let reason = SkipReason::Risky(suggestion.applicability);
+                skipped.push(SkippedFix { suggestion, reason });
+            } else if !fits(source, &suggestion) {
+                let reason = SkipReason::OutOfBounds;
+                skipped.push(SkippedFix { suggestion, reason });
+            } else {
+                candidates.push(suggestion);
+            }
+        }
+
+        // Picking the edit ending first always leaves the most room for the remaining ones,
+        // which yields a maximal set of non-overlapping edits
+        candidates.sort_by_key(|suggestion| extent(suggestion));
+
+        let mut applied: Vec<Suggestion> = vec![];
+        for suggestion in candidates {
+            let reason = if applied.contains(&suggestion) {
+                Some(SkipReason::Duplicate)
+            } else {
+                applied
+                    .iter()
+                    .find(|other| overlaps(other, &suggestion))
+                    .map(|other| SkipReason::Conflict(other.message.clone()))
+            };
+
+            match reason {
+                Some(reason) => skipped.push(SkippedFix { suggestion, reason }),
+                None => applied.push(suggestion),
+            }
+        }
+
+        // Replace from the end of the file, so earlier spans stay valid
+        let mut edits: Vec<(Span, &str)> = applied.iter().flat_map(Suggestion::edits).collect();
+        edits.sort_by_key(|(span, _)| (span.start(), span.end()));
+        let mut fixed = source.to_string();
+        for (span, replacement) in edits.into_iter().rev() {
+            fixed.replace_range(span.start() as usize..span.end() as usize, replacement);
+        }
+
+        FixOutcome {
+            source: fixed,
+            applied,
+            skipped,
+        }
+    }
+}
+
+/// Returns whether the spans of the suggestion lie on character boundaries of the source,
+/// without overlapping each other.
+fn fits(source: &str, suggestion: &Suggestion) -> bool {
+    let edits: Vec<(Span, &str)> = suggestion.edits().collect();
+    edits.iter().enumerate().all(|(index, (span, _))| {
+        let (start, end) = (span.start() as usize, span.end() as usize);
+        start <= end
+            && source.is_char_boundary(start)
+            && source.is_char_boundary(end)
+            && !edits[..index]
+                .iter()
+                .any(|(other, _)| spans_overlap(*span, *other))
+    })
+}
+
+/// Returns the end and start of the region edited by a suggestion, by which candidates are
+/// picked.
+fn extent(suggestion: &Suggestion) -> (u32, u32) {
+    let end = suggestion.edits().map(|(span, _)| span.end()).max();
+    let start = suggestion.edits().map(|(span, _)| span.start()).min();
+    (end.unwrap_or_default(), start.unwrap_or_default())
+}
+
+/// Returns whether two suggestions edit overlapping regions.
+fn overlaps(first: &Suggestion, second: &Suggestion) -> bool {
+    first.edits().any(|(first_span, _)| {
+        second
+            .edits()
+            .any(|(second_span, _)| spans_overlap(first_span, second_span))
+    })
+}
+
+/// Returns whether two spans overlap.
+///
+/// Two insertions at the same position also overlap, since their order would be ambiguous.
+fn spans_overlap(first: Span, second: Span) -> bool {
+    let (first_start, first_end) = (first.start(), first.end());
+    let (second_start, second_end) = (second.start(), second.end());
+
+    (first_start < second_end && second_start < first_end)
+        || (first_start == second_start && (first_start == first_end || second_start == second_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion(
+        start: u32,
+        end: u32,
+        replacement: &str,
+        applicability: Applicability,
+    ) -> Suggestion {
+        Suggestion {
+            message: format!("replace with {replacement}"),
+            span: Span::from(start..end),
+            replacement: replacement.to_string(),
+            applicability,
+            other_edits: vec![],
+        }
+    }
+
+    #[test]
+    fn test_fixer_applies_machine_applicable_suggestions_only() {
+        let source = "let a = 1; let b = 2;";
+        let suggestions = vec![
+            suggestion(8, 9, "10", Applicability::MachineApplicable),
+            suggestion(19, 20, "20", Applicability::MaybeIncorrect),
+        ];
+
+        let outcome = Fixer::new(false).apply(source, suggestions);
+
+        assert_eq!(outcome.source, "let a = 10; let b = 2;");
+        assert_eq!(outcome.applied.len(), 1);
+        assert_eq!(outcome.skipped.len(), 1);
+        assert_eq!(
+            outcome.skipped[0].reason,
+            SkipReason::Risky(Applicability::MaybeIncorrect)
+        );
+    }
+
+    #[test]
+    fn test_fixer_applies_risky_suggestions_when_allowed() {
+        let source = "let a = 1; let b = 2;";
+        let suggestions = vec![
+            suggestion(19, 20, "20", Applicability::HasPlaceholders),
+            suggestion(8, 9, "10", Applicability::MachineApplicable),
+        ];
+
+        let outcome = Fixer::new(true).apply(source, suggestions);
+
+        assert_eq!(outcome.source, "let a = 10; let b = 20;");
+        assert!(outcome.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_fixer_skips_out_of_bounds_suggestions() {
+        let source = "let a = 1;";
+        let suggestions = vec![suggestion(20, 30, "x", Applicability::MachineApplicable)];
+
+        let outcome = Fixer::new(false).apply(source, suggestions);
+
+        assert_eq!(outcome.source, source);
+        assert_eq!(outcome.skipped[0].reason, SkipReason::OutOfBounds);
+    }
+
+    #[test]
+    fn test_fixer_applies_maximal_non_conflicting_subset() {
+        let source = "let a = 1; let b = 2;";
+        let suggestions = vec![
+            suggestion(0, 21, "let c = 3;", Applicability::MachineApplicable),
+            suggestion(8, 9, "10", Applicability::MachineApplicable),
+            suggestion(19, 20, "20", Applicability::MachineApplicable),
+        ];
+
+        let outcome = Fixer::new(false).apply(source, suggestions);
+
+        assert_eq!(outcome.source, "let a = 10; let b = 20;");
+        assert_eq!(outcome.applied.len(), 2);
+        assert_eq!(
+            outcome.skipped[0].reason,
+            SkipReason::Conflict("replace with 10".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fixer_applies_identical_suggestions_once() {
+        let source = "let a = 1;";
+        let suggestions = vec![
+            suggestion(8, 9, "2", Applicability::MachineApplicable),
+            suggestion(8, 9, "2", Applicability::MachineApplicable),
+            suggestion(0, 3, "var", Applicability::MachineApplicable),
+        ];
+
+        let outcome = Fixer::new(false).apply(source, suggestions);
+
+        assert_eq!(outcome.source, "var a = 2;");
+        assert_eq!(outcome.skipped.len(), 1);
+        assert_eq!(outcome.skipped[0].reason, SkipReason::Duplicate);
+    }
+
+    #[test]
+    fn test_fixer_applies_or_skips_the_edits_of_a_suggestion_together() {
+        let source = "let a = 1; let b = 2; let c = a;";
+        let mut rename = suggestion(4, 5, "x", Applicability::MachineApplicable);
+        rename.other_edits = vec![(Span::from(30..31), "x".to_string())];
+
+        let outcome = Fixer::new(false).apply(
+            source,
+            vec![
+                rename.clone(),
+                suggestion(19, 20, "20", Applicability::MachineApplicable),
+            ],
+        );
+        assert_eq!(outcome.source, "let x = 1; let b = 20; let c = x;");
+
+        let outcome = Fixer::new(false).apply(
+            source,
+            vec![
+                rename,
+                suggestion(0, 9, "let y = 1", Applicability::MachineApplicable),
+            ],
+        );
+        assert_eq!(outcome.source, "let y = 1; let b = 2; let c = a;");
+        assert_eq!(
+            outcome.skipped[0].reason,
+            SkipReason::Conflict("replace with let y = 1".to_string())
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! # Frontend Bindings
+//!
+//! Accessors for the parts of the `noirc_frontend` AST whose API tends to change between
+//! releases, such as the locations of nodes, so that following a new release of the frontend
+//! only takes updating this module.
+
+use fm::FileId;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    BlockExpression, Expression, ExpressionKind, FunctionDefinition, Statement,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::{Item, ParserError};
+use noirc_frontend::token::FunctionAttribute;
+
+/// The `noirc_frontend` release the analyzer is built against.
+pub(crate) struct Noir;
+
+impl Noir {
+    /// Parses the source of a file.
+    pub(crate) fn parse_program(source: &str, file_id: FileId) -> (ParsedModule, Vec<ParserError>) {
+        noirc_frontend::parse_program(source, file_id)
+    }
+
+    /// Returns the location of a module item.
+    pub(crate) fn item_location(item: &Item) -> (Span, FileId) {
+        (item.location.span, item.location.file)
+    }
+
+    /// Returns the location of an expression.
+    pub(crate) fn expression_location(expression: &Expression) -> (Span, FileId) {
+        (expression.location.span, expression.location.file)
+    }
+
+    /// Returns the location of a function definition.
+    pub(crate) fn function_location(function: &FunctionDefinition) -> (Span, FileId) {
+        (function.location.span, function.location.file)
+    }
+
+    /// Returns the location of a statement.
+    pub(crate) fn statement_location(statement: &Statement) -> (Span, FileId) {
+        (statement.location.span, statement.location.file)
+    }
+
+    /// Returns the primary attribute of a function, such as `#[test]`, if it has one.
+    pub(crate) fn function_attribute(function: &FunctionDefinition) -> Option<&FunctionAttribute> {
+        function
+            .attributes
+            .function
+            .as_ref()
+            .map(|(attribute, _)| attribute)
+    }
+
+    /// Returns the block wrapped by an `unsafe { }` expression, if the expression is one.
+    pub(crate) fn unsafe_block(expression: &Expression) -> Option<&BlockExpression> {
+        match &expression.kind {
+            ExpressionKind::Unsafe(unsafe_expression) => Some(&unsafe_expression.block),
+            _ => None,
+        }
+    }
+}
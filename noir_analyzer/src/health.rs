@@ -0,0 +1,362 @@
+//! # Health Score
+//!
+//! Sums up the findings of a project into a single number between 0 and 100, for
+//! dashboards tracking code quality over time. Each finding costs the weight of its
+//! category, twice for errors, and the total is scaled by the size of the code, so that a
+//! growing project is not penalized for findings it had all along:
+//!
+//! ```text
+//! score = 100 / (1 + penalty / (lines / 10))
+//! ```
+//!
+//! A project without findings scores 100, and one with a soundness finding per hundred lines
+//! scores 50. Scores are recorded in a history file, by default
+//! `target/noir-analyzer/history.json`, so that each run reports its change since the
+//! previous one.
+
+use crate::diagnostics::finding::Finding;
+use crate::diagnostics::lint::Severity;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::parser::ItemKind;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Default location of the history file, relative to the workspace root.
+pub const HISTORY_PATH: &str = "target/noir-analyzer/history.json";
+
+/// Version of the history format written by this crate.
+const HISTORY_VERSION: u32 = 1;
+
+/// Category of the lints, by lint name. Lints not listed are counted as maintainability.
+const CATEGORIES: &[(&str, Category)] = &[
+    ("black-box-input-range", Category::Soundness),
+    ("bool-field-cast", Category::Performance),
+    ("call-chain-depth", Category::Maintainability),
+    ("conditional-assignment", Category::Soundness),
+    ("contract-function-visibility", Category::Security),
+    ("cyclic-module-imports", Category::Maintainability),
+    ("deep-constraint-expression", Category::Maintainability),
+    ("dependency-hygiene", Category::Security),
+    ("duplicate-public-function", Category::Maintainability),
+    ("empty-block", Category::Maintainability),
+    ("field-accumulation-overflow", Category::Soundness),
+    ("field-modulo", Category::Soundness),
+    ("first-element-comparison", Category::Soundness),
+    ("function-naming", Category::Style),
+    ("god-module", Category::Maintainability),
+    ("hint-drift", Category::Soundness),
+    ("import-order", Category::Style),
+    ("inconsistent-variant-handling", Category::Soundness),
+    ("integer-literal-overflow", Category::Soundness),
+    ("large-struct", Category::Performance),
+    ("needless-visibility", Category::Style),
+    ("oversized-entry-point", Category::Maintainability),
+    ("oversized-unsafe-block", Category::Security),
+    ("private-assert-message", Category::Security),
+    ("private-data-leak", Category::Security),
+    ("prover-input-mismatch", Category::Security),
+    ("public-only-constraint", Category::Performance),
+    ("redundant-equality", Category::Performance),
+    ("redundant-import", Category::Style),
+    ("repeated-linear-combination", Category::Performance),
+    ("shadowed-trait-method", Category::Security),
+    ("single-instantiation-generic", Category::Style),
+    ("stdlib-shadowing", Category::Security),
+    ("struct-field-order", Category::Style),
+    ("trailing-semicolon", Category::Soundness),
+    ("truncating-cast", Category::Soundness),
+    ("unchecked-public-function", Category::Soundness),
+    ("unchecked-slice-conversion", Category::Soundness),
+    ("unconstrained-parameter", Category::Soundness),
+    ("unconstrained-struct-field", Category::Soundness),
+    ("undocumented-numeric-generic", Category::Maintainability),
+    ("unused-function", Category::Maintainability),
+    ("unused-import", Category::Style),
+];
+
+#[derive(Debug, Error)]
+pub enum HistoryError {
+    #[error("Failed to read score history {0:?}: {1}")]
+    FileReadError(PathBuf, String),
+    #[error("Failed to write score history {0:?}: {1}")]
+    FileWriteError(PathBuf, String),
+    #[error("Failed to parse score history: {0}")]
+    ParseError(String),
+    #[error("Unsupported score history version {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// What a finding puts at risk, which sets its weight in the score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    /// Constraints that may not check what they should.
+    Soundness,
+    /// Code exposing more than intended, or hiding what it does from reviewers.
+    Security,
+    /// Constraints or witnesses that could be saved.
+    Performance,
+    /// Code that is hard to review or evolve.
+    Maintainability,
+    /// Formatting and hygiene.
+    Style,
+}
+
+impl Category {
+    /// Every category, from the most to the least severe.
+    pub const ALL: [Category; 5] = [
+        Category::Soundness,
+        Category::Security,
+        Category::Performance,
+        Category::Maintainability,
+        Category::Style,
+    ];
+
+    /// Returns the category of the findings of a lint.
+    pub fn of(lint_name: &str) -> Self {
+        CATEGORIES
+            .binary_search_by_key(&lint_name, |(name, _)| name)
+            .map_or(Category::Maintainability, |index| CATEGORIES[index].1)
+    }
+
+    /// Penalty of a warning of this category; errors cost twice as much.
+    pub fn weight(self) -> f64 {
+        match self {
+            Category::Soundness => 10.0,
+            Category::Security => 5.0,
+            Category::Performance => 2.0,
+            Category::Maintainability => 1.0,
+            Category::Style => 0.5,
+        }
+    }
+
+    /// Returns the name of the category, as written in reports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Category::Soundness => "soundness",
+            Category::Security => "security",
+            Category::Performance => "performance",
+            Category::Maintainability => "maintainability",
+            Category::Style => "style",
+        }
+    }
+}
+
+/// Size of the analyzed code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metrics {
+    /// Number of source files.
+    pub files: usize,
+    /// Number of non-blank lines.
+    pub lines: usize,
+    /// Number of functions declared outside of `impl` blocks and traits.
+    pub functions: usize,
+}
+
+impl Metrics {
+    /// Adds a source file, with its parsed module when it could be parsed.
+    pub fn add_file(&mut self, source: &str, parsed_module: Option<&ParsedModule>) {
+        self.files += 1;
+        self.lines += source
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .count();
+        if let Some(parsed_module) = parsed_module {
+            self.functions += count_functions(parsed_module);
+        }
+    }
+}
+
+/// The health score of a project, with what it was computed from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    /// Score between 0 and 100, rounded to one decimal.
+    pub value: f64,
+    /// Number of findings by category.
+    pub findings: BTreeMap<Category, usize>,
+    pub metrics: Metrics,
+}
+
+impl Score {
+    /// Computes the score of a project from its findings and metrics.
+    pub fn compute(findings: &[Finding], metrics: Metrics) -> Self {
+        let mut counts = BTreeMap::new();
+        let mut penalty = 0.0;
+        for finding in findings {
+            let category = Category::of(finding.lint.name);
+            *counts.entry(category).or_insert(0) += 1;
+            penalty += match finding.lint.severity {
+                Severity::Error => 2.0 * category.weight(),
+                Severity::Warning => category.weight(),
+            };
+        }
+
+        // Small projects are scaled as if they had 100 lines, so that a single finding does
+        // not sink their score
+        let scale = metrics.lines.max(100) as f64 / 10.0;
+        let value = 100.0 / (1.0 + penalty / scale);
+        Self {
+            value: (value * 10.0).round() / 10.0,
+            findings: counts,
+            metrics,
+        }
+    }
+}
+
+/// A score recorded in the history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the score was computed, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub score: Score,
+}
+
+/// The scores of the previous runs, oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub version: u32,
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            version: HISTORY_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl History {
+    /// Parses a history from its JSON representation.
+    pub fn parse(content: &str) -> Result<Self, HistoryError> {
+        let history: Self =
+            serde_json::from_str(content).map_err(|e| HistoryError::ParseError(e.to_string()))?;
+        if history.version != HISTORY_VERSION {
+            return Err(HistoryError::UnsupportedVersion(history.version));
+        }
+        Ok(history)
+    }
+
+    /// Loads a history from a file, or returns an empty one when the file does not exist.
+    pub fn load(path: &Path) -> Result<Self, HistoryError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| HistoryError::FileReadError(path.to_path_buf(), e.to_string()))?;
+        Self::parse(&content)
+    }
+
+    /// Writes the history to a file, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), HistoryError> {
+        let write_error =
+            |e: std::io::Error| HistoryError::FileWriteError(path.to_path_buf(), e.to_string());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        let content = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, content + "\n").map_err(write_error)
+    }
+
+    /// Returns the score of the previous run, if any.
+    pub fn previous(&self) -> Option<&Score> {
+        self.entries.last().map(|entry| &entry.score)
+    }
+
+    /// Records a score, returning its change since the previous run, if any.
+    pub fn record(&mut self, score: Score, timestamp: u64) -> Option<f64> {
+        let delta = self
+            .previous()
+            .map(|previous| ((score.value - previous.value) * 10.0).round() / 10.0);
+        self.entries.push(HistoryEntry { timestamp, score });
+        delta
+    }
+}
+
+/// Counts the functions of a module and its submodules.
+fn count_functions(parsed_module: &ParsedModule) -> usize {
+    parsed_module
+        .items
+        .iter()
+        .map(|item| match &item.kind {
+            ItemKind::Function(_) => 1,
+            ItemKind::Submodules(submodule) => count_functions(&submodule.contents),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::lint::{Confidence, Lint};
+
+    fn finding(name: &'static str, severity: Severity) -> Finding {
+        let lint = Lint {
+            name,
+            severity,
+            confidence: Confidence::High,
+            description: String::new(),
+            message: None,
+            span: None,
+            file_id: None,
+            suggestion: None,
+        };
+        Finding::new(lint, Path::new("src/main.nr"), "")
+    }
+
+    #[test]
+    fn test_score_weighs_findings_by_category_and_size() {
+        let metrics = Metrics {
+            files: 2,
+            lines: 400,
+            functions: 12,
+        };
+        assert_eq!(Score::compute(&[], metrics).value, 100.0);
+
+        let score = Score::compute(
+            &[
+                finding("trailing-semicolon", Severity::Warning),
+                finding("unused-import", Severity::Warning),
+                finding("unused-function", Severity::Error),
+                finding("manifest-missing-version", Severity::Warning),
+            ],
+            metrics,
+        );
+        // Penalty of 10 + 0.5 + 2 + 1 over 400 lines
+        assert_eq!(score.value, 74.8);
+        assert_eq!(
+            score.findings,
+            BTreeMap::from([
+                (Category::Soundness, 1),
+                (Category::Maintainability, 2),
+                (Category::Style, 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_history_reports_the_change_since_the_previous_run() {
+        let metrics = Metrics::default();
+        let mut history = History::default();
+        let first = Score::compute(&[finding("field-modulo", Severity::Warning)], metrics);
+        assert_eq!(history.record(first, 1), None);
+
+        let second = Score::compute(&[], metrics);
+        assert_eq!(history.record(second, 2), Some(50.0));
+
+        let content = serde_json::to_string(&history).unwrap();
+        assert!(content.contains("\"findings\":{\"soundness\":1}"));
+        assert_eq!(History::parse(&content).unwrap(), history);
+    }
+
+    #[test]
+    fn test_categories_are_sorted() {
+        assert!(CATEGORIES.is_sorted_by_key(|(name, _)| *name));
+    }
+}
@@ -0,0 +1,287 @@
+//! # Typed HIR
+//!
+//! The AST neither knows the types of expressions nor what their names resolve to. This
+//! module runs the elaboration of `noirc_frontend` on a program, which resolves and type
+//! checks it along with the standard library, and gives the resulting typed HIR to the
+//! rules implementing `TypedLintRule` through a `TypedContext`.
+//!
+//! Elaboration is an optional second phase of the analysis: it costs much more than
+//! parsing, and fails on programs that do not type check or that depend on packages other
+//! than the standard library, which are then only analyzed on their AST.
+
+use crate::frontend::Noir;
+use fm::{FileId, FileManager};
+use noirc_driver::{CompileOptions, check_crate, file_manager_with_stdlib, prepare_crate};
+use noirc_frontend::Type;
+use noirc_frontend::graph::CrateId;
+use noirc_frontend::hir::Context;
+use noirc_frontend::hir::def_map::ModuleDefId;
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::hir_def::expr::{HirArrayLiteral, HirExpression, HirLiteral};
+use noirc_frontend::hir_def::stmt::HirStatement;
+use noirc_frontend::node_interner::{ExprId, FuncId, NodeInterner, StmtId};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TypedError {
+    #[error("Opening {0:?} with error: {1:?}")]
+    FileReadError(PathBuf, String),
+    #[error("Elaboration failed with errors: {0:?}")]
+    ElaborationError(Vec<String>),
+}
+
+/// Path of the entry file of a program elaborated from its source alone.
+const ENTRY_FILE: &str = "main.nr";
+
+/// A program elaborated into typed HIR.
+pub struct TypedContext {
+    context: Context<'static, 'static>,
+    crate_id: CrateId,
+    /// The file the AST was parsed from, whose spans are shared with the AST.
+    entry_file: FileId,
+}
+
+impl TypedContext {
+    /// Elaborates a program made of a single file.
+    pub fn from_source(source: &str) -> Result<Self, TypedError> {
+        let mut file_manager = file_manager_with_stdlib(Path::new(""));
+        file_manager
+            .add_file_with_source(Path::new(ENTRY_FILE), source.to_string())
+            .expect("The entry file should be the only file of the program");
+        Self::elaborate(file_manager, Path::new(ENTRY_FILE))
+    }
+
+    /// Elaborates a program from its entry file, with the `.nr` files of its directory and
+    /// subdirectories as its modules.
+    pub fn from_entry_file(entry_path: &Path) -> Result<Self, TypedError> {
+        let root = entry_path.parent().unwrap_or(Path::new(""));
+        let mut file_manager = file_manager_with_stdlib(root);
+        add_source_files(root, &mut file_manager)?;
+        Self::elaborate(file_manager, entry_path)
+    }
+
+    fn elaborate(file_manager: FileManager, entry_path: &Path) -> Result<Self, TypedError> {
+        let parsed_files = file_manager
+            .as_file_map()
+            .all_file_ids()
+            .map(|file_id| {
+                let source = file_manager.fetch_file(*file_id).unwrap_or_default();
+                (*file_id, Noir::parse_program(source, *file_id))
+            })
+            .collect();
+
+        let mut context = Context::new(file_manager, parsed_files);
+        let crate_id = prepare_crate(&mut context, entry_path);
+        check_crate(&mut context, crate_id, &CompileOptions::default()).map_err(|diagnostics| {
+            TypedError::ElaborationError(
+                diagnostics
+                    .into_iter()
+                    .filter(|diagnostic| diagnostic.is_error())
+                    .map(|diagnostic| diagnostic.message)
+                    .collect(),
+            )
+        })?;
+
+        let entry_file = context.crate_graph[crate_id].root_file_id;
+        Ok(Self {
+            context,
+            crate_id,
+            entry_file,
+        })
+    }
+
+    /// Returns the interner holding the HIR nodes of the program and their types.
+    pub fn interner(&self) -> &NodeInterner {
+        &self.context.def_interner
+    }
+
+    /// Returns the functions declared in the modules of the program, the standard library
+    /// and the methods of `impl` blocks excluded.
+    pub fn functions(&self) -> Vec<FuncId> {
+        let Some(def_map) = self.context.def_map(&self.crate_id) else {
+            return vec![];
+        };
+        def_map
+            .modules()
+            .iter()
+            .flat_map(|(_, module)| module.value_definitions())
+            .filter_map(|definition| match definition {
+                ModuleDefId::FunctionId(function) => Some(function),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the name of a function.
+    pub fn function_name(&self, function: FuncId) -> &str {
+        self.interner().function_name(&function)
+    }
+
+    /// Returns the expressions of the body of a function, each before the expressions it
+    /// contains.
+    pub fn expressions(&self, function: FuncId) -> Vec<ExprId> {
+        let mut expressions = vec![];
+        if let Some(body) = self.interner().function(&function).try_as_expr() {
+            self.collect_expression(body, &mut expressions);
+        }
+        expressions
+    }
+
+    /// Returns the HIR node of an expression.
+    pub fn expression(&self, expression: ExprId) -> HirExpression {
+        self.interner().expression(&expression)
+    }
+
+    /// Returns the type of an expression, with its type variables replaced by the types
+    /// inferred for them.
+    pub fn type_of(&self, expression: ExprId) -> Type {
+        self.interner().id_type(expression).follow_bindings()
+    }
+
+    /// Returns the span of an expression when it is in the file the AST was parsed from,
+    /// where lints are reported.
+    pub fn span(&self, expression: ExprId) -> Option<Span> {
+        let location = self.interner().expr_location(&expression);
+        (location.file == self.entry_file).then_some(location.span)
+    }
+
+    fn collect_expression(&self, expression: ExprId, expressions: &mut Vec<ExprId>) {
+        expressions.push(expression);
+        let children = match self.expression(expression) {
+            HirExpression::Block(block) | HirExpression::Unsafe(block) => {
+                for statement in block.statements() {
+                    self.collect_statement(*statement, expressions);
+                }
+                vec![]
+            }
+            HirExpression::Prefix(prefix) => vec![prefix.rhs],
+            HirExpression::Infix(infix) => vec![infix.lhs, infix.rhs],
+            HirExpression::Index(index) => vec![index.collection, index.index],
+            HirExpression::MemberAccess(access) => vec![access.lhs],
+            HirExpression::Call(call) => std::iter::once(call.func).chain(call.arguments).collect(),
+            HirExpression::Constrain(constrain) => {
+                std::iter::once(constrain.0).chain(constrain.2).collect()
+            }
+            HirExpression::Cast(cast) => vec![cast.lhs],
+            HirExpression::If(if_expression) => std::iter::once(if_expression.condition)
+                .chain([if_expression.consequence])
+                .chain(if_expression.alternative)
+                .collect(),
+            HirExpression::Tuple(elements) => elements,
+            HirExpression::Lambda(lambda) => vec![lambda.body],
+            HirExpression::Constructor(constructor) => constructor
+                .fields
+                .into_iter()
+                .map(|(_, field)| field)
+                .collect(),
+            HirExpression::Literal(HirLiteral::Array(array) | HirLiteral::Slice(array)) => {
+                match array {
+                    HirArrayLiteral::Standard(elements) => elements,
+                    HirArrayLiteral::Repeated {
+                        repeated_element, ..
+                    } => vec![repeated_element],
+                }
+            }
+            _ => vec![],
+        };
+        for child in children {
+            self.collect_expression(child, expressions);
+        }
+    }
+
+    fn collect_statement(&self, statement: StmtId, expressions: &mut Vec<ExprId>) {
+        match self.interner().statement(&statement) {
+            HirStatement::Let(let_statement) => {
+                self.collect_expression(let_statement.expression, expressions);
+            }
+            HirStatement::Assign(assign) => self.collect_expression(assign.expression, expressions),
+            HirStatement::For(for_loop) => {
+                self.collect_expression(for_loop.start_range, expressions);
+                self.collect_expression(for_loop.end_range, expressions);
+                self.collect_expression(for_loop.block, expressions);
+            }
+            HirStatement::Loop(expression)
+            | HirStatement::Expression(expression)
+            | HirStatement::Semi(expression) => self.collect_expression(expression, expressions),
+            _ => {}
+        }
+    }
+}
+
+/// Adds the `.nr` files of a directory and its subdirectories to the file manager.
+fn add_source_files(directory: &Path, file_manager: &mut FileManager) -> Result<(), TypedError> {
+    let read_error =
+        |e: std::io::Error| TypedError::FileReadError(directory.to_path_buf(), e.to_string());
+    for entry in fs::read_dir(directory).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if path.is_dir() {
+            add_source_files(&path, file_manager)?;
+        } else if path.extension().is_some_and(|extension| extension == "nr") {
+            let source = fs::read_to_string(&path)
+                .map_err(|e| TypedError::FileReadError(path.clone(), e.to_string()))?;
+            file_manager.add_file_with_source(&path, source);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elaborated_expressions_have_types() {
+        let source = r#"
+            fn widen(x: u8) -> u32 {
+                x as u32
+            }
+
+            fn main(x: u8, y: Field) -> pub Field {
+                let z = widen(x);
+                y + z as Field
+            }
+            "#;
+
+        let types = TypedContext::from_source(source).expect("Should type check");
+        let mut names: Vec<&str> = types
+            .functions()
+            .into_iter()
+            .map(|function| types.function_name(function))
+            .collect();
+        names.sort();
+        assert_eq!(names, ["main", "widen"]);
+
+        let widen = types
+            .functions()
+            .into_iter()
+            .find(|function| types.function_name(*function) == "widen")
+            .unwrap();
+        let cast_types: Vec<String> = types
+            .expressions(widen)
+            .into_iter()
+            .filter_map(|expression| match types.expression(expression) {
+                HirExpression::Cast(cast) => {
+                    Some(format!("{} as {}", types.type_of(cast.lhs), cast.r#type))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(cast_types, ["u8 as u32"]);
+    }
+
+    #[test]
+    fn test_type_errors_fail_elaboration() {
+        let source = r#"
+            fn main(x: u8) {
+                let _y: bool = x;
+            }
+            "#;
+
+        assert!(matches!(
+            TypedContext::from_source(source),
+            Err(TypedError::ElaborationError(_))
+        ));
+    }
+}
@@ -1,14 +1,40 @@
 //! # Noir Static Analyzer
 //!
 //! This crate provides static analysis capabilities for Noir programs.
-//! It currently focuses on AST (Abstract Syntax Tree) analysis and will
-//! later extend to ACIR (Abstract Circuit Intermediate Representation) analysis.
+//! It mainly analyzes the AST (Abstract Syntax Tree), and checks compiled
+//! ACIR (Abstract Circuit Intermediate Representation) circuits.
 //!
 //! ## Features
 //! - AST linting using the visitor pattern
-//! - Placeholder structure for ACIR analysis
+//! - ACIR lints on the circuits compiled by `nargo compile`
+//! - Lints on the typed HIR of programs elaborated by `noirc_frontend`
+
+/// The `noirc_frontend` release the analyzer is built against, so that dependents use
+/// matching AST types.
+pub use noirc_frontend;
+
+/// The `toml` release rule options are read with, for the code `declare_lint!` expands to.
+pub use toml;
 
 pub mod acir;
+pub mod advisories;
+pub mod analysis;
 pub mod ast;
+pub mod baseline;
+pub mod blame;
+pub mod changes;
+pub mod compiler_version;
+pub mod config;
 pub mod diagnostics;
+pub mod expansion;
+pub mod expectations;
+pub mod fix;
+mod frontend;
+pub mod health;
+pub mod hir;
 pub mod lints;
+pub mod provenance;
+pub mod schema;
+pub mod scope;
+pub mod sources;
+pub mod webhook;
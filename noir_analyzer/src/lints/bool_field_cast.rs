@@ -0,0 +1,384 @@
+//! # Bool Field Cast Lint
+//!
+//! Flags arithmetic tricks converting between `bool` and `Field`:
+//!
+//! - a boolean cast to `Field` and used as a 0/1 multiplier, as in `flag as Field * y`,
+//!   without a comment explaining it. `if flag { y } else { 0 }` states the selection and
+//!   compiles to the same single multiplication;
+//! - an is-zero comparison cast to `Field`, as in `(f != 0) as Field`. The comparison costs
+//!   an inverse hint and two constraints however the 0/1 value is written, which is worth
+//!   knowing before turning a `Field` into a flag;
+//! - a boolean cast to `Field` and compared back with `0` or `1`, as in `(flag as Field) == 1`,
+//!   whose comparison costs an is-zero check where using the `bool` costs nothing.
+//!
+//! Types are only known from annotations and from the expressions producing booleans, such
+//! as comparisons, so only those values are considered booleans. When the source is known,
+//! each gets a fix rewriting it; only the round-trip fix is machine-applicable, since
+//! assertions in the selected operand of an `if` only apply when the condition holds.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use noirc_frontend::ast::{
+    BinaryOpKind, CastExpression, Expression, ExpressionKind, LetStatement, Literal, Pattern,
+    UnaryOp, UnresolvedTypeData, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::HashSet;
+
+/// Detects conversions between `bool` and `Field` written as arithmetic.
+#[derive(Clone, Default)]
+pub struct BoolFieldCast;
+
+declare_lint! {
+    BoolFieldCast {
+        name: "bool-field-cast",
+        explanation: "Reports booleans cast to `Field` and used as 0/1 multipliers without a comment, is-zero comparisons cast to `Field`, and booleans cast to `Field` only to be compared with 0 or 1. An `if` expression states a selection for the same single multiplication, an is-zero flag costs an inverse hint and two constraints however it is written, and comparing a cast boolean costs an is-zero check that using the `bool` avoids.",
+    }
+}
+
+impl BoolFieldCast {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            let mut collector = CastCollector {
+                function: name,
+                source: context.source,
+                booleans: HashSet::new(),
+                reported: HashSet::new(),
+                lints: vec![],
+            };
+            for parameter in &function.parameters {
+                if let (Pattern::Identifier(ident), UnresolvedTypeData::Bool) =
+                    (&parameter.pattern, &parameter.typ.typ)
+                {
+                    collector.booleans.insert(ident.to_string());
+                }
+            }
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+            lints.extend(collector.lints);
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Reports the conversions of the visited statements.
+struct CastCollector<'a> {
+    function: &'a str,
+    source: Option<&'a str>,
+    /// Names of the variables holding booleans.
+    booleans: HashSet<String>,
+    /// Starts of the casts already reported as part of an enclosing expression.
+    reported: HashSet<u32>,
+    lints: Vec<Lint>,
+}
+
+impl CastCollector<'_> {
+    /// Returns whether an expression evaluates to a boolean.
+    fn is_bool(&self, expression: &Expression) -> bool {
+        match &expression.kind {
+            ExpressionKind::Literal(Literal::Bool(_)) => true,
+            ExpressionKind::Variable(path) if path.segments.len() == 1 => {
+                self.booleans.contains(&path.segments[0].ident.to_string())
+            }
+            ExpressionKind::Parenthesized(inner) => self.is_bool(inner),
+            ExpressionKind::Prefix(prefix) => {
+                matches!(prefix.operator, UnaryOp::Not) && self.is_bool(&prefix.rhs)
+            }
+            ExpressionKind::Infix(infix) => match infix.operator.contents {
+                BinaryOpKind::And | BinaryOpKind::Or | BinaryOpKind::Xor => {
+                    self.is_bool(&infix.lhs) && self.is_bool(&infix.rhs)
+                }
+                operator => is_comparison(operator),
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the boolean cast to `Field` by an expression, if it is such a cast.
+    fn bool_cast<'e>(&self, expression: &'e Expression) -> Option<(&'e Expression, Span)> {
+        match &expression.kind {
+            ExpressionKind::Parenthesized(inner) => self.bool_cast(inner),
+            ExpressionKind::Cast(cast) if is_field_cast(cast) && self.is_bool(&cast.lhs) => {
+                Some((&cast.lhs, Noir::expression_location(expression).0))
+            }
+            _ => None,
+        }
+    }
+
+    fn report(
+        &mut self,
+        expression: &Expression,
+        message: Message,
+        suggestion: Option<Suggestion>,
+    ) {
+        let (span, file_id) = Noir::expression_location(expression);
+        self.lints.push(Lint {
+            name: BoolFieldCast::NAME,
+            severity: Severity::Warning,
+            confidence: Confidence::Medium,
+            description: message.to_string(),
+            message: Some(message),
+            span: Some(span),
+            file_id: Some(file_id),
+            suggestion,
+        });
+    }
+
+    /// Checks a product for a boolean used as a 0/1 multiplier.
+    fn check_multiplier(&mut self, expression: &Expression, lhs: &Expression, rhs: &Expression) {
+        let Some((flag, cast, value)) =
+            [(lhs, rhs), (rhs, lhs)]
+                .into_iter()
+                .find_map(|(operand, other)| {
+                    self.bool_cast(operand)
+                        .map(|(flag, cast)| (flag, cast, other))
+                })
+        else {
+            return;
+        };
+        self.reported.insert(cast.start());
+
+        let (span, _) = Noir::expression_location(expression);
+        if self.source.is_some_and(|source| is_commented(source, span)) {
+            return;
+        }
+        let suggestion = self.source.and_then(|source| {
+            Some(Suggestion {
+                message: "select the value with an `if` expression".to_string(),
+                span,
+                replacement: format!(
+                    "if {} {{ {} }} else {{ 0 }}",
+                    text(source, flag)?,
+                    text(source, value)?
+                ),
+                applicability: Applicability::MaybeIncorrect,
+                other_edits: vec![],
+            })
+        });
+        let message = Message::new("bool-field-cast.multiplier")
+            .with(
+                "flag",
+                self.source
+                    .and_then(|source| text(source, flag))
+                    .unwrap_or("value"),
+            )
+            .with("function", self.function);
+        self.report(expression, message, suggestion);
+    }
+
+    /// Checks a comparison of a boolean cast to `Field` with 0 or 1.
+    fn check_round_trip(
+        &mut self,
+        expression: &Expression,
+        lhs: &Expression,
+        rhs: &Expression,
+        is_equal: bool,
+    ) {
+        let Some((flag, cast, one)) =
+            [(lhs, rhs), (rhs, lhs)]
+                .into_iter()
+                .find_map(|(operand, other)| {
+                    let (flag, cast) = self.bool_cast(operand)?;
+                    Some((flag, cast, zero_or_one(other)?))
+                })
+        else {
+            return;
+        };
+        self.reported.insert(cast.start());
+
+        let (span, _) = Noir::expression_location(expression);
+        let suggestion = self.source.and_then(|source| {
+            let flag_text = text(source, flag)?;
+            let replacement = if is_equal == one {
+                flag_text.to_string()
+            } else if matches!(
+                flag.kind,
+                ExpressionKind::Variable(_) | ExpressionKind::Parenthesized(_)
+            ) {
+                format!("!{flag_text}")
+            } else {
+                format!("!({flag_text})")
+            };
+            Some(Suggestion {
+                message: "use the boolean directly".to_string(),
+                span,
+                replacement,
+                applicability: Applicability::MachineApplicable,
+                other_edits: vec![],
+            })
+        });
+        let message = Message::new("bool-field-cast.round-trip").with("function", self.function);
+        self.report(expression, message, suggestion);
+    }
+
+    /// Checks a cast of an is-zero comparison to `Field`.
+    fn check_flag(&mut self, expression: &Expression, cast: &CastExpression) {
+        let (span, _) = Noir::expression_location(expression);
+        if !is_field_cast(cast) || self.reported.contains(&span.start()) {
+            return;
+        }
+        let mut comparison = &cast.lhs;
+        while let ExpressionKind::Parenthesized(inner) = &comparison.kind {
+            comparison = inner;
+        }
+        let ExpressionKind::Infix(infix) = &comparison.kind else {
+            return;
+        };
+        if !matches!(
+            infix.operator.contents,
+            BinaryOpKind::Equal | BinaryOpKind::NotEqual
+        ) || zero_or_one(&infix.lhs).or_else(|| zero_or_one(&infix.rhs)) != Some(false)
+        {
+            return;
+        }
+
+        let suggestion = self.source.and_then(|source| {
+            Some(Suggestion {
+                message: "write the 0/1 value with an `if` expression".to_string(),
+                span,
+                replacement: format!("if {} {{ 1 }} else {{ 0 }}", text(source, comparison)?),
+                applicability: Applicability::MaybeIncorrect,
+                other_edits: vec![],
+            })
+        });
+        let message = Message::new("bool-field-cast.flag").with("function", self.function);
+        self.report(expression, message, suggestion);
+    }
+}
+
+impl Visitor for CastCollector<'_> {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        if let Pattern::Identifier(ident) = &let_statement.pattern {
+            let is_bool = match &let_statement.r#type.typ {
+                UnresolvedTypeData::Bool => true,
+                UnresolvedTypeData::Unspecified => self.is_bool(&let_statement.expression),
+                _ => false,
+            };
+            if is_bool {
+                self.booleans.insert(ident.to_string());
+            } else {
+                self.booleans.remove(&ident.to_string());
+            }
+        }
+        true
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        match &expression.kind {
+            ExpressionKind::Infix(infix) => match infix.operator.contents {
+                BinaryOpKind::Multiply => self.check_multiplier(expression, &infix.lhs, &infix.rhs),
+                BinaryOpKind::Equal => {
+                    self.check_round_trip(expression, &infix.lhs, &infix.rhs, true)
+                }
+                BinaryOpKind::NotEqual => {
+                    self.check_round_trip(expression, &infix.lhs, &infix.rhs, false)
+                }
+                _ => {}
+            },
+            ExpressionKind::Cast(cast) => self.check_flag(expression, cast),
+            _ => {}
+        }
+        true
+    }
+}
+
+fn is_comparison(operator: BinaryOpKind) -> bool {
+    matches!(
+        operator,
+        BinaryOpKind::Equal
+            | BinaryOpKind::NotEqual
+            | BinaryOpKind::Less
+            | BinaryOpKind::LessEqual
+            | BinaryOpKind::Greater
+            | BinaryOpKind::GreaterEqual
+    )
+}
+
+fn is_field_cast(cast: &CastExpression) -> bool {
+    matches!(cast.r#type.typ, UnresolvedTypeData::FieldElement)
+}
+
+/// Returns whether an expression is the literal 1, `Some(false)` for the literal 0.
+fn zero_or_one(expression: &Expression) -> Option<bool> {
+    match &expression.kind {
+        ExpressionKind::Literal(Literal::Integer(value)) if !value.is_negative() => {
+            match value.absolute_value().try_into_u128()? {
+                0 => Some(false),
+                1 => Some(true),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the source text of an expression.
+fn text<'a>(source: &'a str, expression: &Expression) -> Option<&'a str> {
+    let (span, _) = Noir::expression_location(expression);
+    source.get(span.start() as usize..span.end() as usize)
+}
+
+/// Returns whether a comment is on the lines of a span or on the line above them.
+fn is_commented(source: &str, span: Span) -> bool {
+    let start = span.start() as usize;
+    let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+    let previous_start = source[..line_start.saturating_sub(1)]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1);
+    let line_end = source[span.end() as usize..]
+        .find('\n')
+        .map_or(source.len(), |newline| span.end() as usize + newline);
+    let lines = &source[previous_start..line_end];
+    lines.contains("//") || lines.contains("/*")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    #[test]
+    fn test_bool_field_arithmetic_is_flagged_and_rewritten() {
+        let source = "fn main(enabled: bool, x: Field, y: Field) -> pub Field {\n    let in_range = x != y;\n    let scaled = enabled as Field * y;\n    // Zero out the fee unless the account is active\n    let fee = in_range as Field * x;\n    assert((enabled as Field) == 1);\n    assert(in_range as Field != 1);\n    let is_set = (x != 0) as Field;\n    scaled + fee + is_set + (y as u8) as Field * 2\n}\n";
+        let lint = Box::new(BoolFieldCast);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Boolean 'enabled' is used as a 0/1 multiplier in 'main'; an `if` expression states the selection for the same single multiplication",
+                "Boolean cast to `Field` is compared with 0 or 1 in 'main', which costs an is-zero check that using the `bool` avoids",
+                "Boolean cast to `Field` is compared with 0 or 1 in 'main', which costs an is-zero check that using the `bool` avoids",
+                "Is-zero comparison is cast to `Field` in 'main'; it costs an inverse hint and two constraints however the 0/1 value is written",
+            ]
+        );
+
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(true).apply(source, suggestions).source;
+        assert_eq!(
+            fixed,
+            "fn main(enabled: bool, x: Field, y: Field) -> pub Field {\n    let in_range = x != y;\n    let scaled = if enabled { y } else { 0 };\n    // Zero out the fee unless the account is active\n    let fee = in_range as Field * x;\n    assert(enabled);\n    assert(!in_range);\n    let is_set = if x != 0 { 1 } else { 0 };\n    scaled + fee + is_set + (y as u8) as Field * 2\n}\n"
+        );
+    }
+}
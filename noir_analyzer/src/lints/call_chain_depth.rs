@@ -0,0 +1,140 @@
+//! # Call Chain Depth Lint
+//!
+//! Flags entry points whose longest chain of calls into the program is deeper than a limit.
+//! Deep call chains are inlined by the compiler into a single circuit, which blows up
+//! compilation, and make it hard to trace which function a constraint comes from. The
+//! longest chain is shown in the diagnostic.
+//!
+//! The limit defaults to 8 calls and can be set in `noir-analyzer.toml`:
+//!
+//! ```toml
+//! [lints.call-chain-depth]
+//! max-depth = 12
+//! ```
+
+use crate::analysis::entry_points::{EntryPointKind, entry_points};
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use serde::Deserialize;
+
+/// Default maximum number of nested calls from an entry point.
+pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+/// Detects entry points with call chains deeper than a limit.
+#[derive(Clone, Default)]
+pub struct CallChainDepth {
+    options: CallChainDepthOptions,
+}
+
+/// Options of [`CallChainDepth`], set under `[lints.call-chain-depth]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CallChainDepthOptions {
+    /// Maximum number of nested calls from an entry point.
+    /// Also set by the `[thresholds]` entry of the lint.
+    #[serde(alias = "threshold")]
+    pub max_depth: usize,
+}
+
+impl Default for CallChainDepthOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl CallChainDepth {
+    /// Creates the lint with a custom limit.
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            options: CallChainDepthOptions { max_depth },
+        }
+    }
+}
+
+declare_lint! {
+    CallChainDepth {
+        name: "call-chain-depth",
+        explanation: "Reports entry points whose longest chain of calls is deeper than a limit (8 by default, configurable as max-depth under [lints.call-chain-depth] in noir-analyzer.toml). Deep call chains are inlined into a single circuit, which slows down compilation, and make it hard to review where constraints come from.",
+        requires_whole_program: true,
+        analyses: [CallGraph],
+        options: CallChainDepthOptions,
+    }
+}
+
+impl CallChainDepth {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        for entry_point in entry_points(context.parsed_module, false) {
+            if entry_point.kind == EntryPointKind::Test {
+                continue;
+            }
+            let name = entry_point
+                .path
+                .rsplit("::")
+                .next()
+                .unwrap_or(&entry_point.path);
+            let chain = context.call_graph.longest_chain(name);
+            let depth = chain.len() - 1;
+            if depth <= self.options.max_depth {
+                continue;
+            }
+
+            let message = Message::new("call-chain-depth")
+                .with("entry_point", &entry_point.path)
+                .with("depth", depth)
+                .with("max", self.options.max_depth)
+                .with("chain", chain.join(" -> "));
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::Medium,
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(entry_point.span),
+                file_id: Some(entry_point.file_id),
+                suggestion: None,
+            });
+        }
+
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_deep_call_chain_is_flagged_with_its_path() {
+        let source_code = r#"
+            fn c() -> Field { 1 }
+            fn b() -> Field { c() }
+            fn a() -> Field { b() + c() }
+            fn main() -> pub Field { a() }
+
+            #[test]
+            fn test_a() { let _ = a(); }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint = Box::new(CallChainDepth::with_max_depth(2));
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Call chain from 'main' is 3 calls deep, more than the limit of 2: main -> a -> b -> c"
+        );
+
+        let lint = Box::new(CallChainDepth::default());
+        let result = Analyzer::new(&[lint]).analyze(&root).unwrap();
+        assert!(result.is_empty());
+    }
+}
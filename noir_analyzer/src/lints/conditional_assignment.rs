@@ -0,0 +1,287 @@
+//! # Conditional Assignment Lint
+//!
+//! Flags mutable variables initialized to a placeholder, such as `0`, `false` or
+//! `Default::default()`, then only assigned in an `if` without `else`, before reaching the
+//! return value or an assertion of the function. When the condition does not hold, the
+//! placeholder flows on as if it had been computed, and a constraint that the default value
+//! happens to satisfy passes: the "default zero passes the constraint" bug.
+//!
+//! Only the statements at the top level of a function body are followed. Assignments in
+//! loops or in both branches of an `if` count as initialization, as does an unconditional
+//! assignment after the `if`.
+
+use crate::analysis::taint::ReferenceCollector;
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ast::{
+    ArrayLiteral, AssignStatement, ConstrainExpression, Expression, ExpressionKind, LValue,
+    Literal, Pattern, Statement, StatementKind, Visitor,
+};
+
+/// Detects placeholder values that only an `if` without `else` replaces.
+#[derive(Clone, Default)]
+pub struct ConditionalAssignment;
+
+declare_lint! {
+    ConditionalAssignment {
+        name: "conditional-assignment",
+        explanation: "Reports mutable variables initialized to a placeholder such as `0` and only assigned in an `if` without `else` before reaching the return value or an assertion. When the condition does not hold, the placeholder is used as if it had been computed, and constraints the default value satisfies pass; assign the variable in an `else` branch or assert the condition.",
+    }
+}
+
+/// A placeholder replaced in an `if` without `else`.
+struct Candidate<'ast> {
+    variable: String,
+    initial: String,
+    /// The statement holding the `if`.
+    statement: &'ast Statement,
+    condition: String,
+    /// Index of the statement in the function body.
+    index: usize,
+}
+
+impl ConditionalAssignment {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            if function.is_unconstrained {
+                continue;
+            }
+            let statements = &function.body.statements;
+
+            // Initial values of the variables still holding a placeholder, by name
+            let mut placeholders: Vec<(String, String)> = vec![];
+            let mut candidates = vec![];
+            for (index, statement) in statements.iter().enumerate() {
+                if let StatementKind::Let(let_statement) = &statement.kind {
+                    let (variable, mutable) = match &let_statement.pattern {
+                        Pattern::Identifier(ident) => (ident.to_string(), false),
+                        Pattern::Mutable(pattern, ..) => match pattern.as_ref() {
+                            Pattern::Identifier(ident) => (ident.to_string(), true),
+                            _ => continue,
+                        },
+                        _ => continue,
+                    };
+                    // A new binding shadows any previous one
+                    placeholders.retain(|(name, _)| *name != variable);
+                    if mutable && is_placeholder(&let_statement.expression) {
+                        placeholders.push((variable, let_statement.expression.to_string()));
+                    }
+                    continue;
+                }
+
+                let mut assigned = Assignments::default();
+                statement.accept(&mut assigned);
+                if let Some(if_expression) =
+                    expression(statement).and_then(|expression| match &expression.kind {
+                        ExpressionKind::If(if_expression)
+                            if if_expression.alternative.is_none() =>
+                        {
+                            Some(if_expression)
+                        }
+                        _ => None,
+                    })
+                {
+                    for (variable, initial) in &placeholders {
+                        if assigned.variables.contains(variable) {
+                            candidates.push(Candidate {
+                                variable: variable.clone(),
+                                initial: initial.clone(),
+                                statement,
+                                condition: if_expression.condition.to_string(),
+                                index,
+                            });
+                        }
+                    }
+                }
+                placeholders.retain(|(variable, _)| !assigned.variables.contains(variable));
+            }
+
+            for candidate in candidates {
+                let Some(usage) = usage(&candidate.variable, &statements[candidate.index + 1..])
+                else {
+                    continue;
+                };
+                let (span, file_id) = Noir::statement_location(candidate.statement);
+                let message = Message::new("conditional-assignment")
+                    .with("variable", &candidate.variable)
+                    .with("function", name)
+                    .with("condition", &candidate.condition)
+                    .with("initial", &candidate.initial)
+                    .with("usage", usage);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns the expression of an expression statement.
+fn expression(statement: &Statement) -> Option<&Expression> {
+    match &statement.kind {
+        StatementKind::Expression(expression) | StatementKind::Semi(expression) => Some(expression),
+        _ => None,
+    }
+}
+
+/// Returns where a variable still holding its placeholder is used in the statements
+/// following the `if`: in an assertion or in the return value. Returns `None` when it is
+/// assigned unconditionally first, or not used.
+fn usage(variable: &str, statements: &[Statement]) -> Option<&'static str> {
+    for (index, statement) in statements.iter().enumerate() {
+        if let StatementKind::Assign(assign) = &statement.kind
+            && let LValue::Ident(ident) = &assign.lvalue
+            && ident.to_string() == variable
+        {
+            return None;
+        }
+
+        let mut constraints = ConstraintReferences::default();
+        statement.accept(&mut constraints);
+        if constraints.variables.iter().any(|name| name == variable) {
+            return Some("an assertion");
+        }
+
+        if index + 1 == statements.len()
+            && let StatementKind::Expression(expression) = &statement.kind
+        {
+            let mut references = ReferenceCollector::default();
+            expression.accept(&mut references);
+            if references.variables.iter().any(|name| name == variable) {
+                return Some("the return value");
+            }
+        }
+    }
+    None
+}
+
+/// Returns whether an initial value is a placeholder rather than a computed value.
+fn is_placeholder(expression: &Expression) -> bool {
+    match &expression.kind {
+        ExpressionKind::Literal(Literal::Integer(value)) => {
+            value.absolute_value().try_into_u128() == Some(0)
+        }
+        ExpressionKind::Literal(Literal::Bool(value)) => !value,
+        ExpressionKind::Literal(Literal::Array(ArrayLiteral::Repeated {
+            repeated_element,
+            ..
+        })) => is_placeholder(repeated_element),
+        ExpressionKind::Cast(cast) => is_placeholder(&cast.lhs),
+        ExpressionKind::Parenthesized(inner) => is_placeholder(inner),
+        ExpressionKind::Call(call) if call.arguments.is_empty() => {
+            let function = call.func.to_string();
+            function.ends_with("default") || function.ends_with("zeroed")
+        }
+        _ => false,
+    }
+}
+
+/// Collects the local variables a statement assigns, at any depth.
+#[derive(Default)]
+struct Assignments {
+    variables: Vec<String>,
+}
+
+impl Visitor for Assignments {
+    fn visit_assign_statement(&mut self, assign: &AssignStatement) -> bool {
+        if let LValue::Ident(ident) = &assign.lvalue {
+            self.variables.push(ident.to_string());
+        }
+        true
+    }
+}
+
+/// Collects the local variables referenced by the assertions of a statement.
+#[derive(Default)]
+struct ConstraintReferences {
+    variables: Vec<String>,
+}
+
+impl Visitor for ConstraintReferences {
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        let mut references = ReferenceCollector::default();
+        for argument in &constrain.arguments {
+            argument.accept(&mut references);
+        }
+        self.variables.extend(references.variables);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_placeholders_assigned_without_else_are_flagged() {
+        let source_code = r#"
+            fn main(x: Field, enabled: bool) -> pub Field {
+                let mut fee = 0;
+                if enabled {
+                    fee = x * 2;
+                }
+                assert(fee != 1);
+
+                let mut total = 0;
+                if enabled {
+                    total = x;
+                }
+                total
+            }
+
+            fn covered(x: Field, enabled: bool) -> Field {
+                let mut fee = 0;
+                if enabled {
+                    fee = x;
+                } else {
+                    fee = 1;
+                }
+                let mut bonus = 0;
+                if enabled {
+                    bonus = x;
+                }
+                bonus = bonus + 1;
+                let mut seed = x;
+                if enabled {
+                    seed = 2;
+                }
+                assert(seed + fee != 0);
+                bonus
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint = Box::new(ConditionalAssignment);
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Variable 'fee' of function 'main' is only assigned when `enabled` holds, otherwise its placeholder value 0 reaches an assertion",
+                "Variable 'total' of function 'main' is only assigned when `enabled` holds, otherwise its placeholder value 0 reaches the return value",
+            ]
+        );
+    }
+}
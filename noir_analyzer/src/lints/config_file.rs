@@ -0,0 +1,242 @@
+//! # Configuration Lints
+//!
+//! Checks `noir-analyzer.toml` against the settings the analyzer knows: misspelled keys,
+//! lint names and thresholds are otherwise silently ignored, leaving the configuration
+//! without effect. Invalid levels and thresholds are reported too, although most of them
+//! already fail to load, so that editors get them with a span.
+//!
+//! Like the manifest lints, these lints run on the file rather than on the parsed program,
+//! and are reported with spans into `noir-analyzer.toml`. Names close to a known one come
+//! with a "did you mean" hint.
+
+use crate::config::LINT_LEVELS;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use noirc_frontend::hir::resolution::errors::Span;
+use serde::Deserialize;
+use serde::de::IgnoredAny;
+use std::collections::BTreeMap;
+use toml::{Spanned, Value};
+
+pub const NAME: &str = "invalid-config";
+
+/// Top-level keys of `noir-analyzer.toml`.
+pub(crate) const CONFIG_KEYS: &[&str] = &[
+    "exclude",
+    "flags",
+    "lints",
+    "locations",
+    "messages",
+    "silence_generated",
+    "thresholds",
+    "webhook",
+];
+
+type SpannedTable = BTreeMap<Spanned<String>, Spanned<Value>>;
+
+#[derive(Deserialize)]
+struct Sections {
+    lints: Option<SpannedTable>,
+    thresholds: Option<SpannedTable>,
+}
+
+/// Checks a configuration, given its source, the names of the known lints and of those
+/// taking a threshold. Configurations that are not valid TOML are not checked, as loading
+/// them fails anyway.
+pub fn lint_config(source: &str, lint_names: &[&str], threshold_names: &[&str]) -> Vec<Lint> {
+    let (Ok(keys), Ok(sections)) = (
+        toml::from_str::<BTreeMap<Spanned<String>, IgnoredAny>>(source),
+        toml::from_str::<Sections>(source),
+    ) else {
+        return vec![];
+    };
+
+    let mut lints = vec![];
+    for key in keys.keys() {
+        if !CONFIG_KEYS.contains(&key.get_ref().as_str()) {
+            lints.push(lint(
+                Severity::Warning,
+                Message::new("invalid-config.unknown-key")
+                    .with("key", key.get_ref())
+                    .with("hint", did_you_mean(key.get_ref(), CONFIG_KEYS)),
+                span(key.span()),
+            ));
+        }
+    }
+
+    for (lint_name, setting) in sections.lints.iter().flatten() {
+        if !lint_names.contains(&lint_name.get_ref().as_str()) {
+            lints.push(unknown_lint(lint_name, "lints", lint_names));
+        }
+        // Lints taking options are configured with a table, whose level is optional
+        let level = match setting.get_ref() {
+            Value::Table(options) => options.get("level"),
+            level => Some(level),
+        };
+        let Some(level) = level else {
+            continue;
+        };
+        let text = level.as_str();
+        if !text.is_some_and(|text| LINT_LEVELS.contains(&text)) {
+            lints.push(lint(
+                Severity::Error,
+                Message::new("invalid-config.invalid-level")
+                    .with("lint", lint_name.get_ref())
+                    .with("level", level)
+                    .with(
+                        "hint",
+                        text.map_or(String::new(), |text| did_you_mean(text, LINT_LEVELS)),
+                    ),
+                span(setting.span()),
+            ));
+        }
+    }
+
+    for (lint_name, threshold) in sections.thresholds.iter().flatten() {
+        if !lint_names.contains(&lint_name.get_ref().as_str()) {
+            lints.push(unknown_lint(lint_name, "thresholds", lint_names));
+        } else if !threshold_names.contains(&lint_name.get_ref().as_str()) {
+            // Thresholds are options of their lint, which rejects them
+            lints.push(lint(
+                Severity::Error,
+                Message::new("invalid-config.no-threshold")
+                    .with("lint", lint_name.get_ref())
+                    .with("hint", did_you_mean(lint_name.get_ref(), threshold_names)),
+                span(lint_name.span()),
+            ));
+        }
+        if !threshold
+            .get_ref()
+            .as_integer()
+            .is_some_and(|threshold| threshold > 0)
+        {
+            lints.push(lint(
+                Severity::Error,
+                Message::new("invalid-config.invalid-threshold")
+                    .with("lint", lint_name.get_ref())
+                    .with("value", threshold.get_ref()),
+                span(threshold.span()),
+            ));
+        }
+    }
+
+    lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+    lints
+}
+
+/// Returns the candidate closest to a misspelled name, if any is close enough to be a
+/// likely typo: at most one edit per three characters.
+pub fn closest_match<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Renders the closest match of a name as a hint appended to a message, e.g.
+/// `; did you mean 'warn'?`, or nothing when no candidate is close.
+pub fn did_you_mean(name: &str, candidates: &[&str]) -> String {
+    closest_match(name, candidates).map_or(String::new(), |candidate| {
+        format!("; did you mean '{candidate}'?")
+    })
+}
+
+/// Levenshtein distance between two strings, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn unknown_lint(lint_name: &Spanned<String>, section: &str, lint_names: &[&str]) -> Lint {
+    lint(
+        Severity::Warning,
+        Message::new("invalid-config.unknown-lint")
+            .with("lint", lint_name.get_ref())
+            .with("section", section)
+            .with("hint", did_you_mean(lint_name.get_ref(), lint_names)),
+        span(lint_name.span()),
+    )
+}
+
+fn lint(severity: Severity, message: Message, span: Span) -> Lint {
+    Lint {
+        name: NAME,
+        severity,
+        confidence: Confidence::High,
+        description: message.to_string(),
+        message: Some(message),
+        span: Some(span),
+        file_id: None,
+        suggestion: None,
+    }
+}
+
+fn span(range: std::ops::Range<usize>) -> Span {
+    Span::from(range.start as u32..range.end as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINT_NAMES: &[&str] = &["call-chain-depth", "god-module", "unused-function"];
+    const THRESHOLD_NAMES: &[&str] = &["call-chain-depth"];
+
+    #[test]
+    fn test_misconfiguration_is_flagged_with_spans_and_hints() {
+        let source = r#"exclud = ["vendor/**"]
+
+[lints]
+unused-functon = "allow"
+god-module = "warm"
+
+[thresholds]
+call-chain-depth = 0
+god-module = 3
+"#;
+
+        let lints = lint_config(source, LINT_NAMES, THRESHOLD_NAMES);
+
+        let descriptions: Vec<&str> = lints.iter().map(|lint| lint.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Unknown configuration key 'exclud' is ignored; did you mean 'exclude'?",
+                "Unknown lint 'unused-functon' in [lints] is ignored; did you mean 'unused-function'?",
+                "Invalid level \"warm\" for lint 'god-module', expected \"allow\", \"warn\" or \"deny\"; did you mean 'warn'?",
+                "Threshold of 'call-chain-depth' must be a positive integer, found 0",
+                "Lint 'god-module' takes no threshold",
+            ]
+        );
+        let span = lints[1].span.unwrap();
+        assert_eq!(
+            &source[span.start() as usize..span.end() as usize],
+            "unused-functon"
+        );
+    }
+
+    #[test]
+    fn test_valid_configuration_is_not_flagged() {
+        let source = "[lints]\ngod-module = \"deny\"\n\n[lints.call-chain-depth]\nlevel = \"warn\"\nmax-depth = 12\n";
+        assert!(lint_config(source, LINT_NAMES, THRESHOLD_NAMES).is_empty());
+    }
+
+    #[test]
+    fn test_closest_match_only_suggests_likely_typos() {
+        assert_eq!(closest_match("deny", LINT_LEVELS), Some("deny"));
+        assert_eq!(closest_match("alow", LINT_LEVELS), Some("allow"));
+        assert_eq!(closest_match("loud", LINT_LEVELS), None);
+    }
+}
@@ -0,0 +1,178 @@
+//! # Contract Function Visibility Lint
+//!
+//! Flags functions of Aztec contracts that are not annotated with `#[private]`, `#[public]`
+//! or `#[utility]`, and functions whose attributes contradict each other, such as
+//! `#[view]` with `#[initializer]`. The context an unannotated function runs in has changed
+//! across Aztec releases, so relying on it makes deployments behave unexpectedly after an
+//! upgrade.
+//!
+//! Contracts are recognized as Aztec contracts when they import `aztec` or annotate a
+//! function with a registered attribute. Helpers marked `#[contract_library_method]` and
+//! tests are not entry points, and need no execution context.
+
+use crate::analysis::aztec_attributes::{self, AttributeRole};
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::NoirFunction;
+use noirc_frontend::parser::ItemKind;
+use noirc_frontend::token::FunctionAttribute;
+
+/// Detects Aztec contract functions without an explicit execution context.
+#[derive(Clone, Default)]
+pub struct ContractFunctionVisibility;
+
+declare_lint! {
+    ContractFunctionVisibility {
+        name: "contract-function-visibility",
+        explanation: "Reports Aztec contract functions lacking a #[private], #[public] or #[utility] attribute, and functions with contradictory attributes such as #[view] with #[initializer]. The defaults of unannotated functions changed across Aztec versions, which leads to surprises at deployment.",
+        requires_whole_program: true,
+    }
+}
+
+impl ContractFunctionVisibility {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        self.check_module(context.parsed_module, &mut lints);
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+
+    fn check_module(&self, parsed_module: &ParsedModule, lints: &mut Vec<Lint>) {
+        for item in &parsed_module.items {
+            if let ItemKind::Submodules(submodule) = &item.kind {
+                if submodule.is_contract && is_aztec_contract(&submodule.contents) {
+                    self.check_contract(&submodule.contents, lints);
+                }
+                self.check_module(&submodule.contents, lints);
+            }
+        }
+    }
+
+    fn check_contract(&self, contract: &ParsedModule, lints: &mut Vec<Lint>) {
+        for item in &contract.items {
+            let ItemKind::Function(function) = &item.kind else {
+                continue;
+            };
+            if matches!(
+                Noir::function_attribute(&function.def),
+                Some(FunctionAttribute::Test(..))
+            ) {
+                continue;
+            }
+
+            let attributes = aztec_attributes::function_attributes(&function.def);
+            let contradiction = attributes.iter().enumerate().find_map(|(index, first)| {
+                attributes[index + 1..]
+                    .iter()
+                    .find(|second| aztec_attributes::contradict(first, second))
+                    .map(|second| (first.name, second.name))
+            });
+            let message = if let Some((first, second)) = contradiction {
+                Message::new("contract-function-visibility.contradictory")
+                    .with("function", function.name())
+                    .with("first", first)
+                    .with("second", second)
+            } else if attributes.iter().any(|attribute| {
+                matches!(
+                    attribute.role,
+                    AttributeRole::Execution(_) | AttributeRole::Helper
+                )
+            }) {
+                continue;
+            } else {
+                Message::new("contract-function-visibility.missing")
+                    .with("function", function.name())
+            };
+
+            let (span, file_id) = Noir::function_location(&function.def);
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::High,
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(span),
+                file_id: Some(file_id),
+                suggestion: None,
+            });
+        }
+    }
+}
+
+/// Returns whether a contract is written for Aztec: it imports `aztec`, or annotates a
+/// function with an Aztec attribute.
+fn is_aztec_contract(contract: &ParsedModule) -> bool {
+    contract.items.iter().any(|item| match &item.kind {
+        ItemKind::Import(use_tree, _) => {
+            let path = use_tree.to_string();
+            path.starts_with("aztec") || path.starts_with("dep::aztec")
+        }
+        ItemKind::Function(function) => has_aztec_attribute(function),
+        _ => false,
+    })
+}
+
+fn has_aztec_attribute(function: &NoirFunction) -> bool {
+    !aztec_attributes::function_attributes(&function.def).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_unannotated_and_contradictory_functions_are_flagged() {
+        let source_code = r#"
+            contract Token {
+                use aztec::macros::aztec;
+
+                #[private]
+                fn transfer(amount: Field) {}
+
+                #[public]
+                #[view]
+                fn balance() -> Field { 0 }
+
+                fn mint(amount: Field) {}
+
+                #[public]
+                #[view]
+                #[initializer]
+                fn constructor() {}
+
+                #[contract_library_method]
+                fn helper() {}
+
+                #[test]
+                fn test_transfer() {}
+            }
+
+            contract Plain {
+                fn main() {}
+            }
+            "#;
+
+        let lint = Box::new(ContractFunctionVisibility);
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "Contract function 'mint' has no #[private], #[public] or #[utility] attribute, so its execution context depends on the Aztec version",
+                "Contract function 'constructor' is marked both #[view] and #[initializer]",
+            ]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! # Cyclic Module Imports Lint
+//!
+//! Flags groups of modules that import from each other in a cycle. Cycles make it hard to
+//! reason about modules in isolation and usually signal that shared code should move into
+//! its own module.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+
+/// Detects import cycles between modules using the module graph.
+#[derive(Clone, Default)]
+pub struct CyclicModuleImports;
+
+declare_lint! {
+    CyclicModuleImports {
+        name: "cyclic-module-imports",
+        explanation: "Reports groups of modules importing from each other in a cycle. Cycles make it hard to reason about modules in isolation and usually signal that shared code should move into its own module.",
+        requires_whole_program: true,
+        analyses: [ModuleGraph],
+    }
+}
+
+impl CyclicModuleImports {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let graph = &context.module_graph;
+
+        graph
+            .cycles()
+            .into_iter()
+            .map(|cycle| {
+                // Anchor the lint at the first module of the cycle that has a declaration
+                let anchor = cycle
+                    .iter()
+                    .filter_map(|module| graph.module(module))
+                    .find(|module| module.span.is_some());
+
+                let mut path = cycle.clone();
+                path.push(cycle[0].clone());
+
+                let message = Message::new("cyclic-module-imports").with("cycle", path.join(" → "));
+                Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::High,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: anchor.and_then(|module| module.span),
+                    file_id: anchor.and_then(|module| module.file_id),
+                    suggestion: None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::diagnostics::lint::{Confidence, Severity};
+    use crate::lints::cyclic_module_imports::CyclicModuleImports;
+    use crate::lints::lint_rule::LintRule;
+
+    #[test]
+    fn test_cyclic_module_imports_can_be_created() {
+        let lint = CyclicModuleImports;
+        assert_eq!(lint.name(), "cyclic-module-imports");
+    }
+
+    #[test]
+    fn test_acyclic_imports_are_not_flagged() {
+        let lint = Box::new(CyclicModuleImports);
+        let source_code = r#"
+            mod a {
+                pub fn foo() {}
+            }
+            mod b {
+                use crate::a::foo;
+                pub fn bar() { foo() }
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_import_cycle_is_flagged() {
+        let lint = Box::new(CyclicModuleImports);
+        let source_code = r#"
+            mod a {
+                use crate::b::bar;
+                pub fn foo() { bar() }
+            }
+            mod b {
+                use crate::a::foo;
+                pub fn bar() { foo() }
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].severity, Severity::Warning);
+        assert_eq!(result[0].confidence, Confidence::High);
+        assert_eq!(
+            result[0].description,
+            "Modules import each other in a cycle: crate::a → crate::b → crate::a"
+        );
+        assert!(result[0].span.is_some());
+    }
+}
@@ -0,0 +1,173 @@
+//! # Deep Constraint Expression Lint
+//!
+//! Flags assertions whose expression is nested deeper than a limit, such as long chains of
+//! arithmetic inside a single `assert`. The compiler splits such expressions into many
+//! intermediate witnesses to fit the backend width, and the resulting constraints can no
+//! longer be mapped back to the intent of the assertion. Naming intermediate values with
+//! `let` keeps them reviewable.
+//!
+//! The depth counts the operators on the longest path from the root of the expression to a
+//! leaf; parentheses do not count. The limit defaults to 6 and can be set as `max-depth`
+//! under `[lints.deep-constraint-expression]` in `noir-analyzer.toml`.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ast::{ConstrainExpression, Expression, ExpressionKind, Visitor};
+use noirc_frontend::hir::resolution::errors::Span;
+use serde::Deserialize;
+
+/// Default maximum depth of a constraint expression.
+pub const DEFAULT_MAX_DEPTH: usize = 6;
+
+/// Detects assertions on deeply nested expressions.
+#[derive(Clone, Default)]
+pub struct DeepConstraintExpression {
+    options: DeepConstraintExpressionOptions,
+}
+
+/// Options of [`DeepConstraintExpression`], set under `[lints.deep-constraint-expression]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DeepConstraintExpressionOptions {
+    /// Maximum number of nested operators of a constraint expression.
+    /// Also set by the `[thresholds]` entry of the lint.
+    #[serde(alias = "threshold")]
+    pub max_depth: usize,
+}
+
+impl Default for DeepConstraintExpressionOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl DeepConstraintExpression {
+    /// Creates the lint with a custom limit.
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            options: DeepConstraintExpressionOptions { max_depth },
+        }
+    }
+}
+
+declare_lint! {
+    DeepConstraintExpression {
+        name: "deep-constraint-expression",
+        explanation: "Reports assertions whose expression nests more operators than a limit (6 by default, configurable as max-depth under [lints.deep-constraint-expression] in noir-analyzer.toml). Such expressions are split into many intermediate witnesses that cannot be mapped back to the intent of the assertion; naming intermediate values with `let` keeps them reviewable.",
+        options: DeepConstraintExpressionOptions,
+    }
+}
+
+impl DeepConstraintExpression {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            if function.is_unconstrained {
+                continue;
+            }
+
+            let mut collector = ConstraintDepths::default();
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+
+            for (depth, span, file_id) in collector.depths {
+                if depth <= self.options.max_depth {
+                    continue;
+                }
+                let message = Message::new("deep-constraint-expression")
+                    .with("function", name)
+                    .with("depth", depth)
+                    .with("max", self.options.max_depth);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints
+    }
+}
+
+/// Collects the depth and location of every constraint of a function body.
+#[derive(Default)]
+struct ConstraintDepths {
+    depths: Vec<(usize, Span, FileId)>,
+}
+
+impl Visitor for ConstraintDepths {
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        if let Some(first) = constrain.arguments.first() {
+            let depth = constrain.arguments.iter().map(depth).max().unwrap_or(0);
+            let (span, file_id) = Noir::expression_location(first);
+            self.depths.push((depth, span, file_id));
+        }
+        true
+    }
+}
+
+/// Returns the number of operators on the longest path from the root of an expression to a
+/// leaf.
+fn depth(expression: &Expression) -> usize {
+    let children: Vec<&Expression> = match &expression.kind {
+        ExpressionKind::Parenthesized(inner) => return depth(inner),
+        ExpressionKind::Infix(infix) => vec![&infix.lhs, &infix.rhs],
+        ExpressionKind::Prefix(prefix) => vec![&prefix.rhs],
+        ExpressionKind::Cast(cast) => vec![&cast.lhs],
+        ExpressionKind::Index(index) => vec![&index.collection, &index.index],
+        ExpressionKind::Call(call) => call.arguments.iter().collect(),
+        ExpressionKind::MethodCall(method_call) => std::iter::once(&method_call.object)
+            .chain(&method_call.arguments)
+            .collect(),
+        ExpressionKind::MemberAccess(member_access) => vec![&member_access.lhs],
+        _ => return 0,
+    };
+    1 + children.into_iter().map(depth).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_deeply_nested_assertion_is_flagged() {
+        let source_code = r#"
+            fn main(a: Field, b: Field, c: Field) {
+                assert(((a * b + c) * a - b) * (c + 1) == (a + b) * c);
+                let t = a * b + c;
+                assert(t * a == b);
+            }
+
+            unconstrained fn helper(a: Field) {
+                assert(((((a + 1) + 1) + 1) + 1) + 1 == a);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint = Box::new(DeepConstraintExpression::with_max_depth(4));
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Assertion in function 'main' nests 6 operators, more than the limit of 4; name intermediate values with `let` to keep its constraints reviewable"
+        );
+    }
+}
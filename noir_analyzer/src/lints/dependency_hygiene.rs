@@ -0,0 +1,190 @@
+//! # Dependency Hygiene Lint
+//!
+//! Checks the `[dependencies]` declared in `Nargo.toml`: git dependencies without a `tag`
+//! or `rev` follow whatever the default branch points to, path dependencies leaving the
+//! workspace root are not part of the reviewed tree, and names differing only in case are
+//! easily mistaken for one another. The findings have no location in the Noir sources.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// A dependency as declared in `Nargo.toml`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeclaredDependency {
+    /// A git repository, with the tag or revision it is pinned to, if any.
+    Git {
+        url: String,
+        reference: Option<String>,
+    },
+    /// A directory, relative to the package root.
+    Path(PathBuf),
+}
+
+/// Detects unpinned, escaping and confusable dependencies of a package.
+#[derive(Clone, Default)]
+pub struct DependencyHygiene {
+    /// Directory of the package declaring the dependencies. Both directories should be
+    /// absolute, so that relative paths leaving them can be told apart.
+    pub package_dir: PathBuf,
+    /// Root of the workspace the package belongs to.
+    pub workspace_root: PathBuf,
+    /// Declared dependencies, keyed by name.
+    pub dependencies: Vec<(String, DeclaredDependency)>,
+}
+
+impl DependencyHygiene {
+    /// Creates the lint for the dependencies declared by a package.
+    pub fn new(
+        package_dir: PathBuf,
+        workspace_root: PathBuf,
+        dependencies: Vec<(String, DeclaredDependency)>,
+    ) -> Self {
+        Self {
+            package_dir,
+            workspace_root,
+            dependencies,
+        }
+    }
+}
+
+declare_lint! {
+    DependencyHygiene {
+        name: "dependency-hygiene",
+        explanation: "Reports dependencies of Nargo.toml that weaken supply-chain hygiene: git dependencies without a tag or rev, whose code changes whenever their default branch moves; path dependencies outside the workspace root, which are not part of the reviewed tree; and dependency names differing only in case, which are easily confused.",
+    }
+}
+
+impl DependencyHygiene {
+    fn check(&self, _context: &AstContext) -> Vec<Lint> {
+        let mut messages = vec![];
+        let workspace_root = normalize(&self.workspace_root);
+        for (name, dependency) in &self.dependencies {
+            match dependency {
+                DeclaredDependency::Git {
+                    url,
+                    reference: None,
+                } => messages.push(
+                    Message::new("dependency-hygiene.unpinned")
+                        .with("dependency", name)
+                        .with("url", url),
+                ),
+                DeclaredDependency::Path(path)
+                    if !normalize(&self.package_dir.join(path)).starts_with(&workspace_root) =>
+                {
+                    messages.push(
+                        Message::new("dependency-hygiene.outside-workspace")
+                            .with("dependency", name)
+                            .with("path", path.display()),
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        let mut names: HashMap<String, &str> = HashMap::new();
+        for (name, _) in &self.dependencies {
+            match names.get(&name.to_lowercase()) {
+                Some(other) => messages.push(
+                    Message::new("dependency-hygiene.case-duplicate")
+                        .with("dependency", name)
+                        .with("other", other),
+                ),
+                _ => {
+                    names.insert(name.to_lowercase(), name);
+                }
+            }
+        }
+
+        messages
+            .into_iter()
+            .map(|message| Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::High,
+                description: message.to_string(),
+                message: Some(message),
+                span: None,
+                file_id: None,
+                suggestion: None,
+            })
+            .collect()
+    }
+}
+
+/// Resolves `.` and `..` components without touching the file system, so that paths to
+/// directories that do not exist can be compared.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_unhygienic_dependencies_are_flagged() {
+        let git = |url: &str, reference: Option<&str>| DeclaredDependency::Git {
+            url: url.to_string(),
+            reference: reference.map(str::to_string),
+        };
+        let lint = Box::new(DependencyHygiene::new(
+            PathBuf::from("workspace/circuits/app"),
+            PathBuf::from("workspace"),
+            vec![
+                (
+                    "ec".to_string(),
+                    git("https://github.com/noir-lang/ec", None),
+                ),
+                (
+                    "bignum".to_string(),
+                    git("https://github.com/noir-lang/bignum", Some("v0.4.0")),
+                ),
+                (
+                    "utils".to_string(),
+                    DeclaredDependency::Path("../utils".into()),
+                ),
+                (
+                    "vendored".to_string(),
+                    DeclaredDependency::Path("../../../lib".into()),
+                ),
+                (
+                    "BigNum".to_string(),
+                    DeclaredDependency::Path("../bignum".into()),
+                ),
+            ],
+        ));
+
+        let root = Parser::parse_program_with_dummy_file("fn main() {}").unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "Dependency 'ec' on https://github.com/noir-lang/ec has no tag or rev, so it follows the default branch",
+                "Path dependency 'vendored' at ../../../lib is outside the workspace root",
+                "Dependency 'BigNum' differs from 'bignum' only in case",
+            ]
+        );
+    }
+}
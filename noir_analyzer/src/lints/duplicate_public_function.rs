@@ -0,0 +1,201 @@
+//! # Duplicate Public Function Lint
+//!
+//! Flags public functions that are defined with the same name and the same body in several
+//! packages of a workspace. Copy-pasted utility code drifts apart over time, so such
+//! functions are better factored into a shared dependency.
+
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use crate::lints::lint_rule::{WorkspaceLintRule, WorkspacePackage};
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{ItemVisibility, NoirFunction};
+use noirc_frontend::parser::ItemKind;
+use std::collections::BTreeMap;
+
+/// Detects identical public functions duplicated across workspace members.
+#[derive(Default)]
+pub struct DuplicatePublicFunction;
+
+impl WorkspaceLintRule for DuplicatePublicFunction {
+    fn name(&self) -> &'static str {
+        "duplicate-public-function"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Reports public functions defined with the same name and body in several packages of a workspace. Copy-pasted utility code drifts apart over time, so such functions are better factored into a shared dependency."
+    }
+
+    fn lint(&self, packages: &[WorkspacePackage]) -> Vec<(usize, Lint)> {
+        // (name, normalized definition) → occurrences as (package index, function)
+        let mut definitions: BTreeMap<(String, String), Vec<(usize, &NoirFunction)>> =
+            BTreeMap::new();
+
+        for (index, package) in packages.iter().enumerate() {
+            let mut functions = vec![];
+            collect_public_functions(package.parsed_module, &mut functions);
+
+            for function in functions {
+                definitions
+                    .entry((function.name().to_string(), normalized_definition(function)))
+                    .or_default()
+                    .push((index, function));
+            }
+        }
+
+        let mut lints = vec![];
+
+        for occurrences in definitions.values() {
+            let Some((first_index, _)) = occurrences.first() else {
+                continue;
+            };
+
+            for (index, function) in occurrences {
+                if index == first_index {
+                    continue;
+                }
+
+                let (span, file_id) = Noir::function_location(&function.def);
+                let message = Message::new("duplicate-public-function")
+                    .with("function", function.name())
+                    .with("package", &packages[*index].name)
+                    .with("original_package", &packages[*first_index].name);
+                lints.push((
+                    *index,
+                    Lint {
+                        name: self.name(),
+                        severity: Severity::Warning,
+                        confidence: Confidence::High,
+                        description: message.to_string(),
+                        message: Some(message),
+                        span: Some(span),
+                        file_id: Some(file_id),
+                        suggestion: None,
+                    },
+                ));
+            }
+        }
+
+        lints
+    }
+}
+
+/// Collects the public functions of a module and its inline submodules.
+fn collect_public_functions<'ast>(
+    parsed_module: &'ast ParsedModule,
+    functions: &mut Vec<&'ast NoirFunction>,
+) {
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Function(function) if function.def.visibility == ItemVisibility::Public => {
+                functions.push(function);
+            }
+            ItemKind::Submodules(submodule) => {
+                collect_public_functions(&submodule.contents, functions);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Renders the parts of a function that must match for two definitions to be duplicates.
+///
+/// Rendering through the AST ignores formatting and comment differences.
+fn normalized_definition(function: &NoirFunction) -> String {
+    let parameters: Vec<String> = function
+        .def
+        .parameters
+        .iter()
+        .map(|parameter| format!("{}: {}", parameter.pattern, parameter.typ))
+        .collect();
+
+    format!(
+        "unconstrained={} ({}) -> {} {}",
+        function.def.is_unconstrained,
+        parameters.join(", "),
+        function.def.return_type,
+        function.def.body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::parser::Parser;
+    use crate::lints::duplicate_public_function::DuplicatePublicFunction;
+    use crate::lints::lint_rule::{WorkspaceLintRule, WorkspacePackage};
+
+    #[test]
+    fn test_duplicate_public_function_can_be_created() {
+        let lint = DuplicatePublicFunction;
+        assert_eq!(lint.name(), "duplicate-public-function");
+    }
+
+    #[test]
+    fn test_identical_public_functions_are_flagged() {
+        let first = Parser::parse_program_with_dummy_file(
+            r#"
+            pub fn double(x: Field) -> Field { x + x }
+            "#,
+        )
+        .unwrap();
+        let second = Parser::parse_program_with_dummy_file(
+            r#"
+            pub fn double(x: Field) -> Field {
+                // Same body, different formatting
+                x + x
+            }
+            "#,
+        )
+        .unwrap();
+
+        let packages = [
+            WorkspacePackage {
+                name: "first".to_string(),
+                parsed_module: &first,
+            },
+            WorkspacePackage {
+                name: "second".to_string(),
+                parsed_module: &second,
+            },
+        ];
+        let result = DuplicatePublicFunction.lint(&packages);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0, 1);
+        assert_eq!(
+            result[0].1.description,
+            "Public function 'double' in package 'second' duplicates the one in package 'first'; consider moving it into a shared dependency"
+        );
+    }
+
+    #[test]
+    fn test_different_bodies_or_private_functions_are_not_flagged() {
+        let first = Parser::parse_program_with_dummy_file(
+            r#"
+            pub fn double(x: Field) -> Field { x + x }
+            fn helper() {}
+            "#,
+        )
+        .unwrap();
+        let second = Parser::parse_program_with_dummy_file(
+            r#"
+            pub fn double(x: Field) -> Field { x * 2 }
+            fn helper() {}
+            "#,
+        )
+        .unwrap();
+
+        let packages = [
+            WorkspacePackage {
+                name: "first".to_string(),
+                parsed_module: &first,
+            },
+            WorkspacePackage {
+                name: "second".to_string(),
+                parsed_module: &second,
+            },
+        ];
+
+        assert!(DuplicatePublicFunction.lint(&packages).is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+//! # Empty Block Lint
+//!
+//! Flags empty `else` blocks, loops with an empty body and empty `unsafe` blocks. In circuit
+//! code they are usually what is left of deleted constraints or hints, and deserve a second
+//! look: the constraint may have been removed by mistake.
+//!
+//! When the source is known, each gets a fix removing it, unless it contains comments. An
+//! `unsafe` block is only removed when it stands as a statement, together with the
+//! `// Safety:` comment right above it. Removing a `loop`, a `while` loop or a `for` loop
+//! whose range calls a function may change what the program does, so these fixes are not
+//! machine-applicable.
+
+use crate::ast::ast_context::AstContext;
+use crate::ast::trivia;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ast::{
+    Expression, ExpressionKind, ForRange, Statement, StatementKind, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::HashMap;
+
+/// Detects empty `else`, loop and `unsafe` blocks.
+#[derive(Clone, Default)]
+pub struct EmptyBlock;
+
+declare_lint! {
+    EmptyBlock {
+        name: "empty-block",
+        explanation: "Reports empty `else` blocks, loops with an empty body and empty `unsafe` blocks. They do nothing, and in circuit code are usually what is left of deleted constraints or hints; check that nothing was removed by mistake, then remove them.",
+    }
+}
+
+impl EmptyBlock {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            let mut collector = BlockCollector {
+                function: name,
+                source: context.source,
+                statements: HashMap::new(),
+                lints: vec![],
+            };
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+            lints.extend(collector.lints);
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Reports the empty blocks of the visited statements.
+struct BlockCollector<'a> {
+    function: &'a str,
+    source: Option<&'a str>,
+    /// Spans of the statements made of an `unsafe` block, by start of the block.
+    statements: HashMap<u32, Span>,
+    lints: Vec<Lint>,
+}
+
+impl BlockCollector<'_> {
+    fn report(
+        &mut self,
+        message: Message,
+        span: Span,
+        file_id: FileId,
+        suggestion: Option<Suggestion>,
+    ) {
+        self.lints.push(Lint {
+            name: EmptyBlock::NAME,
+            severity: Severity::Warning,
+            confidence: Confidence::High,
+            description: message.to_string(),
+            message: Some(message),
+            span: Some(span),
+            file_id: Some(file_id),
+            suggestion,
+        });
+    }
+
+    /// Reports an empty loop, with a fix removing its statement.
+    fn report_loop(&mut self, keyword: &str, statement: &Statement, applicability: Applicability) {
+        let (span, file_id) = Noir::statement_location(statement);
+        let suggestion = self
+            .source
+            .and_then(|source| statement_removal_span(source, span, false))
+            .map(|span| Suggestion {
+                message: "remove the empty loop".to_string(),
+                span,
+                replacement: String::new(),
+                applicability,
+                other_edits: vec![],
+            });
+        let message = Message::new("empty-block.loop")
+            .with("loop", keyword)
+            .with("function", self.function);
+        self.report(message, span, file_id, suggestion);
+    }
+}
+
+impl Visitor for BlockCollector<'_> {
+    fn visit_statement(&mut self, statement: &Statement) -> bool {
+        match &statement.kind {
+            StatementKind::For(for_loop) if is_empty(&for_loop.block) => {
+                let bounds = match &for_loop.range {
+                    ForRange::Range(bounds) => vec![&bounds.start, &bounds.end],
+                    ForRange::Array(array) => vec![array],
+                };
+                // Functions called by the range may constrain their arguments
+                let applicability = if bounds.iter().all(|bound| {
+                    matches!(
+                        bound.kind,
+                        ExpressionKind::Literal(_) | ExpressionKind::Variable(_)
+                    )
+                }) {
+                    Applicability::MachineApplicable
+                } else {
+                    Applicability::MaybeIncorrect
+                };
+                self.report_loop("for", statement, applicability);
+            }
+            // An empty `loop` never ends, and an empty `while` loop may not either
+            StatementKind::Loop(body, _) if is_empty(body) => {
+                self.report_loop("loop", statement, Applicability::MaybeIncorrect);
+            }
+            StatementKind::While(while_statement) if is_empty(&while_statement.body) => {
+                self.report_loop("while", statement, Applicability::MaybeIncorrect);
+            }
+            StatementKind::Expression(expression) | StatementKind::Semi(expression)
+                if matches!(expression.kind, ExpressionKind::Unsafe(..)) =>
+            {
+                let (span, _) = Noir::expression_location(expression);
+                self.statements
+                    .insert(span.start(), Noir::statement_location(statement).0);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        match &expression.kind {
+            ExpressionKind::If(if_expression) => {
+                let Some(alternative) = &if_expression.alternative else {
+                    return true;
+                };
+                if !is_empty(alternative) {
+                    return true;
+                }
+                let (span, file_id) = Noir::expression_location(alternative);
+                let consequence_end = Noir::expression_location(&if_expression.consequence)
+                    .0
+                    .end();
+                let suggestion = self
+                    .source
+                    .filter(|source| {
+                        source
+                            .get(consequence_end as usize..span.end() as usize)
+                            .is_some_and(|removed| !has_comment(removed))
+                    })
+                    .map(|_| Suggestion {
+                        message: "remove the empty `else` block".to_string(),
+                        span: Span::from(consequence_end..span.end()),
+                        replacement: String::new(),
+                        applicability: Applicability::MachineApplicable,
+                        other_edits: vec![],
+                    });
+                let message = Message::new("empty-block.else").with("function", self.function);
+                self.report(message, span, file_id, suggestion);
+            }
+            ExpressionKind::Unsafe(..) => {
+                if !Noir::unsafe_block(expression).is_some_and(|block| block.statements.is_empty())
+                {
+                    return true;
+                }
+                let (span, file_id) = Noir::expression_location(expression);
+                let suggestion = self
+                    .source
+                    .zip(self.statements.get(&span.start()))
+                    .and_then(|(source, statement)| {
+                        statement_removal_span(source, *statement, true)
+                    })
+                    .map(|span| Suggestion {
+                        message: "remove the empty `unsafe` block".to_string(),
+                        span,
+                        replacement: String::new(),
+                        applicability: Applicability::MachineApplicable,
+                        other_edits: vec![],
+                    });
+                let message = Message::new("empty-block.unsafe").with("function", self.function);
+                self.report(message, span, file_id, suggestion);
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Returns whether an expression is a block without statements.
+fn is_empty(expression: &Expression) -> bool {
+    matches!(&expression.kind, ExpressionKind::Block(block) if block.statements.is_empty())
+}
+
+fn has_comment(source: &str) -> bool {
+    source.contains("//") || source.contains("/*")
+}
+
+/// Returns the span removing a statement with its `;`, and the whole lines when it stands on
+/// its own lines. With `with_safety_comment`, a `// Safety:` comment on the line above is
+/// removed too. Returns `None` when the statement contains comments.
+fn statement_removal_span(source: &str, span: Span, with_safety_comment: bool) -> Option<Span> {
+    let start = span.start() as usize;
+    let mut end = span.end() as usize;
+    if let Some(semicolon) =
+        trivia::next_token(source, end).filter(|offset| source[*offset..].starts_with(';'))
+    {
+        end = semicolon + 1;
+    }
+    if has_comment(source.get(start..end)?) {
+        return None;
+    }
+
+    let (mut start, end) = trivia::expand_to_lines(source, start, end);
+    if with_safety_comment && start > 0 && source[..start].ends_with('\n') {
+        let previous_start = source[..start - 1]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        if source[previous_start..start]
+            .trim_start()
+            .starts_with("// Safety")
+        {
+            start = previous_start;
+        }
+    }
+    Some(Span::from(start as u32..end as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    #[test]
+    fn test_empty_blocks_are_reported_and_removed() {
+        let source = "unconstrained fn hint(x: Field) -> Field { x }\n\nfn main(x: Field, n: u32) {\n    if x == 0 {\n        assert(n == 0);\n    } else {\n    }\n    for i in 0..n {\n    }\n    // Safety: nothing to check\n    unsafe {\n    };\n    if x == 1 {\n        assert(n == 1);\n    } else {\n        // assert(n == 2);\n    }\n    let y = unsafe { hint(x) };\n    assert(y == x);\n}\n";
+        let lint = Box::new(EmptyBlock);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Empty `else` block in 'main'",
+                "Empty `for` loop in 'main'",
+                "Empty `unsafe` block in 'main'",
+                "Empty `else` block in 'main'",
+            ]
+        );
+        // The commented out assertion is left for review
+        assert!(result[3].suggestion.is_none());
+
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(false).apply(source, suggestions).source;
+        assert_eq!(
+            fixed,
+            "unconstrained fn hint(x: Field) -> Field { x }\n\nfn main(x: Field, n: u32) {\n    if x == 0 {\n        assert(n == 0);\n    }\n    if x == 1 {\n        assert(n == 1);\n    } else {\n        // assert(n == 2);\n    }\n    let y = unsafe { hint(x) };\n    assert(y == x);\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_risky_loop_removals_are_not_machine_applicable() {
+        let source = "fn count(x: Field) -> u32 { x as u32 }\n\nfn main(x: Field) {\n    for i in 0..count(x) {}\n    while x == 0 {}\n}\n";
+        let lint = Box::new(EmptyBlock);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].description, "Empty `while` loop in 'main'");
+        assert!(result.iter().all(|lint| {
+            lint.suggestion
+                .as_ref()
+                .is_some_and(|suggestion| suggestion.applicability == Applicability::MaybeIncorrect)
+        }));
+    }
+}
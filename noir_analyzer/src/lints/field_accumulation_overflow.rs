@@ -0,0 +1,401 @@
+//! # Field Accumulation Overflow Lint
+//!
+//! Flags loops accumulating products into a `Field`, such as `acc += (a as Field) * (b as
+//! Field)`, when the accumulated value can exceed the field modulus. Values are then silently
+//! reduced modulo the field, which breaks code that treats the accumulator as an integer.
+//!
+//! The magnitude of a product is bounded from the declared bit widths of its operands, and
+//! the number of iterations is evaluated from constant loop bounds, including globals. Terms
+//! or bounds that cannot be evaluated are not reported. The BN254 scalar field, Noir's
+//! default, is assumed.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ast::{
+    AssignStatement, BinaryOpKind, Expression, ExpressionKind, ForLoopStatement, ForRange, LValue,
+    LetStatement, Literal, Pattern, UnresolvedType, UnresolvedTypeData, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use noirc_frontend::shared::Signedness;
+use std::collections::{HashMap, HashSet};
+
+/// The BN254 scalar field modulus, as its high and low 128 bits.
+const MODULUS: (u128, u128) = (
+    0x30644e72e131a029b85045b68181585d,
+    0x2833e84879b9709143e1f593f0000001,
+);
+
+/// Detects loops whose `Field` accumulator can wrap around the field modulus.
+#[derive(Clone, Default)]
+pub struct FieldAccumulationOverflow;
+
+declare_lint! {
+    FieldAccumulationOverflow {
+        name: "field-accumulation-overflow",
+        explanation: "Reports loops adding products to a `Field` accumulator when, given the declared bit widths of the factors and the constant loop bounds, the sum can exceed the field modulus. The accumulator then wraps around silently; use narrower inputs, fewer iterations, or reduce the sum explicitly.",
+    }
+}
+
+impl FieldAccumulationOverflow {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let globals = evaluate_globals(context);
+        let mut lints = vec![];
+
+        for function in context.function_definitions.values() {
+            let mut collector = AccumulationCollector::new(&globals);
+            for parameter in &function.parameters {
+                collector.declare(&parameter.pattern, &parameter.typ);
+            }
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+
+            for accumulation in collector.accumulations {
+                let message = Message::new("field-accumulation-overflow")
+                    .with("accumulator", &accumulation.accumulator)
+                    .with("term_bits", accumulation.term_bits)
+                    .with("iterations", accumulation.iterations)
+                    .with("wrap_iteration", accumulation.wrap_iteration);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(accumulation.span),
+                    file_id: Some(accumulation.file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// A loop assignment whose accumulated value can exceed the field modulus.
+struct Accumulation {
+    accumulator: String,
+    term_bits: u32,
+    iterations: u128,
+    wrap_iteration: u128,
+    span: Span,
+    file_id: FileId,
+}
+
+/// Collects the wrapping accumulations of a function.
+struct AccumulationCollector<'a> {
+    globals: &'a HashMap<String, u128>,
+    /// Names of the variables annotated as `Field`.
+    field_variables: HashSet<String>,
+    /// Bit widths of the unsigned variables, and of the elements of unsigned arrays.
+    integer_widths: HashMap<String, u32>,
+    /// Number of iterations of the enclosing loops, or `None` when they are not constant.
+    iterations: Option<u128>,
+    accumulations: Vec<Accumulation>,
+}
+
+impl<'a> AccumulationCollector<'a> {
+    fn new(globals: &'a HashMap<String, u128>) -> Self {
+        Self {
+            globals,
+            field_variables: HashSet::new(),
+            integer_widths: HashMap::new(),
+            iterations: Some(1),
+            accumulations: vec![],
+        }
+    }
+
+    fn declare(&mut self, pattern: &Pattern, r#type: &UnresolvedType) {
+        let Pattern::Identifier(ident) = pattern else {
+            return;
+        };
+
+        let element_type = match &r#type.typ {
+            UnresolvedTypeData::Array(_, element_type) => &element_type.typ,
+            typ => typ,
+        };
+        match element_type {
+            UnresolvedTypeData::FieldElement => {
+                self.field_variables.insert(ident.to_string());
+            }
+            UnresolvedTypeData::Integer(Signedness::Unsigned, bit_size) => {
+                self.integer_widths
+                    .insert(ident.to_string(), u32::from(bit_size.bit_size()));
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns an upper bound of the bit width of the expression.
+    fn bit_width(&self, expression: &Expression) -> Option<u32> {
+        match &expression.kind {
+            ExpressionKind::Literal(Literal::Integer(value)) if !value.is_negative() => {
+                let value = value.absolute_value().try_into_u128()?;
+                Some(u128::BITS - value.leading_zeros())
+            }
+            ExpressionKind::Variable(path) if path.segments.len() == 1 => self
+                .integer_widths
+                .get(&path.segments[0].ident.to_string())
+                .copied(),
+            ExpressionKind::Index(index) => self.bit_width(&index.collection),
+            // Casting to an unsigned integer truncates the value to the target width
+            ExpressionKind::Cast(cast) => match &cast.r#type.typ {
+                UnresolvedTypeData::Integer(Signedness::Unsigned, bit_size) => {
+                    let target = u32::from(bit_size.bit_size());
+                    Some(
+                        self.bit_width(&cast.lhs)
+                            .map_or(target, |width| width.min(target)),
+                    )
+                }
+                _ => self.bit_width(&cast.lhs),
+            },
+            ExpressionKind::Parenthesized(expression) => self.bit_width(expression),
+            ExpressionKind::Infix(infix) => {
+                let lhs = self.bit_width(&infix.lhs)?;
+                let rhs = self.bit_width(&infix.rhs)?;
+                match infix.operator.contents {
+                    BinaryOpKind::Multiply => Some(lhs + rhs),
+                    BinaryOpKind::Add => Some(lhs.max(rhs) + 1),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the product added to `accumulator` by `accumulator = accumulator + product`.
+    fn accumulated_product<'e>(
+        &self,
+        accumulator: &str,
+        expression: &'e Expression,
+    ) -> Option<&'e Expression> {
+        let ExpressionKind::Infix(infix) = &expression.kind else {
+            return None;
+        };
+        if infix.operator.contents != BinaryOpKind::Add {
+            return None;
+        }
+
+        let is_accumulator = |expression: &Expression| {
+            matches!(&expression.kind, ExpressionKind::Variable(path)
+                if path.segments.len() == 1 && path.segments[0].ident.to_string() == accumulator)
+        };
+        let term = if is_accumulator(&infix.lhs) {
+            &infix.rhs
+        } else if is_accumulator(&infix.rhs) {
+            &infix.lhs
+        } else {
+            return None;
+        };
+
+        let mut product = term;
+        while let ExpressionKind::Parenthesized(inner) = &product.kind {
+            product = inner;
+        }
+        match &product.kind {
+            ExpressionKind::Infix(infix) if infix.operator.contents == BinaryOpKind::Multiply => {
+                Some(product)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Visitor for AccumulationCollector<'_> {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        self.declare(&let_statement.pattern, &let_statement.r#type);
+        true
+    }
+
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        let ForRange::Range(bounds) = &for_loop.range else {
+            return true;
+        };
+
+        let start = const_value(&bounds.start, self.globals);
+        let end = const_value(&bounds.end, self.globals);
+        let loop_iterations = start.zip(end).and_then(|(start, end)| {
+            let iterations = end.checked_sub(start)?;
+            if bounds.inclusive {
+                iterations.checked_add(1)
+            } else {
+                Some(iterations)
+            }
+        });
+
+        // The index is bounded by the end of the range
+        let index = for_loop.identifier.to_string();
+        let previous_width = match end {
+            Some(end) => self
+                .integer_widths
+                .insert(index.clone(), u128::BITS - end.leading_zeros()),
+            None => self.integer_widths.remove(&index),
+        };
+        let enclosing_iterations = self.iterations;
+        self.iterations = enclosing_iterations
+            .zip(loop_iterations)
+            .and_then(|(enclosing, iterations)| enclosing.checked_mul(iterations));
+
+        for_loop.block.accept(self);
+
+        self.iterations = enclosing_iterations;
+        match previous_width {
+            Some(width) => self.integer_widths.insert(index, width),
+            None => self.integer_widths.remove(&index),
+        };
+        false
+    }
+
+    fn visit_assign_statement(&mut self, assign: &AssignStatement) -> bool {
+        let LValue::Ident(accumulator) = &assign.lvalue else {
+            return true;
+        };
+        let accumulator = accumulator.to_string();
+        let Some(iterations) = self.iterations.filter(|iterations| *iterations > 1) else {
+            return true;
+        };
+        if !self.field_variables.contains(&accumulator) {
+            return true;
+        }
+
+        let Some(product) = self.accumulated_product(&accumulator, &assign.expression) else {
+            return true;
+        };
+        let Some(term_bits) = self.bit_width(product) else {
+            return true;
+        };
+        let Some(wrap_iteration) = wrap_iteration(term_bits) else {
+            return true;
+        };
+
+        if iterations >= wrap_iteration {
+            let (span, file_id) = Noir::expression_location(&assign.expression);
+            self.accumulations.push(Accumulation {
+                accumulator,
+                term_bits,
+                iterations,
+                wrap_iteration,
+                span,
+                file_id,
+            });
+        }
+        true
+    }
+}
+
+/// Evaluates the globals of the program whose value is a constant integer.
+fn evaluate_globals(context: &AstContext) -> HashMap<String, u128> {
+    let mut globals = HashMap::new();
+
+    for item in &context.parsed_module.items {
+        if let ItemKind::Global(let_statement, ..) = &item.kind
+            && let Pattern::Identifier(ident) = &let_statement.pattern
+            && let Some(value) = const_value(&let_statement.expression, &globals)
+        {
+            globals.insert(ident.to_string(), value);
+        }
+    }
+
+    globals
+}
+
+/// Evaluates a constant unsigned integer expression.
+fn const_value(expression: &Expression, globals: &HashMap<String, u128>) -> Option<u128> {
+    match &expression.kind {
+        ExpressionKind::Literal(Literal::Integer(value)) if !value.is_negative() => {
+            value.absolute_value().try_into_u128()
+        }
+        ExpressionKind::Variable(path) if path.segments.len() == 1 => {
+            globals.get(&path.segments[0].ident.to_string()).copied()
+        }
+        ExpressionKind::Parenthesized(expression) => const_value(expression, globals),
+        ExpressionKind::Cast(cast) => const_value(&cast.lhs, globals),
+        ExpressionKind::Infix(infix) => {
+            let lhs = const_value(&infix.lhs, globals)?;
+            let rhs = const_value(&infix.rhs, globals)?;
+            match infix.operator.contents {
+                BinaryOpKind::Add => lhs.checked_add(rhs),
+                BinaryOpKind::Subtract => lhs.checked_sub(rhs),
+                BinaryOpKind::Multiply => lhs.checked_mul(rhs),
+                BinaryOpKind::Divide => lhs.checked_div(rhs),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the first iteration from which adding terms of `term_bits` bits can exceed the
+/// field modulus, or `None` when no loop can run long enough.
+fn wrap_iteration(term_bits: u32) -> Option<u128> {
+    let (high, low) = MODULUS;
+    // Each term is below 2^term_bits, so the sum can exceed the modulus after
+    // floor(modulus / 2^term_bits) + 1 terms
+    let quotient = match term_bits {
+        0 => return None,
+        1..128 => {
+            if high >> term_bits != 0 {
+                return None;
+            }
+            (high << (128 - term_bits)) | (low >> term_bits)
+        }
+        128..256 => high >> (term_bits - 128),
+        _ => 0,
+    };
+    quotient.checked_add(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_wrapping_accumulation_is_flagged() {
+        let lint = Box::new(FieldAccumulationOverflow);
+        let source_code = r#"
+            global ROUNDS: u32 = 1000000;
+
+            pub fn main(a: u128, b: u64, c: [u32; 4]) {
+                let mut acc: Field = 0;
+                for i in 0..ROUNDS * 1000 {
+                    acc += (a as Field) * (b as Field) * (c[i % 4] as Field);
+                }
+                let mut small: Field = 0;
+                for i in 0..100 {
+                    small += (a as Field) * (b as Field) * (c[i % 4] as Field);
+                }
+                let mut truncated: Field = 0;
+                for i in 0..ROUNDS * 1000 {
+                    truncated += ((a as u8) as Field) * (b as Field) * (c[i % 4] as Field);
+                }
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Field 'acc' accumulates products of up to 224 bits over 1000000000 iterations, and can wrap around the field modulus from iteration 811880051"
+        );
+    }
+
+    #[test]
+    fn test_wrap_iteration_follows_the_modulus() {
+        assert_eq!(wrap_iteration(224), Some(0x30644e72 + 1));
+        assert_eq!(wrap_iteration(253), Some(2));
+        assert_eq!(wrap_iteration(254), Some(1));
+        assert_eq!(wrap_iteration(125), None);
+    }
+}
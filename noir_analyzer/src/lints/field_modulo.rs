@@ -0,0 +1,174 @@
+//! # Field Modulo Lint
+//!
+//! Flags `%` applied to `Field` values when the result is compared with a small integer
+//! literal, as in `x % 2 == 0`. On fields, `%` does not behave like the integer modulo of
+//! other languages, so such checks are a common porting bug. The operands should be cast to
+//! an integer type first.
+//!
+//! Types are only known from annotations, so only parameters and `let` bindings declared as
+//! `Field` are considered.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ast::{
+    BinaryOpKind, Expression, ExpressionKind, InfixExpression, LetStatement, Literal, Pattern,
+    UnresolvedTypeData, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::HashSet;
+
+/// Largest literal considered small enough to suggest integer semantics.
+const SMALL_LITERAL_MAX: u128 = u32::MAX as u128;
+
+/// Detects integer-style modulo checks on `Field` values.
+#[derive(Clone, Default)]
+pub struct FieldModulo;
+
+declare_lint! {
+    FieldModulo {
+        name: "field-modulo",
+        explanation: "Reports `%` applied to `Field` values whose result is compared with a small integer, such as `x % 2 == 0`. On fields, `%` does not behave like integer modulo; cast the operands to an integer type first.",
+    }
+}
+
+impl FieldModulo {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for function in context.function_definitions.values() {
+            let mut collector = FieldModuloCollector::default();
+            for parameter in &function.parameters {
+                collector.declare(&parameter.pattern, &parameter.typ.typ);
+            }
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+
+            for (span, file_id) in collector.modulos {
+                let message = Message::new("field-modulo").with("function", &function.name);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Collects the `Field` modulo expressions compared with small integer literals.
+#[derive(Default)]
+struct FieldModuloCollector {
+    /// Names of the variables annotated as `Field`.
+    field_variables: HashSet<String>,
+    modulos: Vec<(Span, FileId)>,
+}
+
+impl FieldModuloCollector {
+    fn declare(&mut self, pattern: &Pattern, typ: &UnresolvedTypeData) {
+        if let (Pattern::Identifier(ident), UnresolvedTypeData::FieldElement) = (pattern, typ) {
+            self.field_variables.insert(ident.to_string());
+        }
+    }
+
+    /// Returns whether the expression applies `%` to a variable annotated as `Field`.
+    fn is_field_modulo(&self, expression: &Expression) -> bool {
+        let ExpressionKind::Infix(infix) = &expression.kind else {
+            return false;
+        };
+
+        infix.operator.contents == BinaryOpKind::Modulo
+            && [&infix.lhs, &infix.rhs]
+                .into_iter()
+                .any(|operand| self.is_field_variable(operand))
+    }
+
+    fn is_field_variable(&self, expression: &Expression) -> bool {
+        match &expression.kind {
+            ExpressionKind::Variable(path) if path.segments.len() == 1 => self
+                .field_variables
+                .contains(&path.segments[0].ident.to_string()),
+            _ => false,
+        }
+    }
+}
+
+impl Visitor for FieldModuloCollector {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        self.declare(&let_statement.pattern, &let_statement.r#type.typ);
+        true
+    }
+
+    fn visit_infix_expression(&mut self, infix: &InfixExpression, _: Span) -> bool {
+        let is_comparison = matches!(
+            infix.operator.contents,
+            BinaryOpKind::Equal
+                | BinaryOpKind::NotEqual
+                | BinaryOpKind::Less
+                | BinaryOpKind::LessEqual
+                | BinaryOpKind::Greater
+                | BinaryOpKind::GreaterEqual
+        );
+        if !is_comparison {
+            return true;
+        }
+
+        for (operand, other) in [(&infix.lhs, &infix.rhs), (&infix.rhs, &infix.lhs)] {
+            if self.is_field_modulo(operand) && is_small_literal(other) {
+                self.modulos.push(Noir::expression_location(operand));
+            }
+        }
+        true
+    }
+}
+
+fn is_small_literal(expression: &Expression) -> bool {
+    match &expression.kind {
+        ExpressionKind::Literal(Literal::Integer(value)) => value
+            .absolute_value()
+            .try_into_u128()
+            .is_some_and(|value| value <= SMALL_LITERAL_MAX),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_field_modulo_compared_with_integer_is_flagged() {
+        let lint = Box::new(FieldModulo);
+        let source_code = r#"
+            pub fn main(x: Field, y: u64) {
+                let z: Field = x + 1;
+                assert(x % 2 == 0);
+                assert(y % 2 == 0);
+                assert(1 != z % 3);
+                let w = x % 7;
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|lint| lint.name == "field-modulo"));
+        assert_eq!(result[0].confidence, Confidence::Medium);
+    }
+}
@@ -0,0 +1,180 @@
+//! # First Element Comparison Lint
+//!
+//! Flags equality assertions comparing only the first elements of two arrays, such as
+//! `assert(hash[0] == expected[0])`, when the arrays are otherwise handled as whole values:
+//! hash outputs, serialized notes or commitments passed around and returned. Checking a
+//! single element leaves every other element unconstrained, so two different values with
+//! the same first element pass the check.
+//!
+//! Arrays read at any other index in the function are not reported, as the comparison is
+//! then likely part of an element-wise check.
+
+use crate::analysis::usage::UsageIndex;
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use crate::lints::redundant_equality::equality;
+use noirc_frontend::ast::{
+    ConstrainExpression, ConstrainKind, Expression, ExpressionKind, Literal, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+
+/// Detects assertions comparing whole arrays through their first element only.
+#[derive(Clone, Default)]
+pub struct FirstElementComparison;
+
+declare_lint! {
+    FirstElementComparison {
+        name: "first-element-comparison",
+        explanation: "Reports equality assertions comparing only the first elements of two arrays, such as `assert(hash[0] == expected[0])`, when the arrays are used as whole values elsewhere. Every other element is left unconstrained, so different hash outputs or serialized notes sharing their first element pass the check; compare the whole arrays instead.",
+    }
+}
+
+impl FirstElementComparison {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            if function.is_unconstrained {
+                continue;
+            }
+            let usage = UsageIndex::of_function(function);
+            let mut constraints = Constraints::default();
+            for statement in &function.body.statements {
+                statement.accept(&mut constraints);
+            }
+
+            for constrain in constraints.expressions {
+                let Some((lhs, rhs, location)) = equality(&constrain) else {
+                    continue;
+                };
+                let (Some(lhs_array), Some(rhs_array)) =
+                    (first_element_of(lhs), first_element_of(rhs))
+                else {
+                    continue;
+                };
+                // Only arrays never read at another index are compared as a whole
+                let only_first = |array: &str| {
+                    usage
+                        .indices(array)
+                        .is_some_and(|indices| indices.iter().eq(["0"]))
+                };
+                if lhs_array == rhs_array || !only_first(&lhs_array) || !only_first(&rhs_array) {
+                    continue;
+                }
+                let Some(whole) = [&lhs_array, &rhs_array]
+                    .into_iter()
+                    .find(|array| usage.is_used_whole(array))
+                else {
+                    continue;
+                };
+
+                let separator = match constrain.kind {
+                    ConstrainKind::AssertEq => ", ",
+                    _ => " == ",
+                };
+                let (lhs_span, file_id) = Noir::expression_location(lhs);
+                let (rhs_span, _) = Noir::expression_location(rhs);
+                let suggestion = Suggestion {
+                    message: "compare the whole arrays".to_string(),
+                    span: Span::from(lhs_span.start()..rhs_span.end()),
+                    replacement: format!("{lhs_array}{separator}{rhs_array}"),
+                    // The arrays may differ in length, or on purpose past their first element
+                    applicability: Applicability::MaybeIncorrect,
+                    other_edits: vec![],
+                };
+
+                let (span, _) = Noir::expression_location(location);
+                let message = Message::new("first-element-comparison")
+                    .with("function", name)
+                    .with("lhs", &lhs_array)
+                    .with("rhs", &rhs_array)
+                    .with("whole", whole);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: Some(suggestion),
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns the array a side of an equality reads the first element of, for `array[0]`.
+fn first_element_of(expression: &Expression) -> Option<String> {
+    let ExpressionKind::Index(index) = &expression.kind else {
+        return None;
+    };
+    let ExpressionKind::Literal(Literal::Integer(value)) = &index.index.kind else {
+        return None;
+    };
+    if value.absolute_value().try_into_u128() != Some(0) {
+        return None;
+    }
+    match &index.collection.kind {
+        ExpressionKind::Variable(path) if path.segments.len() == 1 => Some(path.to_string()),
+        _ => None,
+    }
+}
+
+/// Collects the assertions of a statement, at any depth.
+#[derive(Default)]
+struct Constraints {
+    expressions: Vec<ConstrainExpression>,
+}
+
+impl Visitor for Constraints {
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        self.expressions.push(constrain.clone());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    const SOURCE: &str = "fn main(note: [Field; 2], expected: [Field; 2], other: [Field; 2]) -> pub [Field; 2] {\n    let hash = std::hash::blake3(note);\n    assert(hash[0] == expected[0]);\n    assert_eq(note[0], expected[0]);\n    assert(other[0] == other[0]);\n    hash\n}\n\nfn element_wise(a: [Field; 2], b: [Field; 2]) {\n    assert(a[0] == b[0]);\n    assert(a[1] == b[1]);\n    let _ = a;\n}\n";
+
+    #[test]
+    fn test_first_element_comparisons_of_whole_arrays_are_flagged() {
+        let lint = Box::new(FirstElementComparison);
+
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Assertion in function 'main' only compares the first elements of 'hash' and 'expected', although 'hash' is used as a whole value",
+                "Assertion in function 'main' only compares the first elements of 'note' and 'expected', although 'note' is used as a whole value",
+            ]
+        );
+
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(true).apply(SOURCE, suggestions).source;
+        assert!(fixed.contains("assert(hash == expected);\n    assert_eq(note, expected);"));
+    }
+}
@@ -0,0 +1,260 @@
+//! # Function Naming Lint
+//!
+//! Flags free functions whose name is not in snake_case, the convention of Noir and its
+//! standard library, such as `computeRoot`. Renaming a function by hand means finding every
+//! call to it, so when the source is known the finding comes with a rename: one edit for the
+//! definition and one for every call the symbol table resolves to it, whether written
+//! `computeRoot`, `crate::hashing::computeRoot` or `super::computeRoot`.
+//!
+//! Files are analyzed one at a time, so the rename is only suggested for private functions
+//! of modules that declare no file modules, which no other file can call. It is not
+//! machine-applicable: imports of the function and uses of it as a value, e.g. passed to
+//! `map`, are left for review. No rename is suggested when the snake_case name is already
+//! taken in the module. The lint is opt-in.
+
+use crate::analysis::module_graph::ROOT_MODULE;
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{ExpressionKind, ItemVisibility, NoirFunction};
+use noirc_frontend::parser::ItemKind;
+
+/// Detects functions whose name is not in snake_case.
+#[derive(Clone, Default)]
+pub struct FunctionNaming;
+
+declare_lint! {
+    FunctionNaming {
+        name: "function-naming",
+        explanation: "Reports free functions whose name is not in snake_case, the naming convention of Noir and its standard library, such as `computeRoot`. For private functions that no other file can call, the fix renames the function together with every call resolved to it; check the imports of the function and its uses as a value afterwards. This lint is opt-in.",
+        requires_whole_program: true,
+        enabled_by_default: false,
+    }
+}
+
+impl FunctionNaming {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        lint_module(
+            context,
+            context.parsed_module,
+            &[ROOT_MODULE.to_string()],
+            &mut lints,
+        );
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Reports the functions of a module and its submodules that are not in snake_case.
+fn lint_module(
+    context: &AstContext,
+    parsed_module: &ParsedModule,
+    module_path: &[String],
+    lints: &mut Vec<Lint>,
+) {
+    // Private functions can be called from submodules, which may live in other files
+    let confined = !declares_file_modules(parsed_module);
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Submodules(submodule) => {
+                let mut submodule_path = module_path.to_vec();
+                submodule_path.push(submodule.name.to_string());
+                lint_module(context, &submodule.contents, &submodule_path, lints);
+            }
+            ItemKind::Function(function) => {
+                let name = function.name();
+                let snake_case = to_snake_case(name);
+                if snake_case == name {
+                    continue;
+                }
+
+                let module = module_path.join("::");
+                let suggestion = context
+                    .source
+                    .filter(|_| confined && function.def.visibility == ItemVisibility::Private)
+                    .filter(|_| !is_defined(context, &format!("{module}::{snake_case}")))
+                    .and_then(|source| {
+                        rename(
+                            source,
+                            context,
+                            function,
+                            &format!("{module}::{name}"),
+                            &snake_case,
+                        )
+                    });
+                let (span, file_id) = Noir::function_location(&function.def);
+                let message = Message::new(FunctionNaming::NAME)
+                    .with("function", name)
+                    .with("snake_case", snake_case);
+                lints.push(Lint {
+                    name: FunctionNaming::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::High,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns whether a module or one of its inline submodules declares a module loaded from
+/// another file, e.g. `mod utils;`.
+fn declares_file_modules(parsed_module: &ParsedModule) -> bool {
+    parsed_module.items.iter().any(|item| match &item.kind {
+        ItemKind::ModuleDecl(_) => true,
+        ItemKind::Submodules(submodule) => declares_file_modules(&submodule.contents),
+        _ => false,
+    })
+}
+
+/// Returns whether the program defines a function of the given fully-qualified path.
+fn is_defined(context: &AstContext, path: &str) -> bool {
+    context.symbols.functions().any(|function| function == path)
+}
+
+/// Returns a fix renaming a function and the calls resolved to its path, with one edit per
+/// occurrence of the name.
+fn rename(
+    source: &str,
+    context: &AstContext,
+    function: &NoirFunction,
+    path: &str,
+    new_name: &str,
+) -> Option<Suggestion> {
+    let name = function.name();
+    let mut calls = vec![];
+    for call in context.resolved_calls.get(path).into_iter().flatten() {
+        // Calls through an alias keep the name they are written with
+        if let ExpressionKind::Variable(call_path) = &call.call.func.kind
+            && let Some(segment) = call_path.segments.last()
+            && segment.ident.to_string() == name
+        {
+            calls.push(segment.ident.span());
+        }
+    }
+    calls.sort_by_key(|span| span.start());
+    calls.dedup();
+
+    let span = function.def.name.span();
+    if source.get(span.start() as usize..span.end() as usize) != Some(name) {
+        return None;
+    }
+    Some(Suggestion {
+        message: format!("rename '{name}' to '{new_name}' along with its calls"),
+        span,
+        replacement: new_name.to_string(),
+        applicability: Applicability::MaybeIncorrect,
+        other_edits: calls
+            .into_iter()
+            .map(|span| (span, new_name.to_string()))
+            .collect(),
+    })
+}
+
+/// Converts a name to snake_case, splitting words at lowercase-to-uppercase boundaries and
+/// before the last capital of an acronym, e.g. `verifyECDSASignature` to
+/// `verify_ecdsa_signature`.
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake_case = String::with_capacity(name.len());
+    for (index, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next = chars.get(index + 1).copied();
+            let starts_word = previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next.is_some_and(|c| c.is_ascii_lowercase()));
+            if starts_word {
+                snake_case.push('_');
+            }
+        }
+        snake_case.push(c.to_ascii_lowercase());
+    }
+    snake_case
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    #[test]
+    fn test_functions_are_renamed_with_their_calls() {
+        let source = "mod hashing {\n    fn computeRoot(leaf: Field) -> Field {\n        leaf * 2\n    }\n\n    pub fn check(leaf: Field, root: Field) {\n        let computed = computeRoot(leaf);\n        assert(crate::hashing::computeRoot(leaf) == computed);\n        assert(tree::root(leaf) == root);\n    }\n\n    mod tree {\n        pub fn root(leaf: Field) -> Field {\n            super::computeRoot(leaf)\n        }\n    }\n}\n\nfn main(leaf: Field, root: Field) {\n    hashing::check(leaf, root);\n}\n";
+        let lint = Box::new(FunctionNaming);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            ["Function 'computeRoot' is not in snake_case; rename it to 'compute_root'"]
+        );
+
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(true).apply(source, suggestions).source;
+        assert_eq!(
+            fixed,
+            "mod hashing {\n    fn compute_root(leaf: Field) -> Field {\n        leaf * 2\n    }\n\n    pub fn check(leaf: Field, root: Field) {\n        let computed = compute_root(leaf);\n        assert(crate::hashing::compute_root(leaf) == computed);\n        assert(tree::root(leaf) == root);\n    }\n\n    mod tree {\n        pub fn root(leaf: Field) -> Field {\n            super::compute_root(leaf)\n        }\n    }\n}\n\nfn main(leaf: Field, root: Field) {\n    hashing::check(leaf, root);\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_taken_names_get_no_rename() {
+        let source = "fn isValid(x: Field) -> bool { x != 0 }\nfn is_valid(x: Field) -> bool { x == 1 }\nfn main(x: Field) { assert(isValid(x) | is_valid(x)); }\n";
+        let lint = Box::new(FunctionNaming);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].suggestion.is_none());
+    }
+
+    #[test]
+    fn test_functions_other_files_may_call_get_no_rename() {
+        let source = "pub fn computeRoot(leaf: Field) -> Field { leaf * 2 }\nmod hashing {\n    mod tree;\n    fn hashPair(a: Field, b: Field) -> Field { a + b }\n}\nfn main(leaf: Field) { assert(computeRoot(leaf) == 2); }\n";
+        let lint = Box::new(FunctionNaming);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|lint| lint.suggestion.is_none()));
+    }
+
+    #[test]
+    fn test_names_are_converted_to_snake_case() {
+        assert_eq!(to_snake_case("computeRoot"), "compute_root");
+        assert_eq!(
+            to_snake_case("verifyECDSASignature"),
+            "verify_ecdsa_signature"
+        );
+        assert_eq!(to_snake_case("hash2Field"), "hash2_field");
+        assert_eq!(to_snake_case("_privateHelper"), "_private_helper");
+        assert_eq!(to_snake_case("Main"), "main");
+        assert_eq!(to_snake_case("already_snake"), "already_snake");
+    }
+}
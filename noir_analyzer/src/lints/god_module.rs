@@ -0,0 +1,190 @@
+//! # God Module Lint
+//!
+//! Flags modules that grew too large or that too many other modules depend on. Such modules
+//! tend to accumulate unrelated circuit logic and are good candidates for decomposition.
+//!
+//! Both limits can be set in `noir-analyzer.toml`:
+//!
+//! ```toml
+//! [lints.god-module]
+//! max-items = 80
+//! max-fan-in = 20
+//! ```
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use serde::Deserialize;
+
+/// Default maximum number of items declared directly in a module.
+pub const DEFAULT_MAX_ITEMS: usize = 50;
+/// Default maximum number of modules importing from a single module.
+pub const DEFAULT_MAX_FAN_IN: usize = 10;
+
+/// Detects modules exceeding size or fan-in thresholds.
+#[derive(Clone, Default)]
+pub struct GodModule {
+    pub options: GodModuleOptions,
+}
+
+/// Options of [`GodModule`], set under `[lints.god-module]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct GodModuleOptions {
+    /// Maximum number of items a module may declare directly.
+    /// Also set by the `[thresholds]` entry of the lint.
+    #[serde(alias = "threshold")]
+    pub max_items: usize,
+    /// Maximum number of modules that may import from a module.
+    pub max_fan_in: usize,
+}
+
+impl Default for GodModuleOptions {
+    fn default() -> Self {
+        Self {
+            max_items: DEFAULT_MAX_ITEMS,
+            max_fan_in: DEFAULT_MAX_FAN_IN,
+        }
+    }
+}
+
+impl GodModule {
+    /// Creates the lint with custom thresholds.
+    pub fn with_thresholds(max_items: usize, max_fan_in: usize) -> Self {
+        Self {
+            options: GodModuleOptions {
+                max_items,
+                max_fan_in,
+            },
+        }
+    }
+}
+
+declare_lint! {
+    GodModule {
+        name: "god-module",
+        explanation: "Reports modules that declare too many items or that too many other modules import from. Such modules tend to accumulate unrelated circuit logic and are good candidates for decomposition.",
+        requires_whole_program: true,
+        analyses: [ModuleGraph],
+        options: GodModuleOptions,
+    }
+}
+
+impl GodModule {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let graph = &context.module_graph;
+        let mut lints = vec![];
+
+        for (path, module) in graph.modules() {
+            let fan_in = graph.fan_in(path);
+            let id = match (
+                module.item_count > self.options.max_items,
+                fan_in > self.options.max_fan_in,
+            ) {
+                (true, true) => "god-module.items-and-fan-in",
+                (true, false) => "god-module.items",
+                (false, true) => "god-module.fan-in",
+                (false, false) => continue,
+            };
+
+            let message = Message::new(id)
+                .with("module", path)
+                .with("items", module.item_count)
+                .with("max_items", self.options.max_items)
+                .with("fan_in", fan_in)
+                .with("max_fan_in", self.options.max_fan_in);
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::High,
+                description: message.to_string(),
+                message: Some(message),
+                span: module.span,
+                file_id: module.file_id,
+                suggestion: None,
+            });
+        }
+
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::lints::god_module::GodModule;
+    use crate::lints::lint_rule::LintRule;
+
+    #[test]
+    fn test_god_module_can_be_created() {
+        let lint = GodModule::default();
+        assert_eq!(lint.name(), "god-module");
+    }
+
+    #[test]
+    fn test_small_modules_are_not_flagged() {
+        let lint = Box::new(GodModule::default());
+        let source_code = r#"
+            mod a {
+                pub fn foo() {}
+            }
+            pub fn main() {}
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_module_exceeding_item_threshold_is_flagged() {
+        let lint = Box::new(GodModule::with_thresholds(2, 10));
+        let source_code = r#"
+            mod utils {
+                pub fn one() {}
+                pub fn two() {}
+                pub fn three() {}
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Module 'crate::utils' declares 3 items (limit 2); consider splitting it"
+        );
+    }
+
+    #[test]
+    fn test_module_exceeding_fan_in_threshold_is_flagged() {
+        let lint = Box::new(GodModule::with_thresholds(50, 1));
+        let source_code = r#"
+            mod utils {
+                pub fn one() {}
+            }
+            mod a {
+                use crate::utils::one;
+            }
+            mod b {
+                use crate::utils::one;
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Module 'crate::utils' is imported by 2 modules (limit 1); consider splitting it"
+        );
+    }
+}
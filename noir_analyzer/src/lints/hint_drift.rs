@@ -0,0 +1,224 @@
+//! # Hint Drift Lint
+//!
+//! Flags unconstrained "hint" functions that duplicate the logic of a constrained function,
+//! when the two copies have drifted apart. A hint computed off-circuit is only sound as long
+//! as it computes what the constrained code checks; after an edit to only one of the copies,
+//! proofs fail for honest provers, or the check no longer matches the value it was meant to
+//! verify.
+//!
+//! A hint and a constrained function are paired when the constrained function calls the
+//! hint, or when their names only differ by a `_hint`, `_unconstrained` or `_unchecked`
+//! suffix or leading underscores. Paired functions whose bodies have the same shape but
+//! whose tokens differ by more than a limit are reported. The limit is a minimum percentage
+//! of matching tokens, 90 by default, and can be set in `noir-analyzer.toml`:
+//!
+//! ```toml
+//! [lints.hint-drift]
+//! min-similarity = 80
+//! ```
+
+use crate::analysis::similarity::{shape, similarity, tokens};
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use serde::Deserialize;
+
+/// Default minimum percentage of matching tokens between a hint and its constrained copy.
+pub const DEFAULT_MIN_SIMILARITY: usize = 90;
+
+/// Minimum similarity of the shapes of two bodies for them to count as copies.
+const MIN_SHAPE_SIMILARITY: f64 = 0.8;
+
+/// Minimum number of tokens of a hint body, below which any two bodies look alike.
+const MIN_TOKENS: usize = 12;
+
+/// Name suffixes marking the unconstrained copy of a function.
+const HINT_SUFFIXES: &[&str] = &["_hint", "_unconstrained", "_unchecked"];
+
+/// Detects unconstrained hints drifting from the constrained logic they duplicate.
+#[derive(Clone, Default)]
+pub struct HintDrift {
+    options: HintDriftOptions,
+}
+
+/// Options of [`HintDrift`], set under `[lints.hint-drift]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct HintDriftOptions {
+    /// Minimum percentage of matching tokens between a hint and its constrained copy.
+    /// Also set by the `[thresholds]` entry of the lint.
+    #[serde(alias = "threshold")]
+    pub min_similarity: usize,
+}
+
+impl Default for HintDriftOptions {
+    fn default() -> Self {
+        Self {
+            min_similarity: DEFAULT_MIN_SIMILARITY,
+        }
+    }
+}
+
+impl HintDrift {
+    /// Creates the lint with a custom minimum percentage of matching tokens.
+    pub fn with_min_similarity(min_similarity: usize) -> Self {
+        Self {
+            options: HintDriftOptions { min_similarity },
+        }
+    }
+}
+
+declare_lint! {
+    HintDrift {
+        name: "hint-drift",
+        explanation: "Reports unconstrained functions duplicating the structure of a constrained function they are paired with, by a call or by name, when less than 90% of their tokens match (configurable as min-similarity under [lints.hint-drift] in noir-analyzer.toml). Hints that drift from the logic they mirror compute values the constraints no longer check; keep the copies in sync, or derive one from the other.",
+        requires_whole_program: true,
+        analyses: [CallGraph],
+        options: HintDriftOptions,
+    }
+}
+
+impl HintDrift {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        for (hint_name, hint) in &context.function_definitions {
+            if !hint.is_unconstrained {
+                continue;
+            }
+            let hint_tokens = tokens(&hint.body.to_string());
+            if hint_tokens.len() < MIN_TOKENS {
+                continue;
+            }
+
+            for (name, function) in &context.function_definitions {
+                if function.is_unconstrained
+                    || (base_name(name) != base_name(hint_name)
+                        && !context
+                            .call_graph
+                            .callees_of(name)
+                            .iter()
+                            .any(|callee| callee == hint_name))
+                {
+                    continue;
+                }
+                let function_tokens = tokens(&function.body.to_string());
+                if similarity(&shape(&hint_tokens), &shape(&function_tokens)) < MIN_SHAPE_SIMILARITY
+                {
+                    continue;
+                }
+                let percent = (similarity(&hint_tokens, &function_tokens) * 100.0).floor() as usize;
+                if percent >= self.options.min_similarity {
+                    continue;
+                }
+
+                let (span, file_id) = Noir::function_location(hint);
+                let message = Message::new("hint-drift")
+                    .with("hint", hint_name)
+                    .with("function", name)
+                    .with("similarity", percent)
+                    .with("min", self.options.min_similarity);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    // The copies may differ on purpose, e.g. to avoid a costly operation
+                    confidence: Confidence::Low,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns the name of a function without the markers of an unconstrained copy.
+fn base_name(name: &str) -> &str {
+    let name = name.trim_start_matches('_');
+    HINT_SUFFIXES
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_drifted_hints_are_flagged() {
+        let source_code = r#"
+            unconstrained fn fee_hint(amount: u64, rate: u64) -> u64 {
+                let base = amount * rate;
+                let rounded = base / 1000;
+                let capped = if rounded > 50 { 50 } else { rounded };
+                capped + 2
+            }
+
+            fn fee(amount: u64, rate: u64) -> u64 {
+                let base = amount * rate;
+                let rounded = base / 100;
+                let capped = if rounded > 20 { 20 } else { rounded };
+                capped - 1
+            }
+
+            unconstrained fn __square(x: u64, y: u64) -> u64 {
+                let a = x * x;
+                let b = y * y;
+                let c = a + b;
+                c * 2
+            }
+
+            fn square(x: u64, y: u64) -> u64 {
+                let a = x * x;
+                let b = y * y;
+                let c = a + b;
+                c * 2
+            }
+
+            fn unrelated(amount: u64, rate: u64) -> u64 {
+                let base = amount + rate;
+                let rounded = base / 10;
+                let capped = if rounded > 5 { 5 } else { rounded };
+                capped + 7
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint = Box::new(HintDrift::default());
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert!(
+            result[0]
+                .description
+                .starts_with("Unconstrained function 'fee_hint' mirrors 'fee', but only ")
+        );
+
+        let lint = Box::new(HintDrift::with_min_similarity(50));
+        let mut analyzer = Analyzer::new(&[lint]);
+        assert!(
+            analyzer
+                .analyze(&root)
+                .expect("Should have passed")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_base_name_strips_hint_markers() {
+        assert_eq!(base_name("__compute"), "compute");
+        assert_eq!(base_name("compute_hint"), "compute");
+        assert_eq!(base_name("compute_unconstrained"), "compute");
+        assert_eq!(base_name("compute"), "compute");
+    }
+}
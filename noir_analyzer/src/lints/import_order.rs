@@ -0,0 +1,356 @@
+//! # Import Order Lint
+//!
+//! Flags blocks of `use` declarations that are not in canonical order, with a fix rewriting
+//! them, as a lightweight import formatter: imports are grouped into the standard library,
+//! dependencies and the current crate, separated by a blank line, sorted within each group,
+//! and declarations importing from the same path are merged into one list, dropping
+//! duplicates.
+//!
+//! A block is a run of `use` declarations separated by whitespace only; blocks containing
+//! comments are left alone, as the fix would drop them. Plain paths are imports from the
+//! crate when they start with a module declared next to them, and from a dependency
+//! otherwise. This formatting rule is opt-in.
+
+use crate::analysis::module_graph::ROOT_MODULE;
+use crate::ast::ast_context::AstContext;
+use crate::ast::trivia;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{ItemVisibility, Path, PathKind, UseTree, UseTreeKind};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::{Item, ItemKind};
+use std::collections::BTreeSet;
+
+/// Detects blocks of imports that are not grouped, sorted and merged.
+#[derive(Clone, Default)]
+pub struct ImportOrder;
+
+declare_lint! {
+    ImportOrder {
+        name: "import-order",
+        explanation: "Reports blocks of `use` declarations that are not grouped into the standard library, dependencies and the current crate, sorted, and merged by path. A deterministic order keeps import changes small in reviews. This formatting lint is opt-in.",
+        requires_whole_program: true,
+        enabled_by_default: false,
+    }
+}
+
+/// Groups of imports, in the order they are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Group {
+    Std,
+    Dependency,
+    Crate,
+}
+
+/// A single imported name, with the path it is imported from as written.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Import {
+    group: Group,
+    parent: String,
+    visibility: &'static str,
+    /// False for `self`, which sorts first.
+    not_self: bool,
+    name: String,
+}
+
+impl ImportOrder {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        if let Some(source) = context.source {
+            self.lint_module(
+                source,
+                context.parsed_module,
+                &[ROOT_MODULE.to_string()],
+                &mut lints,
+            );
+        }
+        lints
+    }
+
+    fn lint_module(
+        &self,
+        source: &str,
+        parsed_module: &ParsedModule,
+        module_path: &[String],
+        lints: &mut Vec<Lint>,
+    ) {
+        let local_modules: BTreeSet<String> = parsed_module
+            .items
+            .iter()
+            .filter_map(|item| match &item.kind {
+                ItemKind::Submodules(submodule) => Some(submodule.name.to_string()),
+                ItemKind::ModuleDecl(declaration) => Some(declaration.ident.to_string()),
+                _ => None,
+            })
+            .collect();
+
+        let mut block: Vec<&Item> = vec![];
+        for item in &parsed_module.items {
+            match &item.kind {
+                ItemKind::Import(..) => {
+                    let contiguous = block.last().is_none_or(|previous| {
+                        let end = declaration_end(source, Noir::item_location(previous).0);
+                        let start = Noir::item_location(item).0.start() as usize;
+                        source
+                            .get(end..start)
+                            .is_some_and(|gap| gap.trim().is_empty())
+                    });
+                    if !contiguous {
+                        self.lint_block(source, &block, &local_modules, module_path, lints);
+                        block.clear();
+                    }
+                    block.push(item);
+                }
+                ItemKind::Submodules(submodule) => {
+                    let mut submodule_path = module_path.to_vec();
+                    submodule_path.push(submodule.name.to_string());
+                    self.lint_module(source, &submodule.contents, &submodule_path, lints);
+                }
+                _ => {}
+            }
+            if !matches!(item.kind, ItemKind::Import(..)) && !block.is_empty() {
+                self.lint_block(source, &block, &local_modules, module_path, lints);
+                block.clear();
+            }
+        }
+        self.lint_block(source, &block, &local_modules, module_path, lints);
+    }
+
+    fn lint_block(
+        &self,
+        source: &str,
+        block: &[&Item],
+        local_modules: &BTreeSet<String>,
+        module_path: &[String],
+        lints: &mut Vec<Lint>,
+    ) {
+        let (Some(first), Some(last)) = (block.first(), block.last()) else {
+            return;
+        };
+        let (first_span, file_id) = Noir::item_location(first);
+        let start = first_span.start() as usize;
+        let end = declaration_end(source, Noir::item_location(last).0);
+        let Some(original) = source.get(start..end) else {
+            return;
+        };
+        if original.contains("//") || original.contains("/*") {
+            return;
+        }
+
+        let mut imports = BTreeSet::new();
+        for item in block {
+            if let ItemKind::Import(use_tree, visibility) = &item.kind {
+                let visibility = match visibility {
+                    ItemVisibility::Public => "pub ",
+                    ItemVisibility::PublicCrate => "pub(crate) ",
+                    ItemVisibility::Private => "",
+                };
+                let group = group(&use_tree.prefix, local_modules);
+                collect_imports(use_tree, group, visibility, String::new(), &mut imports);
+            }
+        }
+
+        let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+        let indent: String = source[line_start..start]
+            .chars()
+            .take_while(|character| character.is_whitespace())
+            .collect();
+        let formatted = format_imports(&imports, &indent);
+        if formatted == original {
+            return;
+        }
+
+        let message = Message::new("import-order").with("module", module_path.join("::"));
+        lints.push(Lint {
+            name: Self::NAME,
+            severity: Severity::Warning,
+            confidence: Confidence::High,
+            description: message.to_string(),
+            message: Some(message),
+            span: Some(first_span),
+            file_id: Some(file_id),
+            suggestion: Some(Suggestion {
+                message: "group, sort and merge the imports".to_string(),
+                span: Span::from(start as u32..end as u32),
+                replacement: formatted,
+                applicability: Applicability::MachineApplicable,
+                other_edits: vec![],
+            }),
+        });
+    }
+}
+
+/// Renders a path as written, with its `crate`, `super` or `dep` prefix when `is_root`.
+pub(crate) fn render_path(path: &Path, is_root: bool) -> String {
+    let kind = match path.kind {
+        _ if !is_root => None,
+        PathKind::Crate => Some("crate".to_string()),
+        PathKind::Super => Some("super".to_string()),
+        PathKind::Dep => Some("dep".to_string()),
+        PathKind::Plain => None,
+    };
+    kind.into_iter()
+        .chain(
+            path.segments
+                .iter()
+                .map(|segment| segment.ident.to_string()),
+        )
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Joins a path and the rest of a use tree, e.g. `a::b` and `{c, d}`.
+pub(crate) fn join_path(prefix: &str, rest: &str) -> String {
+    if prefix.is_empty() {
+        rest.to_string()
+    } else {
+        format!("{prefix}::{rest}")
+    }
+}
+
+/// Returns the group of the imports of a use tree, from the prefix of its root.
+fn group(prefix: &Path, local_modules: &BTreeSet<String>) -> Group {
+    let first = prefix
+        .segments
+        .first()
+        .map(|segment| segment.ident.to_string());
+    match prefix.kind {
+        PathKind::Crate | PathKind::Super => Group::Crate,
+        PathKind::Dep => Group::Dependency,
+        PathKind::Plain => match first.as_deref() {
+            Some("std") => Group::Std,
+            Some(module) if local_modules.contains(module) => Group::Crate,
+            _ => Group::Dependency,
+        },
+    }
+}
+
+/// Flattens a use tree into imported names, keyed by the path they are imported from.
+fn collect_imports(
+    use_tree: &UseTree,
+    group: Group,
+    visibility: &'static str,
+    parent: String,
+    imports: &mut BTreeSet<Import>,
+) {
+    let parent = join_path(&parent, &render_path(&use_tree.prefix, parent.is_empty()));
+    match &use_tree.kind {
+        UseTreeKind::Path(name, alias) => {
+            let name = match alias {
+                Some(alias) => format!("{name} as {alias}"),
+                None => name.to_string(),
+            };
+            imports.insert(Import {
+                group,
+                not_self: name != "self",
+                parent,
+                visibility,
+                name,
+            });
+        }
+        UseTreeKind::List(trees) => {
+            for tree in trees {
+                collect_imports(tree, group, visibility, parent.clone(), imports);
+            }
+        }
+    }
+}
+
+/// Renders sorted imports, one declaration per path and visibility, with a blank line
+/// between groups.
+fn format_imports(imports: &BTreeSet<Import>, indent: &str) -> String {
+    let mut lines: Vec<String> = vec![];
+    let mut imports = imports.iter().peekable();
+    let mut previous_group = None;
+    while let Some(first) = imports.next() {
+        let mut names = vec![first.name.clone()];
+        while let Some(next) = imports.next_if(|next| {
+            (next.group, &next.parent, next.visibility)
+                == (first.group, &first.parent, first.visibility)
+        }) {
+            names.push(next.name.clone());
+        }
+
+        if previous_group.is_some_and(|group| group != first.group) {
+            lines.push(String::new());
+        }
+        previous_group = Some(first.group);
+        let tree = match names.as_slice() {
+            [name] => name.clone(),
+            _ => format!("{{{}}}", names.join(", ")),
+        };
+        lines.push(format!(
+            "{}use {};",
+            first.visibility,
+            join_path(&first.parent, &tree)
+        ));
+    }
+
+    let mut formatted = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            formatted.push('\n');
+            if !line.is_empty() {
+                formatted.push_str(indent);
+            }
+        }
+        formatted.push_str(line);
+    }
+    formatted
+}
+
+/// Returns the offset after a `use` declaration, its `;` included.
+fn declaration_end(source: &str, span: Span) -> usize {
+    let end = span.end() as usize;
+    trivia::next_token(source, end)
+        .filter(|offset| source[*offset..].starts_with(';'))
+        .map_or(end, |semicolon| semicolon + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    #[test]
+    fn test_imports_are_grouped_sorted_and_merged() {
+        let source = "mod a {\n    pub fn foo() {}\n    pub fn bar() {}\n}\nuse a::foo;\nuse std::hash::poseidon2;\nuse dep::aztec::note;\nuse a::{bar, foo};\nuse crate::a::foo as baz;\nuse std::hash::Hash;\n\nfn main() {}\n";
+        let lint = Box::new(ImportOrder);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Imports of 'crate' are not grouped and sorted"
+        );
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(false).apply(source, suggestions).source;
+        assert_eq!(
+            fixed,
+            "mod a {\n    pub fn foo() {}\n    pub fn bar() {}\n}\nuse std::hash::{Hash, poseidon2};\n\nuse dep::aztec::note;\n\nuse a::{bar, foo};\nuse crate::a::foo as baz;\n\nfn main() {}\n"
+        );
+    }
+
+    #[test]
+    fn test_formatted_imports_are_not_reported() {
+        let source = "mod a {\n    pub fn foo() {}\n}\nuse std::hash::Hash;\n\nuse a::foo;\n\nfn main() {}\n";
+        let lint = Box::new(ImportOrder);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+
+        assert!(analyzer.analyze(&root).unwrap().is_empty());
+    }
+}
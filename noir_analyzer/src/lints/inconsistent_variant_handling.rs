@@ -0,0 +1,233 @@
+//! # Inconsistent Variant Handling Lint
+//!
+//! Flags `match` arms handling an enum variant without any constraint, when another arm of
+//! the same `match` constrains a variant carrying an identical payload. Such parallel
+//! variants usually deserve the same checks, and a branch without them is often a forgotten
+//! case of a state machine.
+//!
+//! This heuristic is opt-in: enable it with a `warn` or `deny` level in the configuration.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    ConstrainExpression, Expression, ExpressionKind, MatchExpression, Path, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use std::collections::HashMap;
+
+/// Detects parallel enum variants handled with and without constraints in the same match.
+#[derive(Clone, Default)]
+pub struct InconsistentVariantHandling;
+
+declare_lint! {
+    InconsistentVariantHandling {
+        name: "inconsistent-variant-handling",
+        explanation: "Reports match arms that constrain nothing while another arm of the same match constrains a variant with an identical payload. Parallel variants usually need the same checks, so the unconstrained arm may be a forgotten case. This lint is opt-in.",
+        enabled_by_default: false,
+    }
+}
+
+impl InconsistentVariantHandling {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut payloads = HashMap::new();
+        collect_payloads(context.parsed_module, &mut payloads);
+
+        let mut collector = ArmCollector {
+            payloads: &payloads,
+            arms: vec![],
+        };
+        for function in context.function_definitions.values() {
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+        }
+
+        let mut lints: Vec<Lint> = collector
+            .arms
+            .into_iter()
+            .map(|arm| {
+                let message = Message::new("inconsistent-variant-handling")
+                    .with("variant", &arm.variant)
+                    .with("parallel_variant", &arm.parallel_variant);
+                Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Low,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(arm.span),
+                    file_id: Some(arm.file_id),
+                    suggestion: None,
+                }
+            })
+            .collect();
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Records the payload of every enum variant, keyed by `(enum, variant)` names.
+fn collect_payloads(
+    parsed_module: &ParsedModule,
+    payloads: &mut HashMap<(String, String), String>,
+) {
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Submodules(submodule) => collect_payloads(&submodule.contents, payloads),
+            ItemKind::Enum(noir_enum) => {
+                for variant in &noir_enum.variants {
+                    let payload = variant
+                        .item
+                        .parameters
+                        .iter()
+                        .flatten()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    payloads.insert(
+                        (noir_enum.name.to_string(), variant.item.name.to_string()),
+                        payload,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A match arm handling a variant without constraints.
+struct UnconstrainedArm {
+    variant: String,
+    parallel_variant: String,
+    span: Span,
+    file_id: FileId,
+}
+
+/// Collects the unconstrained arms having a constrained parallel arm.
+struct ArmCollector<'a> {
+    payloads: &'a HashMap<(String, String), String>,
+    arms: Vec<UnconstrainedArm>,
+}
+
+impl Visitor for ArmCollector<'_> {
+    fn visit_match_expression(&mut self, match_expression: &MatchExpression, _: Span) -> bool {
+        // Arms matching a known variant: (variant path, payload, constrains, branch)
+        let arms: Vec<(String, &String, bool, &Expression)> = match_expression
+            .rules
+            .iter()
+            .filter_map(|(pattern, branch)| {
+                let (enum_name, variant) = matched_variant(pattern)?;
+                let payload = self.payloads.get(&(enum_name.clone(), variant.clone()))?;
+                Some((
+                    format!("{enum_name}::{variant}"),
+                    payload,
+                    constrains(branch),
+                    branch,
+                ))
+            })
+            .collect();
+
+        for (variant, payload, is_constrained, branch) in &arms {
+            if *is_constrained {
+                continue;
+            }
+            let parallel = arms
+                .iter()
+                .find(|(other, other_payload, other_constrained, _)| {
+                    *other_constrained && other != variant && other_payload == payload
+                });
+            if let Some((parallel_variant, ..)) = parallel {
+                let (span, file_id) = Noir::expression_location(branch);
+                self.arms.push(UnconstrainedArm {
+                    variant: variant.clone(),
+                    parallel_variant: parallel_variant.clone(),
+                    span,
+                    file_id,
+                });
+            }
+        }
+        true
+    }
+}
+
+/// Returns the `(enum, variant)` names matched by a pattern such as `State::Active(x)`.
+fn matched_variant(pattern: &Expression) -> Option<(String, String)> {
+    let path: &Path = match &pattern.kind {
+        ExpressionKind::Variable(path) => path,
+        ExpressionKind::Call(call) => match &call.func.kind {
+            ExpressionKind::Variable(path) => path,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    match path.segments.as_slice() {
+        [.., enum_name, variant] => Some((enum_name.ident.to_string(), variant.ident.to_string())),
+        _ => None,
+    }
+}
+
+/// Returns whether the expression contains a constraint.
+fn constrains(expression: &Expression) -> bool {
+    let mut finder = ConstraintFinder::default();
+    expression.accept(&mut finder);
+    finder.found
+}
+
+#[derive(Default)]
+struct ConstraintFinder {
+    found: bool,
+}
+
+impl Visitor for ConstraintFinder {
+    fn visit_constrain_statement(&mut self, _: &ConstrainExpression) -> bool {
+        self.found = true;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_unconstrained_parallel_variant_is_flagged() {
+        let lint = Box::new(InconsistentVariantHandling);
+        let source_code = r#"
+            enum Transfer {
+                Deposit(Field),
+                Withdraw(Field),
+                Close,
+            }
+
+            fn apply(transfer: Transfer, limit: Field) {
+                match transfer {
+                    Transfer::Deposit(amount) => { assert(amount != limit); },
+                    Transfer::Withdraw(amount) => { let _ = amount; },
+                    Transfer::Close => {},
+                }
+            }
+
+            fn main() {}
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Variant 'Transfer::Withdraw' is handled without constraints, unlike 'Transfer::Deposit' which carries the same payload"
+        );
+    }
+}
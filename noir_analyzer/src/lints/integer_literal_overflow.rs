@@ -0,0 +1,203 @@
+//! # Integer Literal Overflow Lint
+//!
+//! Flags integer literals that do not fit in the sized integer type they are assigned or
+//! cast to, such as `let x: u8 = 300` or `70000 as u16`. The compiler only rejects these
+//! during type checking, after every other error of the program; reporting them early also
+//! shows the overflowed value next to the type.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ast::{
+    CastExpression, Expression, ExpressionKind, LetStatement, Literal, UnaryOp, UnresolvedType,
+    UnresolvedTypeData, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::shared::Signedness;
+
+/// Detects integer literals exceeding the range of their annotated type.
+#[derive(Clone, Default)]
+pub struct IntegerLiteralOverflow;
+
+declare_lint! {
+    IntegerLiteralOverflow {
+        name: "integer-literal-overflow",
+        explanation: "Reports integer literals assigned or cast to a sized integer type whose range does not contain them, such as `let x: u8 = 300`. Such programs are rejected by the compiler.",
+    }
+}
+
+impl IntegerLiteralOverflow {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut collector = OverflowCollector::default();
+        for function in context.function_definitions.values() {
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+        }
+
+        let mut lints: Vec<Lint> = collector
+            .overflows
+            .into_iter()
+            .map(|overflow| {
+                let message = Message::new("integer-literal-overflow")
+                    .with("value", &overflow.value)
+                    .with("type", &overflow.type_name);
+                Lint {
+                    name: Self::NAME,
+                    severity: Severity::Error,
+                    confidence: Confidence::High,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(overflow.span),
+                    file_id: Some(overflow.file_id),
+                    suggestion: None,
+                }
+            })
+            .collect();
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// An integer literal outside the range of its type.
+struct Overflow {
+    value: String,
+    type_name: String,
+    span: Span,
+    file_id: FileId,
+}
+
+/// Collects the overflowing literals of `let` statements and casts.
+#[derive(Default)]
+struct OverflowCollector {
+    overflows: Vec<Overflow>,
+}
+
+impl OverflowCollector {
+    fn check(&mut self, r#type: &UnresolvedType, expression: &Expression) {
+        let UnresolvedTypeData::Integer(signedness, bit_size) = &r#type.typ else {
+            return;
+        };
+        let Some((is_negative, value)) = integer_literal(expression) else {
+            return;
+        };
+        if fits(
+            is_negative,
+            value,
+            *signedness,
+            u32::from(bit_size.bit_size()),
+        ) {
+            return;
+        }
+
+        let (span, file_id) = Noir::expression_location(expression);
+        let sign = if is_negative { "-" } else { "" };
+        self.overflows.push(Overflow {
+            value: value.map_or_else(|| "value".to_string(), |value| format!("{sign}{value}")),
+            type_name: r#type.to_string(),
+            span,
+            file_id,
+        });
+    }
+}
+
+impl Visitor for OverflowCollector {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        self.check(&let_statement.r#type, &let_statement.expression);
+        true
+    }
+
+    fn visit_cast_expression(&mut self, cast: &CastExpression, _: Span) -> bool {
+        self.check(&cast.r#type, &cast.lhs);
+        true
+    }
+}
+
+/// Returns the sign and absolute value of an integer literal, optionally negated. The value
+/// is `None` when it does not fit in a `u128`.
+fn integer_literal(expression: &Expression) -> Option<(bool, Option<u128>)> {
+    match &expression.kind {
+        ExpressionKind::Literal(Literal::Integer(value)) => {
+            Some((value.is_negative(), value.absolute_value().try_into_u128()))
+        }
+        ExpressionKind::Prefix(prefix) if matches!(prefix.operator, UnaryOp::Minus) => {
+            let (is_negative, value) = integer_literal(&prefix.rhs)?;
+            Some((!is_negative, value))
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the value fits in an integer type of the given signedness and bit size.
+fn fits(is_negative: bool, value: Option<u128>, signedness: Signedness, bit_size: u32) -> bool {
+    let Some(value) = value else {
+        return false;
+    };
+
+    match signedness {
+        Signedness::Unsigned => !is_negative && value <= u128::MAX >> (128 - bit_size),
+        Signedness::Signed => {
+            // The range of signed integers is -2^(n-1)..=2^(n-1)-1
+            let half = 1u128 << (bit_size - 1);
+            if is_negative {
+                value <= half
+            } else {
+                value < half
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_overflowing_literals_are_flagged() {
+        let lint = Box::new(IntegerLiteralOverflow);
+        let source_code = r#"
+            pub fn main() {
+                let a: u8 = 300;
+                let b: u8 = 255;
+                let c = 70000 as u16;
+                let d: i8 = -128;
+                let e: i8 = 128;
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "Integer literal 300 does not fit in u8",
+                "Integer literal 70000 does not fit in u16",
+                "Integer literal 128 does not fit in i8",
+            ]
+        );
+        assert_eq!(result[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_fits_checks_integer_ranges() {
+        assert!(fits(false, Some(255), Signedness::Unsigned, 8));
+        assert!(!fits(false, Some(256), Signedness::Unsigned, 8));
+        assert!(!fits(true, Some(1), Signedness::Unsigned, 8));
+        assert!(fits(false, Some(u128::MAX), Signedness::Unsigned, 128));
+        assert!(fits(true, Some(128), Signedness::Signed, 8));
+        assert!(!fits(true, Some(129), Signedness::Signed, 8));
+        assert!(!fits(false, None, Signedness::Unsigned, 128));
+    }
+}
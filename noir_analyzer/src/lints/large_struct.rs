@@ -0,0 +1,214 @@
+//! # Large Struct Lint
+//!
+//! Flags structs whose flattened representation spans many field elements. Every field,
+//! array element and nested struct field is a separate witness, so passing such structs
+//! around, returning them or selecting between them multiplies copy constraints.
+//!
+//! Sizes are computed from the written field types. Structs with a field of unknown size,
+//! such as a generic parameter or a type from a dependency, are not reported.
+//!
+//! The threshold defaults to 64 field elements and can be set in `noir-analyzer.toml`:
+//!
+//! ```toml
+//! [lints.large-struct]
+//! max-field-elements = 128
+//! ```
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{NoirStruct, UnresolvedType, UnresolvedTypeData};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Default maximum number of field elements a struct may flatten to.
+pub const DEFAULT_MAX_FIELD_ELEMENTS: u64 = 64;
+
+/// Detects structs flattening to more field elements than a threshold.
+#[derive(Clone, Default)]
+pub struct LargeStruct {
+    pub options: LargeStructOptions,
+}
+
+/// Options of [`LargeStruct`], set under `[lints.large-struct]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LargeStructOptions {
+    /// Maximum number of field elements a struct may flatten to.
+    /// Also set by the `[thresholds]` entry of the lint.
+    #[serde(alias = "threshold")]
+    pub max_field_elements: u64,
+}
+
+impl Default for LargeStructOptions {
+    fn default() -> Self {
+        Self {
+            max_field_elements: DEFAULT_MAX_FIELD_ELEMENTS,
+        }
+    }
+}
+
+impl LargeStruct {
+    /// Creates the lint with a custom threshold.
+    pub fn with_threshold(max_field_elements: u64) -> Self {
+        Self {
+            options: LargeStructOptions { max_field_elements },
+        }
+    }
+}
+
+declare_lint! {
+    LargeStruct {
+        name: "large-struct",
+        explanation: "Reports structs whose fields, flattened recursively, span more field elements than a threshold. Each of them is a separate witness, so passing, returning or selecting such structs multiplies copy constraints; consider passing references to smaller parts or hashing large arrays.",
+        requires_whole_program: true,
+        options: LargeStructOptions,
+    }
+}
+
+impl LargeStruct {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut sizes = StructSizes::default();
+        sizes.collect(context.parsed_module);
+
+        let mut lints = vec![];
+        for (name, (noir_struct, span, file_id)) in &sizes.structs {
+            let Some(field_sizes) = sizes.field_sizes(noir_struct) else {
+                continue;
+            };
+            let total: u64 = field_sizes.iter().map(|(_, size)| size).sum();
+            if total <= self.options.max_field_elements {
+                continue;
+            }
+
+            let breakdown: Vec<String> = field_sizes
+                .iter()
+                .map(|(field, size)| format!("{field}: {size}"))
+                .collect();
+            let message = Message::new("large-struct")
+                .with("struct", name)
+                .with("total", total)
+                .with("limit", self.options.max_field_elements)
+                .with("breakdown", breakdown.join(", "));
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::High,
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(*span),
+                file_id: Some(*file_id),
+                suggestion: None,
+            });
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// The structs of the program, sized on demand.
+#[derive(Default)]
+struct StructSizes<'ast> {
+    structs: BTreeMap<String, (&'ast NoirStruct, Span, FileId)>,
+}
+
+impl<'ast> StructSizes<'ast> {
+    fn collect(&mut self, parsed_module: &'ast ParsedModule) {
+        for item in &parsed_module.items {
+            match &item.kind {
+                ItemKind::Submodules(submodule) => self.collect(&submodule.contents),
+                ItemKind::Struct(noir_struct) if noir_struct.generics.is_empty() => {
+                    let (span, file_id) = Noir::item_location(item);
+                    self.structs
+                        .insert(noir_struct.name.to_string(), (noir_struct, span, file_id));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the number of field elements of each field, or `None` when one is unknown.
+    fn field_sizes(&self, noir_struct: &NoirStruct) -> Option<Vec<(String, u64)>> {
+        noir_struct
+            .fields
+            .iter()
+            .map(|field| {
+                let size =
+                    self.type_size(&field.item.typ, &mut vec![noir_struct.name.to_string()])?;
+                Some((field.item.name.to_string(), size))
+            })
+            .collect()
+    }
+
+    /// Returns the number of field elements of a type. `enclosing` lists the structs being
+    /// sized, so that recursive structs are treated as unknown.
+    fn type_size(&self, r#type: &UnresolvedType, enclosing: &mut Vec<String>) -> Option<u64> {
+        match &r#type.typ {
+            UnresolvedTypeData::FieldElement
+            | UnresolvedTypeData::Integer(..)
+            | UnresolvedTypeData::Bool => Some(1),
+            UnresolvedTypeData::Array(length, element) => {
+                let length: u64 = length.to_string().parse().ok()?;
+                length.checked_mul(self.type_size(element, enclosing)?)
+            }
+            UnresolvedTypeData::String(length) => length.to_string().parse().ok(),
+            UnresolvedTypeData::Tuple(elements) => elements
+                .iter()
+                .map(|element| self.type_size(element, enclosing))
+                .sum(),
+            UnresolvedTypeData::Named(path, ..) => {
+                let name = path.segments.last()?.ident.to_string();
+                if enclosing.contains(&name) {
+                    return None;
+                }
+                let (noir_struct, ..) = self.structs.get(&name)?;
+
+                enclosing.push(name);
+                let size = noir_struct
+                    .fields
+                    .iter()
+                    .map(|field| self.type_size(&field.item.typ, enclosing))
+                    .sum();
+                enclosing.pop();
+                size
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_struct_above_threshold_is_flagged() {
+        let lint = Box::new(LargeStruct::with_threshold(16));
+        let source_code = r#"
+            struct Point { x: Field, y: Field }
+            struct Polygon { vertices: [Point; 8], label: str<4>, closed: bool }
+            struct Generic<T> { values: [T; 100] }
+
+            fn main() {}
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Struct 'Polygon' flattens to 21 field elements (limit 16); per field: vertices: 16, label: 4, closed: 1"
+        );
+    }
+}
@@ -1,15 +1,228 @@
 //! # Lint Rule Trait
 //!
-//! Defines a generic interface for lints in the analyzer.
+//! Defines a generic interface for lints in the analyzer, and the `declare_lint!` macro
+//! implementing it from the metadata of a rule.
+//!
+//! Rules taking options, such as thresholds or allow-lists, keep them in a typed struct
+//! deserialized from the `[lints.<name>]` table of `noir-analyzer.toml`:
+//!
+//! ```toml
+//! [lints.call-chain-depth]
+//! level = "deny"
+//! max-depth = 12
+//! ```
 
+use crate::analysis::passes::Analysis;
 use crate::ast::ast_context::AstContext;
 use crate::diagnostics::lint::Lint;
+use crate::hir::TypedContext;
+use noirc_frontend::ParsedModule;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+/// Options given to a rule that cannot take them.
+#[derive(Debug, Error)]
+pub enum RuleConfigError {
+    #[error("Lint '{0}' takes no options")]
+    NoOptions(&'static str),
+    #[error("Invalid options of lint '{0}': {1}")]
+    InvalidOptions(&'static str, String),
+}
+
+/// Deserializes the options of a rule from its configuration table.
+pub fn deserialize_options<T: DeserializeOwned>(
+    name: &'static str,
+    options: toml::Value,
+) -> Result<T, RuleConfigError> {
+    options.try_into().map_err(|e: toml::de::Error| {
+        RuleConfigError::InvalidOptions(name, e.message().to_string())
+    })
+}
 
-pub trait LintRule {
+/// A lint run on the context of a single parsed module.
+///
+/// Rules only read the context, so the analyzer may run several rules concurrently.
+pub trait LintRule: Send + Sync {
     /// Returns the unique name of the lint.
     fn name(&self) -> &'static str;
 
+    /// Returns what the lint detects and why it matters, for `explain` requests.
+    fn explanation(&self) -> &'static str;
+
     fn boxed_clone(&self) -> Box<dyn LintRule>;
 
+    /// Returns whether the rule needs the whole program, rather than one function at a
+    /// time. Such rules are skipped by the low-memory analysis mode.
+    fn requires_whole_program(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the rule runs without being configured. Opt-in rules, typically
+    /// noisy heuristics, only run when enabled with a `warn` or `deny` level.
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the rule only applies to library packages, whose public functions are
+    /// called by other packages. The command line only runs such rules on `lib` packages.
+    fn library_only(&self) -> bool {
+        false
+    }
+
+    /// Returns the whole-program analyses the rule reads from the context. Only the analyses
+    /// required by the rules of a run are computed, the others being left empty.
+    fn required_analyses(&self) -> &'static [Analysis] {
+        &[]
+    }
+
+    /// Sets the options of the rule from its configuration table, with the `level` key
+    /// removed. Rules without options only accept an empty table.
+    fn configure(&mut self, options: toml::Value) -> Result<(), RuleConfigError> {
+        match options {
+            toml::Value::Table(table) if table.is_empty() => Ok(()),
+            _ => Err(RuleConfigError::NoOptions(self.name())),
+        }
+    }
+
     fn lint(&self, context: &AstContext) -> Vec<Lint>;
 }
+
+/// Implements [`LintRule`] from a single declaration of the metadata of a rule, with `lint`
+/// delegating to the inherent `check` method of the rule:
+///
+/// ```ignore
+/// declare_lint! {
+///     UnusedFunction {
+///         name: "unused-function",
+///         explanation: "Reports functions that are defined but never called.",
+///         requires_whole_program: true,
+///         analyses: [CallGraph],
+///     }
+/// }
+///
+/// impl UnusedFunction {
+///     fn check(&self, context: &AstContext) -> Vec<Lint> {
+///         ...
+///     }
+/// }
+/// ```
+///
+/// `requires_whole_program` and `library_only` default to `false` and `enabled_by_default` to
+/// `true`. Rules reading analyses of the context list them by name, as in
+/// `analyses: [CallGraph]`. The rule must implement `Clone`. Its name is also available as the
+/// `NAME` constant, so that registries and configuration refer to the rule without repeating
+/// it.
+///
+/// Rules taking options declare their type with `options: CallChainDepthOptions`, and keep
+/// them in an `options` field, which `configure` replaces. Options types deserialize from a
+/// partial table, so they should use `#[serde(default, deny_unknown_fields)]`.
+#[macro_export]
+macro_rules! declare_lint {
+    (
+        $rule:ident {
+            name: $name:literal,
+            explanation: $explanation:literal
+            $(, requires_whole_program: $requires_whole_program:literal)?
+            $(, enabled_by_default: $enabled_by_default:literal)?
+            $(, library_only: $library_only:literal)?
+            $(, analyses: [$($analysis:ident),* $(,)?])?
+            $(, options: $options:ty)?
+            $(,)?
+        }
+    ) => {
+        impl $rule {
+            /// Unique name of the lint.
+            pub const NAME: &'static str = $name;
+        }
+
+        impl $crate::lints::lint_rule::LintRule for $rule {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn explanation(&self) -> &'static str {
+                $explanation
+            }
+
+            fn boxed_clone(&self) -> Box<dyn $crate::lints::lint_rule::LintRule> {
+                Box::new(self.clone())
+            }
+
+            $(
+                fn requires_whole_program(&self) -> bool {
+                    $requires_whole_program
+                }
+            )?
+
+            $(
+                fn enabled_by_default(&self) -> bool {
+                    $enabled_by_default
+                }
+            )?
+
+            $(
+                fn library_only(&self) -> bool {
+                    $library_only
+                }
+            )?
+
+            $(
+                fn required_analyses(&self) -> &'static [$crate::analysis::passes::Analysis] {
+                    &[$($crate::analysis::passes::Analysis::$analysis),*]
+                }
+            )?
+
+            $(
+                fn configure(
+                    &mut self,
+                    options: $crate::toml::Value,
+                ) -> Result<(), $crate::lints::lint_rule::RuleConfigError> {
+                    self.options = $crate::lints::lint_rule::deserialize_options::<$options>(
+                        $name, options,
+                    )?;
+                    Ok(())
+                }
+            )?
+
+            fn lint(
+                &self,
+                context: &$crate::ast::ast_context::AstContext,
+            ) -> Vec<$crate::diagnostics::lint::Lint> {
+                self.check(context)
+            }
+        }
+    };
+}
+
+/// A lint run on the typed HIR of a program, in a second phase after the rules on its AST.
+///
+/// The HIR cannot be shared between threads, so these rules run one after the other.
+pub trait TypedLintRule {
+    /// Returns the unique name of the lint.
+    fn name(&self) -> &'static str;
+
+    /// Returns what the lint detects and why it matters, for `explain` requests.
+    fn explanation(&self) -> &'static str;
+
+    fn lint(&self, context: &AstContext, types: &TypedContext) -> Vec<Lint>;
+}
+
+/// A package taking part in a workspace-wide lint.
+pub struct WorkspacePackage<'ast> {
+    /// Name of the package, as declared in its manifest.
+    pub name: String,
+    /// The parsed entry point of the package.
+    pub parsed_module: &'ast ParsedModule,
+}
+
+/// A lint comparing several packages of the same workspace.
+pub trait WorkspaceLintRule {
+    /// Returns the unique name of the lint.
+    fn name(&self) -> &'static str;
+
+    /// Returns what the lint detects and why it matters, for `explain` requests.
+    fn explanation(&self) -> &'static str;
+
+    /// Returns the detected lints, each paired with the index of the package it occurs in.
+    fn lint(&self, packages: &[WorkspacePackage]) -> Vec<(usize, Lint)>;
+}
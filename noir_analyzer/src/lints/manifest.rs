@@ -0,0 +1,195 @@
+//! # Manifest Lints
+//!
+//! Checks the hygiene of the `[package]` section of `Nargo.toml`: a missing or wildcard
+//! `compiler_version`, a missing `version`, an `entry` pointing at a file that does not
+//! exist, and keys nargo silently ignores, usually misspelled ones.
+//!
+//! These lints run on the manifest rather than on the parsed program, and are reported with
+//! spans into `Nargo.toml`. Each check is a lint of its own, so that they can be configured
+//! separately; together they form the `manifest-*` group.
+
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use noirc_frontend::hir::resolution::errors::Span;
+use serde::Deserialize;
+use serde::de::IgnoredAny;
+use std::collections::BTreeMap;
+use std::path::Path;
+use toml::{Spanned, Value};
+
+/// Names of the manifest lints.
+pub const LINT_NAMES: &[&str] = &[
+    "manifest-missing-compiler-version",
+    "manifest-missing-entry",
+    "manifest-missing-version",
+    "manifest-unknown-key",
+    "manifest-wildcard-compiler-version",
+];
+
+/// Top-level keys of `Nargo.toml`.
+const TOP_LEVEL_KEYS: &[&str] = &["dependencies", "package", "workspace"];
+
+/// Keys of the `[package]` section of `Nargo.toml`.
+const PACKAGE_KEYS: &[&str] = &[
+    "authors",
+    "backend",
+    "compiler_version",
+    "description",
+    "entry",
+    "expression_width",
+    "keywords",
+    "license",
+    "name",
+    "type",
+    "version",
+];
+
+type SpannedTable = BTreeMap<Spanned<String>, Spanned<Value>>;
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Option<Spanned<SpannedTable>>,
+}
+
+/// Checks the manifest of the package in `package_dir`, given its source. Manifests that
+/// are not valid TOML are not checked, as nargo reports them itself.
+pub fn lint_manifest(source: &str, package_dir: &Path) -> Vec<Lint> {
+    let (Ok(keys), Ok(manifest)) = (
+        toml::from_str::<BTreeMap<Spanned<String>, IgnoredAny>>(source),
+        toml::from_str::<Manifest>(source),
+    ) else {
+        return vec![];
+    };
+
+    let mut lints = vec![];
+    for key in keys.keys() {
+        if !TOP_LEVEL_KEYS.contains(&key.get_ref().as_str()) {
+            lints.push(unknown_key(key, "the manifest"));
+        }
+    }
+
+    let Some(package) = manifest.package else {
+        return lints;
+    };
+    let package_span = span(package.span());
+    let package = package.into_inner();
+    let get = |name: &str| {
+        package
+            .iter()
+            .find(|(key, _)| key.get_ref() == name)
+            .map(|(_, value)| value)
+    };
+
+    match get("compiler_version") {
+        None => lints.push(lint(
+            "manifest-missing-compiler-version",
+            Severity::Warning,
+            Message::new("manifest-missing-compiler-version"),
+            package_span,
+        )),
+        Some(value) => {
+            if let Some(requirement) = value.get_ref().as_str()
+                && requirement.contains('*')
+            {
+                lints.push(lint(
+                    "manifest-wildcard-compiler-version",
+                    Severity::Warning,
+                    Message::new("manifest-wildcard-compiler-version")
+                        .with("requirement", requirement),
+                    span(value.span()),
+                ));
+            }
+        }
+    }
+
+    if get("version").is_none() {
+        lints.push(lint(
+            "manifest-missing-version",
+            Severity::Warning,
+            Message::new("manifest-missing-version"),
+            package_span,
+        ));
+    }
+
+    if let Some(entry) = get("entry")
+        && let Some(path) = entry.get_ref().as_str()
+        && !package_dir.join(path).is_file()
+    {
+        lints.push(lint(
+            "manifest-missing-entry",
+            Severity::Error,
+            Message::new("manifest-missing-entry").with("entry", path),
+            span(entry.span()),
+        ));
+    }
+
+    for key in package.keys() {
+        if !PACKAGE_KEYS.contains(&key.get_ref().as_str()) {
+            lints.push(unknown_key(key, "[package]"));
+        }
+    }
+
+    lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+    lints
+}
+
+fn unknown_key(key: &Spanned<String>, section: &str) -> Lint {
+    lint(
+        "manifest-unknown-key",
+        Severity::Warning,
+        Message::new("manifest-unknown-key")
+            .with("key", key.get_ref())
+            .with("section", section),
+        span(key.span()),
+    )
+}
+
+fn lint(name: &'static str, severity: Severity, message: Message, span: Span) -> Lint {
+    Lint {
+        name,
+        severity,
+        confidence: Confidence::High,
+        description: message.to_string(),
+        message: Some(message),
+        span: Some(span),
+        file_id: None,
+        suggestion: None,
+    }
+}
+
+fn span(range: std::ops::Range<usize>) -> Span {
+    Span::from(range.start as u32..range.end as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_issues_are_flagged_with_spans() {
+        let source = r#"[package]
+name = "circuit"
+type = "bin"
+entry = "src/missing.nr"
+compiler_version = ">=0.*"
+autor = "me"
+
+[dependencies]
+"#;
+
+        let lints = lint_manifest(source, Path::new("does-not-exist"));
+
+        let names: Vec<&str> = lints.iter().map(|lint| lint.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "manifest-missing-version",
+                "manifest-missing-entry",
+                "manifest-wildcard-compiler-version",
+                "manifest-unknown-key",
+            ]
+        );
+        let span = lints[3].span.unwrap();
+        assert_eq!(&source[span.start() as usize..span.end() as usize], "autor");
+    }
+}
@@ -0,0 +1,273 @@
+//! # Expression Matchers
+//!
+//! Composable predicates on expressions for writing lint rules without a hand-written
+//! visitor. A rule describes the expressions it reports, e.g.
+//!
+//! ```ignore
+//! let matcher = call_to("std::hash::pedersen_hash").and(inside_unsafe());
+//! let comparison = binary_op(BinaryOpKind::Equal, any(), literal());
+//! ```
+//!
+//! and [`find_matches`] returns the location of every matching expression of a function
+//! body, in source order. [`Match::lint`] turns a location into a lint, so that rules report
+//! spans consistently.
+//!
+//! New rules can start from the template in `templates/lint-rule`.
+
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ast::{BinaryOpKind, Expression, ExpressionKind, FunctionDefinition, Visitor};
+use noirc_frontend::hir::resolution::errors::Span;
+
+/// Where the matched expression is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Scope {
+    /// Whether the expression is inside an `unsafe { }` block.
+    pub in_unsafe: bool,
+}
+
+type Predicate = dyn Fn(&Expression, Scope) -> bool + Send + Sync;
+
+/// A predicate on an expression and its scope.
+pub struct Matcher(Box<Predicate>);
+
+impl Matcher {
+    /// Creates a matcher from a predicate, for conditions the provided matchers do not
+    /// cover.
+    pub fn new(predicate: impl Fn(&Expression, Scope) -> bool + Send + Sync + 'static) -> Self {
+        Self(Box::new(predicate))
+    }
+
+    /// Returns whether the expression matches.
+    pub fn matches(&self, expression: &Expression, scope: Scope) -> bool {
+        (self.0)(expression, scope)
+    }
+
+    /// Matches expressions matched by both matchers.
+    pub fn and(self, other: Matcher) -> Matcher {
+        Matcher::new(move |expression, scope| {
+            self.matches(expression, scope) && other.matches(expression, scope)
+        })
+    }
+
+    /// Matches expressions matched by either matcher.
+    pub fn or(self, other: Matcher) -> Matcher {
+        Matcher::new(move |expression, scope| {
+            self.matches(expression, scope) || other.matches(expression, scope)
+        })
+    }
+}
+
+/// Matches every expression.
+pub fn any() -> Matcher {
+    Matcher::new(|_, _| true)
+}
+
+/// Matches the expressions the given matcher does not match.
+pub fn not(matcher: Matcher) -> Matcher {
+    Matcher::new(move |expression, scope| !matcher.matches(expression, scope))
+}
+
+/// Matches literals, such as `1`, `true` or `"text"`.
+pub fn literal() -> Matcher {
+    Matcher::new(|expression, _| matches!(expression.kind, ExpressionKind::Literal(_)))
+}
+
+/// Matches a reference to the local variable of the given name.
+pub fn variable(name: &'static str) -> Matcher {
+    Matcher::new(move |expression, _| match &expression.kind {
+        ExpressionKind::Variable(path) => match path.segments.as_slice() {
+            [segment] => segment.ident.to_string() == name,
+            _ => false,
+        },
+        _ => false,
+    })
+}
+
+/// Matches calls to the function of the given path, e.g. `std::hash::pedersen_hash`. Calls
+/// through a shorter path, such as `hash::pedersen_hash` or `pedersen_hash` after an
+/// import, match as well.
+pub fn call_to(path: &'static str) -> Matcher {
+    let expected: Vec<&str> = path.split("::").collect();
+    Matcher::new(move |expression, _| {
+        let ExpressionKind::Call(call) = &expression.kind else {
+            return false;
+        };
+        let ExpressionKind::Variable(called) = &call.func.kind else {
+            return false;
+        };
+        let written = &called.segments;
+        !written.is_empty()
+            && written.len() <= expected.len()
+            && expected[expected.len() - written.len()..]
+                .iter()
+                .zip(written)
+                .all(|(expected, segment)| segment.ident.to_string() == *expected)
+    })
+}
+
+/// Matches calls to the method of the given name, on any receiver.
+pub fn method_call(name: &'static str) -> Matcher {
+    Matcher::new(move |expression, _| match &expression.kind {
+        ExpressionKind::MethodCall(method_call) => method_call.method_name.to_string() == name,
+        _ => false,
+    })
+}
+
+/// Matches binary operations with the given operator, whose operands match `lhs` and `rhs`.
+/// Parentheses around the operands are ignored.
+pub fn binary_op(operator: BinaryOpKind, lhs: Matcher, rhs: Matcher) -> Matcher {
+    Matcher::new(move |expression, scope| match &expression.kind {
+        ExpressionKind::Infix(infix) => {
+            infix.operator.contents == operator
+                && lhs.matches(without_parentheses(&infix.lhs), scope)
+                && rhs.matches(without_parentheses(&infix.rhs), scope)
+        }
+        _ => false,
+    })
+}
+
+/// Matches expressions inside an `unsafe { }` block.
+pub fn inside_unsafe() -> Matcher {
+    Matcher::new(|_, scope| scope.in_unsafe)
+}
+
+fn without_parentheses(expression: &Expression) -> &Expression {
+    match &expression.kind {
+        ExpressionKind::Parenthesized(inner) => without_parentheses(inner),
+        _ => expression,
+    }
+}
+
+/// Location of a matched expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Match {
+    pub span: Span,
+    pub file_id: FileId,
+}
+
+impl Match {
+    /// Creates the lint reporting the matched expression.
+    pub fn lint(
+        &self,
+        name: &'static str,
+        severity: Severity,
+        confidence: Confidence,
+        message: Message,
+    ) -> Lint {
+        Lint {
+            name,
+            severity,
+            confidence,
+            description: message.to_string(),
+            message: Some(message),
+            span: Some(self.span),
+            file_id: Some(self.file_id),
+            suggestion: None,
+        }
+    }
+}
+
+/// Returns the location of every expression of the function body matching the matcher, in
+/// source order.
+pub fn find_matches(function: &FunctionDefinition, matcher: &Matcher) -> Vec<Match> {
+    let mut collector = MatchCollector {
+        matcher,
+        unsafe_depth: 0,
+        matches: vec![],
+    };
+    for statement in &function.body.statements {
+        statement.accept(&mut collector);
+    }
+    collector.matches
+}
+
+struct MatchCollector<'m> {
+    matcher: &'m Matcher,
+    /// Number of `unsafe { }` blocks around the visited expression.
+    unsafe_depth: usize,
+    matches: Vec<Match>,
+}
+
+impl Visitor for MatchCollector<'_> {
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        let scope = Scope {
+            in_unsafe: self.unsafe_depth > 0,
+        };
+        if self.matcher.matches(expression, scope) {
+            let (span, file_id) = Noir::expression_location(expression);
+            self.matches.push(Match { span, file_id });
+        }
+
+        if let Some(block) = Noir::unsafe_block(expression) {
+            self.unsafe_depth += 1;
+            for statement in &block.statements {
+                statement.accept(self);
+            }
+            self.unsafe_depth -= 1;
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+    use noirc_frontend::parser::ItemKind;
+
+    fn matched_text(source: &str, matcher: &Matcher) -> Vec<String> {
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let ItemKind::Function(function) = &root.items[0].kind else {
+            panic!("Expected a function");
+        };
+        find_matches(&function.def, matcher)
+            .into_iter()
+            .map(|found| source[found.span.start() as usize..found.span.end() as usize].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_matchers_compose() {
+        let source = r#"
+            fn main(x: Field) {
+                let a = std::hash::pedersen_hash([x]);
+                let b = hash::pedersen_hash([x]);
+                let c = other::pedersen_hash([x]);
+                assert((x) == 1);
+                assert(a != b);
+                // Safety: only a hint
+                let d = unsafe { hint(x) };
+                let e = hint(c);
+                assert(d == e);
+            }
+            "#;
+
+        assert_eq!(
+            matched_text(source, &call_to("std::hash::pedersen_hash")),
+            ["std::hash::pedersen_hash([x])", "hash::pedersen_hash([x])"]
+        );
+        assert_eq!(
+            matched_text(
+                source,
+                &binary_op(BinaryOpKind::Equal, variable("x"), literal())
+            ),
+            ["(x) == 1"]
+        );
+        assert_eq!(
+            matched_text(source, &call_to("hint").and(inside_unsafe())),
+            ["hint(x)"]
+        );
+        assert_eq!(
+            matched_text(source, &call_to("hint").and(not(inside_unsafe()))),
+            ["hint(c)"]
+        );
+        assert_eq!(
+            matched_text(source, &variable("d").or(variable("e"))),
+            ["d", "e"]
+        );
+    }
+}
@@ -1,2 +1,47 @@
+pub mod bool_field_cast;
+pub mod call_chain_depth;
+pub mod conditional_assignment;
+pub mod config_file;
+pub mod contract_function_visibility;
+pub mod cyclic_module_imports;
+pub mod deep_constraint_expression;
+pub mod dependency_hygiene;
+pub mod duplicate_public_function;
+pub mod empty_block;
+pub mod field_accumulation_overflow;
+pub mod field_modulo;
+pub mod first_element_comparison;
+pub mod function_naming;
+pub mod god_module;
+pub mod hint_drift;
+pub mod import_order;
+pub mod inconsistent_variant_handling;
+pub mod integer_literal_overflow;
+pub mod large_struct;
 pub mod lint_rule;
+pub mod manifest;
+pub mod matchers;
+pub mod needless_visibility;
+pub mod oversized_entry_point;
+pub mod oversized_unsafe_block;
+pub mod private_assert_message;
+pub mod private_data_leak;
+pub mod prover_input_mismatch;
+pub mod public_only_constraint;
+pub mod redundant_equality;
+pub mod redundant_import;
+pub mod registry;
+pub mod shadowed_trait_method;
+pub mod single_instantiation_generic;
+pub mod stdlib_shadowing;
+pub mod struct_field_order;
+pub mod trailing_semicolon;
+pub mod truncating_cast;
+pub mod unchecked_public_function;
+pub mod unchecked_slice_conversion;
+pub mod unconstrained_parameter;
+pub mod unconstrained_struct_field;
+pub mod undocumented_numeric_generic;
+pub mod unsupported_syntax;
 pub mod unused_function;
+pub mod unused_import;
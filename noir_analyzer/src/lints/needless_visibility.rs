@@ -0,0 +1,157 @@
+//! # Needless Visibility Lint
+//!
+//! Flags `pub` and `pub(crate)` functions of binary packages. Nothing outside a binary can
+//! use its items, so these modifiers only exempt the functions from `unused-function`.
+//!
+//! Functions of the crate root are visible to every module of the crate even when private,
+//! so the modifier is removed. Functions of other modules may be used by sibling modules, so
+//! `pub` is narrowed to `pub(crate)`.
+//!
+//! The lint only applies to binary packages; the CLI enables it based on `Nargo.toml`.
+
+use crate::ast::ast_context::AstContext;
+use crate::ast::trivia;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::ItemVisibility;
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+
+/// Detects visibility modifiers that have no effect in binary packages.
+#[derive(Clone, Default)]
+pub struct NeedlessVisibility;
+
+declare_lint! {
+    NeedlessVisibility {
+        name: "needless-visibility",
+        explanation: "Reports `pub` and `pub(crate)` functions in binary packages. No other package can use them, so the modifiers only hide the functions from unused-function; make them private, or `pub(crate)` outside the crate root.",
+        requires_whole_program: true,
+    }
+}
+
+impl NeedlessVisibility {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        self.lint_module(context, context.parsed_module, true, &mut lints);
+        lints
+    }
+
+    fn lint_module(
+        &self,
+        context: &AstContext,
+        parsed_module: &ParsedModule,
+        is_root: bool,
+        lints: &mut Vec<Lint>,
+    ) {
+        for item in &parsed_module.items {
+            match &item.kind {
+                ItemKind::Submodules(submodule) => {
+                    self.lint_module(context, &submodule.contents, false, lints);
+                }
+                ItemKind::Function(function) => {
+                    let (visibility, replacement) = match function.def.visibility {
+                        ItemVisibility::Public if is_root => ("pub", ""),
+                        ItemVisibility::PublicCrate if is_root => ("pub(crate)", ""),
+                        ItemVisibility::Public => ("pub", "pub(crate) "),
+                        _ => continue,
+                    };
+                    if function.name() == "main" {
+                        continue;
+                    }
+
+                    let (span, file_id) = Noir::item_location(item);
+                    let suggestion = context
+                        .source
+                        .and_then(|source| visibility_span(source, span))
+                        .map(|span| Suggestion {
+                            message: if replacement.is_empty() {
+                                "make the function private".to_string()
+                            } else {
+                                "restrict the function to the crate".to_string()
+                            },
+                            span,
+                            replacement: replacement.to_string(),
+                            applicability: Applicability::MachineApplicable,
+                            other_edits: vec![],
+                        });
+
+                    let message = Message::new("needless-visibility")
+                        .with("function", function.name())
+                        .with("visibility", visibility);
+                    lints.push(Lint {
+                        name: Self::NAME,
+                        severity: Severity::Warning,
+                        confidence: Confidence::High,
+                        description: message.to_string(),
+                        message: Some(message),
+                        span: Some(span),
+                        file_id: Some(file_id),
+                        suggestion,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Returns the span of the visibility modifier of a function item, including the whitespace
+/// following it.
+fn visibility_span(source: &str, item_span: Span) -> Option<Span> {
+    let start = item_span.start() as usize;
+    let header = source.get(start..item_span.end() as usize)?;
+    let modifier = start + header[..header.find("fn ")?].find("pub")?;
+
+    let mut end = modifier + "pub".len();
+    if let Some(parenthesis) =
+        trivia::next_token(source, end).filter(|offset| source[*offset..].starts_with('('))
+    {
+        end = parenthesis + source[parenthesis..].find(')')? + 1;
+    }
+    let end = trivia::next_token(source, end)?;
+
+    Some(Span::from(modifier as u32..end as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    #[test]
+    fn test_visible_functions_of_binaries_are_flagged() {
+        let lint = Box::new(NeedlessVisibility);
+        let source_code = r#"
+            mod utils {
+                pub fn double(x: Field) -> Field { x * 2 }
+                pub(crate) fn triple(x: Field) -> Field { x * 3 }
+            }
+
+            pub(crate) fn helper(x: Field) -> Field { x }
+
+            pub fn main(x: Field) {
+                assert(helper(x) == x);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source_code);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 2);
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(false).apply(source_code, suggestions).source;
+        assert!(fixed.contains("    pub(crate) fn double(x: Field)"));
+        assert!(fixed.contains("    pub(crate) fn triple(x: Field)"));
+        assert!(fixed.contains("\n            fn helper(x: Field)"));
+    }
+}
@@ -0,0 +1,222 @@
+//! # Oversized Entry Point Lint
+//!
+//! Flags `main` and contract functions whose bodies hold more statements than a limit,
+//! instead of delegating to named helper functions. An entry point reading as a sequence of
+//! well-named steps is easier to audit: each helper states which part of the circuit it
+//! constrains. The diagnostic outlines how the body could be split, by runs of consecutive
+//! statements and the variables they define.
+//!
+//! This lint is opt-in. The limit defaults to 15 statements, nested blocks included, and
+//! can be set as `max-statements` under `[lints.oversized-entry-point]` in
+//! `noir-analyzer.toml`.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{FunctionDefinition, Statement, StatementKind, Visitor};
+use noirc_frontend::parser::ItemKind;
+use noirc_frontend::token::FunctionAttribute;
+use serde::Deserialize;
+
+/// Default maximum number of statements of an entry point.
+pub const DEFAULT_MAX_STATEMENTS: usize = 15;
+
+/// Number of consecutive top-level statements proposed per helper in the outline.
+const STATEMENTS_PER_HELPER: usize = 5;
+
+/// Detects entry points doing the work themselves rather than delegating it.
+#[derive(Clone, Default)]
+pub struct OversizedEntryPoint {
+    options: OversizedEntryPointOptions,
+}
+
+/// Options of [`OversizedEntryPoint`], set under `[lints.oversized-entry-point]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct OversizedEntryPointOptions {
+    /// Maximum number of statements of an entry point, nested blocks included.
+    /// Also set by the `[thresholds]` entry of the lint.
+    #[serde(alias = "threshold")]
+    pub max_statements: usize,
+}
+
+impl Default for OversizedEntryPointOptions {
+    fn default() -> Self {
+        Self {
+            max_statements: DEFAULT_MAX_STATEMENTS,
+        }
+    }
+}
+
+impl OversizedEntryPoint {
+    /// Creates the lint with a custom limit.
+    pub fn with_max_statements(max_statements: usize) -> Self {
+        Self {
+            options: OversizedEntryPointOptions { max_statements },
+        }
+    }
+}
+
+declare_lint! {
+    OversizedEntryPoint {
+        name: "oversized-entry-point",
+        explanation: "Reports main and contract functions with more statements than a limit (15 by default, configurable as max-statements under [lints.oversized-entry-point] in noir-analyzer.toml), rather than delegating to named helper functions. Entry points reading as a sequence of named steps are easier to audit. Opt-in.",
+        requires_whole_program: true,
+        enabled_by_default: false,
+        options: OversizedEntryPointOptions,
+    }
+}
+
+impl OversizedEntryPoint {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut entry_functions = vec![];
+        collect_entry_functions(context.parsed_module, true, false, &mut entry_functions);
+
+        let mut lints = vec![];
+        for function in entry_functions {
+            let mut counter = StatementCounter::default();
+            for statement in &function.body.statements {
+                statement.accept(&mut counter);
+            }
+            if counter.statements <= self.options.max_statements {
+                continue;
+            }
+
+            let message = Message::new("oversized-entry-point")
+                .with("function", &function.name)
+                .with("statements", counter.statements)
+                .with("max", self.options.max_statements)
+                .with("outline", outline(&function.body.statements));
+            let (span, file_id) = Noir::function_location(function);
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::Low,
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(span),
+                file_id: Some(file_id),
+                suggestion: None,
+            });
+        }
+
+        lints
+    }
+}
+
+/// Collects `main` from the crate root and the functions of contracts, tests excluded.
+fn collect_entry_functions<'ast>(
+    parsed_module: &'ast ParsedModule,
+    is_root: bool,
+    in_contract: bool,
+    functions: &mut Vec<&'ast FunctionDefinition>,
+) {
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Submodules(submodule) => collect_entry_functions(
+                &submodule.contents,
+                false,
+                in_contract || submodule.is_contract,
+                functions,
+            ),
+            ItemKind::Function(function) => {
+                let is_test = matches!(
+                    Noir::function_attribute(&function.def),
+                    Some(FunctionAttribute::Test(..))
+                );
+                if !is_test && (in_contract || (is_root && function.name() == "main")) {
+                    functions.push(&function.def);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Proposes helpers for runs of consecutive top-level statements, with the variables each
+/// run defines, e.g. "statements 1-5 defining `a`, `b`; statements 6-8".
+fn outline(statements: &[Statement]) -> String {
+    statements
+        .chunks(STATEMENTS_PER_HELPER)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let first = index * STATEMENTS_PER_HELPER + 1;
+            let range = format!("statements {first}-{}", first + chunk.len() - 1);
+            let defined: Vec<String> = chunk
+                .iter()
+                .filter_map(|statement| match &statement.kind {
+                    StatementKind::Let(let_statement) => {
+                        Some(format!("`{}`", let_statement.pattern))
+                    }
+                    _ => None,
+                })
+                .collect();
+            if defined.is_empty() {
+                range
+            } else {
+                format!("{range} defining {}", defined.join(", "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Counts the statements of a body, including those of nested blocks.
+#[derive(Default)]
+struct StatementCounter {
+    statements: usize,
+}
+
+impl Visitor for StatementCounter {
+    fn visit_statement(&mut self, _: &Statement) -> bool {
+        self.statements += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_oversized_main_is_flagged_with_an_outline() {
+        let source_code = r#"
+            fn main(x: Field, y: pub Field) {
+                let a = x + 1;
+                let b = a * 2;
+                assert(b != 0);
+                let c = b + y;
+                if c == 0 {
+                    assert(a == 1);
+                }
+                let d = c * c;
+                assert(d != y);
+            }
+
+            fn helper(x: Field) {
+                let a = x + 1;
+                let b = a * 2;
+                let c = b * 2;
+                let d = c * 2;
+                let e = d * 2;
+                assert(e != x);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint = Box::new(OversizedEntryPoint::with_max_statements(6));
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Entry function 'main' has 8 statements, more than the limit of 6; delegate to named helpers, e.g. for statements 1-5 defining `a`, `b`, `c`; statements 6-7 defining `d`"
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! # Oversized Unsafe Block Lint
+//!
+//! Flags `unsafe { }` blocks that wrap statements which do not call unconstrained code.
+//! Keeping unsafe scopes minimal makes it obvious which values come from unconstrained
+//! functions and still need to be constrained.
+//!
+//! The suggested narrower block keeps the statements that call unconstrained code in their
+//! own `unsafe { }` blocks, each preceded by the `// Safety:` comment of the original block,
+//! or by a placeholder to fill in when it has none.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use noirc_frontend::ast::{
+    CallExpression, ExpressionKind, LetStatement, MethodCallExpression, Statement, StatementKind,
+    UnresolvedTypeData, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+
+/// Comment put above the generated `unsafe { }` blocks when the original block has none.
+const SAFETY_PLACEHOLDER: &str = "TODO: explain why the unconstrained result can be trusted";
+
+/// Detects unsafe blocks spanning more statements than the unconstrained calls require.
+#[derive(Clone, Default)]
+pub struct OversizedUnsafeBlock;
+
+declare_lint! {
+    OversizedUnsafeBlock {
+        name: "oversized-unsafe-block",
+        explanation: "Reports `unsafe { }` blocks wrapping statements that do not call unconstrained code. Keeping unsafe scopes minimal makes it obvious which values come from unconstrained functions and still need to be constrained.",
+    }
+}
+
+impl OversizedUnsafeBlock {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for unsafe_block in &context.unsafe_blocks {
+            let statements = &unsafe_block.block.statements;
+            if statements.len() < 2 {
+                continue;
+            }
+
+            let needs_unsafe: Vec<bool> = statements
+                .iter()
+                .map(|statement| requires_unsafe(statement, context))
+                .collect();
+            let required = needs_unsafe.iter().filter(|needed| **needed).count();
+            if required == statements.len() {
+                continue;
+            }
+
+            let message = Message::new("oversized-unsafe-block")
+                .with("statements", statements.len())
+                .with("required", required);
+            let (indent, safety) = context
+                .source
+                .map_or(("", None), |source| block_trivia(source, unsafe_block.span));
+            let safety = safety.unwrap_or(SAFETY_PLACEHOLDER);
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::Medium,
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(unsafe_block.span),
+                file_id: Some(unsafe_block.file_id),
+                suggestion: narrowed_block(statements, &needs_unsafe, indent, safety).map(
+                    |replacement| Suggestion {
+                        message: "narrow the unsafe scope to the unconstrained calls".to_string(),
+                        span: unsafe_block.span,
+                        replacement,
+                        // The block is re-rendered from the AST, dropping other comments, and
+                        // the `// Safety:` comments may need rewording for each new block
+                        applicability: Applicability::MaybeIncorrect,
+                        other_edits: vec![],
+                    },
+                ),
+            });
+        }
+
+        lints
+    }
+}
+
+/// Collects the callees of every call found while visiting a statement.
+///
+/// A callee is the path segments of the called function, or `None` when it cannot be
+/// resolved to a function path (e.g. method calls or calls through closures).
+#[derive(Default)]
+struct CalleeCollector {
+    callees: Vec<Option<Vec<String>>>,
+}
+
+impl Visitor for CalleeCollector {
+    fn visit_call_expression(&mut self, call: &CallExpression, _: Span) -> bool {
+        let callee = match &call.func.kind {
+            ExpressionKind::Variable(path) if !path.segments.is_empty() => Some(
+                path.segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect(),
+            ),
+            _ => None,
+        };
+        self.callees.push(callee);
+        true
+    }
+
+    fn visit_method_call_expression(&mut self, _: &MethodCallExpression, _: Span) -> bool {
+        self.callees.push(None);
+        true
+    }
+}
+
+/// Returns whether the statement may call unconstrained code.
+///
+/// Calls that cannot be resolved to a constrained function defined in the module or in a
+/// dependency of its package are conservatively treated as unconstrained.
+fn requires_unsafe(statement: &Statement, context: &AstContext) -> bool {
+    let mut collector = CalleeCollector::default();
+    statement.accept(&mut collector);
+
+    collector.callees.iter().any(|callee| {
+        let Some(segments) = callee else {
+            return true;
+        };
+        let local = segments
+            .last()
+            .and_then(|name| context.function_definitions.get(name));
+        if let Some(function) = local {
+            return function.is_unconstrained;
+        }
+        context
+            .dependencies
+            .and_then(|dependencies| dependencies.function(segments))
+            .is_none_or(|function| function.is_unconstrained)
+    })
+}
+
+/// Builds a plain block equivalent to the unsafe block, where only the statements calling
+/// unconstrained code are wrapped in `unsafe { }`.
+///
+/// Each new `unsafe { }` block is preceded by a `// Safety:` comment with the given text, and
+/// the block is laid out one statement per line, indented relative to `indent`.
+///
+/// Returns `None` unless every statement that needs to stay unsafe is a `let`, an expression
+/// or an expression followed by a semicolon, since only those can be rewrapped without
+/// changing scoping or evaluation order.
+fn narrowed_block(
+    statements: &[Statement],
+    needs_unsafe: &[bool],
+    indent: &str,
+    safety: &str,
+) -> Option<String> {
+    let mut rendered = Vec::with_capacity(statements.len());
+
+    for (statement, needs_unsafe) in statements.iter().zip(needs_unsafe) {
+        if *needs_unsafe {
+            rendered.push(format!("// Safety: {safety}"));
+        }
+        let text = match &statement.kind {
+            StatementKind::Let(let_statement) => render_let(let_statement, *needs_unsafe),
+            StatementKind::Expression(expression) if *needs_unsafe => {
+                format!("unsafe {{ {expression} }}")
+            }
+            StatementKind::Semi(expression) if *needs_unsafe => {
+                format!("unsafe {{ {expression} }};")
+            }
+            StatementKind::Expression(expression) => expression.to_string(),
+            StatementKind::Semi(expression) => format!("{expression};"),
+            _ => return None,
+        };
+        rendered.push(text);
+    }
+
+    let body: String = rendered
+        .iter()
+        .map(|line| format!("{indent}    {line}\n"))
+        .collect();
+    Some(format!("{{\n{body}{indent}}}"))
+}
+
+/// Returns the indentation of the line a block starts on, and the text of the `// Safety:`
+/// comment on the line above it, if there is one.
+fn block_trivia(source: &str, span: Span) -> (&str, Option<&str>) {
+    let start = span.start() as usize;
+    let Some(before) = source.get(..start) else {
+        return ("", None);
+    };
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    let line = &before[line_start..];
+    let indent = &line[..line.len() - line.trim_start().len()];
+
+    let safety = before[..line_start.saturating_sub(1)]
+        .rsplit('\n')
+        .next()
+        .and_then(|previous| previous.trim().strip_prefix("// Safety:"))
+        .map(str::trim)
+        .filter(|safety| !safety.is_empty());
+    (indent, safety)
+}
+
+fn render_let(let_statement: &LetStatement, wrap_in_unsafe: bool) -> String {
+    let type_annotation = match &let_statement.r#type.typ {
+        UnresolvedTypeData::Unspecified => String::new(),
+        _ => format!(": {}", let_statement.r#type),
+    };
+
+    if wrap_in_unsafe {
+        format!(
+            "let {}{} = unsafe {{ {} }};",
+            let_statement.pattern, type_annotation, let_statement.expression
+        )
+    } else {
+        format!(
+            "let {}{} = {};",
+            let_statement.pattern, type_annotation, let_statement.expression
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::dependencies::DependencyGraph;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::diagnostics::lint::{Confidence, Severity};
+    use crate::diagnostics::suggestion::Applicability;
+    use crate::lints::lint_rule::LintRule;
+    use crate::lints::oversized_unsafe_block::OversizedUnsafeBlock;
+
+    #[test]
+    fn test_oversized_unsafe_block_can_be_created() {
+        let lint = OversizedUnsafeBlock;
+        assert_eq!(lint.name(), "oversized-unsafe-block");
+    }
+
+    #[test]
+    fn test_single_call_unsafe_block_is_not_flagged() {
+        let lint = Box::new(OversizedUnsafeBlock);
+        let source_code = r#"
+            unconstrained fn hint() {}
+            pub fn main() {
+                // Safety: the hint is not used
+                let x = unsafe { hint() };
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_unsafe_block_with_constrained_statements_is_flagged() {
+        let lint = Box::new(OversizedUnsafeBlock);
+        let source_code = r#"
+            unconstrained fn hint() {}
+            pub fn main() {
+                // Safety: the hint is not used
+                let x = unsafe {
+                    let a = 1;
+                    assert(a == 1);
+                    hint()
+                };
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source_code);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "oversized-unsafe-block");
+        assert_eq!(result[0].severity, Severity::Warning);
+        assert_eq!(result[0].confidence, Confidence::Medium);
+        assert_eq!(
+            result[0].description,
+            "Unsafe block spans 3 statements but only 1 of them call unconstrained code"
+        );
+
+        let suggestion = result[0]
+            .suggestion
+            .as_ref()
+            .expect("Should suggest a narrower block");
+        assert!(
+            suggestion
+                .replacement
+                .starts_with("{\n                    let a = 1;\n")
+        );
+        // The safety comment of the original block is carried over to the new one
+        assert!(suggestion.replacement.ends_with(
+            "\n                    // Safety: the hint is not used\n                    unsafe { hint() }\n                }"
+        ));
+        assert_eq!(suggestion.applicability, Applicability::MaybeIncorrect);
+    }
+
+    #[test]
+    fn test_narrowed_block_without_safety_comment_gets_a_placeholder() {
+        let lint = Box::new(OversizedUnsafeBlock);
+        let source_code = "unconstrained fn hint() -> Field { 1 }\nfn main() {\n    let x = unsafe {\n        let a = 1;\n        let b = 2;\n        hint()\n    };\n    assert(x == 2);\n}\n";
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source_code);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let suggestion = result[0]
+            .suggestion
+            .as_ref()
+            .expect("Should suggest a narrower block");
+        assert_eq!(
+            suggestion.replacement,
+            "{\n        let a = 1;\n        let b = 2;\n        // Safety: TODO: explain why the unconstrained result can be trusted\n        unsafe { hint() }\n    }"
+        );
+    }
+
+    #[test]
+    fn test_unsafe_block_with_only_unconstrained_calls_is_not_flagged() {
+        let lint = Box::new(OversizedUnsafeBlock);
+        let source_code = r#"
+            unconstrained fn first() {}
+            unconstrained fn second() {}
+            pub fn main() {
+                // Safety: both hints are not used
+                unsafe {
+                    first();
+                    second();
+                }
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_calls_into_constrained_dependency_functions_are_resolved() {
+        let lint: Box<dyn LintRule> = Box::new(OversizedUnsafeBlock);
+        let source_code = r#"
+            unconstrained fn hint() -> Field { 1 }
+            pub fn main(x: Field) {
+                // Safety: the result is checked by the caller
+                let y = unsafe {
+                    let h = hashes::hash(x);
+                    hint() + h
+                };
+            }
+            "#;
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+
+        // Unknown calls may be unconstrained
+        let mut analyzer = Analyzer::new(std::slice::from_ref(&lint));
+        assert!(
+            analyzer
+                .analyze(&root)
+                .expect("Should have passed")
+                .is_empty()
+        );
+
+        let mut dependencies = DependencyGraph::default();
+        let library = "pub fn hash(x: Field) -> Field { x }";
+        dependencies.add_package(
+            "hashes",
+            &Parser::parse_program_with_dummy_file(library).unwrap(),
+        );
+        let mut analyzer = Analyzer::new(&[lint]).with_dependencies(&dependencies);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Unsafe block spans 2 statements but only 1 of them call unconstrained code"
+        );
+    }
+}
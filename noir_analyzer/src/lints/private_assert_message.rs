@@ -0,0 +1,180 @@
+//! # Private Assert Message Lint
+//!
+//! Flags assertion messages given as format strings that interpolate private values, such as
+//! `assert(x == y, f"secret is {secret}")`. The message is rendered with the value of the
+//! witness when the assertion fails, and failure messages end up in prover logs, error
+//! reports and the output of the tools wrapping the prover, where the value leaks.
+//!
+//! Public values are globals, parameters marked `pub` and values derived from them only,
+//! tracked as described in [`crate::lints::public_only_constraint`]. Any other interpolated
+//! variable is reported.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use crate::lints::public_only_constraint::{global_names, is_public};
+use fm::FileId;
+use noirc_frontend::ast::{
+    ConstrainExpression, ConstrainKind, LetStatement, Pattern, Visibility, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::token::FmtStrFragment;
+use std::collections::HashSet;
+
+/// Detects assertion messages interpolating private values.
+#[derive(Clone, Default)]
+pub struct PrivateAssertMessage;
+
+declare_lint! {
+    PrivateAssertMessage {
+        name: "private-assert-message",
+        explanation: "Reports format-string assertion messages, such as `assert(x == y, f\"secret is {secret}\")`, interpolating values other than globals and public inputs. Failing assertions render the message with the value of the witness, which then leaks through prover logs and error reports; describe the failure without the private value.",
+    }
+}
+
+impl PrivateAssertMessage {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let globals = global_names(context);
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            let mut collector = MessageCollector {
+                public: globals.clone(),
+                leaks: vec![],
+            };
+            for parameter in &function.parameters {
+                if let Pattern::Identifier(ident) = &parameter.pattern
+                    && parameter.visibility == Visibility::Public
+                {
+                    collector.public.insert(ident.to_string());
+                }
+            }
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+
+            for (value, span, file_id) in collector.leaks {
+                let message = Message::new("private-assert-message")
+                    .with("function", name)
+                    .with("value", value);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Collects the private values interpolated in assertion messages, with the location of
+/// their message.
+struct MessageCollector {
+    /// Variables holding public values: globals, public inputs and values derived from them.
+    public: HashSet<String>,
+    leaks: Vec<(String, Span, FileId)>,
+}
+
+impl Visitor for MessageCollector {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        if let Pattern::Identifier(ident) = &let_statement.pattern {
+            if is_public(&self.public, &let_statement.expression) {
+                self.public.insert(ident.to_string());
+            } else {
+                self.public.remove(&ident.to_string());
+            }
+        }
+        true
+    }
+
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        let message = match constrain.kind {
+            ConstrainKind::Assert => constrain.arguments.get(1),
+            ConstrainKind::AssertEq => constrain.arguments.get(2),
+            _ => None,
+        };
+        if let Some(message) = message {
+            let mut interpolations = Interpolations::default();
+            message.accept(&mut interpolations);
+
+            let (span, file_id) = Noir::expression_location(message);
+            for value in interpolations.values {
+                if !self.public.contains(&value) {
+                    self.leaks.push((value, span, file_id));
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Collects the values interpolated in the format strings of an expression.
+#[derive(Default)]
+struct Interpolations {
+    values: Vec<String>,
+}
+
+impl Visitor for Interpolations {
+    fn visit_literal_fmt_str(&mut self, fragments: &[FmtStrFragment], _length: u32, _: Span) {
+        for fragment in fragments {
+            if let FmtStrFragment::Interpolation(value, _) = fragment
+                && !self.values.contains(value)
+            {
+                self.values.push(value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_private_values_in_assert_messages_are_flagged() {
+        let lint = Box::new(PrivateAssertMessage);
+        let source_code = r#"
+            global LIMIT: u32 = 100;
+
+            fn main(secret: u32, limit: pub u32) {
+                let bound = limit * 2;
+                assert(bound < LIMIT, f"bound {bound} exceeds {LIMIT}");
+                assert(secret < limit, f"secret {secret} is not below {limit}");
+                assert_eq(secret, limit, "values differ");
+                check(secret);
+            }
+
+            fn check(value: u32) {
+                assert_eq(value % 2, 0, f"{value} is odd");
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Message of an assertion in 'main' interpolates private value 'secret', which failing proofs disclose",
+                "Message of an assertion in 'check' interpolates private value 'value', which failing proofs disclose",
+            ]
+        );
+    }
+}
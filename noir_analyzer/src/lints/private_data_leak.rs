@@ -0,0 +1,122 @@
+//! # Private Data Leak Lint
+//!
+//! Flags private inputs of `main` that reach a sink, following them through variables,
+//! calls and returned values as described in [`crate::analysis::private_taint`]: values
+//! printed with `print` or `println`, which end up in the output of the prover, values
+//! returned by `main`, which are public, and values passed to unconstrained functions from
+//! constrained code.
+//!
+//! Unconstrained hints routinely take private inputs, so the latter are reported with low
+//! confidence. Hashes and commitments of private inputs are not reported.
+
+use crate::analysis::private_taint::Sink;
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+
+/// Detects private inputs flowing into prints, public outputs and unconstrained calls.
+#[derive(Clone, Default)]
+pub struct PrivateDataLeak;
+
+declare_lint! {
+    PrivateDataLeak {
+        name: "private-data-leak",
+        explanation: "Reports private inputs of `main` that are printed, returned as a public output, or passed to an unconstrained function, directly or through the variables and functions they flow into. Printed values end up in prover logs and returned values are public, which defeats keeping the input private; hash or commit to the value instead. Unconstrained functions are reported with low confidence, as hints usually need the private inputs they check.",
+        requires_whole_program: true,
+        analyses: [PrivateTaint],
+    }
+}
+
+impl PrivateDataLeak {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints: Vec<Lint> = context
+            .private_taint
+            .leaks()
+            .iter()
+            .map(|leak| {
+                let (message, confidence) = match &leak.sink {
+                    Sink::Print => (
+                        Message::new("private-data-leak.print").with("function", &leak.function),
+                        Confidence::High,
+                    ),
+                    Sink::Unconstrained { function } => (
+                        Message::new("private-data-leak.unconstrained")
+                            .with("function", &leak.function)
+                            .with("callee", function),
+                        Confidence::Low,
+                    ),
+                    Sink::PublicOutput => (
+                        Message::new("private-data-leak.public-output"),
+                        Confidence::Medium,
+                    ),
+                };
+                let message = message.with("input", &leak.input);
+                Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(leak.span),
+                    file_id: Some(leak.file_id),
+                    suggestion: None,
+                }
+            })
+            .collect();
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_private_inputs_reaching_sinks_are_flagged() {
+        let lint = Box::new(PrivateDataLeak);
+        let source_code = r#"
+            unconstrained fn sqrt_hint(x: Field) -> Field {
+                x
+            }
+
+            fn debug(label: str<5>, value: Field) {
+                println(label);
+                println(value);
+            }
+
+            fn main(secret: Field, nonce: Field, expected: pub Field) -> pub Field {
+                let masked = secret + nonce;
+                debug("input", masked);
+                debug("bound", expected);
+                // Safety: constrained below
+                let root = unsafe { sqrt_hint(nonce) };
+                assert(root * root == nonce);
+                assert(std::hash::pedersen_hash([secret]) == expected);
+                nonce
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Private input 'secret' is printed in 'debug'",
+                "Private input 'nonce' is printed in 'debug'",
+                "Private input 'nonce' is passed to unconstrained function 'sqrt_hint' in 'main'",
+                "Private input 'nonce' is returned by 'main' as a public output",
+            ]
+        );
+        assert_eq!(result[2].confidence, Confidence::Low);
+    }
+}
@@ -0,0 +1,390 @@
+//! # Prover Input Mismatch Lint
+//!
+//! Compares the inputs written in `Prover.toml` and `Verifier.toml` with the signature of
+//! `main`: missing and unknown keys, values whose shape does not match the parameter type
+//! (array lengths, string lengths, struct fields), and private inputs disclosed in
+//! `Verifier.toml`. Nargo reports these mismatches at execution time, with errors that are
+//! hard to trace back to the input file.
+//!
+//! Types are read as written, so values of types the analyzer cannot resolve, such as
+//! generic parameters or type aliases, are not checked.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use crate::schema::{Schema, StructFieldSchema};
+use noirc_frontend::parser::ItemKind;
+use std::collections::BTreeMap;
+use toml::{Table, Value};
+
+const PROVER_FILE: &str = "Prover.toml";
+const VERIFIER_FILE: &str = "Verifier.toml";
+
+/// Key under which the return value of `main` is written.
+const RETURN_KEY: &str = "return";
+
+/// Detects inputs files that do not match the signature of `main`.
+#[derive(Clone, Default)]
+pub struct ProverInputMismatch {
+    /// The parsed `Prover.toml` of the package, if it has one.
+    pub prover: Option<Table>,
+    /// The parsed `Verifier.toml` of the package, if it has one.
+    pub verifier: Option<Table>,
+}
+
+impl ProverInputMismatch {
+    /// Creates the lint for the inputs files of a binary package.
+    pub fn new(prover: Option<Table>, verifier: Option<Table>) -> Self {
+        Self { prover, verifier }
+    }
+}
+
+declare_lint! {
+    ProverInputMismatch {
+        name: "prover-input-mismatch",
+        explanation: "Reports entries of Prover.toml and Verifier.toml that do not match the parameters of main: missing or unknown keys, values of the wrong shape, and private inputs written to Verifier.toml. Nargo only detects them when executing the circuit, with errors that do not point to the input file.",
+        requires_whole_program: true,
+    }
+}
+
+impl ProverInputMismatch {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let Some((span, file_id)) = context
+            .parsed_module
+            .items
+            .iter()
+            .find_map(|item| match &item.kind {
+                ItemKind::Function(function) if function.name() == "main" => {
+                    Some(Noir::function_location(&function.def))
+                }
+                _ => None,
+            })
+        else {
+            return vec![];
+        };
+
+        let schema = Schema::extract(context.parsed_module);
+        let Some(main) = schema
+            .functions
+            .iter()
+            .find(|function| function.name == "main")
+        else {
+            return vec![];
+        };
+
+        // Expected entries of each file: (key, type, is public)
+        let inputs: Vec<(&str, &str, bool)> = main
+            .parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.name.trim_start_matches("mut "),
+                    parameter.r#type.as_str(),
+                    parameter.visibility != "private",
+                )
+            })
+            .collect();
+        let return_type = main
+            .return_value
+            .as_ref()
+            .map(|return_value| return_value.r#type.as_str());
+
+        let checker = ShapeChecker {
+            structs: &schema.structs,
+        };
+        let mut mismatches = vec![];
+        if let Some(prover) = &self.prover {
+            for (key, r#type, _) in &inputs {
+                match prover.get(*key) {
+                    Some(value) => checker.check(PROVER_FILE, key, value, r#type, &mut mismatches),
+                    None => mismatches.push(Mismatch::error(
+                        Message::new("prover-input-mismatch.missing-input")
+                            .with("file", PROVER_FILE)
+                            .with("input", key),
+                    )),
+                }
+            }
+        }
+        if let Some(verifier) = &self.verifier {
+            for (key, r#type, is_public) in &inputs {
+                match (verifier.get(*key), is_public) {
+                    (Some(value), true) => {
+                        checker.check(VERIFIER_FILE, key, value, r#type, &mut mismatches)
+                    }
+                    (Some(_), false) => mismatches.push(Mismatch::error(
+                        Message::new("prover-input-mismatch.private-input")
+                            .with("file", VERIFIER_FILE)
+                            .with("input", key),
+                    )),
+                    (None, true) => mismatches.push(Mismatch::error(
+                        Message::new("prover-input-mismatch.missing-public-input")
+                            .with("file", VERIFIER_FILE)
+                            .with("input", key),
+                    )),
+                    (None, false) => {}
+                }
+            }
+        }
+
+        for (file, table) in [(PROVER_FILE, &self.prover), (VERIFIER_FILE, &self.verifier)] {
+            let Some(table) = table else {
+                continue;
+            };
+            for (key, value) in table {
+                if inputs.iter().any(|(input, ..)| input == key) {
+                    continue;
+                }
+                match return_type {
+                    Some(r#type) if key == RETURN_KEY => {
+                        checker.check(file, key, value, r#type, &mut mismatches)
+                    }
+                    _ => mismatches.push(Mismatch::warning(
+                        Message::new("prover-input-mismatch.unknown-entry")
+                            .with("file", file)
+                            .with("input", key),
+                    )),
+                }
+            }
+        }
+
+        mismatches
+            .into_iter()
+            .map(|mismatch| Lint {
+                name: Self::NAME,
+                severity: mismatch.severity,
+                confidence: Confidence::High,
+                description: mismatch.message.to_string(),
+                message: Some(mismatch.message),
+                span: Some(span),
+                file_id: Some(file_id),
+                suggestion: None,
+            })
+            .collect()
+    }
+}
+
+/// A difference between an inputs file and the signature of `main`.
+struct Mismatch {
+    severity: Severity,
+    message: Message,
+}
+
+impl Mismatch {
+    fn error(message: Message) -> Self {
+        Self {
+            severity: Severity::Error,
+            message,
+        }
+    }
+
+    fn warning(message: Message) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message,
+        }
+    }
+}
+
+/// The shape of a value, parsed from a written type.
+#[derive(Debug, PartialEq)]
+enum Shape<'a> {
+    /// A field element or an integer, written as a number or a string.
+    Number,
+    Bool,
+    /// An array of the written element type, with its length when it is a literal.
+    Array(&'a str, Option<usize>),
+    /// A string, with its length when it is a literal.
+    Str(Option<usize>),
+    /// A struct of the program, by name.
+    Struct(&'a str),
+    /// A type the analyzer does not check, such as a generic parameter.
+    Unknown,
+}
+
+impl<'a> Shape<'a> {
+    /// Parses a written type, such as `[Point; 2]`.
+    fn parse(r#type: &'a str, structs: &BTreeMap<String, Vec<StructFieldSchema>>) -> Self {
+        let r#type = r#type.trim();
+        if let Some(array) = r#type
+            .strip_prefix('[')
+            .and_then(|array| array.strip_suffix(']'))
+        {
+            return match array.rsplit_once(';') {
+                Some((element, length)) => Shape::Array(element, length.trim().parse().ok()),
+                // Slices have no fixed length
+                None => Shape::Array(array, None),
+            };
+        }
+        if let Some(length) = r#type
+            .strip_prefix("str<")
+            .and_then(|str| str.strip_suffix('>'))
+        {
+            return Shape::Str(length.trim().parse().ok());
+        }
+
+        match r#type {
+            "Field" | "u1" | "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32"
+            | "i64" => Shape::Number,
+            "bool" => Shape::Bool,
+            _ => {
+                let name = r#type.split('<').next().unwrap_or_default();
+                let name = name.rsplit("::").next().unwrap_or_default();
+                if structs.contains_key(name) {
+                    Shape::Struct(name)
+                } else {
+                    Shape::Unknown
+                }
+            }
+        }
+    }
+}
+
+/// Checks input values against written types, resolving the structs of the program.
+struct ShapeChecker<'a> {
+    structs: &'a BTreeMap<String, Vec<StructFieldSchema>>,
+}
+
+impl ShapeChecker<'_> {
+    /// Checks the value of an input, named by its path in the file, e.g. `points[1].x`.
+    fn check(
+        &self,
+        file: &str,
+        input: &str,
+        value: &Value,
+        r#type: &str,
+        mismatches: &mut Vec<Mismatch>,
+    ) {
+        let message = match (Shape::parse(r#type, self.structs), value) {
+            (Shape::Unknown, _)
+            | (Shape::Number, Value::Integer(_) | Value::String(_))
+            | (Shape::Bool, Value::Boolean(_)) => return,
+            (Shape::Str(Some(length)), Value::String(string)) if string.len() != length => {
+                Message::new("prover-input-mismatch.string-length").with("length", string.len())
+            }
+            (Shape::Str(_), Value::String(_)) => return,
+            (Shape::Array(_, Some(length)), Value::Array(elements)) if elements.len() != length => {
+                Message::new("prover-input-mismatch.array-length").with("length", elements.len())
+            }
+            (Shape::Array(element_type, _), Value::Array(elements)) => {
+                for (index, element) in elements.iter().enumerate() {
+                    let input = format!("{input}[{index}]");
+                    self.check(file, &input, element, element_type, mismatches);
+                }
+                return;
+            }
+            (Shape::Struct(name), Value::Table(table)) => {
+                let fields = &self.structs[name];
+                for field in fields {
+                    let input = format!("{input}.{}", field.name);
+                    match table.get(&field.name) {
+                        Some(value) => self.check(file, &input, value, &field.r#type, mismatches),
+                        None => mismatches.push(Mismatch::error(
+                            Message::new("prover-input-mismatch.missing-field")
+                                .with("file", file)
+                                .with("input", &input),
+                        )),
+                    }
+                }
+                for key in table.keys() {
+                    if !fields.iter().any(|field| field.name == *key) {
+                        mismatches.push(Mismatch::warning(
+                            Message::new("prover-input-mismatch.unknown-field")
+                                .with("file", file)
+                                .with("input", format!("{input}.{key}"))
+                                .with("struct", name),
+                        ));
+                    }
+                }
+                return;
+            }
+            (_, value) => Message::new("prover-input-mismatch.wrong-type")
+                .with("value_type", value.type_str()),
+        };
+
+        mismatches.push(Mismatch::error(
+            message
+                .with("file", file)
+                .with("input", input)
+                .with("type", r#type.trim()),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_inputs_not_matching_main_are_flagged() {
+        let source_code = r#"
+            struct Point { x: Field, y: Field }
+
+            fn main(points: [Point; 2], secret: Field, root: pub Field, name: str<3>) {
+                assert(points[0].x + secret == root);
+                assert(name == "abc");
+            }
+            "#;
+        let prover = toml::from_str(
+            r#"
+            secret = "1"
+            root = true
+            name = "abcd"
+            extra = 3
+            points = [{ x = 1, y = 2 }, { x = 3, z = 4 }]
+            "#,
+        )
+        .unwrap();
+        let verifier = toml::from_str(
+            r#"
+            secret = "1"
+            "#,
+        )
+        .unwrap();
+
+        let lint = Box::new(ProverInputMismatch::new(Some(prover), Some(verifier)));
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Prover.toml is missing field 'points[1].y'",
+                "Prover.toml has entry 'points[1].z', which is not a field of 'Point'",
+                "Prover.toml gives 'root' a boolean value, but its type is `Field`",
+                "Prover.toml gives 'name' 4 bytes, but its type is `str<3>`",
+                "Verifier.toml discloses private input 'secret' of main",
+                "Verifier.toml is missing public input 'root' of main",
+                "Prover.toml has entry 'extra', which is not a parameter of main",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_written_types_are_parsed_into_shapes() {
+        let structs = BTreeMap::from([("Point".to_string(), vec![])]);
+
+        assert_eq!(
+            Shape::parse("[[u8; 4]; 2]", &structs),
+            Shape::Array("[u8; 4]", Some(2))
+        );
+        assert_eq!(
+            Shape::parse("[Field]", &structs),
+            Shape::Array("Field", None)
+        );
+        assert_eq!(Shape::parse("str<N>", &structs), Shape::Str(None));
+        assert_eq!(
+            Shape::parse("points::Point", &structs),
+            Shape::Struct("Point")
+        );
+        assert_eq!(Shape::parse("T", &structs), Shape::Unknown);
+    }
+}
@@ -0,0 +1,194 @@
+//! # Public-Only Constraint Lint
+//!
+//! Flags assertions of private functions that reference no private data: only literals,
+//! globals and public inputs. Such checks could be performed by the verifier or in public
+//! code; enforcing them in the private circuit costs proving time and often hints at
+//! misplaced logic.
+//!
+//! Private functions are the entry point `main`, whose parameters are private unless marked
+//! `pub`, and contract functions marked `#[private]`, whose parameters are all private.
+//! Values returned by calls and `unsafe` blocks are assumed to be private.
+
+use crate::analysis::aztec_attributes::{self, ExecutionContext};
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ast::{
+    ConstrainExpression, Expression, ExpressionKind, FunctionDefinition, LetStatement, Pattern,
+    Visibility, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use std::collections::HashSet;
+
+/// Detects assertions of private functions that only depend on public values.
+#[derive(Clone, Default)]
+pub struct PublicOnlyConstraint;
+
+declare_lint! {
+    PublicOnlyConstraint {
+        name: "public-only-constraint",
+        explanation: "Reports assertions in private functions that reference only constants and public inputs. They do not involve private data, so they could be checked by the verifier or in public code instead of costing proving time in the private circuit.",
+    }
+}
+
+impl PublicOnlyConstraint {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let globals = global_names(context);
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            if function.is_unconstrained || !is_private_function(name, function) {
+                continue;
+            }
+
+            let mut collector = ConstraintCollector {
+                public: globals.clone(),
+                constraints: vec![],
+            };
+            for parameter in &function.parameters {
+                if let Pattern::Identifier(ident) = &parameter.pattern
+                    && parameter.visibility == Visibility::Public
+                {
+                    collector.public.insert(ident.to_string());
+                }
+            }
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+
+            for (span, file_id) in collector.constraints {
+                let message = Message::new("public-only-constraint").with("function", name);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns whether the function is executed in a private circuit.
+fn is_private_function(name: &str, function: &FunctionDefinition) -> bool {
+    name == "main"
+        || aztec_attributes::execution_context(function) == Some(ExecutionContext::Private)
+}
+
+/// Returns the names of the globals of the program.
+pub(crate) fn global_names(context: &AstContext) -> HashSet<String> {
+    context
+        .parsed_module
+        .items
+        .iter()
+        .filter_map(|item| match &item.kind {
+            ItemKind::Global(let_statement, ..) => match &let_statement.pattern {
+                Pattern::Identifier(ident) => Some(ident.to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Collects the assertions whose arguments only depend on public values.
+struct ConstraintCollector {
+    /// Variables holding public values: globals, public inputs and values derived from them.
+    public: HashSet<String>,
+    constraints: Vec<(Span, FileId)>,
+}
+
+/// Returns whether the expression only depends on literals and the given public variables.
+pub(crate) fn is_public(public: &HashSet<String>, expression: &Expression) -> bool {
+    match &expression.kind {
+        ExpressionKind::Literal(_) => true,
+        ExpressionKind::Variable(path) => match path.segments.as_slice() {
+            [segment] => public.contains(&segment.ident.to_string()),
+            // Qualified paths refer to globals of other modules
+            _ => true,
+        },
+        ExpressionKind::Infix(infix) => {
+            is_public(public, &infix.lhs) && is_public(public, &infix.rhs)
+        }
+        ExpressionKind::Prefix(prefix) => is_public(public, &prefix.rhs),
+        ExpressionKind::Cast(cast) => is_public(public, &cast.lhs),
+        ExpressionKind::Parenthesized(expression) => is_public(public, expression),
+        ExpressionKind::Index(index) => {
+            is_public(public, &index.collection) && is_public(public, &index.index)
+        }
+        _ => false,
+    }
+}
+
+impl Visitor for ConstraintCollector {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        if let Pattern::Identifier(ident) = &let_statement.pattern {
+            if is_public(&self.public, &let_statement.expression) {
+                self.public.insert(ident.to_string());
+            } else {
+                self.public.remove(&ident.to_string());
+            }
+        }
+        true
+    }
+
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        if let Some(first) = constrain.arguments.first()
+            && constrain
+                .arguments
+                .iter()
+                .all(|argument| is_public(&self.public, argument))
+        {
+            let (span, file_id) = Noir::expression_location(first);
+            self.constraints.push((span, file_id));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_public_only_assertions_are_flagged() {
+        let lint = Box::new(PublicOnlyConstraint);
+        let source_code = r#"
+            global LIMIT: u32 = 100;
+
+            fn check(x: u32) {
+                assert(LIMIT > 1);
+            }
+
+            fn main(secret: u32, limit: pub u32) {
+                let bound = limit * 2;
+                assert(bound < LIMIT);
+                assert(secret < limit);
+                check(secret);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Assertion in private function 'main' only depends on constants and public inputs"
+        );
+    }
+}
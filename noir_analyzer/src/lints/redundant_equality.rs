@@ -0,0 +1,329 @@
+//! # Redundant Equality Lint
+//!
+//! Flags equality assertions implied by earlier ones of the same function: after
+//! `assert(a == b)` and `assert_eq(b, c)`, `assert(a == c)` can never fail, and only adds
+//! gates. Such assertions are often copy-paste checks meant to compare other values.
+//!
+//! Equalities are merged into classes with a union-find as the function body is read, and an
+//! assertion is reported when both of its sides are already in the same class, together with
+//! the chain of assertions implying it. Sides are compared as written, and only variables,
+//! literals, field accesses and constant indices are tracked.
+//!
+//! Only the assertions at the top level of a function body are considered, so that
+//! conditional ones neither imply nor are implied. Variables declared `mut` are never
+//! tracked, and a variable shadowed by a new `let` forgets its equalities.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ast::{
+    BinaryOpKind, ConstrainExpression, ConstrainKind, Expression, ExpressionKind, Ident, Pattern,
+    StatementKind, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Detects equality assertions implied by earlier ones.
+#[derive(Clone, Default)]
+pub struct RedundantEquality;
+
+declare_lint! {
+    RedundantEquality {
+        name: "redundant-equality",
+        explanation: "Reports equality assertions implied by earlier assertions of the same function, such as `assert(a == c)` after `assert(a == b)` and `assert(b == c)`. The assertion can never fail and only adds gates; it is often a copy-paste check meant to compare other values.",
+    }
+}
+
+/// A side of an equality, as written, with the variable it reads, if any.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Term {
+    text: String,
+    root: Option<String>,
+}
+
+/// Equalities asserted so far in a function body.
+#[derive(Default)]
+struct Equalities {
+    /// Asserted equalities, as pairs of terms.
+    asserted: Vec<(Term, Term)>,
+    /// Representative of the class of each term, by term.
+    parents: BTreeMap<Term, Term>,
+}
+
+impl Equalities {
+    fn find(&mut self, term: &Term) -> Term {
+        let parent = self
+            .parents
+            .get(term)
+            .cloned()
+            .unwrap_or_else(|| term.clone());
+        if parent == *term {
+            return parent;
+        }
+        let root = self.find(&parent);
+        self.parents.insert(term.clone(), root.clone());
+        root
+    }
+
+    fn union(&mut self, lhs: &Term, rhs: &Term) {
+        let (lhs, rhs) = (self.find(lhs), self.find(rhs));
+        if lhs != rhs {
+            self.parents.insert(lhs, rhs);
+        }
+    }
+
+    fn assert(&mut self, lhs: Term, rhs: Term) {
+        self.union(&lhs, &rhs);
+        self.asserted.push((lhs, rhs));
+    }
+
+    /// Forgets the equalities of the terms reading a variable, rebuilding the classes.
+    fn forget(&mut self, variable: &str) {
+        let reads = |term: &Term| term.root.as_deref() == Some(variable);
+        self.asserted
+            .retain(|(lhs, rhs)| !reads(lhs) && !reads(rhs));
+        self.parents.clear();
+        for (lhs, rhs) in self.asserted.clone() {
+            self.union(&lhs, &rhs);
+        }
+    }
+
+    /// Returns the asserted equalities linking two terms of the same class, in order.
+    fn chain(&self, from: &Term, to: &Term) -> Vec<(Term, Term)> {
+        let mut previous: BTreeMap<&Term, (&Term, &Term)> = BTreeMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(term) = queue.pop_front() {
+            if term == to {
+                break;
+            }
+            for (lhs, rhs) in &self.asserted {
+                let next = match (lhs == term, rhs == term) {
+                    (true, _) => rhs,
+                    (_, true) => lhs,
+                    _ => continue,
+                };
+                if next != from && !previous.contains_key(next) {
+                    previous.insert(next, (lhs, rhs));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut chain = vec![];
+        let mut term = to;
+        while let Some((lhs, rhs)) = previous.get(term) {
+            chain.push(((*lhs).clone(), (*rhs).clone()));
+            term = if *lhs == term { rhs } else { lhs };
+        }
+        chain.reverse();
+        chain
+    }
+}
+
+impl RedundantEquality {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            if function.is_unconstrained {
+                continue;
+            }
+
+            let mut mutable = MutableVariables::default();
+            for parameter in &function.parameters {
+                parameter.pattern.accept(&mut mutable);
+            }
+            for statement in &function.body.statements {
+                statement.accept(&mut mutable);
+            }
+
+            let mut equalities = Equalities::default();
+            for statement in &function.body.statements {
+                let constrain = match &statement.kind {
+                    StatementKind::Expression(expression) | StatementKind::Semi(expression) => {
+                        match &expression.kind {
+                            ExpressionKind::Constrain(constrain) => Some(constrain),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                let Some(constrain) = constrain else {
+                    let mut shadowed = Bindings::default();
+                    statement.accept(&mut shadowed);
+                    for variable in shadowed.names {
+                        equalities.forget(&variable);
+                    }
+                    continue;
+                };
+
+                let Some((lhs, rhs, location)) = equality(constrain) else {
+                    continue;
+                };
+                let (Some(lhs), Some(rhs)) = (term(lhs), term(rhs)) else {
+                    continue;
+                };
+                let tracked = |term: &Term| {
+                    term.root
+                        .as_ref()
+                        .is_none_or(|root| !mutable.names.contains(root))
+                };
+                if lhs == rhs || !tracked(&lhs) || !tracked(&rhs) {
+                    continue;
+                }
+
+                if equalities.find(&lhs) == equalities.find(&rhs) {
+                    let chain: Vec<String> = equalities
+                        .chain(&lhs, &rhs)
+                        .iter()
+                        .map(|(lhs, rhs)| format!("{} == {}", lhs.text, rhs.text))
+                        .collect();
+                    let (span, file_id) = Noir::expression_location(location);
+                    let message = Message::new("redundant-equality")
+                        .with("lhs", &lhs.text)
+                        .with("rhs", &rhs.text)
+                        .with("function", name)
+                        .with("chain", chain.join(", "));
+                    lints.push(Lint {
+                        name: Self::NAME,
+                        severity: Severity::Warning,
+                        confidence: Confidence::High,
+                        description: message.to_string(),
+                        message: Some(message),
+                        span: Some(span),
+                        file_id: Some(file_id),
+                        suggestion: None,
+                    });
+                } else {
+                    equalities.assert(lhs, rhs);
+                }
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns the sides of an equality assertion, with the expression to report it on.
+pub(crate) fn equality(
+    constrain: &ConstrainExpression,
+) -> Option<(&Expression, &Expression, &Expression)> {
+    let first = constrain.arguments.first()?;
+    match constrain.kind {
+        ConstrainKind::AssertEq => Some((first, constrain.arguments.get(1)?, first)),
+        _ => match &unparenthesized(first).kind {
+            ExpressionKind::Infix(infix) if infix.operator.contents == BinaryOpKind::Equal => {
+                Some((&infix.lhs, &infix.rhs, first))
+            }
+            _ => None,
+        },
+    }
+}
+
+/// Returns the term of a side of an equality, when it is simple enough to compare as
+/// written.
+fn term(expression: &Expression) -> Option<Term> {
+    let expression = unparenthesized(expression);
+    let root = match &expression.kind {
+        ExpressionKind::Literal(_) => None,
+        ExpressionKind::Variable(path) => match path.segments.as_slice() {
+            [segment] => Some(segment.ident.to_string()),
+            // Globals and other items cannot change
+            _ => None,
+        },
+        ExpressionKind::MemberAccess(member_access) => term(&member_access.lhs)?.root,
+        ExpressionKind::Index(index) => {
+            if !matches!(index.index.kind, ExpressionKind::Literal(_)) {
+                return None;
+            }
+            term(&index.collection)?.root
+        }
+        _ => return None,
+    };
+    Some(Term {
+        text: expression.to_string(),
+        root,
+    })
+}
+
+fn unparenthesized(expression: &Expression) -> &Expression {
+    match &expression.kind {
+        ExpressionKind::Parenthesized(inner) => unparenthesized(inner),
+        _ => expression,
+    }
+}
+
+/// Collects the variables declared `mut`.
+#[derive(Default)]
+struct MutableVariables {
+    names: BTreeSet<String>,
+}
+
+impl Visitor for MutableVariables {
+    fn visit_mutable_pattern(&mut self, pattern: &Pattern, _: Span, _: bool) -> bool {
+        let mut bindings = Bindings::default();
+        pattern.accept(&mut bindings);
+        self.names.extend(bindings.names);
+        false
+    }
+}
+
+/// Collects the variables a statement declares.
+#[derive(Default)]
+struct Bindings {
+    names: Vec<String>,
+}
+
+impl Visitor for Bindings {
+    fn visit_identifier_pattern(&mut self, ident: &Ident) {
+        self.names.push(ident.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_transitively_implied_equalities_are_flagged() {
+        let source_code = r#"
+            fn main(a: Field, b: Field, c: Field, d: Field, mut e: Field) {
+                assert(a == b);
+                assert_eq(b, c);
+                assert(a == c);
+                assert((c) == a, "copy-paste");
+                if d == 0 {
+                    assert(a == d);
+                }
+                assert(a == d);
+                e = a;
+                assert(e == a);
+                assert(e == a);
+                let b = d;
+                assert(a == b);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint = Box::new(RedundantEquality);
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Assertion that a == c in function 'main' is implied by earlier assertions: a == b, b == c",
+                "Assertion that c == a in function 'main' is implied by earlier assertions: b == c, a == b",
+            ]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! # Redundant Import Lint
+//!
+//! Flags imports that bring nothing new into scope: paths imported several times in the same
+//! module, directly or through nested use-tree lists, and imports of the module they appear
+//! in. `use` declarations made only of duplicates get a fix removing them.
+//!
+//! Noir has no glob imports, so duplicates are only looked for among explicit imports.
+
+use crate::analysis::module_graph::ROOT_MODULE;
+use crate::analysis::resolution::flatten_use_tree;
+use crate::ast::ast_context::AstContext;
+use crate::ast::trivia;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use std::collections::HashSet;
+
+/// Detects duplicated imports and imports of the current module.
+#[derive(Clone, Default)]
+pub struct RedundantImport;
+
+declare_lint! {
+    RedundantImport {
+        name: "redundant-import",
+        explanation: "Reports paths imported more than once in the same module, including through nested `use` lists, and imports of the current module. They add nothing to the scope and make the imports harder to audit; remove them.",
+        requires_whole_program: true,
+    }
+}
+
+impl RedundantImport {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        self.lint_module(
+            context,
+            context.parsed_module,
+            &[ROOT_MODULE.to_string()],
+            &mut lints,
+        );
+        lints
+    }
+
+    fn lint_module(
+        &self,
+        context: &AstContext,
+        parsed_module: &ParsedModule,
+        module_path: &[String],
+        lints: &mut Vec<Lint>,
+    ) {
+        let current_module = module_path.join("::");
+        let mut imported = HashSet::new();
+
+        for item in &parsed_module.items {
+            let (span, file_id) = Noir::item_location(item);
+            match &item.kind {
+                ItemKind::Submodules(submodule) => {
+                    let mut submodule_path = module_path.to_vec();
+                    submodule_path.push(submodule.name.to_string());
+                    self.lint_module(context, &submodule.contents, &submodule_path, lints);
+                }
+                ItemKind::Import(use_tree, _) => {
+                    let imports = flatten_use_tree(use_tree, module_path, Vec::new());
+                    let mut duplicates = vec![];
+
+                    for (path, name) in &imports {
+                        if *path == current_module {
+                            let message = Message::new("redundant-import.self").with("path", path);
+                            lints.push(Lint {
+                                name: Self::NAME,
+                                severity: Severity::Warning,
+                                confidence: Confidence::High,
+                                description: message.to_string(),
+                                message: Some(message),
+                                span: Some(span),
+                                file_id: Some(file_id),
+                                suggestion: None,
+                            });
+                        } else if !imported.insert((path.clone(), name.clone())) {
+                            duplicates.push(path.clone());
+                        }
+                    }
+                    if duplicates.is_empty() {
+                        continue;
+                    }
+
+                    // Only declarations made entirely of duplicates can be removed as a whole
+                    let suggestion = context
+                        .source
+                        .filter(|_| duplicates.len() == imports.len())
+                        .map(|source| Suggestion {
+                            message: "remove the duplicated import".to_string(),
+                            span: removal_span(source, span),
+                            replacement: String::new(),
+                            applicability: Applicability::MachineApplicable,
+                            other_edits: vec![],
+                        });
+                    let paths: Vec<String> =
+                        duplicates.iter().map(|path| format!("'{path}'")).collect();
+                    let id = if paths.len() == 1 {
+                        "redundant-import.duplicate"
+                    } else {
+                        "redundant-import.duplicates"
+                    };
+                    let message = Message::new(id)
+                        .with("imports", paths.join(", "))
+                        .with("module", &current_module);
+                    lints.push(Lint {
+                        name: Self::NAME,
+                        severity: Severity::Warning,
+                        confidence: Confidence::High,
+                        description: message.to_string(),
+                        message: Some(message),
+                        span: Some(span),
+                        file_id: Some(file_id),
+                        suggestion,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Returns the span removing a `use` declaration, with its `;` and, when it stands on its own
+/// line, the whole line.
+pub(crate) fn removal_span(source: &str, span: Span) -> Span {
+    let mut end = span.end() as usize;
+    if let Some(semicolon) =
+        trivia::next_token(source, end).filter(|offset| source[*offset..].starts_with(';'))
+    {
+        end = semicolon + 1;
+    }
+
+    let (start, end) = trivia::expand_to_lines(source, span.start() as usize, end);
+    Span::from(start as u32..end as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    const SOURCE: &str = "mod a {\n    pub fn foo() {}\n    pub fn bar() {}\n}\nmod b {\n    use crate::b;\n}\nuse a::foo;\nuse a::{bar, foo};\nuse crate::a::foo;\nuse a::foo as baz;\nfn main() {}\n";
+
+    #[test]
+    fn test_duplicated_and_self_imports_are_flagged() {
+        let lint = Box::new(RedundantImport);
+
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(SOURCE);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "Import of 'crate::b' refers to the module it appears in",
+                "'crate::a::foo' is already imported in 'crate'",
+                "'crate::a::foo' is already imported in 'crate'",
+            ]
+        );
+        assert!(result[1].suggestion.is_none());
+        assert!(result[2].suggestion.is_some());
+    }
+
+    #[test]
+    fn test_duplicated_import_fix_removes_the_line() {
+        let lint = Box::new(RedundantImport);
+
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(SOURCE);
+        let suggestions = analyzer
+            .analyze(&root)
+            .expect("Should have passed")
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+
+        let fixed = Fixer::new(false).apply(SOURCE, suggestions).source;
+        assert!(!fixed.contains("use crate::a::foo;"));
+        assert!(fixed.contains("use a::{bar, foo};\nuse a::foo as baz;\n"));
+    }
+}
@@ -0,0 +1,452 @@
+//! # Lint Registry
+//!
+//! Holds the built-in lint rules run on the AST of a program, together with the metadata
+//! that tools list them with: default severity, category, description and stability. The
+//! command line, the daemon and the benchmarks build their rules from the registry rather
+//! than each keeping a list of their own.
+//!
+//! Rules of the library-hygiene group, such as `unchecked-public-function`, only apply to
+//! library packages: `rules` leaves them out, and `library_rules` returns them for the
+//! packages they apply to.
+//!
+//! Findings of the rules returned by the registry are reported with the default severity of
+//! their lint, which configured levels may then change. Rules are enabled unless they are
+//! opt-in, and can be enabled or disabled by name:
+//!
+//! ```ignore
+//! let mut registry = LintRegistry::builtin();
+//! registry.enable(ImportOrder::NAME)?;
+//! registry.disable(GodModule::NAME)?;
+//! let analyzer = Analyzer::new(&registry.rules());
+//! ```
+
+use crate::analysis::passes::Analysis;
+use crate::ast::ast_context::AstContext;
+use crate::diagnostics::lint::{Lint, Severity};
+use crate::health::Category;
+use crate::lints::bool_field_cast::BoolFieldCast;
+use crate::lints::call_chain_depth::CallChainDepth;
+use crate::lints::conditional_assignment::ConditionalAssignment;
+use crate::lints::contract_function_visibility::ContractFunctionVisibility;
+use crate::lints::cyclic_module_imports::CyclicModuleImports;
+use crate::lints::deep_constraint_expression::DeepConstraintExpression;
+use crate::lints::empty_block::EmptyBlock;
+use crate::lints::field_accumulation_overflow::FieldAccumulationOverflow;
+use crate::lints::field_modulo::FieldModulo;
+use crate::lints::first_element_comparison::FirstElementComparison;
+use crate::lints::function_naming::FunctionNaming;
+use crate::lints::god_module::GodModule;
+use crate::lints::hint_drift::HintDrift;
+use crate::lints::import_order::ImportOrder;
+use crate::lints::inconsistent_variant_handling::InconsistentVariantHandling;
+use crate::lints::integer_literal_overflow::IntegerLiteralOverflow;
+use crate::lints::large_struct::LargeStruct;
+use crate::lints::lint_rule::{LintRule, RuleConfigError};
+use crate::lints::oversized_entry_point::OversizedEntryPoint;
+use crate::lints::oversized_unsafe_block::OversizedUnsafeBlock;
+use crate::lints::private_assert_message::PrivateAssertMessage;
+use crate::lints::private_data_leak::PrivateDataLeak;
+use crate::lints::public_only_constraint::PublicOnlyConstraint;
+use crate::lints::redundant_equality::RedundantEquality;
+use crate::lints::redundant_import::RedundantImport;
+use crate::lints::shadowed_trait_method::ShadowedTraitMethod;
+use crate::lints::single_instantiation_generic::SingleInstantiationGeneric;
+use crate::lints::stdlib_shadowing::StdlibShadowing;
+use crate::lints::struct_field_order::StructFieldOrder;
+use crate::lints::trailing_semicolon::TrailingSemicolon;
+use crate::lints::unchecked_public_function::UncheckedPublicFunction;
+use crate::lints::unchecked_slice_conversion::UncheckedSliceConversion;
+use crate::lints::unconstrained_parameter::UnconstrainedParameter;
+use crate::lints::unconstrained_struct_field::UnconstrainedStructField;
+use crate::lints::undocumented_numeric_generic::UndocumentedNumericGeneric;
+use crate::lints::unused_function::UnusedFunction;
+use crate::lints::unused_import::UnusedImport;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("Unknown lint '{0}'")]
+    UnknownLint(String),
+    #[error("Lint '{0}' is already registered")]
+    DuplicateLint(&'static str),
+}
+
+/// How settled a lint is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stability {
+    /// The findings of the lint are not expected to change, other than for bug fixes.
+    Stable,
+    /// The lint relies on heuristics still being tuned, and its findings may change
+    /// between releases.
+    Experimental,
+}
+
+/// Description of a registered lint.
+#[derive(Debug, Clone)]
+pub struct LintMetadata {
+    /// Unique name of the lint.
+    pub name: &'static str,
+    /// Severity of the findings of the lint, unless configured otherwise.
+    pub default_severity: Severity,
+    /// What the findings of the lint put at risk.
+    pub category: Category,
+    /// What the lint detects and why it matters.
+    pub description: &'static str,
+    /// Whether the findings of the lint may still change.
+    pub stability: Stability,
+    /// Whether the lint runs without being enabled.
+    pub enabled_by_default: bool,
+    /// Whether the lint only runs on library packages.
+    pub library_only: bool,
+}
+
+struct RegisteredLint {
+    metadata: LintMetadata,
+    rule: Box<dyn LintRule>,
+    enabled: bool,
+}
+
+/// The lint rules known to the analyzer, in registration order, and whether each runs.
+#[derive(Default)]
+pub struct LintRegistry {
+    lints: Vec<RegisteredLint>,
+}
+
+impl LintRegistry {
+    /// Creates a registry of every built-in rule run on the AST of packages, with default
+    /// options.
+    pub fn builtin() -> Self {
+        use Severity::{Error, Warning};
+        use Stability::{Experimental, Stable};
+
+        let mut registry = Self::default();
+        registry.add(UnusedFunction, Warning, Stable);
+        registry.add(OversizedUnsafeBlock, Warning, Stable);
+        registry.add(CyclicModuleImports, Warning, Stable);
+        registry.add(GodModule::default(), Warning, Stable);
+        registry.add(TrailingSemicolon, Warning, Stable);
+        registry.add(IntegerLiteralOverflow, Error, Stable);
+        registry.add(FieldModulo, Warning, Stable);
+        registry.add(BoolFieldCast, Warning, Stable);
+        registry.add(FieldAccumulationOverflow, Warning, Stable);
+        registry.add(UnconstrainedStructField, Warning, Stable);
+        registry.add(UnconstrainedParameter, Warning, Stable);
+        registry.add(UncheckedSliceConversion, Warning, Stable);
+        registry.add(PublicOnlyConstraint, Warning, Stable);
+        registry.add(PrivateAssertMessage, Warning, Stable);
+        registry.add(PrivateDataLeak, Warning, Experimental);
+        registry.add(ConditionalAssignment, Warning, Stable);
+        registry.add(RedundantEquality, Warning, Stable);
+        registry.add(EmptyBlock, Warning, Stable);
+        registry.add(FirstElementComparison, Warning, Stable);
+        registry.add(RedundantImport, Warning, Stable);
+        registry.add(UnusedImport, Warning, Stable);
+        registry.add(ImportOrder, Warning, Stable);
+        registry.add(ShadowedTraitMethod, Warning, Stable);
+        registry.add(StdlibShadowing::default(), Warning, Stable);
+        registry.add(SingleInstantiationGeneric, Warning, Experimental);
+        registry.add(StructFieldOrder, Warning, Experimental);
+        registry.add(LargeStruct::default(), Warning, Stable);
+        registry.add(ContractFunctionVisibility, Warning, Stable);
+        registry.add(CallChainDepth::default(), Warning, Stable);
+        registry.add(HintDrift::default(), Warning, Experimental);
+        registry.add(OversizedEntryPoint::default(), Warning, Experimental);
+        registry.add(DeepConstraintExpression::default(), Warning, Stable);
+        registry.add(FunctionNaming, Warning, Stable);
+        registry.add(UndocumentedNumericGeneric, Warning, Stable);
+        registry.add(UncheckedPublicFunction::default(), Warning, Experimental);
+        registry.add(InconsistentVariantHandling, Warning, Experimental);
+        registry
+    }
+
+    /// Adds a rule to the registry, enabled unless it is opt-in. Its category is the one
+    /// the health score counts its findings in.
+    pub fn register(
+        &mut self,
+        rule: Box<dyn LintRule>,
+        default_severity: Severity,
+        stability: Stability,
+    ) -> Result<(), RegistryError> {
+        let name = rule.name();
+        if self.get(name).is_some() {
+            return Err(RegistryError::DuplicateLint(name));
+        }
+
+        let metadata = LintMetadata {
+            name,
+            default_severity,
+            category: Category::of(name),
+            description: rule.explanation(),
+            stability,
+            enabled_by_default: rule.enabled_by_default(),
+            library_only: rule.library_only(),
+        };
+        self.lints.push(RegisteredLint {
+            enabled: metadata.enabled_by_default,
+            metadata,
+            rule,
+        });
+        Ok(())
+    }
+
+    /// Adds a built-in rule, whose name is known to be unique.
+    fn add(
+        &mut self,
+        rule: impl LintRule + 'static,
+        default_severity: Severity,
+        stability: Stability,
+    ) {
+        self.register(Box::new(rule), default_severity, stability)
+            .expect("Built-in lints should have unique names");
+    }
+
+    /// Returns the metadata of every registered lint, in registration order.
+    pub fn metadata(&self) -> impl Iterator<Item = &LintMetadata> {
+        self.lints.iter().map(|lint| &lint.metadata)
+    }
+
+    /// Returns the metadata of a lint, if it is registered.
+    pub fn get(&self, name: &str) -> Option<&LintMetadata> {
+        self.find(name).map(|lint| &lint.metadata)
+    }
+
+    /// Returns whether a registered lint runs.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|lint| lint.enabled)
+    }
+
+    /// Makes a lint run, opt-in or not.
+    pub fn enable(&mut self, name: &str) -> Result<(), RegistryError> {
+        self.set_enabled(name, true)
+    }
+
+    /// Keeps a lint from running.
+    pub fn disable(&mut self, name: &str) -> Result<(), RegistryError> {
+        self.set_enabled(name, false)
+    }
+
+    /// Makes every registered lint run, opt-in ones included.
+    pub fn enable_all(&mut self) {
+        for lint in &mut self.lints {
+            lint.enabled = true;
+        }
+    }
+
+    /// Returns the enabled rules applying to every package, in registration order, for an
+    /// `Analyzer` to run.
+    pub fn rules(&self) -> Vec<Box<dyn LintRule>> {
+        self.enabled_rules(false)
+    }
+
+    /// Returns the enabled rules only applying to library packages, in registration order.
+    pub fn library_rules(&self) -> Vec<Box<dyn LintRule>> {
+        self.enabled_rules(true)
+    }
+
+    fn enabled_rules(&self, library_only: bool) -> Vec<Box<dyn LintRule>> {
+        self.lints
+            .iter()
+            .filter(|lint| lint.enabled && lint.metadata.library_only == library_only)
+            .map(|lint| {
+                Box::new(WithSeverity {
+                    rule: lint.rule.boxed_clone(),
+                    severity: lint.metadata.default_severity.clone(),
+                }) as Box<dyn LintRule>
+            })
+            .collect()
+    }
+
+    fn find(&self, name: &str) -> Option<&RegisteredLint> {
+        self.lints.iter().find(|lint| lint.metadata.name == name)
+    }
+
+    fn set_enabled(&mut self, name: &str, enabled: bool) -> Result<(), RegistryError> {
+        let lint = self
+            .lints
+            .iter_mut()
+            .find(|lint| lint.metadata.name == name)
+            .ok_or_else(|| RegistryError::UnknownLint(name.to_string()))?;
+        lint.enabled = enabled;
+        Ok(())
+    }
+}
+
+/// A registered rule, whose findings are reported with the default severity of its lint.
+struct WithSeverity {
+    rule: Box<dyn LintRule>,
+    severity: Severity,
+}
+
+impl LintRule for WithSeverity {
+    fn name(&self) -> &'static str {
+        self.rule.name()
+    }
+
+    fn explanation(&self) -> &'static str {
+        self.rule.explanation()
+    }
+
+    fn boxed_clone(&self) -> Box<dyn LintRule> {
+        Box::new(WithSeverity {
+            rule: self.rule.boxed_clone(),
+            severity: self.severity.clone(),
+        })
+    }
+
+    fn requires_whole_program(&self) -> bool {
+        self.rule.requires_whole_program()
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        self.rule.enabled_by_default()
+    }
+
+    fn library_only(&self) -> bool {
+        self.rule.library_only()
+    }
+
+    fn required_analyses(&self) -> &'static [Analysis] {
+        self.rule.required_analyses()
+    }
+
+    fn configure(&mut self, options: toml::Value) -> Result<(), RuleConfigError> {
+        self.rule.configure(options)
+    }
+
+    fn lint(&self, context: &AstContext) -> Vec<Lint> {
+        self.rule
+            .lint(context)
+            .into_iter()
+            .map(|lint| Lint {
+                severity: self.severity.clone(),
+                ..lint
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    fn rule_names(registry: &LintRegistry) -> Vec<&'static str> {
+        registry
+            .rules()
+            .iter()
+            .map(|lint_rule| lint_rule.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_registry_describes_its_lints() {
+        let registry = LintRegistry::builtin();
+
+        let metadata = registry.get(IntegerLiteralOverflow::NAME).unwrap();
+        assert_eq!(metadata.default_severity, Severity::Error);
+        assert_eq!(metadata.category, Category::Soundness);
+        assert_eq!(metadata.stability, Stability::Stable);
+        assert!(metadata.enabled_by_default);
+        assert!(!metadata.description.is_empty());
+
+        let metadata = registry.get(ImportOrder::NAME).unwrap();
+        assert_eq!(metadata.category, Category::Style);
+        assert!(!metadata.enabled_by_default);
+        assert!(registry.get("no-such-lint").is_none());
+    }
+
+    #[test]
+    fn test_registry_enables_and_disables_lints_by_name() {
+        let mut registry = LintRegistry::builtin();
+        let names = rule_names(&registry);
+        assert!(names.contains(&UnusedFunction::NAME));
+        assert!(!names.contains(&ImportOrder::NAME));
+
+        registry.enable(ImportOrder::NAME).unwrap();
+        registry.disable(GodModule::NAME).unwrap();
+        let names = rule_names(&registry);
+        assert!(names.contains(&ImportOrder::NAME));
+        assert!(!names.contains(&GodModule::NAME));
+        assert!(!registry.is_enabled(GodModule::NAME));
+
+        assert!(matches!(
+            registry.disable("no-such-lint"),
+            Err(RegistryError::UnknownLint(_))
+        ));
+        assert!(matches!(
+            registry.register(
+                Box::new(UnusedFunction),
+                Severity::Warning,
+                Stability::Stable
+            ),
+            Err(RegistryError::DuplicateLint(UnusedFunction::NAME))
+        ));
+
+        registry.enable_all();
+        assert_eq!(
+            rule_names(&registry).len() + registry.library_rules().len(),
+            registry.metadata().count()
+        );
+    }
+
+    #[test]
+    fn test_library_rules_are_kept_apart() {
+        let mut registry = LintRegistry::builtin();
+        let metadata = registry.get(UncheckedPublicFunction::NAME).unwrap();
+        assert!(metadata.library_only);
+        assert!(!metadata.enabled_by_default);
+        assert!(!registry.get(UnusedFunction::NAME).unwrap().library_only);
+
+        let library_rule_names = |registry: &LintRegistry| -> Vec<&'static str> {
+            registry
+                .library_rules()
+                .iter()
+                .map(|lint_rule| lint_rule.name())
+                .collect()
+        };
+        assert_eq!(
+            library_rule_names(&registry),
+            [UndocumentedNumericGeneric::NAME]
+        );
+        registry.enable(UncheckedPublicFunction::NAME).unwrap();
+        assert_eq!(
+            library_rule_names(&registry),
+            [
+                UndocumentedNumericGeneric::NAME,
+                UncheckedPublicFunction::NAME
+            ]
+        );
+        assert!(!rule_names(&registry).contains(&UndocumentedNumericGeneric::NAME));
+    }
+
+    #[test]
+    fn test_findings_have_the_registered_severity() {
+        let source = "fn main(x: Field) {\n    if x == 0 {\n        assert(x == 0);\n    } else {\n    }\n}\n";
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let findings = |registry: &LintRegistry| -> Vec<(&'static str, Severity)> {
+            let lint_rules = registry.rules();
+            let mut analyzer = Analyzer::new(&lint_rules);
+            analyzer
+                .analyze(&root)
+                .expect("Should have passed")
+                .into_iter()
+                .map(|lint| (lint.name, lint.severity))
+                .collect()
+        };
+
+        let mut registry = LintRegistry::default();
+        registry
+            .register(Box::new(EmptyBlock), Severity::Error, Stability::Stable)
+            .unwrap();
+        assert_eq!(findings(&registry), [(EmptyBlock::NAME, Severity::Error)]);
+
+        let mut registry = LintRegistry::builtin();
+        registry.enable_all();
+        let findings = findings(&registry);
+        assert!(!findings.is_empty());
+        for (name, severity) in findings {
+            assert_eq!(severity, registry.get(name).unwrap().default_severity);
+        }
+    }
+}
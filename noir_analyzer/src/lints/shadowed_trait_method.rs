@@ -0,0 +1,143 @@
+//! # Shadowed Trait Method Lint
+//!
+//! Flags inherent methods named like a method of a trait implemented for the same type.
+//! Method calls resolve to the inherent method, so a reader expecting the trait
+//! implementation, and its constraints, reviews the wrong code.
+//!
+//! Types are compared by their written form, and only methods defined in the trait impl are
+//! considered, not the default methods of the trait.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{NoirFunction, TraitImplItemKind};
+use noirc_frontend::parser::ItemKind;
+use std::collections::{HashMap, HashSet};
+
+/// Detects inherent methods shadowing trait methods of the same type.
+#[derive(Clone, Default)]
+pub struct ShadowedTraitMethod;
+
+declare_lint! {
+    ShadowedTraitMethod {
+        name: "shadowed-trait-method",
+        explanation: "Reports inherent methods with the same name as a method of a trait implemented for the same type. Calls resolve to the inherent method, so auditors may review the trait implementation while another function is executed; rename one of them.",
+        requires_whole_program: true,
+    }
+}
+
+impl ShadowedTraitMethod {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut methods = Methods::default();
+        methods.collect(context.parsed_module);
+
+        let mut lints = vec![];
+        for (object_type, inherent_methods) in &methods.inherent {
+            let Some(trait_methods) = methods.traits.get(object_type) else {
+                continue;
+            };
+
+            for method in inherent_methods {
+                if !trait_methods.contains(method.name()) {
+                    continue;
+                }
+
+                let (span, file_id) = Noir::function_location(&method.def);
+                let message = Message::new("shadowed-trait-method")
+                    .with("type", object_type)
+                    .with("method", method.name());
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::High,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// The methods of every type of the program, keyed by the written type.
+#[derive(Default)]
+struct Methods<'ast> {
+    inherent: HashMap<String, Vec<&'ast NoirFunction>>,
+    traits: HashMap<String, HashSet<String>>,
+}
+
+impl<'ast> Methods<'ast> {
+    fn collect(&mut self, parsed_module: &'ast ParsedModule) {
+        for item in &parsed_module.items {
+            match &item.kind {
+                ItemKind::Submodules(submodule) => self.collect(&submodule.contents),
+                ItemKind::Impl(type_impl) => {
+                    self.inherent
+                        .entry(type_impl.object_type.to_string())
+                        .or_default()
+                        .extend(type_impl.methods.iter().map(|(method, _)| &method.item));
+                }
+                ItemKind::TraitImpl(trait_impl) => {
+                    let names = self
+                        .traits
+                        .entry(trait_impl.object_type.to_string())
+                        .or_default();
+                    for item in &trait_impl.items {
+                        if let TraitImplItemKind::Function(function) = &item.item.kind {
+                            names.insert(function.name().to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_inherent_method_shadowing_trait_method_is_flagged() {
+        let lint = Box::new(ShadowedTraitMethod);
+        let source_code = r#"
+            struct Point { x: Field }
+
+            trait Hash {
+                fn hash(self) -> Field;
+            }
+
+            impl Point {
+                fn hash(self) -> Field { 1 }
+                fn norm(self) -> Field { 2 }
+            }
+
+            impl Hash for Point {
+                fn hash(self) -> Field { 3 }
+            }
+
+            fn main() {}
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Method 'Point::hash' shadows the method of a trait implemented for 'Point'"
+        );
+    }
+}
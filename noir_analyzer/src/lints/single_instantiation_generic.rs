@@ -0,0 +1,234 @@
+//! # Single Instantiation Generic Lint
+//!
+//! Flags generic functions whose every call uses the same concrete type arguments. The
+//! generic parameters then only make the function harder to read, and hide the types its
+//! constraints actually operate on.
+//!
+//! Type arguments are read from turbofish paths, such as `hash::<u8>(x)`, or inferred from
+//! arguments passed to parameters typed by a generic, when the argument is an annotated
+//! variable, a parameter or a cast. Functions with a call whose type arguments cannot be
+//! determined are not reported. This pedantic rule is opt-in.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ast::{
+    CallExpression, Expression, ExpressionKind, FunctionDefinition, LetStatement, Literal, Pattern,
+    UnresolvedType, UnresolvedTypeData, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::{BTreeMap, HashMap};
+
+/// Detects generic functions always called with the same type arguments.
+#[derive(Clone, Default)]
+pub struct SingleInstantiationGeneric;
+
+declare_lint! {
+    SingleInstantiationGeneric {
+        name: "single-instantiation-generic",
+        explanation: "Reports generic functions whose every call uses the same concrete type arguments. Replacing the generic parameters by these types makes the function, and the constraints it generates, easier to understand. This pedantic lint is opt-in.",
+        requires_whole_program: true,
+        enabled_by_default: false,
+    }
+}
+
+impl SingleInstantiationGeneric {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let generic_functions: HashMap<&String, &FunctionDefinition> = context
+            .function_definitions
+            .iter()
+            .filter(|(_, function)| !function.generics.is_empty())
+            .collect();
+        if generic_functions.is_empty() {
+            return vec![];
+        }
+
+        let mut collector = CallCollector {
+            generic_functions: &generic_functions,
+            variable_types: HashMap::new(),
+            instantiations: BTreeMap::new(),
+        };
+        for function in context.function_definitions.values() {
+            collector.variable_types = function
+                .parameters
+                .iter()
+                .filter_map(|parameter| declared_type(&parameter.pattern, &parameter.typ))
+                .collect();
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+        }
+
+        let mut lints = vec![];
+        for (name, instantiations) in collector.instantiations {
+            let [first, rest @ ..] = instantiations.as_slice() else {
+                continue;
+            };
+            let Some(type_arguments) = first else {
+                continue;
+            };
+            if rest
+                .iter()
+                .any(|other| other.as_ref() != Some(type_arguments))
+            {
+                continue;
+            }
+
+            let function = generic_functions[&name];
+            let assignments: Vec<String> = function
+                .generics
+                .iter()
+                .zip(type_arguments)
+                .map(|(generic, type_argument)| format!("{} = {type_argument}", generic.ident()))
+                .collect();
+            let (span, file_id) = Noir::function_location(function);
+            let message = Message::new("single-instantiation-generic")
+                .with("function", &name)
+                .with("type_arguments", assignments.join(", "));
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::Low,
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(span),
+                file_id: Some(file_id),
+                suggestion: None,
+            });
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns the name and written type of a variable declared with a type.
+fn declared_type(pattern: &Pattern, r#type: &UnresolvedType) -> Option<(String, String)> {
+    let ident = match pattern {
+        Pattern::Identifier(ident) => ident,
+        Pattern::Mutable(pattern, ..) => match pattern.as_ref() {
+            Pattern::Identifier(ident) => ident,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if matches!(r#type.typ, UnresolvedTypeData::Unspecified) {
+        return None;
+    }
+    Some((ident.to_string(), r#type.to_string()))
+}
+
+/// Records the type arguments of every call to a generic function.
+struct CallCollector<'a> {
+    generic_functions: &'a HashMap<&'a String, &'a FunctionDefinition>,
+    /// Written types of the variables of the current function.
+    variable_types: HashMap<String, String>,
+    /// Type arguments of each call, `None` when they could not be determined.
+    instantiations: BTreeMap<String, Vec<Option<Vec<String>>>>,
+}
+
+impl CallCollector<'_> {
+    /// Returns the type arguments of a call, in the order of the callee generics.
+    fn type_arguments(
+        &self,
+        call: &CallExpression,
+        callee: &FunctionDefinition,
+        turbofish: Option<&Vec<UnresolvedType>>,
+    ) -> Option<Vec<String>> {
+        if let Some(turbofish) = turbofish {
+            return (turbofish.len() == callee.generics.len())
+                .then(|| turbofish.iter().map(ToString::to_string).collect());
+        }
+
+        callee
+            .generics
+            .iter()
+            .map(|generic| {
+                let generic = generic.ident().to_string();
+                callee
+                    .parameters
+                    .iter()
+                    .zip(&call.arguments)
+                    .filter(|(parameter, _)| parameter.typ.to_string() == generic)
+                    .find_map(|(_, argument)| self.argument_type(argument))
+            })
+            .collect()
+    }
+
+    /// Returns the written type of an argument, when it is evident.
+    fn argument_type(&self, argument: &Expression) -> Option<String> {
+        match &argument.kind {
+            ExpressionKind::Variable(path) if path.segments.len() == 1 => self
+                .variable_types
+                .get(&path.segments[0].ident.to_string())
+                .cloned(),
+            ExpressionKind::Cast(cast) => Some(cast.r#type.to_string()),
+            ExpressionKind::Literal(Literal::Bool(_)) => Some("bool".to_string()),
+            ExpressionKind::Parenthesized(expression) => self.argument_type(expression),
+            _ => None,
+        }
+    }
+}
+
+impl Visitor for CallCollector<'_> {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        if let Some((name, r#type)) = declared_type(&let_statement.pattern, &let_statement.r#type) {
+            self.variable_types.insert(name, r#type);
+        }
+        true
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression, _: Span) -> bool {
+        let ExpressionKind::Variable(path) = &call.func.kind else {
+            return true;
+        };
+        let Some(segment) = path.segments.last() else {
+            return true;
+        };
+        let name = segment.ident.to_string();
+        let Some(callee) = self.generic_functions.get(&name) else {
+            return true;
+        };
+
+        let type_arguments = self.type_arguments(call, callee, segment.generics.as_ref());
+        self.instantiations
+            .entry(name)
+            .or_default()
+            .push(type_arguments);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_generic_function_called_with_one_type_is_flagged() {
+        let lint = Box::new(SingleInstantiationGeneric);
+        let source_code = r#"
+            fn double<T>(x: T) -> [T; 2] { [x, x] }
+            fn identity<T>(x: T) -> T { x }
+
+            pub fn main(a: u8, b: Field) {
+                let c: u8 = 3;
+                assert(double(a)[0] == double::<u8>(c)[1]);
+                assert(identity(a) as Field == identity(b));
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Generic function 'double' is only called with T = u8"
+        );
+    }
+}
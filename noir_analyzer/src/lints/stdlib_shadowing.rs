@@ -0,0 +1,254 @@
+//! # Standard Library Shadowing Lint
+//!
+//! Flags functions and imports named like a function of the standard library, such as a
+//! local `pedersen_hash`. Call sites do not say where a name comes from, so `pedersen_hash(x)`
+//! silently binds to the local definition, which a reviewer reads as the standard hash: a
+//! sneaky way to bypass real hashing, or signature verification, in reviewed code.
+//!
+//! Only the cryptographic primitives and the assertion and printing helpers of the standard
+//! library are checked. Imports of these functions from the standard library under their
+//! own name are fine; importing another function under their name, e.g.
+//! `use std::hash::poseidon2_permutation as pedersen_hash`, is reported too.
+//!
+//! Functions a project shadows on purpose, e.g. a `println` wrapper, can be allowed in
+//! `noir-analyzer.toml`:
+//!
+//! ```toml
+//! [lints.stdlib-shadowing]
+//! allow = ["println"]
+//! ```
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use crate::lints::import_order::{join_path, render_path};
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{UseTree, UseTreeKind};
+use noirc_frontend::parser::{Item, ItemKind};
+use serde::Deserialize;
+
+/// Functions of the standard library that are dangerous to shadow, with their path.
+const STD_FUNCTIONS: &[(&str, &str)] = &[
+    ("aes128_encrypt", "std::aes128::aes128_encrypt"),
+    ("as_witness", "std::as_witness"),
+    ("assert_constant", "std::assert_constant"),
+    ("blake2s", "std::hash::blake2s"),
+    ("blake3", "std::hash::blake3"),
+    ("derive_generators", "std::hash::derive_generators"),
+    (
+        "embedded_curve_add",
+        "std::embedded_curve_ops::embedded_curve_add",
+    ),
+    (
+        "fixed_base_scalar_mul",
+        "std::embedded_curve_ops::fixed_base_scalar_mul",
+    ),
+    ("hash_to_field", "std::hash::hash_to_field"),
+    ("keccakf1600", "std::hash::keccakf1600"),
+    (
+        "multi_scalar_mul",
+        "std::embedded_curve_ops::multi_scalar_mul",
+    ),
+    ("pedersen_commitment", "std::hash::pedersen_commitment"),
+    ("pedersen_hash", "std::hash::pedersen_hash"),
+    ("poseidon2_permutation", "std::hash::poseidon2_permutation"),
+    ("print", "std::print"),
+    ("println", "std::println"),
+    ("sha256_compression", "std::hash::sha256_compression"),
+    ("static_assert", "std::static_assert"),
+    ("verify_proof", "std::verify_proof"),
+    ("verify_signature", "std::ecdsa_secp256k1::verify_signature"),
+];
+
+/// Detects functions and imports shadowing a function of the standard library.
+#[derive(Clone, Default)]
+pub struct StdlibShadowing {
+    options: StdlibShadowingOptions,
+}
+
+/// Options of [`StdlibShadowing`], set under `[lints.stdlib-shadowing]`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct StdlibShadowingOptions {
+    /// Names of the standard library functions that may be shadowed.
+    pub allow: Vec<String>,
+}
+
+declare_lint! {
+    StdlibShadowing {
+        name: "stdlib-shadowing",
+        explanation: "Reports functions and imports named like a cryptographic primitive or assertion helper of the standard library, such as `pedersen_hash`. Calls then bind to the local item while reading like the standard one, which can hide a weakened implementation from reviewers; rename the item, or call it through its module.",
+        requires_whole_program: true,
+        options: StdlibShadowingOptions,
+    }
+}
+
+impl StdlibShadowing {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        self.lint_module(context.parsed_module, &mut lints);
+        lints
+    }
+
+    fn lint_module(&self, parsed_module: &ParsedModule, lints: &mut Vec<Lint>) {
+        for item in &parsed_module.items {
+            match &item.kind {
+                ItemKind::Submodules(submodule) => {
+                    self.lint_module(&submodule.contents, lints);
+                }
+                ItemKind::Function(function) => {
+                    let name = function.name();
+                    if let Some(std_path) = self.std_path(name) {
+                        let message = Message::new("stdlib-shadowing.function")
+                            .with("function", name)
+                            .with("std_path", std_path);
+                        lints.push(self.lint(item, message));
+                    }
+                }
+                ItemKind::Import(use_tree, _) => {
+                    let mut imports = vec![];
+                    collect_imports(use_tree, String::new(), &mut imports);
+                    for (path, name) in imports {
+                        let Some(std_path) = self.std_path(&name) else {
+                            continue;
+                        };
+                        let from_std = path.strip_prefix("dep::").unwrap_or(&path);
+                        if from_std.starts_with("std::") && from_std.ends_with(&format!("::{name}"))
+                        {
+                            continue;
+                        }
+                        let message = Message::new("stdlib-shadowing.import")
+                            .with("path", path)
+                            .with("name", name)
+                            .with("std_path", std_path);
+                        lints.push(self.lint(item, message));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the path of the standard library function of the given name, if it is checked
+    /// and not allowed.
+    fn std_path(&self, name: &str) -> Option<&'static str> {
+        std_path(name).filter(|_| !self.options.allow.iter().any(|allowed| allowed == name))
+    }
+
+    fn lint(&self, item: &Item, message: Message) -> Lint {
+        let (span, file_id) = Noir::item_location(item);
+        Lint {
+            name: Self::NAME,
+            severity: Severity::Warning,
+            // Wrappers may shadow a function on purpose
+            confidence: Confidence::Medium,
+            description: message.to_string(),
+            message: Some(message),
+            span: Some(span),
+            file_id: Some(file_id),
+            suggestion: None,
+        }
+    }
+}
+
+/// Returns the path of the standard library function of the given name, if it is checked.
+fn std_path(name: &str) -> Option<&'static str> {
+    STD_FUNCTIONS
+        .binary_search_by_key(&name, |(function, _)| function)
+        .ok()
+        .map(|index| STD_FUNCTIONS[index].1)
+}
+
+/// Collects the paths a use tree imports, with the name each is brought into scope as.
+fn collect_imports(use_tree: &UseTree, parent: String, imports: &mut Vec<(String, String)>) {
+    let parent = join_path(&parent, &render_path(&use_tree.prefix, parent.is_empty()));
+    match &use_tree.kind {
+        UseTreeKind::Path(name, alias) => {
+            let scope_name = alias.as_ref().unwrap_or(name).to_string();
+            imports.push((join_path(&parent, &name.to_string()), scope_name));
+        }
+        UseTreeKind::List(trees) => {
+            for tree in trees {
+                collect_imports(tree, parent.clone(), imports);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::lints::lint_rule::LintRule;
+
+    #[test]
+    fn test_shadowed_stdlib_functions_are_reported() {
+        let source = r#"
+            use std::hash::pedersen_hash;
+            use std::hash::poseidon2_permutation as sha256_compression;
+            use dep::lib::{blake3, keccak};
+
+            mod crypto {
+                pub fn verify_signature(_key: Field) -> bool {
+                    true
+                }
+            }
+
+            fn main(x: Field) {
+                let _ = pedersen_hash([x]);
+            }
+        "#;
+        let lint = Box::new(StdlibShadowing::default());
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Import of 'std::hash::poseidon2_permutation' shadows 'std::hash::sha256_compression', so calls to 'sha256_compression' bind to it instead of the standard library",
+                "Import of 'dep::lib::blake3' shadows 'std::hash::blake3', so calls to 'blake3' bind to it instead of the standard library",
+                "Function 'verify_signature' shadows 'std::ecdsa_secp256k1::verify_signature', so calls to it in its module bind to it instead of the standard library",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allowed_functions_are_not_reported() {
+        let source = r#"
+            use dep::lib::blake3;
+
+            pub fn println<T>(input: T) {
+                std::println(input);
+            }
+        "#;
+        let mut lint = StdlibShadowing::default();
+        lint.configure(toml::toml! { allow = ["println"] }.into())
+            .expect("Should have configured");
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[Box::new(lint)]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].description.contains("'dep::lib::blake3'"));
+        assert!(
+            StdlibShadowing::default()
+                .configure(toml::toml! { allowed = ["println"] }.into())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_std_functions_are_sorted() {
+        assert!(STD_FUNCTIONS.is_sorted_by_key(|(function, _)| *function));
+    }
+}
@@ -0,0 +1,282 @@
+//! # Struct Field Order Lint
+//!
+//! Flags struct constructor expressions listing their fields in another order than the
+//! declaration of the struct, such as `Point { y, x }` for `struct Point { x: Field, y: Field }`.
+//! The fields of a struct are laid out in declaration order in the witness, and constructors
+//! written in the same order keep that layout visible when reviewing diffs.
+//!
+//! When the source is known, each constructor gets a fix reordering its fields and keeping
+//! the separators between them; constructors with comments between their fields get no fix,
+//! as it would move the comments. Structs are matched by name, `Self` included within `impl`
+//! blocks; names declared by several structs are left alone. This pedantic rule is opt-in.
+
+use crate::analysis::call_graph::{collect_functions, type_name};
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{ConstructorExpression, Expression, ExpressionKind, Visitor};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use std::collections::HashMap;
+
+/// Detects struct constructors whose fields are not in declaration order.
+#[derive(Clone, Default)]
+pub struct StructFieldOrder;
+
+declare_lint! {
+    StructFieldOrder {
+        name: "struct-field-order",
+        explanation: "Reports struct constructor expressions whose fields are not listed in the order of the struct declaration. Fields are laid out in declaration order in the witness, so constructors following it are easier to review against the layout. This pedantic lint is opt-in.",
+        requires_whole_program: true,
+        enabled_by_default: false,
+    }
+}
+
+impl StructFieldOrder {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        // Field names of each struct, `None` for names declared by several structs
+        let mut structs = HashMap::new();
+        collect_structs(context.parsed_module, &mut structs);
+
+        let mut functions = vec![];
+        collect_functions(context.parsed_module, &mut functions);
+
+        let mut collector = ConstructorCollector {
+            structs: &structs,
+            self_type: None,
+            source: context.source,
+            lints: vec![],
+        };
+        for node in functions {
+            collector.self_type = node.self_type;
+            for statement in &node.function.def.body.statements {
+                statement.accept(&mut collector);
+            }
+        }
+
+        let mut lints = collector.lints;
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Collects the field names of the structs of a module and its submodules.
+fn collect_structs(
+    parsed_module: &ParsedModule,
+    structs: &mut HashMap<String, Option<Vec<String>>>,
+) {
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Submodules(submodule) => collect_structs(&submodule.contents, structs),
+            ItemKind::Struct(noir_struct) => {
+                let fields = noir_struct
+                    .fields
+                    .iter()
+                    .map(|field| field.item.name.to_string())
+                    .collect();
+                structs
+                    .entry(noir_struct.name.to_string())
+                    .and_modify(|declared| *declared = None)
+                    .or_insert(Some(fields));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reports the constructors of the visited statements whose fields are out of order.
+struct ConstructorCollector<'a> {
+    structs: &'a HashMap<String, Option<Vec<String>>>,
+    /// Name of the type `Self` refers to in the current function.
+    self_type: Option<String>,
+    source: Option<&'a str>,
+    lints: Vec<Lint>,
+}
+
+impl ConstructorCollector<'_> {
+    fn check_constructor(&mut self, constructor: &ConstructorExpression, expression: &Expression) {
+        let Some(mut name) = type_name(&constructor.typ) else {
+            return;
+        };
+        if name == "Self" {
+            let Some(self_type) = &self.self_type else {
+                return;
+            };
+            name = self_type.clone();
+        }
+        let Some(Some(declared)) = self.structs.get(&name) else {
+            return;
+        };
+
+        // Positions of the fields in the declaration, in the order they are written
+        let positions: Option<Vec<usize>> = constructor
+            .fields
+            .iter()
+            .map(|(field, _)| {
+                let field = field.to_string();
+                declared.iter().position(|declared| *declared == field)
+            })
+            .collect();
+        let Some(positions) = positions else {
+            return;
+        };
+        if positions.is_sorted() {
+            return;
+        }
+
+        let mut ordered: Vec<(usize, String)> = positions
+            .iter()
+            .zip(&constructor.fields)
+            .map(|(position, (field, _))| (*position, field.to_string()))
+            .collect();
+        ordered.sort();
+        let order: Vec<String> = ordered
+            .iter()
+            .map(|(_, field)| format!("'{field}'"))
+            .collect();
+
+        let (span, file_id) = Noir::expression_location(expression);
+        let message = Message::new("struct-field-order")
+            .with("struct", &name)
+            .with("order", order.join(", "));
+        self.lints.push(Lint {
+            name: StructFieldOrder::NAME,
+            severity: Severity::Warning,
+            confidence: Confidence::High,
+            description: message.to_string(),
+            message: Some(message),
+            span: Some(span),
+            file_id: Some(file_id),
+            suggestion: self
+                .source
+                .and_then(|source| reorder_fields(source, constructor, &positions)),
+        });
+    }
+}
+
+impl Visitor for ConstructorCollector<'_> {
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        if let ExpressionKind::Constructor(constructor) = &expression.kind {
+            self.check_constructor(constructor, expression);
+        }
+        true
+    }
+}
+
+/// Returns the fix listing the fields of a constructor in declaration order, given the
+/// declaration position of each field as written. Each field keeps its value, and the
+/// separators between fields stay in place.
+fn reorder_fields(
+    source: &str,
+    constructor: &ConstructorExpression,
+    positions: &[usize],
+) -> Option<Suggestion> {
+    // Source range of each field, from its name to the end of its value
+    let ranges: Vec<(usize, usize)> = constructor
+        .fields
+        .iter()
+        .map(|(field, value)| {
+            let start = field.span().start() as usize;
+            let end = Noir::expression_location(value)
+                .0
+                .end()
+                .max(field.span().end());
+            (start, end as usize)
+        })
+        .collect();
+    let (start, end) = (ranges.first()?.0, ranges.last()?.1);
+    let separators: Vec<&str> = ranges
+        .windows(2)
+        .map(|pair| source.get(pair[0].1..pair[1].0))
+        .collect::<Option<_>>()?;
+    if separators
+        .iter()
+        .any(|separator| separator.contains("//") || separator.contains("/*"))
+    {
+        return None;
+    }
+
+    let mut fields: Vec<(usize, &str)> = positions
+        .iter()
+        .zip(&ranges)
+        .map(|(position, (start, end))| Some((*position, source.get(*start..*end)?)))
+        .collect::<Option<_>>()?;
+    fields.sort_by_key(|(position, _)| *position);
+
+    let mut replacement = String::new();
+    for (index, (_, field)) in fields.iter().enumerate() {
+        if index > 0 {
+            replacement.push_str(separators[index - 1]);
+        }
+        replacement.push_str(field);
+    }
+    Some(Suggestion {
+        message: "list the fields in declaration order".to_string(),
+        span: Span::from(start as u32..end as u32),
+        replacement,
+        applicability: Applicability::MachineApplicable,
+        other_edits: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    #[test]
+    fn test_constructors_out_of_declaration_order_are_fixed() {
+        let source = "struct Point {\n    x: Field,\n    y: Field,\n    z: Field,\n}\n\nimpl Point {\n    fn new(x: Field, y: Field) -> Self {\n        Self { y, z: x + y, x }\n    }\n}\n\nfn main(x: Field) -> pub Point {\n    let origin = Point { x: 0, y: 0, z: 0 };\n    Point {\n        z: origin.z,\n        x,\n        y: x * 2,\n    }\n}\n";
+        let lint = Box::new(StructFieldOrder);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Fields of this 'Point' constructor are not in declaration order ('x', 'y', 'z')",
+                "Fields of this 'Point' constructor are not in declaration order ('x', 'y', 'z')",
+            ]
+        );
+
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(false).apply(source, suggestions).source;
+        assert_eq!(
+            fixed,
+            "struct Point {\n    x: Field,\n    y: Field,\n    z: Field,\n}\n\nimpl Point {\n    fn new(x: Field, y: Field) -> Self {\n        Self { x, y, z: x + y }\n    }\n}\n\nfn main(x: Field) -> pub Point {\n    let origin = Point { x: 0, y: 0, z: 0 };\n    Point {\n        x,\n        y: x * 2,\n        z: origin.z,\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_structs_and_commented_fields() {
+        let source = "mod a {\n    pub struct Pair { first: Field, second: Field }\n}\nstruct Pair { second: Field, first: Field }\nstruct Range { low: u32, high: u32 }\n\nfn main() -> pub Range {\n    let pair = Pair { first: 1, second: 2 };\n    Range { high: 2, /* inclusive */ low: 1 }\n}\n";
+        let lint = Box::new(StructFieldOrder);
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        // `Pair` names two structs, and the fix would move the comment
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Fields of this 'Range' constructor are not in declaration order ('low', 'high')"
+        );
+        assert!(result[0].suggestion.is_none());
+    }
+}
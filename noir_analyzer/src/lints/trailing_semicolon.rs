@@ -0,0 +1,141 @@
+//! # Trailing Semicolon Lint
+//!
+//! Flags functions without a return type whose body ends with a comparison followed by `;`,
+//! such as `x == y;`. The semicolon turns the final expression into a statement, so the
+//! checked boolean is silently dropped and the function returns `()`. An `assert` was most
+//! likely intended.
+//!
+//! The AST does not keep the semicolon, so it is located in the source.
+
+use crate::ast::ast_context::AstContext;
+use crate::ast::trivia;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use noirc_frontend::ast::{
+    BinaryOpKind, Expression, ExpressionKind, FunctionReturnType, StatementKind,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+
+/// Detects comparisons whose result is dropped by a trailing semicolon.
+#[derive(Clone, Default)]
+pub struct TrailingSemicolon;
+
+declare_lint! {
+    TrailingSemicolon {
+        name: "trailing-semicolon",
+        explanation: "Reports functions without a return type ending with a comparison followed by `;`. The semicolon drops the checked boolean, so nothing is constrained; the comparison was most likely meant to be asserted.",
+    }
+}
+
+impl TrailingSemicolon {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let Some(source) = context.source else {
+            return vec![];
+        };
+        let mut lints = vec![];
+
+        for function in context.function_definitions.values() {
+            if !matches!(function.return_type, FunctionReturnType::Default(_)) {
+                continue;
+            }
+            let Some(statement) = function.body.statements.last() else {
+                continue;
+            };
+            let StatementKind::Semi(expression) = &statement.kind else {
+                continue;
+            };
+            if !is_comparison(expression) {
+                continue;
+            }
+
+            let (span, file_id) = Noir::expression_location(expression);
+            let Some(semicolon) = trivia::next_token(source, span.end() as usize)
+                .filter(|offset| source[*offset..].starts_with(';'))
+            else {
+                continue;
+            };
+            let condition = &source[span.start() as usize..span.end() as usize];
+
+            let message = Message::new("trailing-semicolon").with("function", &function.name);
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::High,
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(Span::from(semicolon as u32..semicolon as u32 + 1)),
+                file_id: Some(file_id),
+                suggestion: Some(Suggestion {
+                    message: "assert the comparison".to_string(),
+                    span,
+                    replacement: format!("assert({condition})"),
+                    // Returning the value may have been intended instead
+                    applicability: Applicability::MaybeIncorrect,
+                    other_edits: vec![],
+                }),
+            });
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns whether the expression compares two values.
+fn is_comparison(expression: &Expression) -> bool {
+    let ExpressionKind::Infix(infix) = &expression.kind else {
+        return false;
+    };
+
+    matches!(
+        infix.operator.contents,
+        BinaryOpKind::Equal
+            | BinaryOpKind::NotEqual
+            | BinaryOpKind::Less
+            | BinaryOpKind::LessEqual
+            | BinaryOpKind::Greater
+            | BinaryOpKind::GreaterEqual
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_trailing_semicolon_after_comparison_is_flagged() {
+        let lint = Box::new(TrailingSemicolon);
+        let source_code = "fn check(x: Field) { x == 5; }\nfn main() { check(5); }";
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source_code);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].span, Some(Span::from(27..28)));
+        let suggestion = result[0].suggestion.as_ref().unwrap();
+        assert_eq!(suggestion.span, Span::from(21..27));
+        assert_eq!(suggestion.replacement, "assert(x == 5)");
+    }
+
+    #[test]
+    fn test_returned_and_asserted_comparisons_are_not_flagged() {
+        let lint = Box::new(TrailingSemicolon);
+        let source_code = r#"
+            fn is_five(x: Field) -> bool { x == 5 }
+            fn check(x: Field) { assert(x == 5); }
+            fn main() { check(5); }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source_code);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert!(result.is_empty());
+    }
+}
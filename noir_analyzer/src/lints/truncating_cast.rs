@@ -0,0 +1,132 @@
+//! # Truncating Cast Lint
+//!
+//! Flags casts of a `Field` or integer value to a narrower integer type, such as `x as u8`
+//! with `x: u32`. The cast keeps the low bits of the value and drops the others without
+//! failing, so a value that does not fit silently becomes another one.
+//!
+//! The type of the value cast is only known once the program is type checked, so the rule
+//! runs on the typed HIR. Casts of literals are not reported.
+
+use crate::ast::ast_context::AstContext;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::hir::TypedContext;
+use crate::lints::lint_rule::TypedLintRule;
+use noirc_frontend::Type;
+use noirc_frontend::hir_def::expr::HirExpression;
+
+/// Number of bits of a `Field` element of the BN254 curve.
+const FIELD_BITS: u32 = 254;
+
+/// Detects casts to integer types narrower than the value cast.
+#[derive(Clone, Default)]
+pub struct TruncatingCast;
+
+impl TruncatingCast {
+    /// Unique name of the lint.
+    pub const NAME: &'static str = "truncating-cast";
+}
+
+impl TypedLintRule for TruncatingCast {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Reports casts of `Field` and integer values to a narrower integer type, such as `x as u8` with `x: u32`. The cast keeps the low bits of the value without checking that the others are zero, so a value out of range silently wraps around. Assert the range of the value before casting it, or keep the wider type."
+    }
+
+    fn lint(&self, _context: &AstContext, types: &TypedContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for function in types.functions() {
+            for expression in types.expressions(function) {
+                let HirExpression::Cast(cast) = types.expression(expression) else {
+                    continue;
+                };
+                if matches!(types.expression(cast.lhs), HirExpression::Literal(_)) {
+                    continue;
+                }
+
+                let from = types.type_of(cast.lhs);
+                let to = cast.r#type.follow_bindings();
+                let (Some(from_bits), Some(to_bits)) = (bit_size(&from), bit_size(&to)) else {
+                    continue;
+                };
+                if to_bits >= from_bits {
+                    continue;
+                }
+                let Some(span) = types.span(expression) else {
+                    continue;
+                };
+
+                let message = Message::new(Self::NAME)
+                    .with("from", &from)
+                    .with("to", &to)
+                    .with("function", types.function_name(function));
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: None,
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns the number of bits of a `Field` or integer type.
+fn bit_size(r#type: &Type) -> Option<u32> {
+    match r#type {
+        Type::FieldElement => Some(FIELD_BITS),
+        Type::Integer(_, bit_size) => Some(u32::from(bit_size.bit_size())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_narrowing_casts_are_flagged() {
+        let source_code = r#"
+            fn main(x: u32, y: Field, z: u8) -> pub u64 {
+                let a = x as u8;
+                let b = y as u64;
+                let c = z as u32;
+                let d = 300 as u8;
+                let _e = x as Field;
+                b + (a + d) as u64 + c as u64
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let types = TypedContext::from_source(source_code).expect("Should type check");
+        let mut analyzer = Analyzer::new(&[]);
+        analyzer.analyze(&root).expect("Should have passed");
+        let lint_rules: Vec<Box<dyn TypedLintRule>> = vec![Box::new(TruncatingCast)];
+        let result = analyzer.analyze_types(&types, &lint_rules);
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Cast of a 'u32' value to 'u8' in 'main' drops its high bits",
+                "Cast of a 'Field' value to 'u64' in 'main' drops its high bits",
+            ]
+        );
+    }
+}
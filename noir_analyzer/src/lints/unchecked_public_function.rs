@@ -0,0 +1,204 @@
+//! # Unchecked Public Function Lint
+//!
+//! Flags constrained `pub` functions of library packages whose body asserts nothing and
+//! computes its result from the parameters alone, without calling other functions. Libraries
+//! often pair a function computing a value with one checking it, such as `compute_root` and
+//! `check_root`; a function of this shape is then frequently the check that was never
+//! written, or a computation dependents take for one.
+//!
+//! Helpers that are pure computations on purpose are listed under the `allow` option. The
+//! lint belongs to the library-hygiene group, which the CLI only runs on library packages,
+//! and is opt-in.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    ConstrainExpression, Expression, ExpressionKind, FunctionReturnType, ItemVisibility,
+    NoirFunction, Visitor,
+};
+use noirc_frontend::parser::ItemKind;
+use serde::Deserialize;
+
+/// Detects public functions of libraries that compute a result without checking anything.
+#[derive(Clone, Default)]
+pub struct UncheckedPublicFunction {
+    options: UncheckedPublicFunctionOptions,
+}
+
+/// Options of [`UncheckedPublicFunction`], set under `[lints.unchecked-public-function]`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct UncheckedPublicFunctionOptions {
+    /// Names of the functions that are pure computations on purpose.
+    pub allow: Vec<String>,
+}
+
+declare_lint! {
+    UncheckedPublicFunction {
+        name: "unchecked-public-function",
+        explanation: "Reports constrained `pub` functions of library packages whose body contains no assertion and computes its result from the parameters alone, without calling other functions. Such a function is often the checking half of a compute/check pair that was never written, and dependents may rely on it to constrain their inputs. Add the missing assertions, or list intentional helpers under `allow`. This lint is opt-in.",
+        requires_whole_program: true,
+        enabled_by_default: false,
+        library_only: true,
+        options: UncheckedPublicFunctionOptions,
+    }
+}
+
+impl UncheckedPublicFunction {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        self.lint_module(context.parsed_module, &mut lints);
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+
+    fn lint_module(&self, parsed_module: &ParsedModule, lints: &mut Vec<Lint>) {
+        for item in &parsed_module.items {
+            match &item.kind {
+                ItemKind::Submodules(submodule) => self.lint_module(&submodule.contents, lints),
+                ItemKind::Function(function)
+                    if function.def.visibility == ItemVisibility::Public =>
+                {
+                    self.lint_function(function, lints);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn lint_function(&self, function: &NoirFunction, lints: &mut Vec<Lint>) {
+        let name = function.name();
+        if function.def.is_unconstrained
+            || function.def.parameters.is_empty()
+            || matches!(function.def.return_type, FunctionReturnType::Default(_))
+            || function.def.body.statements.is_empty()
+            || self.options.allow.iter().any(|allowed| allowed == name)
+        {
+            return;
+        }
+
+        let mut computation = PureComputation { is_pure: true };
+        for statement in &function.def.body.statements {
+            statement.accept(&mut computation);
+        }
+        if !computation.is_pure {
+            return;
+        }
+
+        let (span, file_id) = Noir::function_location(&function.def);
+        let message = Message::new(Self::NAME).with("function", name);
+        lints.push(Lint {
+            name: Self::NAME,
+            severity: Severity::Warning,
+            confidence: Confidence::Low,
+            description: message.to_string(),
+            message: Some(message),
+            span: Some(span),
+            file_id: Some(file_id),
+            suggestion: None,
+        });
+    }
+}
+
+/// Checks that the visited statements neither assert nor call anything, so that their
+/// result only depends on the values they read.
+struct PureComputation {
+    is_pure: bool,
+}
+
+impl Visitor for PureComputation {
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        if matches!(
+            expression.kind,
+            ExpressionKind::Constrain(_)
+                | ExpressionKind::Call(_)
+                | ExpressionKind::MethodCall(_)
+                | ExpressionKind::Unsafe(..)
+                | ExpressionKind::Comptime(..)
+        ) {
+            self.is_pure = false;
+        }
+        self.is_pure
+    }
+
+    fn visit_constrain_statement(&mut self, _: &ConstrainExpression) -> bool {
+        self.is_pure = false;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::lints::lint_rule::LintRule;
+
+    const SOURCE: &str = r#"
+        pub fn compute_root(leaf: Field, sibling: Field) -> Field {
+            let sum = leaf + sibling;
+            sum * sum
+        }
+
+        pub fn check_root(leaf: Field, sibling: Field, root: Field) {
+            assert(compute_root(leaf, sibling) == root);
+        }
+
+        pub fn hash_pair(left: Field, right: Field) -> Field {
+            std::hash::pedersen_hash([left, right])
+        }
+
+        pub fn is_small(x: u32) -> bool {
+            x < 100
+        }
+
+        pub unconstrained fn hint(x: Field) -> Field {
+            x * 2
+        }
+
+        fn private_double(x: Field) -> Field {
+            x * 2
+        }
+        "#;
+
+    fn descriptions(lint_rule: UncheckedPublicFunction) -> Vec<String> {
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let lint_rules: Vec<Box<dyn LintRule>> = vec![Box::new(lint_rule)];
+        let mut analyzer = Analyzer::new(&lint_rules);
+        analyzer
+            .analyze(&root)
+            .expect("Should have passed")
+            .into_iter()
+            .map(|lint| lint.description)
+            .collect()
+    }
+
+    #[test]
+    fn test_public_functions_without_checks_are_flagged() {
+        assert_eq!(
+            descriptions(UncheckedPublicFunction::default()),
+            [
+                "Public function 'compute_root' returns a computation of its inputs without asserting anything",
+                "Public function 'is_small' returns a computation of its inputs without asserting anything",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allowed_functions_are_not_flagged() {
+        let mut lint_rule = UncheckedPublicFunction::default();
+        lint_rule
+            .configure(toml::toml! { allow = ["is_small"] }.into())
+            .expect("Should have configured");
+        assert_eq!(
+            descriptions(lint_rule),
+            [
+                "Public function 'compute_root' returns a computation of its inputs without asserting anything",
+            ]
+        );
+    }
+}
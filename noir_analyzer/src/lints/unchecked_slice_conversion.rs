@@ -0,0 +1,249 @@
+//! # Unchecked Slice Conversion Lint
+//!
+//! Flags `slice.as_array()` conversions when the length of the slice is not asserted
+//! beforehand. The conversion fails at proving time when the lengths differ, with an error
+//! that does not point at the slice; an explicit `assert(slice.len() == N)` documents the
+//! invariant and fails with a clear location.
+//!
+//! When the conversion initializes a `let` annotated with an array type, the assertion is
+//! suggested with the annotated length.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use fm::FileId;
+use noirc_frontend::ast::{
+    ConstrainExpression, Expression, ExpressionKind, MethodCallExpression, Statement,
+    StatementKind, UnresolvedTypeData, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use std::collections::HashSet;
+
+/// Detects slice-to-array conversions without a preceding length assertion.
+#[derive(Clone, Default)]
+pub struct UncheckedSliceConversion;
+
+declare_lint! {
+    UncheckedSliceConversion {
+        name: "unchecked-slice-conversion",
+        explanation: "Reports `as_array()` conversions of slices whose length is never asserted before. A length mismatch makes proving fail far from its cause; assert `slice.len() == N` before converting.",
+    }
+}
+
+impl UncheckedSliceConversion {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for function in context.function_definitions.values() {
+            let mut collector = ConversionCollector::default();
+            for statement in &function.body.statements {
+                statement.accept(&mut collector);
+            }
+
+            for conversion in collector.conversions {
+                let suggestion = conversion.annotated_length.map(|(length, statement_span)| {
+                    let indentation = context
+                        .source
+                        .map(|source| indentation(source, statement_span.start() as usize))
+                        .unwrap_or(" ");
+                    Suggestion {
+                        message: "assert the length of the slice".to_string(),
+                        span: Span::from(statement_span.start()..statement_span.start()),
+                        replacement: format!(
+                            "assert({}.len() == {length});\n{indentation}",
+                            conversion.slice
+                        ),
+                        // The assertion adds a constraint, which may not be wanted when the
+                        // length is proven by other means
+                        applicability: Applicability::MaybeIncorrect,
+                        other_edits: vec![],
+                    }
+                });
+
+                let message = Message::new("unchecked-slice-conversion")
+                    .with("slice", &conversion.slice)
+                    .with("function", &function.name);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(conversion.span),
+                    file_id: Some(conversion.file_id),
+                    suggestion,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// A slice converted to an array without a length assertion.
+struct Conversion {
+    slice: String,
+    span: Span,
+    file_id: FileId,
+    /// The annotated array length, and the span of the `let` statement, when known.
+    annotated_length: Option<(String, Span)>,
+}
+
+/// Collects, in source order, the conversions of slices whose length was not asserted.
+#[derive(Default)]
+struct ConversionCollector {
+    /// Slices whose length is referenced by an assertion.
+    asserted: HashSet<String>,
+    /// The conversion initializing the current `let`, with the annotated length.
+    pending_let: Option<(Span, String, Span)>,
+    conversions: Vec<Conversion>,
+}
+
+impl Visitor for ConversionCollector {
+    fn visit_statement(&mut self, statement: &Statement) -> bool {
+        if let StatementKind::Let(let_statement) = &statement.kind
+            && let UnresolvedTypeData::Array(length, _) = &let_statement.r#type.typ
+            && converted_slice(&let_statement.expression).is_some()
+        {
+            let (expression_span, _) = Noir::expression_location(&let_statement.expression);
+            let (statement_span, _) = Noir::statement_location(statement);
+            self.pending_let = Some((expression_span, length.to_string(), statement_span));
+        }
+        true
+    }
+
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        let mut lengths = LengthCollector::default();
+        for argument in &constrain.arguments {
+            argument.accept(&mut lengths);
+        }
+        self.asserted.extend(lengths.slices);
+        true
+    }
+
+    fn visit_method_call_expression(
+        &mut self,
+        method_call: &MethodCallExpression,
+        span: Span,
+    ) -> bool {
+        if method_call.method_name.to_string() != "as_array" {
+            return true;
+        }
+        let Some(slice) = variable_name(&method_call.object) else {
+            return true;
+        };
+        if self.asserted.contains(&slice) {
+            return true;
+        }
+
+        let annotated_length = self
+            .pending_let
+            .take()
+            .filter(|(expression_span, _, _)| *expression_span == span)
+            .map(|(_, length, statement_span)| (length, statement_span));
+        let (_, file_id) = Noir::expression_location(&method_call.object);
+        self.conversions.push(Conversion {
+            slice,
+            span,
+            file_id,
+            annotated_length,
+        });
+        true
+    }
+}
+
+/// Collects the slices whose `len()` is referenced by an expression.
+#[derive(Default)]
+struct LengthCollector {
+    slices: Vec<String>,
+}
+
+impl Visitor for LengthCollector {
+    fn visit_method_call_expression(
+        &mut self,
+        method_call: &MethodCallExpression,
+        _: Span,
+    ) -> bool {
+        if method_call.method_name.to_string() == "len" {
+            self.slices.extend(variable_name(&method_call.object));
+        }
+        true
+    }
+}
+
+/// Returns the slice converted by an `as_array()` expression.
+fn converted_slice(expression: &Expression) -> Option<String> {
+    match &expression.kind {
+        ExpressionKind::MethodCall(method_call)
+            if method_call.method_name.to_string() == "as_array" =>
+        {
+            variable_name(&method_call.object)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the name of a local variable expression.
+fn variable_name(expression: &Expression) -> Option<String> {
+    match &expression.kind {
+        ExpressionKind::Variable(path) if path.segments.len() == 1 => {
+            Some(path.segments[0].ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the whitespace preceding the code on the line containing the offset.
+fn indentation(source: &str, offset: usize) -> &str {
+    let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line = &source[line_start..];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_unchecked_conversion_is_flagged() {
+        let lint = Box::new(UncheckedSliceConversion);
+        let source_code = r#"
+            fn checked(s: [Field]) -> [Field; 2] {
+                assert(s.len() == 2);
+                s.as_array()
+            }
+
+            fn unchecked(s: [Field]) -> [Field; 2] {
+                let array: [Field; 2] = s.as_array();
+                array
+            }
+
+            fn main() {}
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source_code);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Slice 's' is converted to an array in 'unchecked' without asserting its length"
+        );
+        let suggestion = result[0].suggestion.as_ref().unwrap();
+        assert_eq!(
+            suggestion.replacement,
+            "assert(s.len() == 2);\n                "
+        );
+        assert_eq!(
+            &source_code[suggestion.span.start() as usize..][..9],
+            "let array"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! # Unconstrained Parameter Lint
+//!
+//! Flags parameters of constrained functions that every caller populates with an unchecked
+//! unconstrained value, such as a variable bound to `unsafe { hint() }`, when no constraint
+//! of the function references them either. Each side can then assume that the other checks
+//! the value, and neither does: the function should constrain the parameter itself, or be
+//! marked `unconstrained` so that its callers know they have to.
+//!
+//! Call sites are found with the [`crate::analysis::call_graph`], and values are tracked as
+//! described in [`crate::analysis::taint`]. Only constrained callers are considered, and a
+//! function without any is not reported. Constraints in the functions the parameter is passed
+//! on to are not followed.
+
+use crate::analysis::taint::{Taint, variable_name};
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ast::{CallExpression, Expression, ExpressionKind, Pattern, Visitor};
+use noirc_frontend::hir::resolution::errors::Span;
+
+/// Detects parameters that only receive unchecked unconstrained values.
+#[derive(Clone, Default)]
+pub struct UnconstrainedParameter;
+
+declare_lint! {
+    UnconstrainedParameter {
+        name: "unconstrained-parameter",
+        explanation: "Reports parameters of constrained functions that every constrained caller passes an unconstrained value to, without constraining it, while no constraint of the function references them. Responsibility for checking the value is ambiguous, and no one does; constrain the parameter in the function, or mark the function `unconstrained`.",
+        requires_whole_program: true,
+        analyses: [CallGraph],
+    }
+}
+
+/// A call to a function from a constrained function.
+struct CallSite<'a> {
+    caller: &'a str,
+    /// Whether each argument is an unconstrained value the caller does not check.
+    unchecked: Vec<bool>,
+}
+
+impl UnconstrainedParameter {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        for (name, function) in &context.function_definitions {
+            if function.is_unconstrained || function.parameters.is_empty() {
+                continue;
+            }
+            let call_sites = call_sites(context, name);
+            if call_sites.is_empty() {
+                continue;
+            }
+            let constrained = Taint::of_function(context, function).constrained;
+
+            for (index, parameter) in function.parameters.iter().enumerate() {
+                let Pattern::Identifier(ident) = &parameter.pattern else {
+                    continue;
+                };
+                let parameter_name = ident.to_string();
+                if constrained.contains(&parameter_name)
+                    || !call_sites
+                        .iter()
+                        .all(|call_site| call_site.unchecked.get(index) == Some(&true))
+                {
+                    continue;
+                }
+
+                let mut callers: Vec<&str> = call_sites
+                    .iter()
+                    .map(|call_site| call_site.caller)
+                    .collect();
+                callers.dedup();
+                let callers = callers
+                    .iter()
+                    .map(|caller| format!("'{caller}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let (span, file_id) = Noir::function_location(function);
+                let message = Message::new("unconstrained-parameter")
+                    .with("parameter", &parameter_name)
+                    .with("function", name)
+                    .with("callers", callers);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    // Values may be checked later in the caller, or by the functions the
+                    // parameter is passed on to
+                    confidence: Confidence::Low,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+/// Returns the calls to a function made by the other constrained functions of the program,
+/// in the alphabetical order of their callers.
+fn call_sites<'a>(context: &'a AstContext, function: &str) -> Vec<CallSite<'a>> {
+    let mut call_sites = vec![];
+    for caller_name in context.call_graph.callers_of(function) {
+        let Some(caller) = context.function_definitions.get(caller_name) else {
+            continue;
+        };
+        // Recursive calls pass on the values the function already received
+        if caller.is_unconstrained || caller_name == function {
+            continue;
+        }
+
+        let taint = Taint::of_function(context, caller);
+        let mut collector = ArgumentCollector {
+            callee: function,
+            calls: vec![],
+        };
+        for statement in &caller.body.statements {
+            statement.accept(&mut collector);
+        }
+        for arguments in collector.calls {
+            call_sites.push(CallSite {
+                caller: caller_name,
+                unchecked: arguments
+                    .iter()
+                    .map(|argument| is_unchecked(&taint, argument))
+                    .collect(),
+            });
+        }
+    }
+    call_sites
+}
+
+/// Returns whether an argument is an unconstrained value that the caller does not check.
+fn is_unchecked(taint: &Taint, argument: &Expression) -> bool {
+    match &argument.kind {
+        ExpressionKind::Variable(path) => {
+            variable_name(path).is_some_and(|variable| taint.is_unchecked(&variable))
+        }
+        _ => taint.is_unconstrained(argument),
+    }
+}
+
+/// Collects the arguments of the calls to a function, resolved by the last segment of their
+/// path like in the call graph.
+struct ArgumentCollector<'a> {
+    callee: &'a str,
+    calls: Vec<Vec<Expression>>,
+}
+
+impl Visitor for ArgumentCollector<'_> {
+    fn visit_call_expression(&mut self, call: &CallExpression, _: Span) -> bool {
+        if let ExpressionKind::Variable(path) = &call.func.kind
+            && path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident.to_string() == self.callee)
+        {
+            self.calls.push(call.arguments.clone());
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_parameters_only_receiving_unchecked_values_are_flagged() {
+        let source_code = r#"
+            unconstrained fn hint() -> Field { 1 }
+
+            fn scale(a: Field, b: Field) -> Field {
+                assert(b != 0);
+                a * b
+            }
+
+            fn store(value: Field) -> Field { value + 1 }
+
+            fn double(value: Field) -> Field { value * 2 }
+
+            unconstrained fn offchain() -> Field { double(hint()) }
+
+            fn main(x: Field) -> pub Field {
+                // Safety: scaled below
+                let h = unsafe { hint() };
+                // Safety: checked right away
+                let c = unsafe { hint() };
+                assert(c != x);
+                let s = scale(h, h);
+                let t = store(h) + store(x);
+                s + t + double(c)
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint = Box::new(UnconstrainedParameter);
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Parameter 'a' of 'scale' only receives unconstrained values from 'main', and no assertion of 'scale' references it; constrain it, or mark 'scale' unconstrained"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! # Unconstrained Struct Field Lint
+//!
+//! Flags fields of a struct returned by a constrained function that are populated directly
+//! from unconstrained code, such as a variable bound to `unsafe { hint() }`, when no
+//! constraint of the function references them. The prover can then choose the field freely,
+//! and callers trusting the returned struct rely on an unchecked value.
+//!
+//! Values are tracked as described in [`crate::analysis::taint`]. Only the struct built by
+//! the final expression of the function is considered.
+
+use crate::analysis::taint::{Taint, variable_name};
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ast::{ExpressionKind, FunctionReturnType, StatementKind};
+
+/// Detects returned struct fields holding unconstrained values.
+#[derive(Clone, Default)]
+pub struct UnconstrainedStructField;
+
+declare_lint! {
+    UnconstrainedStructField {
+        name: "unconstrained-struct-field",
+        explanation: "Reports fields of a struct returned by a constrained function that come directly from unconstrained code and are referenced by no constraint. The prover can set such fields to any value; constrain them before returning the struct.",
+    }
+}
+
+impl UnconstrainedStructField {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            if function.is_unconstrained
+                || matches!(function.return_type, FunctionReturnType::Default(_))
+            {
+                continue;
+            }
+            let Some(returned) = function.body.statements.last() else {
+                continue;
+            };
+            let StatementKind::Expression(returned) = &returned.kind else {
+                continue;
+            };
+            let ExpressionKind::Constructor(constructor) = &returned.kind else {
+                continue;
+            };
+
+            let taint = Taint::of_function(context, function);
+
+            for (field, value) in &constructor.fields {
+                let is_unchecked = match &value.kind {
+                    ExpressionKind::Variable(path) => {
+                        variable_name(path).is_some_and(|variable| taint.is_unchecked(&variable))
+                    }
+                    _ => taint.is_unconstrained(value),
+                };
+                if !is_unchecked {
+                    continue;
+                }
+
+                let (span, file_id) = Noir::expression_location(value);
+                let message = Message::new("unconstrained-struct-field")
+                    .with("field", field)
+                    .with("function", name);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::Medium,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_unchecked_returned_field_is_flagged() {
+        let lint = Box::new(UnconstrainedStructField);
+        let source_code = r#"
+            struct Pair { a: Field, b: Field, c: Field }
+
+            unconstrained fn hint() -> Field { 1 }
+
+            fn make(x: Field) -> Pair {
+                // Safety: a is checked below
+                let a = unsafe { hint() };
+                // Safety: b is returned as is
+                let b = unsafe { hint() };
+                assert(a == x);
+                Pair { a, b, c: x }
+            }
+
+            fn main(x: Field) {
+                let pair = make(x);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].description,
+            "Field 'b' of the struct returned by 'make' comes from unconstrained code and is never constrained"
+        );
+    }
+}
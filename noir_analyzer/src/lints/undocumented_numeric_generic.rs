@@ -0,0 +1,200 @@
+//! # Undocumented Numeric Generic Lint
+//!
+//! Flags numeric generic parameters of `pub` functions, such as `N` in
+//! `pub fn pad<let N: u32>(..)`, that neither the doc comment of the function mentions nor an
+//! assertion of its body checks. Downstream packages instantiate library functions with
+//! whatever size fits their code, and a size outside the range the function was written for
+//! usually fails far from the call site, or silently truncates.
+//!
+//! A generic counts as checked when it appears in an `assert`, `assert_eq` or
+//! `static_assert` call of the body. The lint belongs to the library-hygiene group, which the
+//! CLI only runs on library packages.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{
+    CallExpression, ConstrainExpression, ExpressionKind, ItemVisibility, NoirFunction, Path,
+    UnresolvedGeneric, Visitor,
+};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use std::collections::HashSet;
+
+/// Detects numeric generics of public functions without documented or asserted ranges.
+#[derive(Clone, Default)]
+pub struct UndocumentedNumericGeneric;
+
+declare_lint! {
+    UndocumentedNumericGeneric {
+        name: "undocumented-numeric-generic",
+        explanation: "Reports numeric generic parameters of `pub` functions of library packages that the doc comment of the function does not mention and no assertion of its body checks. Dependents instantiate them without knowing the sizes the function supports; document the valid range, or assert it with `static_assert`.",
+        requires_whole_program: true,
+        library_only: true,
+    }
+}
+
+impl UndocumentedNumericGeneric {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        lint_module(context.parsed_module, &mut lints);
+        lints.sort_by_key(|lint| lint.span.map(|span| span.start()));
+        lints
+    }
+}
+
+fn lint_module(parsed_module: &ParsedModule, lints: &mut Vec<Lint>) {
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Submodules(submodule) => lint_module(&submodule.contents, lints),
+            ItemKind::Function(function) if function.def.visibility == ItemVisibility::Public => {
+                lint_function(function, &item.doc_comments, lints);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn lint_function(function: &NoirFunction, doc_comments: &[String], lints: &mut Vec<Lint>) {
+    let numeric: Vec<&UnresolvedGeneric> = function
+        .def
+        .generics
+        .iter()
+        .filter(|generic| matches!(generic, UnresolvedGeneric::Numeric { .. }))
+        .collect();
+    if numeric.is_empty() {
+        return;
+    }
+
+    let documented: HashSet<&str> = doc_comments
+        .iter()
+        .flat_map(|line| line.split(|c: char| !c.is_alphanumeric() && c != '_'))
+        .collect();
+    let mut asserted = AssertedNames::default();
+    for statement in &function.def.body.statements {
+        statement.accept(&mut asserted);
+    }
+
+    let (_, file_id) = Noir::function_location(&function.def);
+    for generic in numeric {
+        let name = generic.ident().to_string();
+        if documented.contains(name.as_str()) || asserted.names.contains(&name) {
+            continue;
+        }
+        let message = Message::new("undocumented-numeric-generic")
+            .with("function", function.name())
+            .with("generic", &name);
+        lints.push(Lint {
+            name: UndocumentedNumericGeneric::NAME,
+            severity: Severity::Warning,
+            confidence: Confidence::Medium,
+            description: message.to_string(),
+            message: Some(message),
+            span: Some(generic.ident().span()),
+            file_id: Some(file_id),
+            suggestion: None,
+        });
+    }
+}
+
+/// Collects the names referenced by the assertions of the visited statements.
+#[derive(Default)]
+struct AssertedNames {
+    names: HashSet<String>,
+}
+
+impl Visitor for AssertedNames {
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainExpression) -> bool {
+        for argument in &constrain.arguments {
+            argument.accept(&mut Names(&mut self.names));
+        }
+        true
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression, _: Span) -> bool {
+        if let ExpressionKind::Variable(path) = &call.func.kind
+            && path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident.to_string() == "static_assert")
+        {
+            for argument in &call.arguments {
+                argument.accept(&mut Names(&mut self.names));
+            }
+        }
+        true
+    }
+}
+
+/// Collects the single-segment names referenced by the visited expressions.
+struct Names<'a>(&'a mut HashSet<String>);
+
+impl Visitor for Names<'_> {
+    fn visit_variable(&mut self, path: &Path, _: Span) -> bool {
+        if let [segment] = path.segments.as_slice() {
+            self.0.insert(segment.ident.to_string());
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_numeric_generics_without_documented_range_are_flagged() {
+        let lint = Box::new(UndocumentedNumericGeneric);
+        let source_code = r#"
+            /// Pads `input` with zeros up to `M` elements, for any `M` of at least `N`.
+            pub fn pad<let N: u32, let M: u32>(input: [Field; N]) -> [Field; M] {
+                let mut padded = [0; M];
+                for i in 0..N {
+                    padded[i] = input[i];
+                }
+                padded
+            }
+
+            pub fn truncate<let N: u32, let K: u32, T>(input: [T; N]) -> [T; K] {
+                static_assert(K <= N, "cannot truncate to a larger array");
+                let mut output = [input[0]; K];
+                for i in 0..K {
+                    output[i] = input[i];
+                }
+                output
+            }
+
+            pub fn sum<let N: u32>(input: [Field; N]) -> Field {
+                let mut total = 0;
+                for i in 0..N {
+                    total += input[i];
+                }
+                total
+            }
+
+            fn private_sum<let N: u32>(input: [Field; N]) -> Field {
+                sum(input)
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Public function 'sum' neither documents nor asserts the valid range of numeric generic 'N'",
+            ]
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! # Unsupported Syntax Lint
+//!
+//! Flags syntax that is newer than the Noir compiler declared in `Nargo.toml`. Such code
+//! compiles with the analyzer's frontend but fails with the compiler the package targets.
+
+use crate::ast::ast_context::AstContext;
+use crate::compiler_version::{CAPABILITIES, NoirVersion};
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+
+/// Detects uses of language features introduced after the targeted compiler version.
+#[derive(Clone)]
+pub struct UnsupportedSyntax {
+    /// The compiler version declared by the package.
+    pub compiler_version: NoirVersion,
+}
+
+impl UnsupportedSyntax {
+    /// Creates the lint for packages targeting the given compiler version.
+    pub fn new(compiler_version: NoirVersion) -> Self {
+        Self { compiler_version }
+    }
+}
+
+declare_lint! {
+    UnsupportedSyntax {
+        name: "unsupported-syntax",
+        explanation: "Reports syntax introduced after the Noir version declared as `compiler_version` in `Nargo.toml`. Such code fails to compile with the targeted compiler, so either the syntax or the declared version should change.",
+    }
+}
+
+impl UnsupportedSyntax {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+
+        for capability in CAPABILITIES {
+            if self.compiler_version.supports(capability) {
+                continue;
+            }
+
+            for (span, file_id) in (capability.find)(context) {
+                let message = Message::new("unsupported-syntax")
+                    .with("syntax", capability.name)
+                    .with("since", &capability.since)
+                    .with("version", &self.compiler_version);
+                lints.push(Lint {
+                    name: Self::NAME,
+                    severity: Severity::Warning,
+                    confidence: Confidence::High,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion: None,
+                });
+            }
+        }
+
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    const SOURCE: &str = r#"
+        unconstrained fn hint() -> Field { 1 }
+
+        fn main() {
+            // Safety: the hint is checked below
+            let a = unsafe { hint() };
+            assert(a == 1);
+        }
+    "#;
+
+    #[test]
+    fn test_unsupported_syntax_flags_unsafe_blocks_for_old_compilers() {
+        let lint = Box::new(UnsupportedSyntax::new(NoirVersion::new(0, 30, 0)));
+
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let lints = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(lints.len(), 1);
+        assert_eq!(
+            lints[0].description,
+            "unsafe blocks require Noir 0.39.0, but the package targets Noir 0.30.0"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_syntax_accepts_supported_syntax() {
+        let lint = Box::new(UnsupportedSyntax::new(NoirVersion::new(1, 0, 0)));
+
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let lints = analyzer.analyze(&root).expect("Should have passed");
+
+        assert!(lints.is_empty());
+    }
+}
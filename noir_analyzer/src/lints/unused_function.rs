@@ -1,51 +1,260 @@
 //! # Unused Function Lint
 //!
-//! This lint will eventually check for functions that are defined but never used.
+//! Flags private and `pub(crate)` functions that are never called. When the source is known,
+//! each gets a fix deleting the whole function with its doc comments and attributes, and the
+//! `use` declarations importing nothing but unused functions are reported with a fix deleting
+//! them, so that removing dead code does not leave imports of missing functions behind.
+//! Functions with a function attribute, such as `#[test]` or `#[fuzz]`, and `#[export]`
+//! functions are called by tooling and never reported.
+//!
+//! Calls are matched to functions by fully-qualified path, as resolved by
+//! [`crate::analysis::resolution`]: a function called through an alias or a `crate::` path is
+//! used, while a call to a function of another module with the same name does not count.
+//! Functions only called by themselves, according to the [`crate::analysis::call_graph`], are
+//! unused too.
+//!
+//! Deleting a function can leave the functions only it called unused; `check --fix` analyzes
+//! the fixed source again and removes them in turn.
 
+use crate::analysis::module_graph::ROOT_MODULE;
+use crate::analysis::resolution::flatten_use_tree;
 use crate::ast::ast_context::AstContext;
-use crate::diagnostics::lint::{Lint, Severity};
-use crate::lints::lint_rule::LintRule;
-use noirc_frontend::ast::ItemVisibility;
+use crate::ast::trivia;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use crate::lints::redundant_import::removal_span;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{FunctionDefinition, ItemVisibility, NoirFunction};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+use std::collections::BTreeMap;
 
 /// A placeholder lint for detecting unused functions.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct UnusedFunction;
 
-impl LintRule for UnusedFunction {
-    fn name(&self) -> &'static str {
-        "unused-function"
-    }
-
-    fn boxed_clone(&self) -> Box<dyn LintRule> {
-        Box::new(UnusedFunction)
+declare_lint! {
+    UnusedFunction {
+        name: "unused-function",
+        explanation: "Reports functions that are defined but never called. Dead code still has to be read and audited, and may hide a missing call.",
+        requires_whole_program: true,
+        analyses: [CallGraph],
     }
+}
 
-    fn lint(&self, context: &AstContext) -> Vec<Lint> {
+impl UnusedFunction {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
         let mut lints = vec![];
+        let mut items = vec![];
+        function_items(
+            context.parsed_module,
+            &[ROOT_MODULE.to_string()],
+            &mut items,
+        );
+        // Absolute paths of the unused functions, mapped to their names
+        let mut unused_paths = BTreeMap::new();
 
-        for (name, function) in &context.function_definitions {
+        for item in &items {
+            let function = &item.function.def;
             if function.visibility != ItemVisibility::Public
-                && !context.function_calls.contains_key(name)
+                && !is_called_by_tooling(function)
+                && !is_called(context, &items, item)
             {
+                let (span, file_id) = Noir::function_location(function);
+                unused_paths.insert(item.path.clone(), function.name.to_string());
+                let suggestion = context.source.map(|source| Suggestion {
+                    message: "remove the unused function".to_string(),
+                    span: item_removal_span(source, item.span),
+                    replacement: String::new(),
+                    applicability: Applicability::MachineApplicable,
+                    other_edits: vec![],
+                });
+
+                let message = Message::new("unused-function").with("function", &function.name);
                 lints.push(Lint {
-                    name: self.name(),
+                    name: Self::NAME,
                     severity: Severity::Warning,
-                    description: format!("Function '{}' is unused", function.name),
-                    span: Some(function.location.span),
-                    file_id: Some(function.location.file), // Now includes file info
+                    confidence: Confidence::High,
+                    description: message.to_string(),
+                    message: Some(message),
+                    span: Some(span),
+                    file_id: Some(file_id),
+                    suggestion,
                 });
             }
         }
 
+        if !unused_paths.is_empty() {
+            self.lint_imports(
+                context,
+                context.parsed_module,
+                &[ROOT_MODULE.to_string()],
+                &unused_paths,
+                &mut lints,
+            );
+        }
         lints
     }
+
+    /// Reports the `use` declarations importing only unused functions.
+    fn lint_imports(
+        &self,
+        context: &AstContext,
+        parsed_module: &ParsedModule,
+        module_path: &[String],
+        unused_paths: &BTreeMap<String, String>,
+        lints: &mut Vec<Lint>,
+    ) {
+        for item in &parsed_module.items {
+            match &item.kind {
+                ItemKind::Submodules(submodule) => {
+                    let mut submodule_path = module_path.to_vec();
+                    submodule_path.push(submodule.name.to_string());
+                    self.lint_imports(
+                        context,
+                        &submodule.contents,
+                        &submodule_path,
+                        unused_paths,
+                        lints,
+                    );
+                }
+                ItemKind::Import(use_tree, _) => {
+                    let imports = flatten_use_tree(use_tree, module_path, Vec::new());
+                    let functions: Option<Vec<&String>> = imports
+                        .iter()
+                        .map(|(path, _)| unused_paths.get(path))
+                        .collect();
+                    let Some(functions) = functions else {
+                        continue;
+                    };
+
+                    let (span, file_id) = Noir::item_location(item);
+                    let suggestion = context.source.map(|source| Suggestion {
+                        message: "remove the import".to_string(),
+                        span: removal_span(source, span),
+                        replacement: String::new(),
+                        applicability: Applicability::MachineApplicable,
+                        other_edits: vec![],
+                    });
+                    let functions: Vec<String> = functions
+                        .iter()
+                        .map(|function| format!("'{function}'"))
+                        .collect();
+                    let message = Message::new("unused-function.import")
+                        .with("functions", functions.join(", "));
+                    lints.push(Lint {
+                        name: Self::NAME,
+                        severity: Severity::Warning,
+                        confidence: Confidence::High,
+                        description: message.to_string(),
+                        message: Some(message),
+                        span: Some(span),
+                        file_id: Some(file_id),
+                        suggestion,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A function item of the parsed program.
+struct FunctionItem<'ast> {
+    function: &'ast NoirFunction,
+    /// Absolute path of the function, e.g. `crate::a::foo`.
+    path: String,
+    /// Span of the whole item.
+    span: Span,
+}
+
+/// Returns whether a function is called by another function of the program, according to
+/// the resolved calls and the call graph. Recursive calls only count when another function
+/// has the same name, since the call graph does not tell them apart.
+fn is_called(context: &AstContext, items: &[FunctionItem], item: &FunctionItem) -> bool {
+    if !context.resolved_calls.contains_key(&item.path) {
+        return false;
+    }
+    let name = item.function.name();
+    let homonyms = items
+        .iter()
+        .filter(|other| other.function.name() == name)
+        .count();
+    homonyms > 1
+        || context
+            .call_graph
+            .callers_of(name)
+            .iter()
+            .any(|caller| *caller != name)
+}
+
+/// Returns whether a function is run by tooling rather than called from the program, such
+/// as tests, fuzzing harnesses, oracles and exported functions.
+fn is_called_by_tooling(function: &FunctionDefinition) -> bool {
+    Noir::function_attribute(function).is_some()
+        || function
+            .attributes
+            .secondary
+            .iter()
+            .any(|attribute| attribute.to_string() == "#[export]")
+}
+
+/// Collects the function items of a module and its submodules, in source order.
+fn function_items<'ast>(
+    parsed_module: &'ast ParsedModule,
+    module_path: &[String],
+    items: &mut Vec<FunctionItem<'ast>>,
+) {
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Submodules(submodule) => {
+                let mut submodule_path = module_path.to_vec();
+                submodule_path.push(submodule.name.to_string());
+                function_items(&submodule.contents, &submodule_path, items);
+            }
+            ItemKind::Function(function) => items.push(FunctionItem {
+                function,
+                path: format!("{}::{}", module_path.join("::"), function.name()),
+                span: Noir::item_location(item).0,
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the span removing a function item together with the doc comments and attributes
+/// on the lines above it and, when it stands on its own lines, the whole lines.
+fn item_removal_span(source: &str, span: Span) -> Span {
+    let mut start = span.start() as usize;
+    loop {
+        let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+        if line_start == 0 || !source[line_start..start].trim().is_empty() {
+            break;
+        }
+        let previous_start = source[..line_start - 1]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        let previous = source[previous_start..line_start].trim();
+        if !(previous.starts_with("///") || previous.starts_with("#[")) {
+            break;
+        }
+        start = previous_start
+            + (source[previous_start..].len() - source[previous_start..].trim_start().len());
+    }
+
+    let (start, end) = trivia::expand_to_lines(source, start, span.end() as usize);
+    Span::from(start as u32..end as u32)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::ast::analyzer::Analyzer;
     use crate::ast::parser::Parser;
-    use crate::diagnostics::lint::{Lint, Severity};
+    use crate::diagnostics::lint::{Confidence, Lint, Severity};
+    use crate::diagnostics::message::Message;
     use crate::lints::lint_rule::LintRule;
     use crate::lints::unused_function::UnusedFunction;
     use fm::FileId;
@@ -89,9 +298,12 @@ mod tests {
             Lint {
                 name: "unused-function",
                 severity: Severity::Warning,
+                confidence: Confidence::High,
                 description: "Function 'foo' is unused".to_string(),
+                message: Some(Message::new("unused-function").with("function", "foo")),
                 span: Some(Span::from(22..24)),
                 file_id: Some(FileId::dummy()), // Adjusted test to include file_id
+                suggestion: None,
             }
         );
     }
@@ -138,9 +350,12 @@ mod tests {
             Lint {
                 name: "unused-function",
                 severity: Severity::Warning,
+                confidence: Confidence::High,
                 description: "Function 'private_fn_2' is unused".to_string(),
+                message: Some(Message::new("unused-function").with("function", "private_fn_2")),
                 span: Some(Span::from(65..68)),
                 file_id: Some(FileId::dummy()), // Adjusted to include dummy file_id
+                suggestion: None,
             }
         );
 
@@ -149,10 +364,120 @@ mod tests {
             Lint {
                 name: "unused-function",
                 severity: Severity::Warning,
+                confidence: Confidence::High,
                 description: "Function 'crate_fn_2' is unused".to_string(),
+                message: Some(Message::new("unused-function").with("function", "crate_fn_2")),
                 span: Some(Span::from(151..154)),
                 file_id: Some(FileId::dummy()), // Adjusted to include dummy file_id
+                suggestion: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_calls_are_matched_to_functions_by_resolved_path() {
+        let lint = Box::new(UnusedFunction);
+        let source_code = r#"
+            mod a {
+                fn helper() {}
+                pub(crate) fn scale() { super::b::helper() }
+                pub(crate) fn hash() {}
             }
+            mod b {
+                pub(crate) fn helper() {}
+            }
+            use a::hash as digest;
+
+            pub fn main() {
+                crate::a::scale();
+                digest();
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        // Only `b::helper` is called, through a `super::` path
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].description, "Function 'helper' is unused");
+        assert_eq!(
+            result[0].span.map(|span| span.start()),
+            source_code.find("helper").map(|start| start as u32)
+        );
+    }
+
+    #[test]
+    fn test_functions_only_called_by_themselves_are_unused() {
+        let lint = Box::new(UnusedFunction);
+        let source_code = r#"
+            fn countdown(n: u32) -> u32 { if n == 0 { 0 } else { countdown(n - 1) } }
+            fn factorial(n: u32) -> u32 { if n == 0 { 1 } else { n * factorial(n - 1) } }
+            pub fn main(n: u32) -> pub u32 { factorial(n) }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].description, "Function 'countdown' is unused");
+    }
+
+    #[test]
+    fn test_functions_run_by_tooling_are_not_unused() {
+        let lint = Box::new(UnusedFunction);
+        let source_code = r#"
+            #[test]
+            fn test_main() { main(); }
+            #[fuzz]
+            fn fuzz_main(x: Field) { assert(x != 0); }
+            #[export]
+            fn exported(x: Field) -> Field { x }
+            #[deprecated]
+            fn helper() {}
+            pub fn main() {}
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].description, "Function 'helper' is unused");
+    }
+
+    #[test]
+    fn test_unused_function_fix_removes_the_item_and_its_imports() {
+        use crate::fix::Fixer;
+
+        let lint = Box::new(UnusedFunction);
+        let source = "mod a {\n    /// Helper.\n    #[deprecated]\n    pub(crate) fn helper() {}\n}\nuse a::helper;\n\n#[test]\nfn test_main() {}\n\npub fn main() {}\n";
+
+        let root = Parser::parse_program_with_dummy_file(source).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(source);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Function 'helper' is unused",
+                "Import only brings the unused function(s) 'helper' into scope",
+            ]
+        );
+
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(false).apply(source, suggestions).source;
+        assert_eq!(
+            fixed,
+            "mod a {\n}\n\n#[test]\nfn test_main() {}\n\npub fn main() {}\n"
         );
     }
 }
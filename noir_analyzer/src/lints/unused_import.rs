@@ -0,0 +1,231 @@
+//! # Unused Import Lint
+//!
+//! Flags imported names that no item of their module mentions. The AST does not record which
+//! names a path resolves through, so the source of the items of the module, imports and
+//! submodules aside, is searched for the name as a whole identifier instead.
+//!
+//! `use` declarations made only of unused names get a fix removing them, and the others a
+//! fix rewriting them without the unused names. Traits are imported for their methods
+//! without being named, so the fixes of capitalized names are not machine-applicable.
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::diagnostics::suggestion::{Applicability, Suggestion};
+use crate::frontend::Noir;
+use crate::lints::import_order::{join_path, render_path};
+use crate::lints::redundant_import::removal_span;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::{UseTree, UseTreeKind};
+use noirc_frontend::hir::resolution::errors::Span;
+use noirc_frontend::parser::ItemKind;
+
+/// Detects imported names that their module never mentions.
+#[derive(Clone, Default)]
+pub struct UnusedImport;
+
+declare_lint! {
+    UnusedImport {
+        name: "unused-import",
+        explanation: "Reports imported names that no item of their module mentions. Dead imports hide the actual dependencies of a module; remove them. Traits imported only for their methods are reported too, so check capitalized names before removing them.",
+        requires_whole_program: true,
+    }
+}
+
+impl UnusedImport {
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let mut lints = vec![];
+        if let Some(source) = context.source {
+            self.lint_module(source, context.parsed_module, &mut lints);
+        }
+        lints
+    }
+
+    fn lint_module(&self, source: &str, parsed_module: &ParsedModule, lints: &mut Vec<Lint>) {
+        // Source of the items that may mention imported names
+        let items: Vec<&str> = parsed_module
+            .items
+            .iter()
+            .filter(|item| !matches!(item.kind, ItemKind::Import(..) | ItemKind::Submodules(_)))
+            .filter_map(|item| {
+                let span = Noir::item_location(item).0;
+                source.get(span.start() as usize..span.end() as usize)
+            })
+            .collect();
+
+        for item in &parsed_module.items {
+            let use_tree = match &item.kind {
+                ItemKind::Submodules(submodule) => {
+                    self.lint_module(source, &submodule.contents, lints);
+                    continue;
+                }
+                ItemKind::Import(use_tree, _) => use_tree,
+                _ => continue,
+            };
+
+            let mut names = vec![];
+            scope_names(use_tree, &mut names);
+            let unused: Vec<String> = names
+                .into_iter()
+                .filter(|name| name != "self" && !items.iter().any(|text| mentions(text, name)))
+                .collect();
+            if unused.is_empty() {
+                continue;
+            }
+
+            let (span, file_id) = Noir::item_location(item);
+            let applicability = if unused
+                .iter()
+                .any(|name| name.starts_with(char::is_uppercase))
+            {
+                Applicability::MaybeIncorrect
+            } else {
+                Applicability::MachineApplicable
+            };
+            let suggestion = match prune(use_tree, &unused, true) {
+                None => Suggestion {
+                    message: "remove the import".to_string(),
+                    span: removal_span(source, span),
+                    replacement: String::new(),
+                    applicability,
+                    other_edits: vec![],
+                },
+                Some(pruned) => Suggestion {
+                    message: "remove the unused names".to_string(),
+                    span: tree_span(source, span),
+                    replacement: pruned,
+                    applicability,
+                    other_edits: vec![],
+                },
+            };
+
+            let names: Vec<String> = unused.iter().map(|name| format!("'{name}'")).collect();
+            let message = Message::new("unused-import").with("names", names.join(", "));
+            lints.push(Lint {
+                name: Self::NAME,
+                severity: Severity::Warning,
+                confidence: Confidence::Medium,
+                description: message.to_string(),
+                message: Some(message),
+                span: Some(span),
+                file_id: Some(file_id),
+                suggestion: Some(suggestion),
+            });
+        }
+    }
+}
+
+/// Collects the names a use tree brings into scope.
+fn scope_names(use_tree: &UseTree, names: &mut Vec<String>) {
+    match &use_tree.kind {
+        UseTreeKind::Path(name, alias) => {
+            names.push(alias.as_ref().unwrap_or(name).to_string());
+        }
+        UseTreeKind::List(trees) => {
+            for tree in trees {
+                scope_names(tree, names);
+            }
+        }
+    }
+}
+
+/// Renders a use tree without the given names, or returns `None` when nothing is left.
+fn prune(use_tree: &UseTree, unused: &[String], is_root: bool) -> Option<String> {
+    let prefix = render_path(&use_tree.prefix, is_root);
+    let rest = match &use_tree.kind {
+        UseTreeKind::Path(name, alias) => {
+            let scope_name = alias.as_ref().unwrap_or(name).to_string();
+            if unused.contains(&scope_name) {
+                return None;
+            }
+            match alias {
+                Some(alias) => format!("{name} as {alias}"),
+                None => name.to_string(),
+            }
+        }
+        UseTreeKind::List(trees) => {
+            let kept: Vec<String> = trees
+                .iter()
+                .filter_map(|tree| prune(tree, unused, false))
+                .collect();
+            match kept.as_slice() {
+                [] => return None,
+                [single] => single.clone(),
+                _ => format!("{{{}}}", kept.join(", ")),
+            }
+        }
+    };
+    Some(join_path(&prefix, &rest))
+}
+
+/// Returns the span of the use tree of a `use` declaration, after the `use` keyword.
+fn tree_span(source: &str, item_span: Span) -> Span {
+    let start = item_span.start() as usize;
+    let keyword = source
+        .get(start..item_span.end() as usize)
+        .and_then(|text| text.find("use "))
+        .map_or(start, |offset| start + offset + "use ".len());
+    Span::from(keyword as u32..item_span.end())
+}
+
+/// Returns whether a source text contains the name as a whole identifier.
+fn mentions(text: &str, name: &str) -> bool {
+    let is_identifier = |character: char| character.is_alphanumeric() || character == '_';
+    text.match_indices(name).any(|(offset, _)| {
+        let before = text[..offset].chars().next_back();
+        let after = text[offset + name.len()..].chars().next();
+        !before.is_some_and(is_identifier) && !after.is_some_and(is_identifier)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+    use crate::fix::Fixer;
+
+    const SOURCE: &str = "mod a {\n    pub fn foo() {}\n    pub fn bar() {}\n    pub fn baz() {}\n}\nuse a::foo;\nuse a::{bar, baz as qux};\nuse std::hash::Hash;\n\nfn main() {\n    qux();\n}\n";
+
+    #[test]
+    fn test_unused_imports_are_removed() {
+        let lint = Box::new(UnusedImport);
+
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let mut analyzer = Analyzer::new(&[lint]).with_source(SOURCE);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        let descriptions: Vec<&str> = result
+            .iter()
+            .map(|lint| lint.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "Imported name(s) 'foo' are never used",
+                "Imported name(s) 'bar' are never used",
+                "Imported name(s) 'Hash' are never used",
+            ]
+        );
+        // Traits may be used through their methods
+        assert_eq!(
+            result[2].suggestion.as_ref().unwrap().applicability,
+            Applicability::MaybeIncorrect
+        );
+
+        let suggestions = result
+            .into_iter()
+            .filter_map(|lint| lint.suggestion)
+            .collect();
+        let fixed = Fixer::new(false).apply(SOURCE, suggestions).source;
+        assert!(fixed.contains("}\nuse a::baz as qux;\nuse std::hash::Hash;\n"));
+    }
+
+    #[test]
+    fn test_mentions_matches_whole_identifiers() {
+        assert!(mentions("fn f() { foo::bar() }", "foo"));
+        assert!(!mentions("fn f() { food() }", "foo"));
+        assert!(!mentions("fn f() { my_foo() }", "foo"));
+    }
+}
@@ -0,0 +1,205 @@
+//! # Dependency Provenance
+//!
+//! Describes where each dependency of a package comes from: its source, the tag or commit
+//! it is pinned to and its license, as declared by its manifest or guessed from its license
+//! files. Git dependencies pinned to a branch are flagged, since the code they resolve to
+//! changes whenever the branch moves, unnoticed by the circuits depending on it.
+//!
+//! Nothing is fetched: git dependencies are inspected in the checkouts nargo keeps in
+//! `~/nargo`, so the commit and license of a dependency that was never fetched are unknown.
+
+use crate::advisories::{Dependency, DependencySource};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// File names of license files, compared case-insensitively without extension.
+const LICENSE_FILE_NAMES: &[&str] = &[
+    "license",
+    "licence",
+    "license-mit",
+    "license-apache",
+    "copying",
+];
+
+/// What a dependency is pinned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Pin {
+    /// A release tag, such as `v0.1.2`.
+    Tag,
+    /// A commit hash.
+    Commit,
+    /// A branch, or any other reference that can move.
+    Branch,
+    /// A directory on disk.
+    Path,
+}
+
+/// Provenance of a dependency.
+#[derive(Debug, Clone, Serialize)]
+pub struct Provenance {
+    pub name: String,
+    /// Git repository or directory of the dependency.
+    pub source: String,
+    /// Tag, branch or commit named in the manifest, for git dependencies.
+    pub reference: Option<String>,
+    pub pin: Pin,
+    /// Commit checked out by nargo, when known.
+    pub commit: Option<String>,
+    /// License declared by the manifest, or guessed from the license files.
+    pub license: Option<String>,
+    /// Whether the license was guessed from the license files.
+    pub license_guessed: bool,
+    /// Names of the packages requiring the dependency, from the analyzed package down.
+    pub required_by: Vec<String>,
+}
+
+impl Provenance {
+    /// Describes the provenance of a resolved dependency.
+    pub fn of(dependency: &Dependency) -> Self {
+        let (source, reference, pin) = match &dependency.source {
+            DependencySource::Git { url, tag } => (url.clone(), Some(tag.clone()), pin_of(tag)),
+            DependencySource::Path(path) => (path.display().to_string(), None, Pin::Path),
+        };
+        let commit = match pin {
+            Pin::Path => None,
+            _ => dependency.dir.as_deref().and_then(head_commit),
+        };
+        let guessed_license = match &dependency.license {
+            Some(_) => None,
+            None => dependency.dir.as_deref().and_then(guess_license),
+        };
+
+        Self {
+            name: dependency.name.clone(),
+            source,
+            reference,
+            pin,
+            commit,
+            license_guessed: guessed_license.is_some(),
+            license: dependency.license.clone().or(guessed_license),
+            required_by: dependency.required_by.clone(),
+        }
+    }
+}
+
+/// Classifies the reference a git dependency is pinned to. References that look neither
+/// like a version nor like a commit hash are assumed to be branches.
+fn pin_of(reference: &str) -> Pin {
+    let version = reference.strip_prefix('v').unwrap_or(reference);
+    if reference.len() >= 7 && reference.chars().all(|c| c.is_ascii_hexdigit()) {
+        Pin::Commit
+    } else if version.starts_with(|c: char| c.is_ascii_digit())
+        && version
+            .split(['.', '-', '+'])
+            .next()
+            .is_some_and(|major| major.chars().all(|c| c.is_ascii_digit()))
+    {
+        Pin::Tag
+    } else {
+        Pin::Branch
+    }
+}
+
+/// Returns the commit checked out in a git directory.
+fn head_commit(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Guesses the SPDX identifier of the licenses found in the license files of a directory.
+/// Several licenses, such as `LICENSE-MIT` and `LICENSE-APACHE`, are joined with `OR`.
+pub fn guess_license(dir: &Path) -> Option<String> {
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_stem().is_some_and(|stem| {
+                LICENSE_FILE_NAMES.contains(&stem.to_string_lossy().to_lowercase().as_str())
+            })
+        })
+        .collect();
+    files.sort();
+
+    let mut licenses: Vec<&str> = files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|text| identify_license(&text))
+        .collect();
+    licenses.dedup();
+    (!licenses.is_empty()).then(|| licenses.join(" OR "))
+}
+
+/// Identifies a license from distinctive phrases of its text.
+fn identify_license(text: &str) -> Option<&'static str> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let text = text.to_lowercase();
+    let license = if text.contains("apache license") && text.contains("version 2.0") {
+        "Apache-2.0"
+    } else if text.contains("permission is hereby granted, free of charge") {
+        "MIT"
+    } else if text.contains("gnu lesser general public license") {
+        "LGPL-3.0"
+    } else if text.contains("gnu affero general public license") {
+        "AGPL-3.0"
+    } else if text.contains("gnu general public license") {
+        "GPL-3.0"
+    } else if text.contains("mozilla public license") {
+        "MPL-2.0"
+    } else if text.contains("redistribution and use in source and binary forms") {
+        if text.contains("neither the name") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        }
+    } else if text.contains("this is free and unencumbered software") {
+        "Unlicense"
+    } else {
+        return None;
+    };
+    Some(license)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_references_are_classified() {
+        assert_eq!(pin_of("v0.1.2"), Pin::Tag);
+        assert_eq!(pin_of("1.0.0-beta.3"), Pin::Tag);
+        assert_eq!(pin_of("3f2a9c1d"), Pin::Commit);
+        assert_eq!(pin_of("main"), Pin::Branch);
+        assert_eq!(pin_of("release-1"), Pin::Branch);
+    }
+
+    #[test]
+    fn test_licenses_are_guessed_from_license_files() {
+        let dir = std::env::temp_dir().join("noir-analyzer-provenance-license");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("LICENSE-APACHE"),
+            "Apache License\n Version 2.0, January 2004",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("LICENSE-MIT"),
+            "Permission is hereby granted, free of\ncharge, to any person",
+        )
+        .unwrap();
+
+        assert_eq!(guess_license(&dir), Some("Apache-2.0 OR MIT".to_string()));
+    }
+}
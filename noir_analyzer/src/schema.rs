@@ -0,0 +1,185 @@
+//! # Circuit Interface Schema
+//!
+//! Extracts the interface of a circuit: the parameters of `main`, or of every contract
+//! function, with their types and visibilities, and the return value. Verifier integrators
+//! and frontend teams can generate bindings from its JSON form.
+//!
+//! Types are reported as written. Structs of the program referenced by the interface,
+//! directly or through other structs, are described with their fields.
+
+use crate::analysis::entry_points::{EntryPointKind, EntryPointParameter, entry_points};
+use noirc_frontend::ParsedModule;
+use noirc_frontend::parser::ItemKind;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The public interface of a circuit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Schema {
+    /// The functions a proof can be generated for.
+    pub functions: Vec<FunctionSchema>,
+    /// The structs of the program used by the functions, keyed by name.
+    pub structs: BTreeMap<String, Vec<StructFieldSchema>>,
+}
+
+/// A function of the circuit interface.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FunctionSchema {
+    /// Path of the function from the crate root.
+    pub name: String,
+    /// `main` or `contract`.
+    pub kind: &'static str,
+    pub parameters: Vec<EntryPointParameter>,
+    /// The returned value, `None` for functions returning nothing.
+    pub return_value: Option<ReturnSchema>,
+}
+
+/// The value returned by a function of the circuit interface.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReturnSchema {
+    pub r#type: String,
+    /// Whether the verifier sees the value: `public`, `private` or `databus`.
+    pub visibility: &'static str,
+}
+
+/// A field of a struct used by the circuit interface.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StructFieldSchema {
+    pub name: String,
+    pub r#type: String,
+}
+
+impl Schema {
+    /// Extracts the interface of a parsed program.
+    pub fn extract(parsed_module: &ParsedModule) -> Self {
+        let functions: Vec<FunctionSchema> = entry_points(parsed_module, false)
+            .into_iter()
+            .filter(|entry_point| {
+                matches!(
+                    entry_point.kind,
+                    EntryPointKind::Main | EntryPointKind::Contract
+                )
+            })
+            .map(|entry_point| FunctionSchema {
+                name: entry_point.path,
+                kind: entry_point.kind.as_str(),
+                parameters: entry_point.parameters,
+                return_value: entry_point.return_type.map(|r#type| ReturnSchema {
+                    r#type,
+                    visibility: entry_point.return_visibility,
+                }),
+            })
+            .collect();
+
+        let mut definitions = BTreeMap::new();
+        collect_structs(parsed_module, &mut definitions);
+
+        // Follow the struct names referenced by the interface, then by the used structs
+        let mut pending: Vec<String> = functions
+            .iter()
+            .flat_map(|function| {
+                let parameter_types = function
+                    .parameters
+                    .iter()
+                    .map(|parameter| parameter.r#type.as_str());
+                let return_type = function
+                    .return_value
+                    .iter()
+                    .map(|return_value| return_value.r#type.as_str());
+                parameter_types.chain(return_type).flat_map(type_names)
+            })
+            .map(str::to_string)
+            .collect();
+        let mut structs = BTreeMap::new();
+        while let Some(name) = pending.pop() {
+            if structs.contains_key(&name) {
+                continue;
+            }
+            let Some(fields) = definitions.get(&name) else {
+                continue;
+            };
+            pending.extend(
+                fields
+                    .iter()
+                    .flat_map(|field| type_names(&field.r#type))
+                    .map(str::to_string),
+            );
+            structs.insert(name, fields.clone());
+        }
+
+        Schema { functions, structs }
+    }
+}
+
+/// Records the fields of every struct of the program, keyed by struct name.
+fn collect_structs(
+    parsed_module: &ParsedModule,
+    definitions: &mut BTreeMap<String, Vec<StructFieldSchema>>,
+) {
+    for item in &parsed_module.items {
+        match &item.kind {
+            ItemKind::Submodules(submodule) => collect_structs(&submodule.contents, definitions),
+            ItemKind::Struct(noir_struct) => {
+                let fields = noir_struct
+                    .fields
+                    .iter()
+                    .map(|field| StructFieldSchema {
+                        name: field.item.name.to_string(),
+                        r#type: field.item.typ.to_string(),
+                    })
+                    .collect();
+                definitions.insert(noir_struct.name.to_string(), fields);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the identifiers of a written type, e.g. `Point` and `Field` for `[Point<Field>; 2]`.
+fn type_names(r#type: &str) -> impl Iterator<Item = &str> {
+    r#type
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_schema_describes_main_and_its_structs() {
+        let source_code = r#"
+            struct Point { x: Field, y: Field }
+            struct Segment { start: Point, end: Point }
+            struct Unused { value: u8 }
+
+            fn main(segment: Segment, length: pub u64) -> pub Field {
+                segment.start.x + length as Field
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let schema = Schema::extract(&root);
+
+        assert_eq!(schema.functions.len(), 1);
+        let main = &schema.functions[0];
+        assert_eq!(main.name, "main");
+        assert_eq!(main.parameters[0].visibility, "private");
+        assert_eq!(main.parameters[1].visibility, "public");
+        assert_eq!(
+            main.return_value,
+            Some(ReturnSchema {
+                r#type: "Field".to_string(),
+                visibility: "public",
+            })
+        );
+        assert_eq!(
+            schema.structs.keys().collect::<Vec<_>>(),
+            ["Point", "Segment"]
+        );
+
+        let json: serde_json::Value = serde_json::to_value(&schema).unwrap();
+        assert_eq!(json["functions"][0]["parameters"][1]["type"], "u64");
+    }
+}
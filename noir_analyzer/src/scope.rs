@@ -0,0 +1,254 @@
+//! # Analysis Scope
+//!
+//! Restricts the reported findings to one function, or to a range of lines of a file, so
+//! that developers iterating on a function of a large project are not flooded with the
+//! findings of the others. Files with nothing in scope are not analyzed; the others still
+//! are as a whole, since rules such as `unused-function` depend on the rest of the program.
+//!
+//! A function is named by its path from the crate root or by the end of it: `main`,
+//! `utils::check` or `crate::utils::Point::new`, methods being named after their type. Lines
+//! are given as `src/main.nr:10-80`, inclusive and 1-based, or `src/main.nr:10` for a single
+//! line, the file being matched against the end of the analyzed paths.
+
+use crate::analysis::call_graph::collect_functions;
+use crate::analysis::module_graph::ROOT_MODULE;
+use crate::changes::line_of;
+use crate::diagnostics::lint::Lint;
+use crate::frontend::Noir;
+use noirc_frontend::ParsedModule;
+use noirc_frontend::ast::NoirFunction;
+use noirc_frontend::hir::resolution::errors::Span;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ScopeError {
+    #[error("Invalid function path '{0}', expected e.g. `utils::check`")]
+    InvalidFunction(String),
+    #[error("Invalid span '{0}', expected e.g. `src/main.nr:10-80`")]
+    InvalidSpan(String),
+}
+
+/// The part of a program whose findings are reported.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scope {
+    /// The functions whose path from the crate root ends with these segments.
+    Function(Vec<String>),
+    /// Inclusive, 1-based lines of the files whose path ends with `path`.
+    Lines {
+        path: PathBuf,
+        start: usize,
+        end: usize,
+    },
+}
+
+impl Scope {
+    /// Parses the path of a function, such as `utils::check`.
+    pub fn function(path: &str) -> Result<Self, ScopeError> {
+        let segments: Vec<String> = path.split("::").map(str::to_string).collect();
+        if segments
+            .iter()
+            .any(|segment| segment.is_empty() || segment.contains(char::is_whitespace))
+        {
+            return Err(ScopeError::InvalidFunction(path.to_string()));
+        }
+        Ok(Scope::Function(segments))
+    }
+
+    /// Parses a file and range of lines, such as `src/main.nr:10-80`.
+    pub fn lines(span: &str) -> Result<Self, ScopeError> {
+        let invalid = || ScopeError::InvalidSpan(span.to_string());
+        let (path, lines) = span.rsplit_once(':').ok_or_else(invalid)?;
+        let (start, end) = lines.split_once('-').unwrap_or((lines, lines));
+        let start: usize = start.trim().parse().map_err(|_| invalid())?;
+        let end: usize = end.trim().parse().map_err(|_| invalid())?;
+        if path.is_empty() || start == 0 || end < start {
+            return Err(invalid());
+        }
+
+        let path = Path::new(path);
+        Ok(Scope::Lines {
+            path: path.strip_prefix(".").unwrap_or(path).to_path_buf(),
+            start,
+            end,
+        })
+    }
+
+    /// Returns whether anything of the given file is in scope, i.e. whether it needs to be
+    /// analyzed.
+    pub fn covers(&self, path: &Path, parsed_module: &ParsedModule) -> bool {
+        match self {
+            Scope::Function(segments) => !function_spans(parsed_module, segments).is_empty(),
+            Scope::Lines {
+                path: scope_path, ..
+            } => path.ends_with(scope_path),
+        }
+    }
+
+    /// Keeps the lints of a file that are in scope: those within the function, or touching
+    /// the lines. Lints without a span, which are about the whole file, are dropped.
+    pub fn retain(
+        &self,
+        lints: Vec<Lint>,
+        path: &Path,
+        source: &str,
+        parsed_module: &ParsedModule,
+    ) -> Vec<Lint> {
+        match self {
+            Scope::Function(segments) => {
+                let spans = function_spans(parsed_module, segments);
+                lints
+                    .into_iter()
+                    .filter(|lint| {
+                        lint.span.is_some_and(|lint_span| {
+                            spans.iter().any(|span| {
+                                span.start() <= lint_span.start() && lint_span.end() <= span.end()
+                            })
+                        })
+                    })
+                    .collect()
+            }
+            Scope::Lines {
+                path: scope_path,
+                start,
+                end,
+            } => {
+                if !path.ends_with(scope_path) {
+                    return vec![];
+                }
+                lints
+                    .into_iter()
+                    .filter(|lint| {
+                        lint.span.is_some_and(|span| {
+                            line_of(source, span.start()) <= *end
+                                && *start <= line_of(source, span.end())
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Returns the spans of the functions and methods whose path ends with `segments`.
+fn function_spans(parsed_module: &ParsedModule, segments: &[String]) -> Vec<Span> {
+    let mut functions = vec![];
+    collect_functions(parsed_module, &mut functions);
+    functions
+        .into_iter()
+        .filter(|node| {
+            // Methods are named after their type, e.g. `Point::new`
+            let path: Vec<String> = std::iter::once(ROOT_MODULE.to_string())
+                .chain(node.module.iter().cloned())
+                .chain(node.name.split("::").map(str::to_string))
+                .collect();
+            path.ends_with(segments)
+        })
+        .map(|node| match node.self_type {
+            Some(_) => function_extent(node.function),
+            None => node.span,
+        })
+        .collect()
+}
+
+/// Returns the span of a function from its definition to its last statement.
+fn function_extent(function: &NoirFunction) -> Span {
+    let definition = Noir::function_location(&function.def).0;
+    let end = function
+        .def
+        .body
+        .statements
+        .last()
+        .map_or(definition.end(), |statement| {
+            Noir::statement_location(statement).0.end()
+        })
+        .max(definition.end());
+    Span::from(definition.start()..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parser::Parser;
+    use crate::diagnostics::lint::{Confidence, Severity};
+
+    const SOURCE: &str = r#"
+mod utils {
+    pub fn check(x: Field) {
+        assert(x == x);
+    }
+
+    struct Point { x: Field }
+
+    impl Point {
+        fn check(self) {
+            assert(self.x == self.x);
+        }
+    }
+}
+
+fn main(x: Field) {
+    assert(x == x);
+    utils::check(x);
+}
+"#;
+
+    /// Returns the lines of the lints on the assertions of `SOURCE` that are in scope.
+    fn lints(scope: &Scope) -> Vec<usize> {
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let lints = SOURCE
+            .match_indices("assert")
+            .map(|(start, _)| Lint {
+                name: "test",
+                severity: Severity::Warning,
+                confidence: Confidence::High,
+                description: String::new(),
+                message: None,
+                span: Some(Span::from(start as u32..start as u32 + 6)),
+                file_id: None,
+                suggestion: None,
+            })
+            .collect();
+
+        scope
+            .retain(lints, Path::new("/project/src/main.nr"), SOURCE, &root)
+            .iter()
+            .filter_map(|lint| lint.span)
+            .map(|span| line_of(SOURCE, span.start()))
+            .collect()
+    }
+
+    #[test]
+    fn test_function_scope_keeps_lints_of_matching_functions() {
+        assert_eq!(lints(&Scope::function("main").unwrap()), [17]);
+        assert_eq!(lints(&Scope::function("check").unwrap()), [4, 11]);
+        assert_eq!(lints(&Scope::function("Point::check").unwrap()), [11]);
+        assert_eq!(lints(&Scope::function("crate::utils::check").unwrap()), [4]);
+        assert!(lints(&Scope::function("helper").unwrap()).is_empty());
+
+        let root = Parser::parse_program_with_dummy_file(SOURCE).unwrap();
+        let path = Path::new("src/main.nr");
+        assert!(
+            Scope::function("utils::Point::check")
+                .unwrap()
+                .covers(path, &root)
+        );
+        assert!(!Scope::function("main::check").unwrap().covers(path, &root));
+    }
+
+    #[test]
+    fn test_lines_scope_keeps_lints_touching_the_lines_of_the_file() {
+        assert_eq!(lints(&Scope::lines("src/main.nr:10-17").unwrap()), [11, 17]);
+        assert_eq!(lints(&Scope::lines("./src/main.nr:4").unwrap()), [4]);
+        assert!(lints(&Scope::lines("src/lib.nr:1-100").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_scopes_are_rejected() {
+        assert!(Scope::function("utils::").is_err());
+        assert!(Scope::lines("src/main.nr").is_err());
+        assert!(Scope::lines("src/main.nr:0-3").is_err());
+        assert!(Scope::lines("src/main.nr:8-3").is_err());
+        assert!(Scope::lines(":3").is_err());
+    }
+}
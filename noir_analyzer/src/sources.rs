@@ -0,0 +1,243 @@
+//! # Source Discovery
+//!
+//! Finds the Noir source files to analyze below a workspace root. The walk honors
+//! `.gitignore` and `.noir-analyzer-ignore` files (both using gitignore syntax) as well as
+//! the `exclude` globs from `noir-analyzer.toml`, so generated code and vendored
+//! dependencies are skipped.
+//!
+//! Globs are matched against paths relative to the root, e.g. `src/generated/**`.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Name of the analyzer-specific ignore file.
+pub const IGNORE_FILE_NAME: &str = ".noir-analyzer-ignore";
+
+/// Extension of Noir source files.
+const NOIR_EXTENSION: &str = "nr";
+
+/// File name of Nargo manifests.
+const MANIFEST_FILE_NAME: &str = "Nargo.toml";
+
+#[derive(Debug, Error)]
+pub enum SourceError {
+    #[error("Invalid glob {0:?}: {1}")]
+    InvalidGlob(String, String),
+}
+
+/// Walks a directory tree collecting the Noir files selected for analysis.
+pub struct SourceWalker {
+    root: PathBuf,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl SourceWalker {
+    /// Creates a walker selecting every Noir file below `root`.
+    pub fn new(root: &Path) -> Self {
+        // A manifest given as a bare file name has an empty parent directory
+        let root = if root.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            root
+        };
+
+        Self {
+            root: root.to_path_buf(),
+            include: None,
+            exclude: GlobSet::empty(),
+        }
+    }
+
+    /// Restricts the walk to files matching at least one of the given globs.
+    pub fn include(mut self, patterns: &[String]) -> Result<Self, SourceError> {
+        if !patterns.is_empty() {
+            self.include = Some(build_glob_set(patterns)?);
+        }
+        Ok(self)
+    }
+
+    /// Skips files matching any of the given globs.
+    pub fn exclude(mut self, patterns: &[String]) -> Result<Self, SourceError> {
+        self.exclude = build_glob_set(patterns)?;
+        Ok(self)
+    }
+
+    /// Returns whether the given file is excluded by the configured globs.
+    ///
+    /// Ignore files are not consulted, since they only apply while walking.
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        self.exclude.is_match(self.relative(path))
+    }
+
+    /// Returns the selected Noir files, sorted by path.
+    pub fn walk(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = walk_files(&self.root)
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == NOIR_EXTENSION)
+            })
+            .filter(|path| {
+                let relative = self.relative(path);
+                !self.exclude.is_match(relative)
+                    && self
+                        .include
+                        .as_ref()
+                        .is_none_or(|include| include.is_match(relative))
+            })
+            .collect();
+
+        files.sort();
+        files
+    }
+
+    fn relative<'path>(&self, path: &'path Path) -> &'path Path {
+        path.strip_prefix(&self.root).unwrap_or(path)
+    }
+}
+
+/// Returns the Nargo manifests below `root`, sorted by path, e.g. to analyze every project
+/// of a monorepo. Ignore files are honored as for source files.
+pub fn find_manifests(root: &Path) -> Vec<PathBuf> {
+    let mut manifests: Vec<PathBuf> = walk_files(root)
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name == MANIFEST_FILE_NAME)
+        })
+        .collect();
+    manifests.sort();
+    manifests
+}
+
+/// Returns the files below `root`, skipping the ones matched by ignore files.
+fn walk_files(root: &Path) -> impl Iterator<Item = PathBuf> {
+    WalkBuilder::new(root)
+        .add_custom_ignore_filename(IGNORE_FILE_NAME)
+        .require_git(false)
+        .build()
+        .flatten()
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_file())
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, SourceError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|e| SourceError::InvalidGlob(pattern.clone(), e.to_string()))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| SourceError::InvalidGlob(patterns.join(", "), e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Creates a fresh directory tree with the given files, relative to the returned root.
+    fn create_tree(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("noir-analyzer-sources-{name}"));
+        let _ = fs::remove_dir_all(&root);
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        root
+    }
+
+    fn relative_paths(root: &Path, files: Vec<PathBuf>) -> Vec<String> {
+        files
+            .iter()
+            .map(|file| file.strip_prefix(root).unwrap().display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_walker_collects_noir_files() {
+        let root = create_tree(
+            "collect",
+            &[
+                ("src/main.nr", ""),
+                ("src/utils/hash.nr", ""),
+                ("Nargo.toml", ""),
+            ],
+        );
+
+        let files = SourceWalker::new(&root).walk();
+
+        assert_eq!(
+            relative_paths(&root, files),
+            vec!["src/main.nr", "src/utils/hash.nr"]
+        );
+    }
+
+    #[test]
+    fn test_walker_honors_ignore_files_and_excludes() {
+        let root = create_tree(
+            "ignore",
+            &[
+                (".gitignore", "target/\n"),
+                (IGNORE_FILE_NAME, "src/generated/\n"),
+                ("src/main.nr", ""),
+                ("src/generated/codegen.nr", ""),
+                ("src/vendor/lib.nr", ""),
+                ("target/debug.nr", ""),
+            ],
+        );
+
+        let walker = SourceWalker::new(&root)
+            .exclude(&["src/vendor/**".to_string()])
+            .unwrap();
+
+        assert_eq!(relative_paths(&root, walker.walk()), vec!["src/main.nr"]);
+        assert!(walker.is_excluded(&root.join("src/vendor/lib.nr")));
+    }
+
+    #[test]
+    fn test_walker_restricts_to_included_globs() {
+        let root = create_tree("include", &[("src/main.nr", ""), ("src/utils/hash.nr", "")]);
+
+        let walker = SourceWalker::new(&root)
+            .include(&["src/utils/*.nr".to_string()])
+            .unwrap();
+
+        assert_eq!(
+            relative_paths(&root, walker.walk()),
+            vec!["src/utils/hash.nr"]
+        );
+    }
+
+    #[test]
+    fn test_manifests_are_found_below_root() {
+        let root = create_tree(
+            "manifests",
+            &[
+                (".gitignore", "vendor/\n"),
+                ("circuits/a/Nargo.toml", ""),
+                ("circuits/b/Nargo.toml", ""),
+                ("circuits/b/src/main.nr", ""),
+                ("vendor/c/Nargo.toml", ""),
+            ],
+        );
+
+        assert_eq!(
+            relative_paths(&root, find_manifests(&root)),
+            vec!["circuits/a/Nargo.toml", "circuits/b/Nargo.toml"]
+        );
+    }
+
+    #[test]
+    fn test_walker_rejects_invalid_globs() {
+        assert!(
+            SourceWalker::new(Path::new("."))
+                .exclude(&["src/[".to_string()])
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! # Webhook Notifications
+//!
+//! Posts the findings of a `check` run to a webhook, so that results reach chat channels or
+//! issue trackers without glue scripts. The webhook is set in `noir-analyzer.toml`:
+//!
+//! ```toml
+//! [webhook]
+//! url = "https://hooks.example.com/noir-analyzer"
+//! min-severity = "error"
+//! redact = ["paths", "authors"]
+//! ```
+//!
+//! The payload is the JSON document of `check --format json`, with file paths relative to
+//! the workspace root. Only findings at or above `min-severity` (`warning` by default) are
+//! sent. Redactions keep data out of third-party services:
+//! - `paths` keeps the file names of the findings, without their directories;
+//! - `messages` replaces descriptions by rule names, and drops suggestions and circuit
+//!   evidence, which quote source code;
+//! - `authors` drops the names and emails of the blame data.
+//!
+//! Webhook URLs often embed a secret: set them from the environment, e.g. with
+//! `NOIR_ANALYZER_WEBHOOK__URL`, rather than in the file. Requests are sent with `curl`,
+//! which reads the URL and the payload from a config on its standard input, so that they do
+//! not show in the process list. A delivery the webhook rejects with an HTTP error status
+//! is an error, carrying the response body.
+
+use crate::diagnostics::finding::Finding;
+use crate::diagnostics::lint::Severity;
+use crate::diagnostics::reporter::Reporter;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("Failed to run curl: {0}")]
+    CurlUnavailable(String),
+    #[error("Webhook request failed: {0}")]
+    RequestFailed(String),
+}
+
+/// Data that can be left out of the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Redaction {
+    /// Directories of the reported files.
+    Paths,
+    /// Descriptions, suggestions and circuit evidence of the findings.
+    Messages,
+    /// Names and emails of the blamed authors.
+    Authors,
+}
+
+/// The `[webhook]` section of the configuration. Keys are kebab-case, like the entries of
+/// the other tables, so that they can be overridden from the environment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WebhookConfig {
+    /// URL the findings are posted to.
+    pub url: String,
+    /// Severity below which findings are not sent.
+    #[serde(default = "default_min_severity")]
+    pub min_severity: Severity,
+    /// Data left out of the payload.
+    #[serde(default)]
+    pub redact: Vec<Redaction>,
+}
+
+fn default_min_severity() -> Severity {
+    Severity::Warning
+}
+
+impl WebhookConfig {
+    /// Returns the payload posted for the findings of a workspace with the given root.
+    pub fn payload(&self, findings: &[Finding], root: &Path) -> Value {
+        let findings: Vec<Finding> = findings
+            .iter()
+            .filter(|finding| finding.lint.severity >= self.min_severity)
+            .map(|finding| self.redact(finding.clone(), root))
+            .collect();
+        let mut payload = Reporter::json_value(&findings, root);
+        // Directories are only dropped from the rendered findings, so that fingerprints
+        // stay those of the other reports
+        if self.redact.contains(&Redaction::Paths) {
+            for finding in payload["findings"].as_array_mut().into_iter().flatten() {
+                let file = finding["file"].as_str().unwrap_or_default();
+                let file_name = file.rsplit('/').next().unwrap_or(file).to_string();
+                finding["file"] = Value::String(file_name);
+            }
+        }
+        payload
+    }
+
+    /// Posts the payload for the findings of a workspace to the webhook.
+    pub fn send(&self, findings: &[Finding], root: &Path) -> Result<(), WebhookError> {
+        let config = self.curl_config(&self.payload(findings, root).to_string());
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail-with-body"])
+            .args(["--request", "POST"])
+            .args(["--header", "Content-Type: application/json"])
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| WebhookError::CurlUnavailable(e.to_string()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(config.as_bytes())
+                .map_err(|e| WebhookError::RequestFailed(e.to_string()))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| WebhookError::RequestFailed(e.to_string()))?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let body = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            return Err(WebhookError::RequestFailed(if body.is_empty() {
+                error
+            } else {
+                format!("{error}: {body}")
+            }));
+        }
+        Ok(())
+    }
+
+    /// Returns the curl config posting a payload to the webhook, read by curl from its
+    /// standard input so that the URL is not passed on the command line.
+    fn curl_config(&self, payload: &str) -> String {
+        format!(
+            "url = {}\ndata-binary = {}\n",
+            curl_string(&self.url),
+            curl_string(payload)
+        )
+    }
+
+    /// Applies the redactions of messages and authors to a finding, whose path is made
+    /// relative to the root.
+    fn redact(&self, mut finding: Finding, root: &Path) -> Finding {
+        if let Ok(path) = finding.file_path.strip_prefix(root) {
+            finding.file_path = path.to_path_buf();
+        }
+        if self.redact.contains(&Redaction::Messages) {
+            finding.lint.description = finding.lint.name.to_string();
+            finding.lint.suggestion = None;
+            finding.circuit_evidence.clear();
+        }
+        if self.redact.contains(&Redaction::Authors)
+            && let Some(blame) = &mut finding.blame
+        {
+            blame.author.clear();
+            blame.author_email.clear();
+        }
+        finding
+    }
+}
+
+/// Quotes a value for a curl config file.
+fn curl_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::lint::{Confidence, Lint};
+
+    fn finding(severity: Severity, description: &str) -> Finding {
+        let lint = Lint {
+            name: "unused-function",
+            severity,
+            confidence: Confidence::High,
+            description: description.to_string(),
+            message: None,
+            span: None,
+            file_id: None,
+            suggestion: None,
+        };
+        Finding::new(lint, Path::new("workspace/src/utils/math.nr"), "")
+    }
+
+    #[test]
+    fn test_payload_filters_by_severity_and_redacts() {
+        let config: WebhookConfig = toml::from_str(
+            r#"
+            url = "https://hooks.example.com/noir-analyzer"
+            min-severity = "error"
+            redact = ["paths", "messages"]
+            "#,
+        )
+        .unwrap();
+        let findings = [
+            finding(Severity::Warning, "Function 'foo' is unused"),
+            finding(Severity::Error, "Function 'bar' is unused"),
+        ];
+
+        let payload = config.payload(&findings, Path::new("workspace"));
+
+        let sent = payload["findings"].as_array().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0]["severity"], "error");
+        assert_eq!(sent[0]["file"], "math.nr");
+        assert_eq!(sent[0]["description"], "unused-function");
+        assert_eq!(
+            sent[0]["fingerprint"],
+            findings[1].fingerprint(Path::new("workspace"))
+        );
+    }
+
+    #[test]
+    fn test_payload_keeps_relative_paths_and_messages_by_default() {
+        let config: WebhookConfig = toml::from_str(r#"url = "https://hooks.example.com""#).unwrap();
+
+        let payload = config.payload(
+            &[finding(Severity::Warning, "Function 'foo' is unused")],
+            Path::new("workspace"),
+        );
+
+        assert_eq!(payload["findings"][0]["file"], "src/utils/math.nr");
+        assert_eq!(
+            payload["findings"][0]["description"],
+            "Function 'foo' is unused"
+        );
+    }
+
+    #[test]
+    fn test_curl_config_quotes_the_url_and_payload() {
+        let config: WebhookConfig =
+            toml::from_str(r#"url = "https://hooks.example.com/?token=a\"b""#).unwrap();
+
+        let curl_config = config.curl_config(r#"{"description":"a \"b\""}"#);
+
+        assert_eq!(
+            curl_config,
+            r##"url = "https://hooks.example.com/?token=a\"b"
+data-binary = "{\"description\":\"a \\\"b\\\"\"}"
+"##
+        );
+    }
+}
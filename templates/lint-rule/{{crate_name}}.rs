@@ -0,0 +1,65 @@
+//! # {{project-name | title_case}} Lint
+//!
+//! {{description}}
+
+use crate::ast::ast_context::AstContext;
+use crate::declare_lint;
+use crate::diagnostics::lint::{Confidence, Lint, Severity};
+use crate::diagnostics::message::Message;
+use crate::lints::matchers::{Matcher, call_to, find_matches};
+
+/// {{description}}
+#[derive(Clone, Default)]
+pub struct {{project-name | upper_camel_case}};
+
+declare_lint! {
+    {{project-name | upper_camel_case}} {
+        name: "{{project-name}}",
+        explanation: "{{description}}",
+    }
+}
+
+impl {{project-name | upper_camel_case}} {
+    /// Describes the reported expressions, see `crate::lints::matchers`.
+    fn matcher() -> Matcher {
+        call_to("std::hash::pedersen_hash")
+    }
+
+    fn check(&self, context: &AstContext) -> Vec<Lint> {
+        let matcher = Self::matcher();
+        let mut lints = vec![];
+
+        for (name, function) in &context.function_definitions {
+            for found in find_matches(function, &matcher) {
+                // Add the message to `DEFAULT_TEMPLATES` in `diagnostics/message.rs`
+                let message = Message::new("{{project-name}}").with("function", name);
+                lints.push(found.lint(Self::NAME, Severity::Warning, Confidence::Medium, message));
+            }
+        }
+
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::analyzer::Analyzer;
+    use crate::ast::parser::Parser;
+
+    #[test]
+    fn test_{{crate_name}}_is_reported() {
+        let source_code = r#"
+            fn main(x: Field) {
+                let _ = std::hash::pedersen_hash([x]);
+            }
+            "#;
+
+        let root = Parser::parse_program_with_dummy_file(source_code).unwrap();
+        let lint = Box::new({{project-name | upper_camel_case}});
+        let mut analyzer = Analyzer::new(&[lint]);
+        let result = analyzer.analyze(&root).expect("Should have passed");
+
+        assert_eq!(result.len(), 1);
+    }
+}
@@ -0,0 +1,497 @@
+//! `cargo xtask corpus`: analyzes real-world Noir projects and summarizes what changed since
+//! the last run.
+//!
+//! The projects listed in `xtask/corpus.toml` are cloned below `target/corpus/repos`, and
+//! analyzed with `noir-analyzer check --recursive`, built in release mode from the
+//! workspace. The summary reports, for each project, the panics of the analyzer, the
+//! projects it could not analyze, the kinds of AST nodes it skipped, and the findings that
+//! appeared or disappeared since the previous run. The results of each run are kept in
+//! `target/corpus/last-run.json` to compare the next one with.
+//!
+//! Findings are compared by rule, file and description, without their line, so that
+//! unrelated edits of the projects do not show up as changed findings.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Output};
+use thiserror::Error;
+
+/// Name of the findings reporting panics caught by the analyzer.
+const INTERNAL_ERROR_LINT: &str = "analyzer-internal-error";
+
+/// Arguments of the `corpus` task.
+#[derive(Debug, clap::Args)]
+pub struct CorpusArgs {
+    /// List of projects to analyze. Defaults to `xtask/corpus.toml`
+    #[arg(long, value_name = "PATH")]
+    list: Option<PathBuf>,
+    /// Only analyze the project of this name. Can be repeated
+    #[arg(long = "project", value_name = "NAME")]
+    projects: Vec<String>,
+    /// Fetch the latest commit of the default branch of the projects without a pinned `rev`,
+    /// instead of analyzing the commit cloned first
+    #[arg(long)]
+    update: bool,
+    /// Also fail when findings or skipped nodes changed since the last run, not only when
+    /// the analyzer panicked or failed
+    #[arg(long)]
+    fail_on_diff: bool,
+    /// Print the summary as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum CorpusError {
+    #[error("Failed to access {path:?}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Invalid project list {path:?}: {source}")]
+    List {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("Unknown project `{0}`, not in the project list")]
+    UnknownProject(String),
+    #[error("`{command}` failed: {message}")]
+    Command { command: String, message: String },
+}
+
+impl CorpusError {
+    fn io(path: &Path, source: io::Error) -> Self {
+        Self::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectList {
+    #[serde(default, rename = "project")]
+    projects: Vec<Project>,
+}
+
+/// A project of the corpus.
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: String,
+    /// URL to clone the project from.
+    url: String,
+    /// Tag or commit to analyze, instead of the default branch.
+    rev: Option<String>,
+    /// Directory of the repository to analyze, when not its root.
+    path: Option<PathBuf>,
+}
+
+/// Result of analyzing a project, as kept between runs.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct ProjectRun {
+    /// Commit analyzed.
+    revision: String,
+    /// Errors preventing the analysis of the project, or of some of its packages.
+    errors: Vec<String>,
+    /// Panics of the analyzer, by message.
+    panics: Vec<String>,
+    /// Number of skipped AST nodes, by kind.
+    skipped_nodes: BTreeMap<String, usize>,
+    /// `rule file: description` of every finding, sorted.
+    findings: Vec<String>,
+}
+
+/// Runs the task. Fails when the analyzer panicked or failed on any project, or with
+/// `--fail-on-diff`, when findings or skipped nodes changed.
+pub fn run(args: &CorpusArgs) -> Result<ExitCode, CorpusError> {
+    let root = workspace_root();
+    let list_path = args
+        .list
+        .clone()
+        .unwrap_or_else(|| root.join("xtask/corpus.toml"));
+    let list = read_list(&list_path)?;
+    if let Some(unknown) = args
+        .projects
+        .iter()
+        .find(|name| !list.projects.iter().any(|project| &project.name == *name))
+    {
+        return Err(CorpusError::UnknownProject(unknown.clone()));
+    }
+
+    let target_dir =
+        env::var_os("CARGO_TARGET_DIR").map_or_else(|| root.join("target"), PathBuf::from);
+    let corpus_dir = target_dir.join("corpus");
+    let repos_dir = corpus_dir.join("repos");
+    fs::create_dir_all(&repos_dir).map_err(|e| CorpusError::io(&repos_dir, e))?;
+    let analyzer = build_analyzer(&root, &target_dir)?;
+
+    let last_run_path = corpus_dir.join("last-run.json");
+    let previous = read_last_run(&last_run_path);
+    // Projects not analyzed this time keep their previous results
+    let mut runs = previous.clone();
+    let mut summaries = vec![];
+    for project in &list.projects {
+        if !args.projects.is_empty() && !args.projects.contains(&project.name) {
+            continue;
+        }
+        eprintln!("Analyzing {}", project.name);
+        let run = match checkout(project, &repos_dir, args.update) {
+            Ok((dir, revision)) => {
+                let dir = project
+                    .path
+                    .as_ref()
+                    .map_or(dir.clone(), |path| dir.join(path));
+                analyze(&analyzer, &dir, revision)
+            }
+            Err(e) => ProjectRun {
+                errors: vec![e.to_string()],
+                ..ProjectRun::default()
+            },
+        };
+        summaries.push(summarize(&project.name, previous.get(&project.name), &run));
+        runs.insert(project.name.clone(), run);
+    }
+
+    let serialized = serde_json::to_string_pretty(&runs).unwrap();
+    fs::write(&last_run_path, serialized).map_err(|e| CorpusError::io(&last_run_path, e))?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&summaries).unwrap());
+    } else {
+        for summary in &summaries {
+            print!("{}", render(summary));
+        }
+    }
+
+    let failed = summaries.iter().any(|summary| {
+        summary["panics"]
+            .as_array()
+            .is_some_and(|panics| !panics.is_empty())
+            || summary["errors"]
+                .as_array()
+                .is_some_and(|errors| !errors.is_empty())
+            || (args.fail_on_diff && summary["changed"] == true)
+    });
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask is a member of the workspace")
+        .to_path_buf()
+}
+
+fn read_list(path: &Path) -> Result<ProjectList, CorpusError> {
+    let contents = fs::read_to_string(path).map_err(|e| CorpusError::io(path, e))?;
+    toml::from_str(&contents).map_err(|source| CorpusError::List {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Reads the results of the last run. A missing or unreadable file is the first run.
+fn read_last_run(path: &Path) -> BTreeMap<String, ProjectRun> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Builds the analyzer in release mode, and returns the path of its executable.
+fn build_analyzer(root: &Path, target_dir: &Path) -> Result<PathBuf, CorpusError> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command.current_dir(root).args([
+        "build",
+        "--release",
+        "--package",
+        "cli",
+        "--bin",
+        "noir-analyzer",
+    ]);
+    let status = command
+        .status()
+        .map_err(|e| command_failed(&command, e.to_string()))?;
+    if !status.success() {
+        return Err(command_failed(&command, status.to_string()));
+    }
+    Ok(target_dir
+        .join("release")
+        .join(format!("noir-analyzer{}", env::consts::EXE_SUFFIX)))
+}
+
+/// Clones the project, or reuses its clone, and checks out the revision to analyze.
+/// Returns the directory of the clone and the commit checked out.
+fn checkout(
+    project: &Project,
+    repos_dir: &Path,
+    update: bool,
+) -> Result<(PathBuf, String), CorpusError> {
+    let dir = repos_dir.join(&project.name);
+    if !dir.join(".git").exists() {
+        // Blobs are fetched on checkout, so that history does not weigh on large repositories
+        git(
+            repos_dir,
+            &[
+                "clone",
+                "--quiet",
+                "--filter=blob:none",
+                &project.url,
+                &project.name,
+            ],
+        )?;
+    }
+
+    if let Some(rev) = &project.rev {
+        if git(&dir, &["checkout", "--quiet", "--detach", rev]).is_err() {
+            // A revision more recent than the clone
+            git(&dir, &["fetch", "--quiet", "--tags", "origin"])?;
+            git(&dir, &["checkout", "--quiet", "--detach", rev])?;
+        }
+    } else if update {
+        git(&dir, &["fetch", "--quiet", "origin"])?;
+        git(&dir, &["checkout", "--quiet", "--detach", "origin/HEAD"])?;
+    }
+    let revision = git(&dir, &["rev-parse", "HEAD"])?;
+    Ok((dir, revision))
+}
+
+/// Runs git in a directory, and returns its output.
+fn git(dir: &Path, args: &[&str]) -> Result<String, CorpusError> {
+    let mut command = Command::new("git");
+    command.current_dir(dir).args(args);
+    let output = command
+        .output()
+        .map_err(|e| command_failed(&command, e.to_string()))?;
+    if !output.status.success() {
+        return Err(command_failed(
+            &command,
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn command_failed(command: &Command, message: String) -> CorpusError {
+    let program = command.get_program().to_string_lossy();
+    let args: Vec<_> = command
+        .get_args()
+        .map(|arg| arg.to_string_lossy())
+        .collect();
+    CorpusError::Command {
+        command: format!("{program} {}", args.join(" ")),
+        message,
+    }
+}
+
+/// Analyzes every package below `dir` with the analyzer executable.
+fn analyze(analyzer: &Path, dir: &Path, revision: String) -> ProjectRun {
+    let mut run = ProjectRun {
+        revision,
+        ..ProjectRun::default()
+    };
+    let output = Command::new(analyzer)
+        .arg("check")
+        .arg("--recursive")
+        .arg(dir)
+        .args(["--format", "json", "--report-coverage", "--no-progress"])
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            run.errors.push(format!("Failed to run {analyzer:?}: {e}"));
+            return run;
+        }
+    };
+    run.skipped_nodes = skipped_nodes(&String::from_utf8_lossy(&output.stderr));
+
+    let Ok(report) = serde_json::from_slice::<Value>(&output.stdout) else {
+        // Panics outside of the analysis are caught, so the process crashed, e.g. on a
+        // stack overflow
+        run.panics.push(crash_message(&output));
+        return run;
+    };
+    if let Some(error) = report.get("error") {
+        record_error(&mut run, None, error);
+    }
+    for project in report["projects"].as_array().into_iter().flatten() {
+        let name = project["project"].as_str().unwrap_or_default();
+        if let Some(error) = project.get("error") {
+            record_error(&mut run, Some(name), error);
+        }
+        for finding in project["findings"].as_array().into_iter().flatten() {
+            let rule = finding["name"].as_str().unwrap_or_default();
+            let file = finding["file"].as_str().unwrap_or_default();
+            let description = finding["description"].as_str().unwrap_or_default();
+            if rule == INTERNAL_ERROR_LINT {
+                run.panics.push(format!("{file}: {description}"));
+            } else {
+                run.findings.push(format!("{rule} {file}: {description}"));
+            }
+        }
+    }
+    run.findings.sort();
+    run
+}
+
+/// Records an error reported by the analyzer: internal errors are panics.
+fn record_error(run: &mut ProjectRun, project: Option<&str>, error: &Value) {
+    let message = error["message"].as_str().unwrap_or_default();
+    let message = match project {
+        Some(project) => format!("{project}: {message}"),
+        None => message.to_string(),
+    };
+    if error["kind"] == "internal" {
+        run.panics.push(message);
+    } else {
+        run.errors.push(message);
+    }
+}
+
+/// Describes an analyzer process that did not print a report, from its last lines of
+/// output.
+fn crash_message(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let last_lines: Vec<&str> = stderr.lines().rev().take(3).collect();
+    let last_lines: Vec<&str> = last_lines.into_iter().rev().collect();
+    format!(
+        "analyzer exited with {}: {}",
+        output.status,
+        last_lines.join(" / ")
+    )
+}
+
+/// Reads the skipped nodes from the coverage report printed by `--report-coverage`, made
+/// of `count kind` lines after its header.
+fn skipped_nodes(stderr: &str) -> BTreeMap<String, usize> {
+    stderr
+        .lines()
+        .skip_while(|line| !line.starts_with("Coverage:"))
+        .skip(1)
+        .map_while(|line| {
+            let (count, kind) = line.trim().split_once(char::is_whitespace)?;
+            Some((kind.trim().to_string(), count.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Compares the run of a project with the previous one.
+fn summarize(name: &str, previous: Option<&ProjectRun>, run: &ProjectRun) -> Value {
+    let empty = ProjectRun::default();
+    let previous_run = previous.unwrap_or(&empty);
+    let new_findings = difference(&run.findings, &previous_run.findings);
+    let resolved_findings = difference(&previous_run.findings, &run.findings);
+    let new_panics = difference(&run.panics, &previous_run.panics);
+    let new_skipped: Vec<&String> = run
+        .skipped_nodes
+        .keys()
+        .filter(|kind| !previous_run.skipped_nodes.contains_key(*kind))
+        .collect();
+    // Without a previous run, there is nothing to compare with
+    let changed = previous.is_some()
+        && (!new_findings.is_empty() || !resolved_findings.is_empty() || !new_skipped.is_empty());
+
+    json!({
+        "project": name,
+        "revision": run.revision,
+        "previous_revision": previous.map(|previous| &previous.revision),
+        "errors": run.errors,
+        "panics": run.panics,
+        "new_panics": new_panics,
+        "skipped_nodes": run.skipped_nodes,
+        "new_skipped_nodes": new_skipped,
+        "findings": run.findings.len(),
+        "new_findings": if previous.is_some() { new_findings } else { vec![] },
+        "resolved_findings": resolved_findings,
+        "changed": changed,
+    })
+}
+
+/// Returns the entries of `a` that are not in `b`, counting duplicates: a finding
+/// reported twice instead of once is new.
+fn difference<'a>(a: &'a [String], b: &[String]) -> Vec<&'a String> {
+    let mut remaining: BTreeMap<&String, usize> = BTreeMap::new();
+    for entry in b {
+        *remaining.entry(entry).or_default() += 1;
+    }
+    a.iter()
+        .filter(|entry| match remaining.get_mut(entry) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+/// Renders the summary of a project for humans.
+fn render(summary: &Value) -> String {
+    let list = |key: &str| -> Vec<&str> {
+        summary[key]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect()
+    };
+    let revision = summary["revision"].as_str().unwrap_or_default();
+    let mut output = format!(
+        "\x1b[1m{}\x1b[0m ({})\n",
+        summary["project"].as_str().unwrap_or_default(),
+        &revision[..revision.len().min(12)]
+    );
+
+    for error in list("errors") {
+        output.push_str(&format!("  \x1b[1;31merror\x1b[0m: {error}\n"));
+    }
+    let new_panics = list("new_panics");
+    for panic in list("panics") {
+        let marker = if new_panics.contains(&panic) {
+            " (new)"
+        } else {
+            ""
+        };
+        output.push_str(&format!("  \x1b[1;31mpanic\x1b[0m{marker}: {panic}\n"));
+    }
+
+    let skipped = summary["skipped_nodes"].as_object();
+    let skipped_total: u64 = skipped
+        .into_iter()
+        .flatten()
+        .filter_map(|(_, count)| count.as_u64())
+        .sum();
+    output.push_str(&format!("  skipped nodes: {skipped_total}"));
+    let new_skipped = list("new_skipped_nodes");
+    if !new_skipped.is_empty() {
+        output.push_str(&format!(" (new kinds: {})", new_skipped.join(", ")));
+    }
+    output.push('\n');
+
+    let new_findings = list("new_findings");
+    let resolved_findings = list("resolved_findings");
+    output.push_str(&format!("  findings: {}", summary["findings"]));
+    if summary["previous_revision"].is_null() {
+        output.push_str(" (first run)\n");
+    } else {
+        output.push_str(&format!(
+            " (+{}, -{} since the last run)\n",
+            new_findings.len(),
+            resolved_findings.len()
+        ));
+    }
+    for finding in new_findings {
+        output.push_str(&format!("    \x1b[32m+\x1b[0m {finding}\n"));
+    }
+    for finding in resolved_findings {
+        output.push_str(&format!("    \x1b[31m-\x1b[0m {finding}\n"));
+    }
+    output
+}
@@ -0,0 +1,33 @@
+//! Development tasks of the workspace, run with `cargo xtask <task>`.
+
+mod corpus;
+
+use std::process::ExitCode;
+
+#[derive(Debug, clap::Parser)]
+#[command(
+    name = "xtask",
+    about = "Development tasks of the noir-analyzer workspace"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Analyze public Noir projects and summarize the panics, skipped AST nodes and changed
+    /// findings since the last run, e.g. before upgrading the analyzer in CI
+    Corpus(corpus::CorpusArgs),
+}
+
+fn main() -> ExitCode {
+    let args = <Cli as clap::Parser>::parse();
+    let result = match &args.command {
+        Command::Corpus(corpus_args) => corpus::run(corpus_args),
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        ExitCode::FAILURE
+    })
+}